@@ -0,0 +1,60 @@
+//! Measures how many instructions per second the CPU executes on a loop of register heavy instructions, which
+//! exercises the register accessors and the memory bus reads of the opcode fetch.
+//!
+//! Run with `cargo bench --bench instruction_throughput`.
+
+use criterion::{Criterion, Throughput, criterion_group, criterion_main};
+use gameboy_emulator_rust::gameboy_core::{constants::INITIAL_PC, cpu::Cpu};
+
+/// Number of instructions executed in each iteration of the benchmark.
+const INSTRUCTIONS_PER_ITERATION: u64 = 10_000;
+
+/// Number of ticks of the long run, which also includes the PPU and timer updates of many frames.
+const LONG_RUN_TICKS: u64 = 10_000_000;
+
+/// LD B,A / INC C / ADD A,B / LD D,C / XOR E / LD H,D / LD L,E / JR -9, jumping back to the first instruction.
+const REGISTER_LOOP: [u8; 9] = [0x47, 0x0C, 0x80, 0x51, 0xAB, 0x62, 0x6B, 0x18, 0xF7];
+
+fn cpu_with_register_loop() -> Cpu {
+    let mut cpu = Cpu::new();
+    for (offset, &byte) in REGISTER_LOOP.iter().enumerate() {
+        cpu.memory_bus.write_byte(INITIAL_PC + offset as u16, byte);
+    }
+    cpu.registers.pc = INITIAL_PC;
+    cpu
+}
+
+fn instruction_throughput(c: &mut Criterion) {
+    let mut cpu = cpu_with_register_loop();
+
+    let mut group = c.benchmark_group("cpu");
+    group.throughput(Throughput::Elements(INSTRUCTIONS_PER_ITERATION));
+    group.bench_function("register_loop", |b| {
+        b.iter(|| {
+            for _ in 0..INSTRUCTIONS_PER_ITERATION {
+                cpu.tick();
+            }
+        })
+    });
+    group.finish();
+}
+
+fn long_run_throughput(c: &mut Criterion) {
+    let mut cpu = cpu_with_register_loop();
+
+    let mut group = c.benchmark_group("cpu");
+    // Each iteration takes a fraction of a second, fewer samples keep the run short
+    group.sample_size(10);
+    group.throughput(Throughput::Elements(LONG_RUN_TICKS));
+    group.bench_function("ten_million_ticks", |b| {
+        b.iter(|| {
+            for _ in 0..LONG_RUN_TICKS {
+                cpu.tick();
+            }
+        })
+    });
+    group.finish();
+}
+
+criterion_group!(benches, instruction_throughput, long_run_throughput);
+criterion_main!(benches);
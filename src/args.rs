@@ -0,0 +1,34 @@
+use std::path::PathBuf;
+
+use clap::Parser;
+
+/// Command line arguments of the emulator.
+#[derive(Parser, Debug, PartialEq)]
+#[command(version, about = "Game Boy emulator")]
+pub struct Args {
+    /// Path to the ROM file to run, e.g. files/roms/games/Tetris.gb. When missing a ROM picker is shown
+    pub rom: Option<PathBuf>,
+    /// Start in debug mode: logs the executed instructions and opens the tile map debug window
+    #[arg(short, long)]
+    pub debug: bool,
+    /// Scale of the emulator window, from 1 to 4
+    #[arg(long, value_parser = clap::value_parser!(u8).range(1..=4))]
+    pub scale: Option<u8>,
+    /// Run without sound
+    #[arg(long)]
+    pub no_audio: bool,
+}
+
+/// Parses the arguments of the current process, exiting with the usage message if they are invalid.
+pub fn parse_args() -> Args {
+    Args::parse()
+}
+
+/// Parses the given arguments, where the first one is the binary name.
+pub fn parse_args_from<I, T>(args: I) -> Result<Args, clap::Error>
+where
+    I: IntoIterator<Item = T>,
+    T: Into<std::ffi::OsString> + Clone,
+{
+    Args::try_parse_from(args)
+}
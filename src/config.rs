@@ -0,0 +1,169 @@
+use std::{
+    collections::HashMap,
+    fmt, fs, io,
+    path::{Path, PathBuf},
+};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    args::Args,
+    gameboy_core::{
+        components::{keyboard::DEFAULT_KEY_BINDINGS, screen::DEFAULT_SCANLINE_OPACITY},
+        constants::SCREEN_SCALE,
+    },
+};
+
+/// Name of the folder holding the config file inside the user's config directory.
+const CONFIG_FOLDER_NAME: &str = "gameboy_emulator";
+const CONFIG_FILE_NAME: &str = "config.toml";
+
+/// Settings of the emulator that are kept between runs in a TOML file.
+/// Missing entries in the file take their default value.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Config {
+    /// Scale of the emulator window, from 1 to 4
+    pub scale: u8,
+    /// Index of the selected palette in BUILTIN_PALETTES
+    pub palette_index: usize,
+    /// Keyboard key assigned to each Game Boy button, e.g. "A" = "Z"
+    pub key_bindings: HashMap<String, String>,
+    /// Controller button assigned to each Game Boy button, e.g. "A" = "South". Used with the gamepad feature.
+    pub gamepad_bindings: HashMap<String, String>,
+    /// Volume of the audio, from 0.0 (muted) to 1.0
+    pub audio_volume: f32,
+    /// Folder where the battery saves and save states are stored
+    pub save_directory: PathBuf,
+    /// Opacity of the CRT filter scanlines, from 0.0 to 1.0
+    pub scanline_opacity: f32,
+}
+
+#[derive(Debug)]
+pub enum ConfigError {
+    Io(io::Error),
+    Parse(toml::de::Error),
+    Serialize(toml::ser::Error),
+    Json(serde_json::Error),
+}
+
+impl fmt::Display for ConfigError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ConfigError::Io(e) => write!(f, "Could not access the config file: {}", e),
+            ConfigError::Parse(e) => write!(f, "Invalid config file: {}", e),
+            ConfigError::Serialize(e) => write!(f, "Could not serialize the config: {}", e),
+            ConfigError::Json(e) => write!(f, "Invalid recent ROMs file: {}", e),
+        }
+    }
+}
+
+impl std::error::Error for ConfigError {}
+
+impl From<io::Error> for ConfigError {
+    fn from(e: io::Error) -> Self {
+        ConfigError::Io(e)
+    }
+}
+
+impl From<toml::de::Error> for ConfigError {
+    fn from(e: toml::de::Error) -> Self {
+        ConfigError::Parse(e)
+    }
+}
+
+impl From<toml::ser::Error> for ConfigError {
+    fn from(e: toml::ser::Error) -> Self {
+        ConfigError::Serialize(e)
+    }
+}
+
+impl From<serde_json::Error> for ConfigError {
+    fn from(e: serde_json::Error) -> Self {
+        ConfigError::Json(e)
+    }
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        let key_bindings = DEFAULT_KEY_BINDINGS
+            .iter()
+            .map(|(button, key)| (button.to_string(), key.to_string()))
+            .collect();
+
+        let gamepad_bindings = [
+            ("Up", "DPadUp"),
+            ("Down", "DPadDown"),
+            ("Left", "DPadLeft"),
+            ("Right", "DPadRight"),
+            ("A", "South"),
+            ("B", "West"),
+            ("Start", "Start"),
+            ("Select", "Select"),
+        ]
+        .iter()
+        .map(|(button, gamepad_button)| (button.to_string(), gamepad_button.to_string()))
+        .collect();
+
+        Self {
+            scale: SCREEN_SCALE as u8,
+            palette_index: 0,
+            key_bindings,
+            gamepad_bindings,
+            audio_volume: 1.0,
+            save_directory: PathBuf::from("saves"),
+            scanline_opacity: DEFAULT_SCANLINE_OPACITY,
+        }
+    }
+}
+
+impl Config {
+    pub fn load_from_file(path: &Path) -> Result<Config, ConfigError> {
+        let content = fs::read_to_string(path)?;
+        Ok(toml::from_str(&content)?)
+    }
+
+    /// Saves the config as TOML, creating the parent folders if needed.
+    pub fn save_to_file(&self, path: &Path) -> Result<(), ConfigError> {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(path, toml::to_string_pretty(self)?)?;
+        Ok(())
+    }
+
+    /// Loads the config from the default path. When there is no config file yet the default config is used.
+    pub fn load() -> Result<Config, ConfigError> {
+        match Self::get_default_path() {
+            Some(path) if path.exists() => Self::load_from_file(&path),
+            _ => Ok(Config::default()),
+        }
+    }
+
+    /// Path of the config file: `%APPDATA%\gameboy_emulator\config.toml` on Windows and
+    /// `~/.config/gameboy_emulator/config.toml` on Linux and Mac. None if the user folder is unknown.
+    pub fn get_default_path() -> Option<PathBuf> {
+        Some(Self::get_config_dir()?.join(CONFIG_FILE_NAME))
+    }
+
+    /// Folder of the emulator files in the user's config directory, e.g. `~/.config/gameboy_emulator`.
+    pub fn get_config_dir() -> Option<PathBuf> {
+        let config_dir = if cfg!(windows) {
+            PathBuf::from(std::env::var_os("APPDATA")?)
+        } else {
+            PathBuf::from(std::env::var_os("HOME")?).join(".config")
+        };
+
+        Some(config_dir.join(CONFIG_FOLDER_NAME))
+    }
+
+    /// Applies the command line arguments over the values of the config file, the arguments take precedence.
+    pub fn merge_args(&mut self, args: &Args) {
+        if let Some(scale) = args.scale {
+            self.scale = scale;
+        }
+        if args.no_audio {
+            self.audio_volume = 0.0;
+        }
+    }
+}
@@ -0,0 +1,98 @@
+use std::collections::VecDeque;
+use std::time::Duration;
+
+use crate::gameboy_core::components::{
+    font::{self, CHAR_ADVANCE, CHAR_HEIGHT, CHAR_WIDTH},
+    screen::darken_pixels,
+};
+
+/// Number of frames the FPS is averaged over.
+pub const FPS_AVERAGE_FRAMES: usize = 60;
+/// Color of the overlay text.
+pub const OVERLAY_TEXT_COLOR: u32 = 0xFFFF00;
+/// How much the pixels behind the overlay text are darkened, from 0.0 (no effect) to 1.0 (black).
+pub const OVERLAY_BACKGROUND_OPACITY: f32 = 0.6;
+/// Space in pixels between the border of the overlay background and the text.
+pub const OVERLAY_PADDING: usize = 2;
+/// Vertical space used by each line of text, including 2 pixels of spacing.
+pub const OVERLAY_LINE_HEIGHT: usize = CHAR_HEIGHT + 2;
+
+/// Performance statistics shown on top of the game with F1.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct DebugOverlay {
+    /// Frames per second, averaged over the last FPS_AVERAGE_FRAMES frames.
+    pub fps: f32,
+    /// Time spent emulating and rendering the last frame, without the time the frame limiter waited.
+    pub frame_time_ms: f32,
+    pub cpu_cycles_per_frame: u64,
+    /// Value of the LY register (0xFF44) at the end of the frame.
+    pub current_ly: u8,
+}
+
+impl DebugOverlay {
+    /// Lines of text of the overlay, from top to bottom.
+    pub fn get_lines(&self) -> [String; 4] {
+        [
+            format!("FPS {:.1}", self.fps),
+            format!("MS {:.1}", self.frame_time_ms),
+            format!("CYC {}", self.cpu_cycles_per_frame),
+            format!("LY {}", self.current_ly),
+        ]
+    }
+}
+
+/// Keeps the duration of the last FPS_AVERAGE_FRAMES frames to compute a rolling FPS average.
+#[derive(Debug, Default)]
+pub struct FrameStats {
+    frame_durations: VecDeque<Duration>,
+}
+
+impl FrameStats {
+    pub fn new() -> FrameStats {
+        FrameStats {
+            frame_durations: VecDeque::with_capacity(FPS_AVERAGE_FRAMES),
+        }
+    }
+
+    /// Adds the duration of a frame, dropping the oldest one when there are already FPS_AVERAGE_FRAMES.
+    pub fn push_frame_duration(&mut self, duration: Duration) {
+        if self.frame_durations.len() == FPS_AVERAGE_FRAMES {
+            self.frame_durations.pop_front();
+        }
+        self.frame_durations.push_back(duration);
+    }
+
+    /// Average frames per second of the frames kept, 0.0 when there are none.
+    pub fn get_fps(&self) -> f32 {
+        let total: Duration = self.frame_durations.iter().sum();
+        if total.is_zero() {
+            return 0.0;
+        }
+        self.frame_durations.len() as f32 / total.as_secs_f32()
+    }
+}
+
+/// Draws the overlay in the top-left corner of a buffer of the given width. The pixels behind the text are
+/// darkened so it can be read over any game image, the parts that don't fit in the buffer are clipped.
+pub fn render_debug_overlay(overlay: &DebugOverlay, buffer: &mut [u32], width: usize) {
+    if width == 0 {
+        return;
+    }
+
+    let lines = overlay.get_lines();
+    let longest_line = lines.iter().map(|line| line.chars().count()).max().unwrap_or(0);
+    let text_width = longest_line * CHAR_ADVANCE - (CHAR_ADVANCE - CHAR_WIDTH);
+    let text_height = lines.len() * OVERLAY_LINE_HEIGHT - (OVERLAY_LINE_HEIGHT - CHAR_HEIGHT);
+
+    let background_width = (text_width + 2 * OVERLAY_PADDING).min(width);
+    let background_height = (text_height + 2 * OVERLAY_PADDING).min(buffer.len() / width);
+    for row in 0..background_height {
+        let row_start = row * width;
+        darken_pixels(&mut buffer[row_start..row_start + background_width], OVERLAY_BACKGROUND_OPACITY);
+    }
+
+    for (line_index, line) in lines.iter().enumerate() {
+        let row = OVERLAY_PADDING + line_index * OVERLAY_LINE_HEIGHT;
+        font::draw_text(buffer, width, row, OVERLAY_PADDING, line, OVERLAY_TEXT_COLOR);
+    }
+}
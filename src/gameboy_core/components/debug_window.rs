@@ -0,0 +1,99 @@
+use minifb::{Key, KeyRepeat, Window, WindowOptions};
+
+use crate::gameboy_core::{
+    components::{
+        screen::{PALETTE_VIEW_HEIGHT, Screen},
+        tile_map_viewer::TileMapViewer,
+    },
+    constants::BG_AND_WINDOW_MAP_SCREEN_SIZE,
+    cpu::Cpu,
+};
+
+/// The tile map is shown at the top of the window and the palette view below it.
+const DEBUG_WINDOW_WIDTH: usize = BG_AND_WINDOW_MAP_SCREEN_SIZE;
+const DEBUG_WINDOW_HEIGHT: usize = BG_AND_WINDOW_MAP_SCREEN_SIZE + PALETTE_VIEW_HEIGHT;
+
+/// Scale applied to the 256x256 tile map so it is readable on modern screens.
+const DEBUG_WINDOW_SCALE: minifb::Scale = minifb::Scale::X2;
+
+/// Secondary minifb window used in debug mode to show the background tile map.
+/// Arrow keys move the selected tile and Z zooms in/out of it, the title shows the selected tile index and palette.
+/// O toggles the OAM inspector overlay showing all 40 objects. The BGP, OBP0 and OBP1 palettes are shown below the tile map.
+pub struct DebugWindow {
+    pub window: Window,
+    pub tile_map_viewer: TileMapViewer,
+    pub is_oam_view_visible: bool,
+    /// Buffer of the palette view sub-panel
+    pub palette_view_buffer: Vec<u32>,
+}
+
+impl DebugWindow {
+    pub fn new() -> Result<DebugWindow, minifb::Error> {
+        let window = Window::new(
+            "Tile Map",
+            DEBUG_WINDOW_WIDTH,
+            DEBUG_WINDOW_HEIGHT,
+            WindowOptions {
+                scale: DEBUG_WINDOW_SCALE,
+                ..WindowOptions::default()
+            },
+        )?;
+
+        Ok(Self {
+            window,
+            tile_map_viewer: TileMapViewer::new(),
+            is_oam_view_visible: false,
+            palette_view_buffer: Vec::new(),
+        })
+    }
+
+    pub fn is_open(&self) -> bool {
+        self.window.is_open()
+    }
+
+    /// Handles the navigation keys, renders the tile map and updates the window with it.
+    pub fn update(&mut self, cpu: &Cpu) {
+        self.handle_keys();
+        self.tile_map_viewer.render(cpu);
+        if self.is_oam_view_visible {
+            Screen::render_oam_view(cpu, &mut self.tile_map_viewer.buffer);
+        }
+        Screen::render_palette_view(&cpu.memory_bus, &mut self.palette_view_buffer);
+
+        let tile_info = self.tile_map_viewer.get_selected_tile_info(cpu);
+        self.window.set_title(&format!(
+            "Tile Map - tile ({}, {}) index: {:#04X} BGP: {:?}",
+            tile_info.row, tile_info.col, tile_info.tile_index, tile_info.palette
+        ));
+
+        let buffer: Vec<u32> = [
+            self.tile_map_viewer.buffer.as_slice(),
+            self.palette_view_buffer.as_slice(),
+        ]
+        .concat();
+        self.window
+            .update_with_buffer(&buffer, DEBUG_WINDOW_WIDTH, DEBUG_WINDOW_HEIGHT)
+            .unwrap();
+    }
+
+    fn handle_keys(&mut self) {
+        if self.window.is_key_pressed(Key::Up, KeyRepeat::Yes) {
+            self.tile_map_viewer.move_selection(-1, 0);
+        }
+        if self.window.is_key_pressed(Key::Down, KeyRepeat::Yes) {
+            self.tile_map_viewer.move_selection(1, 0);
+        }
+        if self.window.is_key_pressed(Key::Left, KeyRepeat::Yes) {
+            self.tile_map_viewer.move_selection(0, -1);
+        }
+        if self.window.is_key_pressed(Key::Right, KeyRepeat::Yes) {
+            self.tile_map_viewer.move_selection(0, 1);
+        }
+        if self.window.is_key_pressed(Key::Z, KeyRepeat::No) {
+            self.tile_map_viewer.toggle_zoom();
+        }
+        if self.window.is_key_pressed(Key::O, KeyRepeat::No) {
+            self.is_oam_view_visible = !self.is_oam_view_visible;
+        }
+    }
+}
@@ -0,0 +1,74 @@
+/// Width in pixels of each character of the debug font.
+pub const CHAR_WIDTH: usize = 3;
+/// Height in pixels of each character of the debug font.
+pub const CHAR_HEIGHT: usize = 5;
+/// Horizontal space used by each character, including 1 pixel of spacing.
+pub const CHAR_ADVANCE: usize = CHAR_WIDTH + 1;
+
+/// Returns the 3x5 glyph of a character, each row is stored in the lower 3 bits where bit 2 is the leftmost pixel.
+/// Lowercase letters are drawn as uppercase, unsupported characters are drawn as blank spaces.
+fn get_glyph(character: char) -> [u8; CHAR_HEIGHT] {
+    match character.to_ascii_uppercase() {
+        '0' => [0b111, 0b101, 0b101, 0b101, 0b111],
+        '1' => [0b010, 0b110, 0b010, 0b010, 0b111],
+        '2' => [0b111, 0b001, 0b111, 0b100, 0b111],
+        '3' => [0b111, 0b001, 0b111, 0b001, 0b111],
+        '4' => [0b101, 0b101, 0b111, 0b001, 0b001],
+        '5' => [0b111, 0b100, 0b111, 0b001, 0b111],
+        '6' => [0b111, 0b100, 0b111, 0b101, 0b111],
+        '7' => [0b111, 0b001, 0b001, 0b001, 0b001],
+        '8' => [0b111, 0b101, 0b111, 0b101, 0b111],
+        '9' => [0b111, 0b101, 0b111, 0b001, 0b111],
+        'A' => [0b010, 0b101, 0b111, 0b101, 0b101],
+        'B' => [0b110, 0b101, 0b110, 0b101, 0b110],
+        'C' => [0b011, 0b100, 0b100, 0b100, 0b011],
+        'D' => [0b110, 0b101, 0b101, 0b101, 0b110],
+        'E' => [0b111, 0b100, 0b110, 0b100, 0b111],
+        'F' => [0b111, 0b100, 0b110, 0b100, 0b100],
+        'G' => [0b011, 0b100, 0b101, 0b101, 0b011],
+        'H' => [0b101, 0b101, 0b111, 0b101, 0b101],
+        'I' => [0b111, 0b010, 0b010, 0b010, 0b111],
+        'J' => [0b001, 0b001, 0b001, 0b101, 0b010],
+        'K' => [0b101, 0b101, 0b110, 0b101, 0b101],
+        'L' => [0b100, 0b100, 0b100, 0b100, 0b111],
+        'M' => [0b101, 0b111, 0b111, 0b101, 0b101],
+        'N' => [0b110, 0b101, 0b101, 0b101, 0b101],
+        'O' => [0b010, 0b101, 0b101, 0b101, 0b010],
+        'P' => [0b110, 0b101, 0b110, 0b100, 0b100],
+        'Q' => [0b010, 0b101, 0b101, 0b110, 0b011],
+        'R' => [0b110, 0b101, 0b110, 0b101, 0b101],
+        'S' => [0b011, 0b100, 0b010, 0b001, 0b110],
+        'T' => [0b111, 0b010, 0b010, 0b010, 0b010],
+        'U' => [0b101, 0b101, 0b101, 0b101, 0b111],
+        'V' => [0b101, 0b101, 0b101, 0b101, 0b010],
+        'W' => [0b101, 0b101, 0b111, 0b111, 0b101],
+        'X' => [0b101, 0b101, 0b010, 0b101, 0b101],
+        'Y' => [0b101, 0b101, 0b010, 0b010, 0b010],
+        'Z' => [0b111, 0b001, 0b010, 0b100, 0b111],
+        ':' => [0b000, 0b010, 0b000, 0b010, 0b000],
+        '-' => [0b000, 0b000, 0b111, 0b000, 0b000],
+        '.' => [0b000, 0b000, 0b000, 0b000, 0b010],
+        _ => [0; CHAR_HEIGHT],
+    }
+}
+
+/// Draws text into a 1D buffer of the given width using the 3x5 debug font, starting at (row, col).
+/// Pixels outside the buffer are ignored.
+pub fn draw_text(buffer: &mut [u32], buffer_width: usize, row: usize, col: usize, text: &str, color: u32) {
+    for (char_index, character) in text.chars().enumerate() {
+        let glyph = get_glyph(character);
+        let char_col = col + char_index * CHAR_ADVANCE;
+
+        for (glyph_row, glyph_bits) in glyph.iter().enumerate() {
+            for glyph_col in 0..CHAR_WIDTH {
+                let is_pixel_set = (glyph_bits >> (CHAR_WIDTH - 1 - glyph_col)) & 1 == 1;
+                let buffer_col = char_col + glyph_col;
+                let buffer_index = (row + glyph_row) * buffer_width + buffer_col;
+
+                if is_pixel_set && buffer_col < buffer_width && buffer_index < buffer.len() {
+                    buffer[buffer_index] = color;
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,192 @@
+use std::collections::HashMap;
+
+#[cfg(feature = "gamepad")]
+use gilrs::{Axis, Button, EventType, Gilrs};
+
+#[cfg(feature = "gamepad")]
+use crate::gameboy_core::cpu_components::MemoryBus;
+
+use crate::gameboy_core::joypad::{JoypadButton, JoypadState};
+
+/// D-pad axis values beyond this are considered a press. Some controllers report the D-pad as two axes
+/// instead of four buttons.
+const DPAD_AXIS_THRESHOLD: f32 = 0.5;
+
+/// Controller buttons that can be assigned to a Game Boy button, named like the gilrs buttons.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum GamepadButton {
+    South,
+    East,
+    North,
+    West,
+    LeftTrigger,
+    RightTrigger,
+    Select,
+    Start,
+    DPadUp,
+    DPadDown,
+    DPadLeft,
+    DPadRight,
+}
+
+impl GamepadButton {
+    /// Parses the button names used in the config file, e.g. "South".
+    pub fn from_name(name: &str) -> Option<GamepadButton> {
+        match name {
+            "South" => Some(GamepadButton::South),
+            "East" => Some(GamepadButton::East),
+            "North" => Some(GamepadButton::North),
+            "West" => Some(GamepadButton::West),
+            "LeftTrigger" => Some(GamepadButton::LeftTrigger),
+            "RightTrigger" => Some(GamepadButton::RightTrigger),
+            "Select" => Some(GamepadButton::Select),
+            "Start" => Some(GamepadButton::Start),
+            "DPadUp" => Some(GamepadButton::DPadUp),
+            "DPadDown" => Some(GamepadButton::DPadDown),
+            "DPadLeft" => Some(GamepadButton::DPadLeft),
+            "DPadRight" => Some(GamepadButton::DPadRight),
+            _ => None,
+        }
+    }
+}
+
+/// Controller input relevant to the emulator. The gilrs events are converted to this so the mapping
+/// logic does not depend on a real controller.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum GamepadEvent {
+    ButtonPressed(GamepadButton),
+    ButtonReleased(GamepadButton),
+    /// Horizontal D-pad axis, from -1.0 (left) to 1.0 (right)
+    DPadXChanged(f32),
+    /// Vertical D-pad axis, from -1.0 (down) to 1.0 (up)
+    DPadYChanged(f32),
+}
+
+/// Assignment of controller buttons to Game Boy buttons.
+#[derive(Debug, Clone, PartialEq)]
+pub struct GamepadMapping {
+    buttons: HashMap<GamepadButton, JoypadButton>,
+}
+
+impl Default for GamepadMapping {
+    fn default() -> Self {
+        let buttons = [
+            (GamepadButton::South, JoypadButton::A),
+            (GamepadButton::West, JoypadButton::B),
+            (GamepadButton::Start, JoypadButton::Start),
+            (GamepadButton::Select, JoypadButton::Select),
+            (GamepadButton::DPadUp, JoypadButton::Up),
+            (GamepadButton::DPadDown, JoypadButton::Down),
+            (GamepadButton::DPadLeft, JoypadButton::Left),
+            (GamepadButton::DPadRight, JoypadButton::Right),
+        ]
+        .into_iter()
+        .collect();
+
+        Self { buttons }
+    }
+}
+
+impl GamepadMapping {
+    /// Creates the mapping from the config file bindings, e.g. "A" = "East". Each binding replaces the default
+    /// controller button of that Game Boy button, bindings with unknown names are ignored.
+    pub fn from_bindings(bindings: &HashMap<String, String>) -> Self {
+        let mut mapping = Self::default();
+
+        for (joypad_name, gamepad_name) in bindings {
+            if let Some(joypad_button) = JoypadButton::from_name(joypad_name)
+                && let Some(gamepad_button) = GamepadButton::from_name(gamepad_name)
+            {
+                mapping.buttons.retain(|_, button| *button != joypad_button);
+                mapping.buttons.insert(gamepad_button, joypad_button);
+            }
+        }
+
+        mapping
+    }
+
+    pub fn get_joypad_button(&self, gamepad_button: GamepadButton) -> Option<JoypadButton> {
+        self.buttons.get(&gamepad_button).copied()
+    }
+
+    /// Presses or releases the Game Boy buttons affected by the controller event.
+    pub fn apply_event(&self, event: GamepadEvent, joypad_state: &mut JoypadState) {
+        match event {
+            GamepadEvent::ButtonPressed(button) => {
+                if let Some(joypad_button) = self.get_joypad_button(button) {
+                    joypad_state.set_button(joypad_button, true);
+                }
+            }
+            GamepadEvent::ButtonReleased(button) => {
+                if let Some(joypad_button) = self.get_joypad_button(button) {
+                    joypad_state.set_button(joypad_button, false);
+                }
+            }
+            GamepadEvent::DPadXChanged(value) => {
+                joypad_state.set_button(JoypadButton::Left, value < -DPAD_AXIS_THRESHOLD);
+                joypad_state.set_button(JoypadButton::Right, value > DPAD_AXIS_THRESHOLD);
+            }
+            GamepadEvent::DPadYChanged(value) => {
+                joypad_state.set_button(JoypadButton::Down, value < -DPAD_AXIS_THRESHOLD);
+                joypad_state.set_button(JoypadButton::Up, value > DPAD_AXIS_THRESHOLD);
+            }
+        }
+    }
+}
+
+/// Reads the connected controllers through gilrs and updates the joypad with their buttons.
+#[cfg(feature = "gamepad")]
+pub struct GamepadInput {
+    gilrs: Gilrs,
+    mapping: GamepadMapping,
+}
+
+#[cfg(feature = "gamepad")]
+impl GamepadInput {
+    /// Returns None when the controller backend is not available, the emulator then runs with the keyboard only.
+    pub fn new(mapping: GamepadMapping) -> Option<Self> {
+        Gilrs::new().ok().map(|gilrs| Self { gilrs, mapping })
+    }
+
+    /// Processes all the controller events received since the last call. It should be called once per frame.
+    pub fn poll(&mut self, memory_bus: &mut MemoryBus) {
+        let mut joypad_state = memory_bus.joypad_state;
+        while let Some(event) = self.gilrs.next_event() {
+            if let Some(event) = to_gamepad_event(event.event) {
+                self.mapping.apply_event(event, &mut joypad_state);
+            }
+        }
+        // The buttons pressed on the controller request the joypad interrupt like the keyboard ones
+        memory_bus.set_joypad_state(joypad_state);
+    }
+}
+
+#[cfg(feature = "gamepad")]
+fn to_gamepad_event(event: EventType) -> Option<GamepadEvent> {
+    match event {
+        EventType::ButtonPressed(button, _) => to_gamepad_button(button).map(GamepadEvent::ButtonPressed),
+        EventType::ButtonReleased(button, _) => to_gamepad_button(button).map(GamepadEvent::ButtonReleased),
+        EventType::AxisChanged(Axis::DPadX, value, _) => Some(GamepadEvent::DPadXChanged(value)),
+        EventType::AxisChanged(Axis::DPadY, value, _) => Some(GamepadEvent::DPadYChanged(value)),
+        _ => None,
+    }
+}
+
+#[cfg(feature = "gamepad")]
+fn to_gamepad_button(button: Button) -> Option<GamepadButton> {
+    match button {
+        Button::South => Some(GamepadButton::South),
+        Button::East => Some(GamepadButton::East),
+        Button::North => Some(GamepadButton::North),
+        Button::West => Some(GamepadButton::West),
+        Button::LeftTrigger => Some(GamepadButton::LeftTrigger),
+        Button::RightTrigger => Some(GamepadButton::RightTrigger),
+        Button::Select => Some(GamepadButton::Select),
+        Button::Start => Some(GamepadButton::Start),
+        Button::DPadUp => Some(GamepadButton::DPadUp),
+        Button::DPadDown => Some(GamepadButton::DPadDown),
+        Button::DPadLeft => Some(GamepadButton::DPadLeft),
+        Button::DPadRight => Some(GamepadButton::DPadRight),
+        _ => None,
+    }
+}
@@ -0,0 +1,130 @@
+use std::collections::HashMap;
+
+use minifb::{Key, KeyRepeat, Window};
+
+use crate::gameboy_core::{cpu_components::MemoryBus, joypad::JoypadButton};
+
+/// Keyboard state read by `poll_input`. It is implemented by the minifb window, tests can simulate key presses
+/// without opening a window.
+pub trait KeyboardInput {
+    /// True when the key went down since the last window update
+    fn is_key_pressed(&self, key: Key) -> bool;
+    /// True when the key went up since the last window update
+    fn is_key_released(&self, key: Key) -> bool;
+}
+
+impl KeyboardInput for Window {
+    fn is_key_pressed(&self, key: Key) -> bool {
+        Window::is_key_pressed(self, key, KeyRepeat::No)
+    }
+
+    fn is_key_released(&self, key: Key) -> bool {
+        Window::is_key_released(self, key)
+    }
+}
+
+/// Assignment of keyboard keys to Game Boy buttons, one key per button.
+#[derive(Debug, Clone, PartialEq)]
+pub struct InputMapping {
+    keys: HashMap<JoypadButton, Key>,
+}
+
+/// Default key of each Game Boy button, as written in the config file. Both `InputMapping::default` and the default
+/// config bindings are built from it.
+pub const DEFAULT_KEY_BINDINGS: [(&str, &str); 8] = [
+    ("Up", "Up"),
+    ("Down", "Down"),
+    ("Left", "Left"),
+    ("Right", "Right"),
+    ("A", "Z"),
+    ("B", "X"),
+    ("Start", "Enter"),
+    ("Select", "Backspace"),
+];
+
+impl Default for InputMapping {
+    fn default() -> Self {
+        let keys = DEFAULT_KEY_BINDINGS
+            .iter()
+            .filter_map(|(joypad_name, key_name)| {
+                Some((JoypadButton::from_name(joypad_name)?, key_from_name(key_name)?))
+            })
+            .collect();
+
+        Self { keys }
+    }
+}
+
+impl InputMapping {
+    /// Creates the mapping from the config file bindings, e.g. "A" = "K". Each binding replaces the default key of
+    /// that Game Boy button, bindings with unknown names are ignored.
+    pub fn from_bindings(bindings: &HashMap<String, String>) -> Self {
+        let mut mapping = Self::default();
+
+        for (joypad_name, key_name) in bindings {
+            if let Some(joypad_button) = JoypadButton::from_name(joypad_name)
+                && let Some(key) = key_from_name(key_name)
+            {
+                mapping.keys.insert(joypad_button, key);
+            }
+        }
+
+        mapping
+    }
+
+    pub fn get_key(&self, joypad_button: JoypadButton) -> Option<Key> {
+        self.keys.get(&joypad_button).copied()
+    }
+}
+
+/// Presses the Game Boy buttons whose key went down and releases the ones whose key went up since the last window
+/// update. Only the changes are applied, so the buttons held on a controller are not released by the keyboard.
+/// It should be called once per frame, before running the frame.
+pub fn poll_input(window: &impl KeyboardInput, memory_bus: &mut MemoryBus, mapping: &InputMapping) {
+    for (&joypad_button, &key) in &mapping.keys {
+        if window.is_key_pressed(key) {
+            memory_bus.set_joypad_button(joypad_button, true);
+        } else if window.is_key_released(key) {
+            memory_bus.set_joypad_button(joypad_button, false);
+        }
+    }
+}
+
+/// Parses the key names used in the config file: the letters, the digits ("0"-"9"), the arrows ("Up") and a few
+/// other keys ("Enter", "Backspace", "Space", "Tab", "LeftShift", "RightShift").
+pub fn key_from_name(name: &str) -> Option<Key> {
+    let key = match name {
+        "Up" => Key::Up,
+        "Down" => Key::Down,
+        "Left" => Key::Left,
+        "Right" => Key::Right,
+        "Enter" => Key::Enter,
+        "Backspace" => Key::Backspace,
+        "Space" => Key::Space,
+        "Tab" => Key::Tab,
+        "LeftShift" => Key::LeftShift,
+        "RightShift" => Key::RightShift,
+        _ => return key_from_character(name),
+    };
+    Some(key)
+}
+
+fn key_from_character(name: &str) -> Option<Key> {
+    const LETTERS: [Key; 26] = [
+        Key::A, Key::B, Key::C, Key::D, Key::E, Key::F, Key::G, Key::H, Key::I, Key::J, Key::K, Key::L, Key::M,
+        Key::N, Key::O, Key::P, Key::Q, Key::R, Key::S, Key::T, Key::U, Key::V, Key::W, Key::X, Key::Y, Key::Z,
+    ];
+    const DIGITS: [Key; 10] = [
+        Key::Key0, Key::Key1, Key::Key2, Key::Key3, Key::Key4, Key::Key5, Key::Key6, Key::Key7, Key::Key8, Key::Key9,
+    ];
+
+    let mut characters = name.chars();
+    let (Some(character), None) = (characters.next(), characters.next()) else {
+        return None;
+    };
+    match character.to_ascii_uppercase() {
+        letter @ 'A'..='Z' => Some(LETTERS[(letter as u8 - b'A') as usize]),
+        digit @ '0'..='9' => Some(DIGITS[(digit as u8 - b'0') as usize]),
+        _ => None,
+    }
+}
@@ -1 +1,16 @@
-pub mod screen;
\ No newline at end of file
+// The windows and views built on minifb are only available in the desktop build with std, minifb doesn't support
+// WebAssembly
+#[cfg(all(feature = "std", not(target_arch = "wasm32")))]
+pub mod screen;
+#[cfg(all(feature = "std", not(target_arch = "wasm32")))]
+pub mod tile_map_viewer;
+#[cfg(all(feature = "std", not(target_arch = "wasm32")))]
+pub mod debug_window;
+pub mod font;
+pub mod palette;
+#[cfg(feature = "std")]
+pub mod gamepad;
+#[cfg(all(feature = "std", not(target_arch = "wasm32")))]
+pub mod keyboard;
+#[cfg(all(feature = "std", not(target_arch = "wasm32")))]
+pub mod debug_overlay;
@@ -0,0 +1,62 @@
+use crate::gameboy_core::constants::{COLORS, GAME_SECTION_HEIGHT, GAME_SECTION_WIDTH};
+
+/// Set of 4 colors used to display the game, from the lightest (color 0) to the darkest (color 3) shade.
+/// The PPU always outputs the grayscale COLORS values, which are replaced with the palette colors when displayed.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Palette {
+    pub colors: [u32; 4],
+    pub name: &'static str,
+}
+
+/// Palettes that can be selected with the P key. The first one is the default grayscale palette.
+pub const BUILTIN_PALETTES: &[Palette] = &[
+    Palette {
+        colors: COLORS,
+        name: "Grayscale",
+    },
+    Palette {
+        colors: [0x9BBC0F, 0x8BAC0F, 0x306230, 0x0F380F],
+        name: "DMG Green",
+    },
+    Palette {
+        colors: [0xC4CFA1, 0x8B956D, 0x4D533C, 0x1F1F1F],
+        name: "Game Boy Pocket",
+    },
+    Palette {
+        colors: [0x00B581, 0x009A71, 0x00694A, 0x004F3B],
+        name: "Game Boy Light",
+    },
+    Palette {
+        colors: [0xF7E7C6, 0xD68E49, 0xA63725, 0x331E50],
+        name: "Super Game Boy",
+    },
+    Palette {
+        colors: [0xE0F8D0, 0x88C070, 0x346856, 0x081820],
+        name: "Pastel Green",
+    },
+];
+
+impl Default for Palette {
+    fn default() -> Self {
+        BUILTIN_PALETTES[0]
+    }
+}
+
+impl Palette {
+    /// Maps one of the grayscale COLORS values to the color of this palette with the same shade.
+    /// Colors that are not part of COLORS are returned unchanged.
+    pub fn map_color(&self, color: u32) -> u32 {
+        match COLORS.iter().position(|&shade_color| shade_color == color) {
+            Some(shade) => self.colors[shade],
+            None => color,
+        }
+    }
+
+    /// Returns a copy of the game screen with its colors mapped to this palette.
+    pub fn apply(
+        &self,
+        screen: &[[u32; GAME_SECTION_WIDTH]; GAME_SECTION_HEIGHT],
+    ) -> [[u32; GAME_SECTION_WIDTH]; GAME_SECTION_HEIGHT] {
+        screen.map(|row| row.map(|color| self.map_color(color)))
+    }
+}
@@ -1,150 +1,751 @@
-use crate::{
-    TILE_SIZE,
-    gameboy_core::constants::{COLORS, GAME_SECTION_HEIGHT},
-};
-use minifb::{Window, WindowOptions};
+use crate::gameboy_core::constants::{COLORS, GAME_SECTION_HEIGHT, TILE_SIZE};
+use minifb::{Key, KeyRepeat, Window, WindowOptions};
+use std::time::{Duration, Instant};
 
 use crate::gameboy_core::{
     self,
-    constants::{GAME_SECTION_WIDTH, SCREEN_SCALE},
-    ppu_components::Tile,
+    components::{
+        font,
+        palette::{BUILTIN_PALETTES, Palette},
+    },
+    constants::{GAME_SECTION_WIDTH, GAMEBOY_FPS, MAX_SCREEN_SCALE, MIN_SCREEN_SCALE, SCREEN_SCALE},
+    cpu_components::{MemoryAccess, MemoryBus},
+    emulator_error::EmulatorError,
+    ppu::{Object, ObjectPallete, Ppu},
+    ppu_components::{LcdcRegister, Tile, decode_palette},
+    registers_contants::{BGP, OBP0, OBP1},
 };
 
 const TILES_PER_ROW: usize = 16; // 16 tiles wide
 const TILES_PER_COL: usize = 24; // 24 tiles tall
 const MARGIN: usize = 20; // Margin between game screen and tile data
 const TILE_MARGIN: usize = 2; // Margin between each tile in the tile data section
-const TILE_DATA_WIDTH: usize =
-    TILES_PER_ROW * TILE_SIZE * SCREEN_SCALE + (TILES_PER_ROW - 1) * TILE_MARGIN * SCREEN_SCALE;
-const TILE_DATA_HEIGHT: usize =
-    TILES_PER_COL * TILE_SIZE * SCREEN_SCALE + (TILES_PER_COL - 1) * TILE_MARGIN * SCREEN_SCALE;
-
-pub(crate) const TOTAL_WINDOW_WIDTH: usize =
-    (GAME_SECTION_WIDTH * SCREEN_SCALE) + MARGIN + TILE_DATA_WIDTH;
-// FIX: Window height must accommodate the full scaled game area
-// Previously was only TILE_DATA_HEIGHT, which could clip the game area if SCREEN_SCALE is large
-// Now we ensure both the game area and tile data area fit within the window height
-pub(crate) const TOTAL_WINDOW_HEIGHT: usize = 
-    if GAME_SECTION_HEIGHT * SCREEN_SCALE > TILE_DATA_HEIGHT {
-        GAME_SECTION_HEIGHT * SCREEN_SCALE
-    } else {
-        TILE_DATA_HEIGHT
-    };
+
+/// Title of the emulator window, the emulation speed is added as suffix.
+pub const WINDOW_TITLE: &str = "Gameboy Emulator";
+/// Size in pixels of the emulator window at the default scale: the game area, the margin and the tile data section.
+pub const TOTAL_WINDOW_WIDTH: usize = Screen::get_window_width(SCREEN_SCALE);
+pub const TOTAL_WINDOW_HEIGHT: usize = Screen::get_window_height(SCREEN_SCALE);
+
+/// Background color of the emulator window and debug views.
+const BACKGROUND_COLOR: u32 = 0x000080;
+/// Color of the debug border drawn around the game image.
+const GAME_AREA_BORDER_COLOR: u32 = 0xFF00FF;
+/// Default opacity of the CRT filter scanlines.
+pub const DEFAULT_SCANLINE_OPACITY: f32 = 0.3;
+/// Color used for the text annotations of the debug views.
+const TEXT_COLOR: u32 = 0xFFFF00;
+
+/// The OAM view shows the 40 objects in a grid of 8 columns and 5 rows.
+const OAM_VIEW_COLUMNS: usize = 8;
+/// Each OAM cell holds the object (up to 8x16 pixels) and two lines of annotations below it.
+const OAM_CELL_WIDTH: usize = 32;
+const OAM_CELL_HEIGHT: usize = 36;
+/// Width of the OAM view buffer in pixels.
+pub const OAM_VIEW_WIDTH: usize = OAM_VIEW_COLUMNS * OAM_CELL_WIDTH;
+/// Height of the OAM view buffer in pixels.
+pub const OAM_VIEW_HEIGHT: usize = (40 / OAM_VIEW_COLUMNS) * OAM_CELL_HEIGHT;
+
+/// Names of the 4 shades, in the same order as the COLORS array.
+const SHADE_NAMES: [&str; 4] = ["White", "Light Gray", "Dark Gray", "Black"];
+/// Size in pixels of each color swatch in the palette view.
+const PALETTE_SWATCH_SIZE: usize = 16;
+/// Width of the column holding the palette register names.
+const PALETTE_LABEL_WIDTH: usize = 24;
+/// Each swatch column is wide enough to fit the longest shade name below it.
+const PALETTE_SWATCH_COLUMN_WIDTH: usize = 56;
+/// Each palette row holds the swatches, their names and some spacing.
+const PALETTE_ROW_HEIGHT: usize = 28;
+/// Width of the palette view buffer in pixels.
+pub const PALETTE_VIEW_WIDTH: usize = 256;
+/// Height of the palette view buffer in pixels, one row for each of BGP, OBP0 and OBP1.
+pub const PALETTE_VIEW_HEIGHT: usize = 3 * PALETTE_ROW_HEIGHT + 4;
+
+/// How the 160x144 game image is scaled into the game area, which is the part of the window on the left of the tile data.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ScalingMode {
+    /// Fixed integer multiplier. The image is centered in the game area and clipped if it doesn't fit.
+    Integer(u8),
+    /// Largest integer multiplier that fits in the game area, keeping the 160:144 aspect ratio.
+    Fit,
+    /// Stretches the image as much as the game area allows keeping the 160:144 aspect ratio,
+    /// the remaining borders are letterboxed.
+    Fill,
+}
+
+/// Position and size in pixels of the scaled game image inside the game area.
+#[derive(Debug, PartialEq)]
+pub struct GameImageRect {
+    pub top: usize,
+    pub left: usize,
+    pub width: usize,
+    pub height: usize,
+}
+
+impl ScalingMode {
+    /// Computes where the game image is drawn in a game area of the given size, centered on both axes.
+    pub fn get_game_image_rect(&self, area_width: usize, area_height: usize) -> GameImageRect {
+        let (width, height) = match *self {
+            ScalingMode::Integer(multiplier) => {
+                let multiplier = (multiplier as usize).max(1);
+                (GAME_SECTION_WIDTH * multiplier, GAME_SECTION_HEIGHT * multiplier)
+            }
+            ScalingMode::Fit => {
+                let multiplier = (area_width / GAME_SECTION_WIDTH)
+                    .min(area_height / GAME_SECTION_HEIGHT)
+                    .max(1);
+                (GAME_SECTION_WIDTH * multiplier, GAME_SECTION_HEIGHT * multiplier)
+            }
+            ScalingMode::Fill => {
+                // The side that limits the image takes the whole area, the other one keeps the aspect ratio
+                if area_width * GAME_SECTION_HEIGHT <= area_height * GAME_SECTION_WIDTH {
+                    (area_width, area_width * GAME_SECTION_HEIGHT / GAME_SECTION_WIDTH)
+                } else {
+                    (area_height * GAME_SECTION_WIDTH / GAME_SECTION_HEIGHT, area_height)
+                }
+            }
+        };
+
+        GameImageRect {
+            top: area_height.saturating_sub(height) / 2,
+            left: area_width.saturating_sub(width) / 2,
+            width,
+            height,
+        }
+    }
+}
+
+/// Darkens every other row of the game image to imitate the scanlines of a CRT display.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CrtFilter {
+    pub enabled: bool,
+    /// How much the scanlines are blended with black, from 0.0 (no effect) to 1.0 (black rows).
+    pub scanline_opacity: f32,
+}
+
+impl Default for CrtFilter {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            scanline_opacity: DEFAULT_SCANLINE_OPACITY,
+        }
+    }
+}
+
+/// Darkens the odd rows of the first `area_width` columns of a buffer by blending them with black at the given
+/// opacity (0.0-1.0), i.e. the RGB components are multiplied by (1.0 - opacity). The columns to the right of the
+/// area are left untouched.
+pub fn apply_crt_filter(buffer: &mut [u32], buffer_width: usize, area_width: usize, height: usize, opacity: f32) {
+    for row in (1..height).step_by(2) {
+        let row_start = row * buffer_width;
+        darken_pixels(&mut buffer[row_start..row_start + area_width], opacity);
+    }
+}
+
+/// Multiplies the RGB components of each pixel by (1.0 - opacity), rounding to the nearest value.
+pub(crate) fn darken_pixels(pixels: &mut [u32], opacity: f32) {
+    let factor = 1.0 - opacity.clamp(0.0, 1.0);
+    let darken = |component: u32| ((component as f32 * factor).round() as u32).min(0xFF);
+
+    for pixel in pixels.iter_mut() {
+        let red = darken((*pixel >> 16) & 0xFF);
+        let green = darken((*pixel >> 8) & 0xFF);
+        let blue = darken(*pixel & 0xFF);
+        *pixel = (red << 16) | (green << 8) | blue;
+    }
+}
 
 pub struct Screen {
     pub window: Window,
-    pub buffer: Box<[[u32; TOTAL_WINDOW_WIDTH]; TOTAL_WINDOW_HEIGHT]>,
+    /// Pixels of the whole emulator window, row by row: the game on the left and the tile data on the right.
+    pub buffer: Vec<u32>,
+    pub buffer_width: usize,
+    pub buffer_height: usize,
+    /// Each tile data pixel is drawn as a scale x scale block, it also sets the initial size of the window.
+    pub scale: usize,
+    pub scaling_mode: ScalingMode,
+    /// Color of the window background, which fills the borders around the game image when it doesn't fill the
+    /// whole game area and the space between the game and the tile data. Navy by default.
+    pub background_color: u32,
+    /// Debug option to draw a 1 pixel border around the game image, to check its position and size.
+    pub show_game_area_border: bool,
+    /// Shows the FPS, frame time, CPU cycles and LY on top of the game.
+    pub show_debug_overlay: bool,
+    pub crt_filter: CrtFilter,
+    /// Colors the game is displayed with, the tile data is always shown in grayscale.
+    pub palette: Palette,
+    /// Index in BUILTIN_PALETTES of the selected palette, used to cycle through them.
+    pub palette_index: usize,
+    /// Emulation speed selected with the number keys, shown in the window title.
+    pub speed: EmulationSpeed,
+    /// When the last frame was shown, used by the frame limiter.
+    last_frame_time: Instant,
+    title: String,
+}
+
+/// Speed of the emulation relative to the real Game Boy.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum EmulationSpeed {
+    Half,
+    Normal,
+    Double,
+    Triple,
+    Quadruple,
+    /// The frame limiter is disabled and the emulator runs as fast as possible.
+    Uncapped,
+}
+
+impl EmulationSpeed {
+    /// Returns the speed multiplier, None when uncapped.
+    pub fn get_multiplier(self) -> Option<f64> {
+        match self {
+            EmulationSpeed::Half => Some(0.5),
+            EmulationSpeed::Normal => Some(1.0),
+            EmulationSpeed::Double => Some(2.0),
+            EmulationSpeed::Triple => Some(3.0),
+            EmulationSpeed::Quadruple => Some(4.0),
+            EmulationSpeed::Uncapped => None,
+        }
+    }
+
+    /// Time each frame should take at this speed, None when uncapped.
+    pub fn get_frame_duration(self) -> Option<Duration> {
+        self.get_multiplier()
+            .map(|multiplier| Duration::from_secs_f64((1.0 / GAMEBOY_FPS) / multiplier))
+    }
+
+    /// Label shown in the window title, e.g. "2x".
+    pub fn get_label(self) -> &'static str {
+        match self {
+            EmulationSpeed::Half => "0.5x",
+            EmulationSpeed::Normal => "1x",
+            EmulationSpeed::Double => "2x",
+            EmulationSpeed::Triple => "3x",
+            EmulationSpeed::Quadruple => "4x",
+            EmulationSpeed::Uncapped => "Uncapped",
+        }
+    }
 }
 
 impl Screen {
-    pub fn new(title: String) -> Result<Screen, minifb::Error> {
-        let window: Window = Self::create_screen(title)?;
+    /// Opens the window at the default scale, its size is TOTAL_WINDOW_WIDTH x TOTAL_WINDOW_HEIGHT.
+    pub fn new(title: String) -> Result<Screen, EmulatorError> {
+        Self::with_scale(title, SCREEN_SCALE)
+    }
+
+    pub fn with_scale(title: String, scale: usize) -> Result<Screen, EmulatorError> {
+        let window: Window = Self::create_screen(&Self::get_title_with_speed(&title, EmulationSpeed::Normal), scale)?;
 
         Ok(Self {
             window,
-            buffer: Box::new([[0x000080; TOTAL_WINDOW_WIDTH]; TOTAL_WINDOW_HEIGHT]),
+            buffer: Self::create_buffer(scale),
+            buffer_width: Self::get_window_width(scale),
+            buffer_height: Self::get_window_height(scale),
+            scale,
+            scaling_mode: ScalingMode::Integer(scale as u8),
+            background_color: BACKGROUND_COLOR,
+            show_game_area_border: false,
+            show_debug_overlay: false,
+            crt_filter: CrtFilter::default(),
+            palette: Palette::default(),
+            palette_index: 0,
+            speed: EmulationSpeed::Normal,
+            last_frame_time: Instant::now(),
+            title,
         })
     }
 
+    /// Window title with the emulation speed as suffix, e.g. "Gameboy Emulator [2x]".
+    pub fn get_title_with_speed(title: &str, speed: EmulationSpeed) -> String {
+        format!("{} [{}]", title, speed.get_label())
+    }
+
+    /// Width in pixels of the tile data section for the given scale.
+    const fn get_tile_data_width(scale: usize) -> usize {
+        TILES_PER_ROW * TILE_SIZE * scale + (TILES_PER_ROW - 1) * TILE_MARGIN * scale
+    }
+
+    /// Height in pixels of the tile data section for the given scale.
+    const fn get_tile_data_height(scale: usize) -> usize {
+        TILES_PER_COL * TILE_SIZE * scale + (TILES_PER_COL - 1) * TILE_MARGIN * scale
+    }
+
+    /// Width in pixels of the whole emulator window for the given scale.
+    pub const fn get_window_width(scale: usize) -> usize {
+        (GAME_SECTION_WIDTH * scale) + MARGIN + Self::get_tile_data_width(scale)
+    }
+
+    /// Height in pixels of the whole emulator window for the given scale.
+    /// The window must accommodate both the full scaled game area and the tile data area.
+    pub const fn get_window_height(scale: usize) -> usize {
+        let game_area_height = GAME_SECTION_HEIGHT * scale;
+        let tile_data_height = Self::get_tile_data_height(scale);
+        if game_area_height > tile_data_height { game_area_height } else { tile_data_height }
+    }
+
+    /// Width of the game area, the part of the buffer on the left of the tile data section.
+    pub fn get_game_area_width(buffer_width: usize, scale: usize) -> usize {
+        buffer_width.saturating_sub(MARGIN + Self::get_tile_data_width(scale))
+    }
+
+    /// Creates the window buffer for the given scale filled with the background color.
+    pub(crate) fn create_buffer(scale: usize) -> Vec<u32> {
+        vec![BACKGROUND_COLOR; Self::get_window_width(scale) * Self::get_window_height(scale)]
+    }
+
+    /// Changes the scale, recreating the window and the buffer with the new size. The game is drawn with the same
+    /// integer multiplier. Values outside 1x-4x are ignored, as well as setting the current scale again.
+    pub fn set_scale(&mut self, scale: usize) -> Result<(), minifb::Error> {
+        if !(MIN_SCREEN_SCALE..=MAX_SCREEN_SCALE).contains(&scale) || scale == self.scale {
+            return Ok(());
+        }
+
+        self.window = Self::create_screen(&Self::get_title_with_speed(&self.title, self.speed), scale)?;
+        self.buffer = Self::create_buffer(scale);
+        self.buffer_width = Self::get_window_width(scale);
+        self.buffer_height = Self::get_window_height(scale);
+        self.scale = scale;
+        self.set_scaling_mode(ScalingMode::Integer(scale as u8));
+        Ok(())
+    }
+
+    pub fn set_scaling_mode(&mut self, mode: ScalingMode) {
+        self.scaling_mode = mode;
+    }
+
+    pub fn is_ctrl_down(&self) -> bool {
+        self.window.is_key_down(Key::LeftCtrl) || self.window.is_key_down(Key::RightCtrl)
+    }
+
+    /// Handles Ctrl + the number keys: 1-4 select the 1x-4x scale, 5 fits the game to the window and 6 fills it.
+    pub fn handle_scale_keys(&mut self) -> Result<(), minifb::Error> {
+        if !self.is_ctrl_down() {
+            return Ok(());
+        }
+
+        let scale_keys = [(Key::Key1, 1), (Key::Key2, 2), (Key::Key3, 3), (Key::Key4, 4)];
+
+        for (key, scale) in scale_keys {
+            if self.window.is_key_pressed(key, KeyRepeat::No) {
+                return self.set_scale(scale);
+            }
+        }
+
+        if self.window.is_key_pressed(Key::Key5, KeyRepeat::No) {
+            self.set_scaling_mode(ScalingMode::Fit);
+        } else if self.window.is_key_pressed(Key::Key6, KeyRepeat::No) {
+            self.set_scaling_mode(ScalingMode::Fill);
+        }
+        Ok(())
+    }
+
+    pub fn set_speed(&mut self, speed: EmulationSpeed) {
+        self.speed = speed;
+        self.window.set_title(&Self::get_title_with_speed(&self.title, speed));
+    }
+
+    /// Handles the number keys without Ctrl: 0 selects 0.5x speed, 1-4 select 1x-4x and 5 disables the frame limiter.
+    pub fn handle_speed_keys(&mut self) {
+        if self.is_ctrl_down() {
+            return;
+        }
+
+        let speed_keys = [
+            (Key::Key0, EmulationSpeed::Half),
+            (Key::Key1, EmulationSpeed::Normal),
+            (Key::Key2, EmulationSpeed::Double),
+            (Key::Key3, EmulationSpeed::Triple),
+            (Key::Key4, EmulationSpeed::Quadruple),
+            (Key::Key5, EmulationSpeed::Uncapped),
+        ];
+
+        for (key, speed) in speed_keys {
+            if self.window.is_key_pressed(key, KeyRepeat::No) {
+                self.set_speed(speed);
+                return;
+            }
+        }
+    }
+
+    /// Frame limiter: sleeps until the frame has taken the time it should at the current speed.
+    pub fn wait_for_next_frame(&mut self) {
+        if let Some(frame_duration) = self.speed.get_frame_duration() {
+            let elapsed = self.last_frame_time.elapsed();
+            if elapsed < frame_duration {
+                std::thread::sleep(frame_duration - elapsed);
+            }
+        }
+        self.last_frame_time = Instant::now();
+    }
+
+    /// Changes the colors the game is displayed with. When it is one of the builtin palettes, cycling with
+    /// the P key continues from it.
+    pub fn set_palette(&mut self, palette: Palette) {
+        if let Some(index) = BUILTIN_PALETTES.iter().position(|builtin| *builtin == palette) {
+            self.palette_index = index;
+        }
+        self.palette = palette;
+    }
+
+    /// Handles the P key, which cycles through the builtin palettes.
+    pub fn handle_palette_key(&mut self) {
+        if self.window.is_key_pressed(Key::P, KeyRepeat::No) {
+            let next_index = (self.palette_index + 1) % BUILTIN_PALETTES.len();
+            self.set_palette(BUILTIN_PALETTES[next_index]);
+        }
+    }
+
+    /// Handles the C key, which turns the CRT scanline filter on and off.
+    pub fn handle_crt_filter_key(&mut self) {
+        if self.window.is_key_pressed(Key::C, KeyRepeat::No) {
+            self.crt_filter.enabled = !self.crt_filter.enabled;
+        }
+    }
+
+    /// Changes the window background color, the whole buffer is cleared with it.
+    pub fn set_background_color(&mut self, color: u32) {
+        self.background_color = color;
+        self.buffer.fill(color);
+    }
+
+    /// Handles the B key, which shows or hides the debug border around the game image.
+    pub fn handle_game_area_border_key(&mut self) {
+        if self.window.is_key_pressed(Key::B, KeyRepeat::No) {
+            self.show_game_area_border = !self.show_game_area_border;
+        }
+    }
+
+    /// Handles the F1 key, which shows or hides the debug overlay with the performance statistics.
+    pub fn handle_debug_overlay_key(&mut self) {
+        if self.window.is_key_pressed(Key::F1, KeyRepeat::No) {
+            self.show_debug_overlay = !self.show_debug_overlay;
+        }
+    }
+
+    /// Resizes the buffer when the user resized the window, so the next render recomputes the scaling for the new size.
+    /// The buffer is never smaller than the tile data section, minifb stretches it if the window is smaller.
+    pub fn handle_window_resize(&mut self) {
+        let (window_width, window_height) = self.window.get_size();
+        let width = window_width.max(MARGIN + Self::get_tile_data_width(self.scale) + 1);
+        let height = window_height.max(Self::get_tile_data_height(self.scale));
+
+        if width != self.buffer_width || height != self.buffer_height {
+            self.buffer = vec![self.background_color; width * height];
+            self.buffer_width = width;
+            self.buffer_height = height;
+        }
+    }
+
     /// Updates the minifb window with the current buffer content.
     pub fn update_window_with_buffer(&mut self) {
-        let buffer: Vec<u32> = self.parse_2d_vector_to_1d();
         self.window
-            .update_with_buffer(&buffer, TOTAL_WINDOW_WIDTH, TOTAL_WINDOW_HEIGHT)
+            .update_with_buffer(&self.buffer, self.buffer_width, self.buffer_height)
             .unwrap();
     }
 
-    /// Render the current Game to the screen buffer with scaling applied.
+    /// Render the current Game to the screen buffer with the current palette and scaling mode applied.
+    /// When the CRT filter is enabled the scanlines are applied to the game area afterwards.
     pub fn render_game_to_screen_buffer(&mut self, cpu: &gameboy_core::cpu::Cpu) {
-        for row in 0..GAME_SECTION_HEIGHT {
-            for col in 0..GAME_SECTION_WIDTH {
-                // Apply scaling
-                for scale_row in 0..SCREEN_SCALE {
-                    for scale_col in 0..SCREEN_SCALE {
-                        let buffer_row = row * SCREEN_SCALE + scale_row;
-                        let buffer_col = col * SCREEN_SCALE + scale_col;
-
-                        if buffer_row < TOTAL_WINDOW_HEIGHT && buffer_col < TOTAL_WINDOW_WIDTH {
-                            self.buffer[buffer_row][buffer_col] = cpu.ppu.screen[row][col];
-                        }
-                    }
-                }
+        let area_width = Self::get_game_area_width(self.buffer_width, self.scale);
+
+        Screen::render_game_to_buffer(
+            &mut self.buffer,
+            self.buffer_width,
+            area_width,
+            self.buffer_height,
+            &self.palette.apply(&cpu.ppu.screen),
+            self.scaling_mode,
+            self.background_color,
+        );
+
+        if self.crt_filter.enabled {
+            // Only the game area is darkened, the tile data on the right is left untouched
+            apply_crt_filter(
+                &mut self.buffer,
+                self.buffer_width,
+                area_width,
+                self.buffer_height,
+                self.crt_filter.scanline_opacity,
+            );
+        }
+
+        if self.show_game_area_border {
+            let rect = self.scaling_mode.get_game_image_rect(area_width, self.buffer_height);
+            Screen::draw_game_area_border(
+                &mut self.buffer,
+                self.buffer_width,
+                &rect,
+                area_width,
+                self.buffer_height,
+                GAME_AREA_BORDER_COLOR,
+            );
+        }
+    }
+
+    /// Draws a 1 pixel border on the outermost pixels of the game image. Parts of the image clipped by the
+    /// game area (e.g. a big integer scale in a small window) are clipped from the border too.
+    pub(crate) fn draw_game_area_border(
+        buffer: &mut [u32],
+        buffer_width: usize,
+        rect: &GameImageRect,
+        area_width: usize,
+        area_height: usize,
+        color: u32,
+    ) {
+        // An empty image (e.g. a minimized window) or one entirely outside the game area has no border to draw
+        if rect.width == 0 || rect.height == 0 || rect.left >= area_width || rect.top >= area_height {
+            return;
+        }
+
+        let bottom = (rect.top + rect.height).min(area_height) - 1;
+        let right = (rect.left + rect.width).min(area_width) - 1;
+
+        for col in rect.left..=right {
+            buffer[rect.top * buffer_width + col] = color;
+            buffer[bottom * buffer_width + col] = color;
+        }
+        for row in rect.top..=bottom {
+            buffer[row * buffer_width + rect.left] = color;
+            buffer[row * buffer_width + right] = color;
+        }
+    }
+
+    /// Draws the 160x144 game screen into the game area on the left side of the buffer. The image is scaled and
+    /// centered according to the scaling mode and the rest of the game area is filled with the background color.
+    pub(crate) fn render_game_to_buffer(
+        buffer: &mut [u32],
+        buffer_width: usize,
+        area_width: usize,
+        area_height: usize,
+        screen: &[[u32; GAME_SECTION_WIDTH]; GAME_SECTION_HEIGHT],
+        scaling_mode: ScalingMode,
+        background_color: u32,
+    ) {
+        let rect = scaling_mode.get_game_image_rect(area_width, area_height);
+
+        for buffer_row in 0..area_height {
+            for buffer_col in 0..area_width {
+                let is_inside_image = (rect.top..rect.top + rect.height).contains(&buffer_row)
+                    && (rect.left..rect.left + rect.width).contains(&buffer_col);
+
+                buffer[buffer_row * buffer_width + buffer_col] = if is_inside_image {
+                    // Nearest neighbor sampling of the game pixel under this buffer pixel
+                    let row = (buffer_row - rect.top) * GAME_SECTION_HEIGHT / rect.height;
+                    let col = (buffer_col - rect.left) * GAME_SECTION_WIDTH / rect.width;
+                    screen[row][col]
+                } else {
+                    background_color
+                };
             }
         }
     }
 
     /// Renders tile data to the screen buffer for visualization and debugging purposes.
     /// Takes all 384 tiles from memory and arranges them in a grid (16 tiles wide × 24 tiles tall).
-    /// Each tile is 8×8 pixels and rendered with the Game Boy color palette.
+    /// Each tile is 8×8 pixels and rendered with the current BGP palette.
     pub fn render_tile_data_to_screen_buffer(&mut self, cpu: &gameboy_core::cpu::Cpu) {
         let tiles: [Tile; 384] = cpu.ppu.get_tiles_data(&cpu.memory_bus);
+        let bgp_register = cpu.memory_bus.read_byte(BGP);
 
-        // Starting position for tile data (next to the game screen with margin)
-        let start_col_offset = GAME_SECTION_WIDTH * SCREEN_SCALE + MARGIN;
-
-        for tile_index in 0..384 {
-            // Calculate the grid position of this tile (16 tiles per row)
-            let grid_row = tile_index / TILES_PER_ROW;
-            let grid_col = tile_index % TILES_PER_ROW;
+        Screen::render_tiles_to_buffer(&mut self.buffer, self.buffer_width, &tiles, bgp_register, self.scale);
+    }
 
-            let tile = &tiles[tile_index];
-            let pixels_block = Screen::parse_tile_to_8x8_pixels_block_color(tile);
+    /// Draws the 384 tiles into the tile data section of the buffer, which is on the right of the game area.
+    pub(crate) fn render_tiles_to_buffer(
+        buffer: &mut [u32],
+        buffer_width: usize,
+        tiles: &[Tile; 384],
+        bgp_register: u8,
+        scale: usize,
+    ) {
+        for (tile_index, tile) in tiles.iter().enumerate() {
+            let pixels_block = Screen::parse_tile_to_8x8_pixels_block_color(tile, bgp_register);
 
             // Render each pixel of the tile
             for tile_row in 0..TILE_SIZE {
                 for tile_col in 0..TILE_SIZE {
                     let color = pixels_block[tile_row][tile_col];
+                    let (pixel_row, pixel_col) = Screen::get_tile_pixel_buffer_position(
+                        tile_index,
+                        tile_row,
+                        tile_col,
+                        buffer_width,
+                        scale,
+                    );
 
-                    // Calculate the screen position with margins between tiles
-                    let screen_row = grid_row * (TILE_SIZE + TILE_MARGIN);
-                    let screen_col = grid_col * (TILE_SIZE + TILE_MARGIN);
-
-                    // Apply scaling and offset
-                    for scale_row in 0..SCREEN_SCALE {
-                        for scale_col in 0..SCREEN_SCALE {
-                            let buffer_row =
-                                screen_row * SCREEN_SCALE + tile_row * SCREEN_SCALE + scale_row;
-                            let buffer_col = start_col_offset
-                                + screen_col * SCREEN_SCALE
-                                + tile_col * SCREEN_SCALE
-                                + scale_col;
-
-                            if buffer_row < TOTAL_WINDOW_HEIGHT && buffer_col < TOTAL_WINDOW_WIDTH {
-                                self.buffer[buffer_row][buffer_col] = color;
-                            }
-                        }
+                    // Apply scaling, each tile pixel becomes a scale x scale block
+                    for scale_row in 0..scale {
+                        let buffer_start = (pixel_row + scale_row) * buffer_width + pixel_col;
+                        buffer[buffer_start..buffer_start + scale].fill(color);
                     }
                 }
             }
         }
     }
 
-    /// Parses the 2D buffer into a 1D vector for minifb window update.
-    fn parse_2d_vector_to_1d(&mut self) -> Vec<u32> {
-        self.buffer
-            .iter()
-            .flat_map(|row| row.iter())
-            .cloned()
-            .collect::<Vec<u32>>()
+    /// Returns the buffer (row, col) of the top-left corner of a scaled tile pixel in the tile data section.
+    /// Tiles are placed 16 per row with TILE_MARGIN pixels between them, the section is aligned to the right edge
+    /// of the buffer, MARGIN pixels after the game area.
+    pub(crate) fn get_tile_pixel_buffer_position(
+        tile_index: usize,
+        tile_row: usize,
+        tile_col: usize,
+        buffer_width: usize,
+        scale: usize,
+    ) -> (usize, usize) {
+        // Starting position for tile data (next to the game area with margin)
+        let start_col_offset = Self::get_game_area_width(buffer_width, scale) + MARGIN;
+
+        // Calculate the grid position of this tile (16 tiles per row)
+        let grid_row = tile_index / TILES_PER_ROW;
+        let grid_col = tile_index % TILES_PER_ROW;
+
+        // Calculate the unscaled position with margins between tiles
+        let screen_row = grid_row * (TILE_SIZE + TILE_MARGIN) + tile_row;
+        let screen_col = grid_col * (TILE_SIZE + TILE_MARGIN) + tile_col;
+
+        (screen_row * scale, start_col_offset + screen_col * scale)
     }
 
-    /// Creates a new window for the Gameboy emulator screen using minifb.
-    fn create_screen(title: String) -> Result<Window, minifb::Error> {
-        Window::new(
-            &title,
-            TOTAL_WINDOW_WIDTH,
-            TOTAL_WINDOW_HEIGHT,
-            WindowOptions::default(),
+    /// Renders the 40 OAM entries in a grid of 8 columns, where each object is drawn with its tile(s), flips and
+    /// OBP0/OBP1 palette. Below each object two lines show its X and Y position ("X08Y10") and its tile index and
+    /// attribute flags ("T01A90"), all in hexadecimal. The buffer has OAM_VIEW_WIDTH pixels per row.
+    pub fn render_oam_view(cpu: &gameboy_core::cpu::Cpu, buffer: &mut Vec<u32>) {
+        if buffer.len() < OAM_VIEW_WIDTH * OAM_VIEW_HEIGHT {
+            buffer.resize(OAM_VIEW_WIDTH * OAM_VIEW_HEIGHT, BACKGROUND_COLOR);
+        }
+        buffer[..OAM_VIEW_WIDTH * OAM_VIEW_HEIGHT].fill(BACKGROUND_COLOR);
+
+        let objects = Ppu::get_objects(&cpu.memory_bus);
+        let tiles: [Tile; 384] = cpu.ppu.get_tiles_data(&cpu.memory_bus);
+        let lcdc_register = LcdcRegister::get_lcdc_register(&cpu.memory_bus);
+        let object_height = lcdc_register.obj_size() as usize;
+
+        for (object_index, object) in objects.iter().enumerate() {
+            let cell_row = (object_index / OAM_VIEW_COLUMNS) * OAM_CELL_HEIGHT;
+            let cell_col = (object_index % OAM_VIEW_COLUMNS) * OAM_CELL_WIDTH;
+
+            for row in 0..object_height {
+                for col in 0..TILE_SIZE {
+                    let object_row = if object.attributes.y_flip { object_height - 1 - row } else { row };
+                    let object_col = if object.attributes.x_flip { TILE_SIZE - 1 - col } else { col };
+
+                    // In 8x16 mode the top tile is the index with bit 0 cleared and the bottom tile is the next one
+                    let tile_index = if object_height == 16 {
+                        (object.tile_index & 0xFE) + object_row / TILE_SIZE
+                    } else {
+                        object.tile_index
+                    };
+                    let pixel_value = tiles[tile_index].pixels[object_row % TILE_SIZE][object_col] as u8;
+
+                    // Color 0 is transparent for objects
+                    if pixel_value != 0 {
+                        let palette_index =
+                            cpu.memory_bus.decode_obp_palette(object.attributes.pallete as u8, pixel_value);
+                        buffer[(cell_row + 2 + row) * OAM_VIEW_WIDTH + cell_col + 2 + col] = COLORS[palette_index];
+                    }
+                }
+            }
+
+            let attributes = Screen::get_object_attributes_byte(object);
+            font::draw_text(buffer, OAM_VIEW_WIDTH, cell_row + 20, cell_col + 2, &format!("X{:02X}Y{:02X}", object.x, object.y), TEXT_COLOR);
+            font::draw_text(buffer, OAM_VIEW_WIDTH, cell_row + 27, cell_col + 2, &format!("T{:02X}A{:02X}", object.tile_index, attributes), TEXT_COLOR);
+        }
+    }
+
+    /// Returns a textual dump of an OAM entry with its position, tile index and decoded attribute flags.
+    pub fn format_oam_entry(obj: &Object) -> String {
+        let palette = match obj.attributes.pallete {
+            ObjectPallete::OBP0 => "OBP0",
+            ObjectPallete::OBP1 => "OBP1",
+        };
+
+        format!(
+            "Y: {}, X: {}, Tile: {:#04X}, Priority: {}, Y-Flip: {}, X-Flip: {}, Palette: {}",
+            obj.y,
+            obj.x,
+            obj.tile_index,
+            obj.attributes.priority as u8,
+            obj.attributes.y_flip as u8,
+            obj.attributes.x_flip as u8,
+            palette
         )
     }
 
-    /// Parses a Tile into an 8x8 block of u32 pixels, where the pixel value is then parsed to an actual color from the COLORS palette array.
-    fn parse_tile_to_8x8_pixels_block_color(tile: &Tile) -> [[u32; 8]; 8] {
+    /// Packs the decoded attribute flags of an object back into the OAM attributes byte (bits 7-4).
+    fn get_object_attributes_byte(obj: &Object) -> u8 {
+        ((obj.attributes.priority as u8) << 7)
+            | ((obj.attributes.y_flip as u8) << 6)
+            | ((obj.attributes.x_flip as u8) << 5)
+            | ((obj.attributes.pallete as u8) << 4)
+    }
+
+    /// Draws four color swatches for each palette register (BGP, OBP0 and OBP1), labeled with the shade names.
+    /// Each swatch is 16x16 pixels and uses the same COLORS value the PPU uses. The buffer has PALETTE_VIEW_WIDTH pixels per row.
+    pub fn render_palette_view(memory_bus: &MemoryBus, buffer: &mut Vec<u32>) {
+        if buffer.len() < PALETTE_VIEW_WIDTH * PALETTE_VIEW_HEIGHT {
+            buffer.resize(PALETTE_VIEW_WIDTH * PALETTE_VIEW_HEIGHT, BACKGROUND_COLOR);
+        }
+        buffer[..PALETTE_VIEW_WIDTH * PALETTE_VIEW_HEIGHT].fill(BACKGROUND_COLOR);
+
+        let palettes = [("BGP", BGP), ("OBP0", OBP0), ("OBP1", OBP1)];
+        for (palette_row, (name, address)) in palettes.iter().enumerate() {
+            let row = 4 + palette_row * PALETTE_ROW_HEIGHT;
+            let shades = Screen::get_palette_shades(memory_bus.read_byte(*address));
+
+            font::draw_text(buffer, PALETTE_VIEW_WIDTH, row + 6, 2, name, TEXT_COLOR);
+
+            for (color_index, &shade) in shades.iter().enumerate() {
+                let col = PALETTE_LABEL_WIDTH + color_index * PALETTE_SWATCH_COLUMN_WIDTH;
+
+                for swatch_row in 0..PALETTE_SWATCH_SIZE {
+                    let start = (row + swatch_row) * PALETTE_VIEW_WIDTH + col;
+                    buffer[start..start + PALETTE_SWATCH_SIZE].fill(COLORS[shade as usize]);
+                }
+
+                font::draw_text(
+                    buffer,
+                    PALETTE_VIEW_WIDTH,
+                    row + PALETTE_SWATCH_SIZE + 2,
+                    col,
+                    SHADE_NAMES[shade as usize],
+                    TEXT_COLOR,
+                );
+            }
+        }
+    }
+
+    /// Extracts the four 2 bits shade indices of a palette register, where index i is the shade of color i.
+    /// E.g. 0xE4 (11 10 01 00) returns [0, 1, 2, 3].
+    pub fn get_palette_shades(palette_byte: u8) -> [u8; 4] {
+        [0, 1, 2, 3].map(|color_index| decode_palette(palette_byte, color_index) as u8)
+    }
+
+    /// Creates a new window for the Gameboy emulator screen using minifb, sized for the given scale.
+    /// The window can be resized by the user, the game image is then scaled according to the scaling mode.
+    fn create_screen(title: &str, scale: usize) -> Result<Window, minifb::Error> {
+        let mut window = Window::new(
+            title,
+            Self::get_window_width(scale),
+            Self::get_window_height(scale),
+            WindowOptions {
+                resize: true,
+                ..WindowOptions::default()
+            },
+        )?;
+        // The frame rate is controlled by wait_for_next_frame, the minifb limiter would cap the uncapped speed
+        window.set_target_fps(0);
+        Ok(window)
+    }
+
+    /// Parses a Tile into an 8x8 block of u32 pixels, where the pixel value is mapped through the BGP register
+    /// and then parsed to an actual color from the COLORS palette array.
+    fn parse_tile_to_8x8_pixels_block_color(tile: &Tile, bgp_register: u8) -> [[u32; 8]; 8] {
         let mut pixels_block = [[0u32; 8]; 8];
         for row in 0..TILE_SIZE {
             for col in 0..TILE_SIZE {
-                let pixel_value = tile.pixels[row][col];
-                let color = COLORS[pixel_value as usize];
+                let pixel_value = tile.pixels[row][col] as u8;
+                let color = COLORS[decode_palette(bgp_register, pixel_value)];
 
                 pixels_block[row][col] = color;
             }
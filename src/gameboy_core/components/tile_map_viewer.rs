@@ -0,0 +1,165 @@
+use crate::gameboy_core::{
+    components::screen::Screen,
+    constants::{
+        BG_AND_WINDOW_MAP_SCREEN_SIZE, BG_AND_WINDOW_TILE_COUNT_PER_ROW_COL, COLORS,
+        GAME_SECTION_HEIGHT, GAME_SECTION_WIDTH, TILE_SIZE,
+    },
+    cpu::Cpu,
+    cpu_components::MemoryAccess,
+    ppu_components::LcdcRegister,
+    registers_contants::BGP,
+};
+
+/// Color of the rectangle showing the part of the background visible on the screen (SCX/SCY).
+const VIEWPORT_BORDER_COLOR: u32 = 0xFF0000;
+/// Color of the border around the tile selected with the arrow keys.
+const SELECTED_TILE_BORDER_COLOR: u32 = 0x0080FF;
+/// Size of each tile pixel when the selected tile is zoomed in, so the tile fills the whole 256x256 view.
+const ZOOMED_PIXEL_SIZE: usize = BG_AND_WINDOW_MAP_SCREEN_SIZE / TILE_SIZE;
+
+/// Renders the full 256x256 background tile map (32x32 tiles) at 1x scale for debugging purposes.
+/// Tiles are picked from the tile data and tile map areas selected by LCDC and colored with the BGP palette.
+/// The viewport (the 160x144 area at SCX/SCY) is drawn on top of it, and a single tile can be selected to be zoomed in.
+pub struct TileMapViewer {
+    pub buffer: Vec<u32>,
+    /// Row of the selected tile in the 32x32 tile map
+    pub selected_tile_row: usize,
+    /// Column of the selected tile in the 32x32 tile map
+    pub selected_tile_col: usize,
+    /// When true the selected tile is rendered filling the whole view instead of the tile map
+    pub is_zoomed: bool,
+}
+
+/// Information about the tile selected in the tile map viewer.
+#[derive(Debug, PartialEq)]
+pub struct SelectedTileInfo {
+    pub row: usize,
+    pub col: usize,
+    /// Tile index stored in the tile map at the selected position
+    pub tile_index: u8,
+    /// Shade (0-3) each color index is mapped to by the BGP register
+    pub palette: [u8; 4],
+}
+
+impl Default for TileMapViewer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl TileMapViewer {
+    pub fn new() -> Self {
+        Self {
+            buffer: vec![0; BG_AND_WINDOW_MAP_SCREEN_SIZE * BG_AND_WINDOW_MAP_SCREEN_SIZE],
+            selected_tile_row: 0,
+            selected_tile_col: 0,
+            is_zoomed: false,
+        }
+    }
+
+    /// Renders the tile map (or the zoomed selected tile) into the buffer.
+    pub fn render<M: MemoryAccess>(&mut self, cpu: &Cpu<M>) {
+        if self.is_zoomed {
+            self.render_zoomed_tile(cpu);
+        } else {
+            self.render_tile_map(cpu);
+            self.render_viewport_border(cpu);
+            self.render_selected_tile_border();
+        }
+    }
+
+    /// Moves the selected tile by the given number of rows and columns, wrapping around the 32x32 tile map.
+    pub fn move_selection(&mut self, row_delta: isize, col_delta: isize) {
+        let size = BG_AND_WINDOW_TILE_COUNT_PER_ROW_COL as isize;
+        self.selected_tile_row = (self.selected_tile_row as isize + row_delta).rem_euclid(size) as usize;
+        self.selected_tile_col = (self.selected_tile_col as isize + col_delta).rem_euclid(size) as usize;
+    }
+
+    pub fn toggle_zoom(&mut self) {
+        self.is_zoomed = !self.is_zoomed;
+    }
+
+    /// Returns the tile index and palette values of the selected tile.
+    pub fn get_selected_tile_info<M: MemoryAccess>(&self, cpu: &Cpu<M>) -> SelectedTileInfo {
+        let lcdc_register = LcdcRegister::get_lcdc_register(&cpu.memory_bus);
+        let tile_map = cpu.memory_bus.get_bg_tile_map(&lcdc_register);
+        let bgp_register = cpu.memory_bus.read_byte(BGP);
+
+        SelectedTileInfo {
+            row: self.selected_tile_row,
+            col: self.selected_tile_col,
+            tile_index: tile_map[self.selected_tile_row * BG_AND_WINDOW_TILE_COUNT_PER_ROW_COL + self.selected_tile_col],
+            palette: Screen::get_palette_shades(bgp_register),
+        }
+    }
+
+    /// Draws the 256x256 background built from the tile data and tile map areas selected by LCDC.
+    fn render_tile_map<M: MemoryAccess>(&mut self, cpu: &Cpu<M>) {
+        let lcdc_register = LcdcRegister::get_lcdc_register(&cpu.memory_bus);
+        let tiles = cpu.ppu.get_tiles(&cpu.memory_bus);
+        let bg_buffer = cpu.ppu.get_entire_bg_buffer(&cpu.memory_bus, &tiles, &lcdc_register);
+
+        for (row, bg_row) in bg_buffer.iter().enumerate() {
+            for (col, &color_index) in bg_row.iter().enumerate() {
+                self.buffer[row * BG_AND_WINDOW_MAP_SCREEN_SIZE + col] =
+                    COLORS[cpu.memory_bus.decode_bg_palette(color_index)];
+            }
+        }
+    }
+
+    /// Draws the selected tile enlarged to fill the whole view.
+    fn render_zoomed_tile<M: MemoryAccess>(&mut self, cpu: &Cpu<M>) {
+        let lcdc_register = LcdcRegister::get_lcdc_register(&cpu.memory_bus);
+        let tiles = cpu.ppu.get_tiles(&cpu.memory_bus);
+        let bg_buffer = cpu.ppu.get_entire_bg_buffer(&cpu.memory_bus, &tiles, &lcdc_register);
+
+        for row in 0..BG_AND_WINDOW_MAP_SCREEN_SIZE {
+            for col in 0..BG_AND_WINDOW_MAP_SCREEN_SIZE {
+                let bg_row = self.selected_tile_row * TILE_SIZE + row / ZOOMED_PIXEL_SIZE;
+                let bg_col = self.selected_tile_col * TILE_SIZE + col / ZOOMED_PIXEL_SIZE;
+                self.buffer[row * BG_AND_WINDOW_MAP_SCREEN_SIZE + col] =
+                    COLORS[cpu.memory_bus.decode_bg_palette(bg_buffer[bg_row][bg_col])];
+            }
+        }
+    }
+
+    /// Draws the border of the 160x144 area visible on the screen. The viewport starts at (SCY, SCX)
+    /// and wraps around the edges of the 256x256 background, just like the PPU does.
+    fn render_viewport_border<M: MemoryAccess>(&mut self, cpu: &Cpu<M>) {
+        let scy = cpu.memory_bus.get_scy_register() as usize;
+        let scx = cpu.memory_bus.get_scx_register() as usize;
+
+        self.render_rectangle_border(scy, scx, GAME_SECTION_HEIGHT, GAME_SECTION_WIDTH, VIEWPORT_BORDER_COLOR);
+    }
+
+    fn render_selected_tile_border(&mut self) {
+        self.render_rectangle_border(
+            self.selected_tile_row * TILE_SIZE,
+            self.selected_tile_col * TILE_SIZE,
+            TILE_SIZE,
+            TILE_SIZE,
+            SELECTED_TILE_BORDER_COLOR,
+        );
+    }
+
+    /// Draws a 1 pixel border of a rectangle, wrapping around the edges of the buffer.
+    fn render_rectangle_border(&mut self, top: usize, left: usize, height: usize, width: usize, color: u32) {
+        for offset in 0..width {
+            let col = (left + offset) % BG_AND_WINDOW_MAP_SCREEN_SIZE;
+            self.set_pixel(top, col, color);
+            self.set_pixel(top + height - 1, col, color);
+        }
+
+        for offset in 0..height {
+            let row = (top + offset) % BG_AND_WINDOW_MAP_SCREEN_SIZE;
+            self.set_pixel(row, left, color);
+            self.set_pixel(row, left + width - 1, color);
+        }
+    }
+
+    fn set_pixel(&mut self, row: usize, col: usize, color: u32) {
+        let row = row % BG_AND_WINDOW_MAP_SCREEN_SIZE;
+        let col = col % BG_AND_WINDOW_MAP_SCREEN_SIZE;
+        self.buffer[row * BG_AND_WINDOW_MAP_SCREEN_SIZE + col] = color;
+    }
+}
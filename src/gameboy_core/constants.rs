@@ -4,6 +4,24 @@ pub const MEMORY_SIZE: usize = 0x10000; // 65536 in decimal which is 64KB
 /// The initial value of the Program Counter (PC) at CPU start-up.
 pub const INITIAL_PC: u16 = 0x0100;
 
+/// Address of the cartridge type in the ROM header, it tells which Memory Bank Controller the cartridge uses.
+pub const CARTRIDGE_TYPE_ADDRESS: usize = 0x0147;
+
+/// Address of the RAM size in the ROM header, the number of external RAM banks of the cartridge.
+pub const RAM_SIZE_ADDRESS: usize = 0x0149;
+
+/// The cartridge header ends at 0x014F, smaller ROMs can't be valid.
+pub const ROM_HEADER_END: usize = 0x014F;
+
+/// Size of the ROM mapped at 0x0000-0x7FFF when the cartridge has no Memory Bank Controller.
+pub const ROM_WITHOUT_MBC_SIZE: usize = 0x8000;
+
+/// Size of each ROM bank (16 KiB). Bank 0 is mapped at $0000-$3FFF and the bank selected by the MBC at $4000-$7FFF.
+pub const ROM_BANK_SIZE: usize = 0x4000;
+
+/// End of the ROM region in the Gameboy memory map, writes to $0000-$7FFF go to the MBC registers.
+pub const ROM_END: u16 = 0x7FFF;
+
 /// Screen width of the Game Section in the overall emulator screen in pixels.
 pub const GAME_SECTION_WIDTH: usize = 160;
 
@@ -13,9 +31,20 @@ pub const GAME_SECTION_HEIGHT: usize = 144;
 /// Size of each tile in pixels (8x8).
 pub const TILE_SIZE: usize = 8; // 8x8 pixels per tile
 
-/// Scale factor for rendering the Game Section and tiles on the emulator screen. This helps to enlarge the display for better visibility.
+/// Scale factor used when the emulator starts for rendering the Game Section and tiles on the emulator screen.
+/// This helps to enlarge the display for better visibility, it can be changed at runtime with Ctrl + the number keys 1-4.
 pub const SCREEN_SCALE: usize = 3;
 
+/// Scale factors that can be selected at runtime (1x to 4x).
+pub const MIN_SCREEN_SCALE: usize = 1;
+pub const MAX_SCREEN_SCALE: usize = 4;
+
+/// T-cycles per frame: 456 T-cycles per scanline × 154 lines (144 visible + 10 of V-Blank).
+pub const T_CYCLES_PER_FRAME: u64 = 70224;
+
+/// Frames per second of the Game Boy LCD: 4,194,304 Hz clock / 70,224 T-cycles per frame.
+pub const GAMEBOY_FPS: f64 = 59.7275;
+
 /// The size of the map used for background and window rendering in pixels (256x256).
 pub const BG_AND_WINDOW_MAP_SCREEN_SIZE: usize = 256;
 /// Number of tiles per row and column in the background and window tile map (32x32).
@@ -39,15 +68,61 @@ pub const VRAM_START: u16 = 0x8000;
 /// End of the Video RAM (VRAM) region in the Gameboy memory map.
 pub const VRAM_END: u16 = 0x9FFF;
 
+/// Start of the External RAM region in the Gameboy memory map, the RAM of the cartridge mapped by the MBC.
+pub const EXTERNAL_RAM_START: u16 = 0xA000;
+
+/// End of the External RAM region in the Gameboy memory map.
+pub const EXTERNAL_RAM_END: u16 = 0xBFFF;
+
+/// Size of each external RAM bank (8 KiB), the bank selected by the MBC is mapped at $A000-$BFFF.
+pub const EXTERNAL_RAM_BANK_SIZE: usize = 0x2000;
+
+/// Start of the Work RAM (WRAM) bank 0, which is always mapped at $C000-$CFFF.
+pub const WRAM_BANK_0_START: u16 = 0xC000;
+
+/// Start of the switchable Work RAM (WRAM) bank region at $D000-$DFFF. On DMG this is always bank 1,
+/// on GBC the bank is selected through the SVBK register.
+pub const WRAM_BANK_N_START: u16 = 0xD000;
+
+/// End of the Work RAM (WRAM) region in the Gameboy memory map.
+pub const WRAM_END: u16 = 0xDFFF;
+
+/// Size of each Work RAM bank (4 KiB).
+pub const WRAM_BANK_SIZE: usize = 0x1000;
+
+/// Number of Work RAM banks available on GBC (bank 0 fixed + banks 1-7 switchable).
+pub const WRAM_BANK_COUNT: usize = 8;
+
 /// Start of the Object Attribute Memory (OAM) region in the Gameboy memory map.i
 pub const OAM_START: u16 = 0xFE00;
 
 /// End of the Object Attribute Memory (OAM) region in the Gameboy memory map.
 pub const OAM_END: u16 = 0xFE9F;
 
+/// Number of objects (sprites) in OAM, each one described by 4 bytes.
+pub const OAM_OBJECT_COUNT: usize = 40;
+
+/// Size of the OAM in bytes: 40 objects of 4 bytes.
+pub const OAM_SIZE: usize = OAM_OBJECT_COUNT * 4;
+
+/// Start of the IO registers, followed by HRAM and the IE register up to $FFFF.
+pub const IO_REGISTERS_START: u16 = 0xFF00;
+
+/// Dots (T-cycles) spent by the PPU in mode 2 (OAM Search) at the start of each visible scanline.
+pub const OAM_SEARCH_DOTS: u16 = 80;
+
+/// Shortest duration of mode 3 (Pixel Transfer). On real hardware it lasts up to 289 dots depending on the objects
+/// and the scroll, the emulator always uses the shortest one.
+pub const PIXEL_TRANSFER_MIN_DOTS: u16 = 172;
+
+/// Dot of the scanline where mode 0 (H-Blank) starts when Pixel Transfer takes the shortest duration.
+pub const HBLANK_START_MIN_DOTS: u16 = OAM_SEARCH_DOTS + PIXEL_TRANSFER_MIN_DOTS;
+
 // Tile data is stored in VRAM in the memory area at $8000-$97FF;
 pub const TILE_DATA_START: u16 = 0x8000;
 pub const TILE_DATA_END: u16 = 0x97FF;
+/// Size in bytes of the tile data, 384 tiles of 16 bytes.
+pub const TILE_DATA_SIZE: usize = (TILE_DATA_END - TILE_DATA_START + 1) as usize;
 
 // There are two possible addressing modes for BG and Window data:
 // 1. $8000-$8FFF: Unsigned indices (0 to 255)
@@ -69,6 +144,8 @@ pub const TILE_MAP_AREA_0_START: u16 = 0x9800;
 pub const TILE_MAP_AREA_0_END: u16 = 0x9BFF;
 pub const TILE_MAP_AREA_1_START: u16 = 0x9C00;
 pub const TILE_MAP_AREA_1_END: u16 = 0x9FFF;
+/// Size in bytes of each tile map, one tile index for each of the 32x32 tiles.
+pub const TILE_MAP_SIZE: usize = (TILE_MAP_AREA_0_END - TILE_MAP_AREA_0_START + 1) as usize;
 
 /// The address of the interrupt handlers.
 pub const VBLANK_INTERRUT_HANDLER_ADDRESS: u16 = 0x40;
@@ -83,4 +160,54 @@ pub const INTERRUPTS_HANDLERS_ADDRESSES: [u16; 5] = [
     TIMER_INTERRUPT_HANDLER_ADDRESS,
     SERIAL_INTERRUPT_HANDLER_ADDRESS,
     JOYPAD_INTERRUPT_HANDLER_ADDRESS,
-];
\ No newline at end of file
+];
+
+/// T-cycles taken by each instruction, indexed by opcode (Pan Docs timing table). Conditional jumps, calls and
+/// returns use the count when the branch is taken. Illegal opcodes are 0, except 0xE3 and 0xED which the emulator runs
+/// as NOP, and 0xCB is the 4 T-cycles of the prefix, the whole CB instruction is in CB_OPCODE_CYCLES.
+/// The instructions advance the clock themselves one machine cycle at a time, so their memory accesses stay
+/// interleaved with the PPU and the timer, `Cpu::execute` checks them against this table in debug builds.
+#[rustfmt::skip]
+pub const OPCODE_CYCLES: [u8; 256] = [
+//  x0  x1  x2  x3  x4  x5  x6  x7  x8  x9  xA  xB  xC  xD  xE  xF
+     4, 12,  8,  8,  4,  4,  8,  4, 20,  8,  8,  8,  4,  4,  8,  4, // 0x
+     4, 12,  8,  8,  4,  4,  8,  4, 12,  8,  8,  8,  4,  4,  8,  4, // 1x
+    12, 12,  8,  8,  4,  4,  8,  4, 12,  8,  8,  8,  4,  4,  8,  4, // 2x
+    12, 12,  8,  8, 12, 12, 12,  4, 12,  8,  8,  8,  4,  4,  8,  4, // 3x
+     4,  4,  4,  4,  4,  4,  8,  4,  4,  4,  4,  4,  4,  4,  8,  4, // 4x
+     4,  4,  4,  4,  4,  4,  8,  4,  4,  4,  4,  4,  4,  4,  8,  4, // 5x
+     4,  4,  4,  4,  4,  4,  8,  4,  4,  4,  4,  4,  4,  4,  8,  4, // 6x
+     8,  8,  8,  8,  8,  8,  4,  8,  4,  4,  4,  4,  4,  4,  8,  4, // 7x
+     4,  4,  4,  4,  4,  4,  8,  4,  4,  4,  4,  4,  4,  4,  8,  4, // 8x
+     4,  4,  4,  4,  4,  4,  8,  4,  4,  4,  4,  4,  4,  4,  8,  4, // 9x
+     4,  4,  4,  4,  4,  4,  8,  4,  4,  4,  4,  4,  4,  4,  8,  4, // Ax
+     4,  4,  4,  4,  4,  4,  8,  4,  4,  4,  4,  4,  4,  4,  8,  4, // Bx
+    20, 12, 16, 16, 24, 16,  8, 16, 20, 16, 16,  4, 24, 24,  8, 16, // Cx
+    20, 12, 16,  0, 24, 16,  8, 16, 20, 16, 16,  0, 24,  0,  8, 16, // Dx
+    12, 12,  8,  4,  0, 16,  8, 16, 16,  4, 16,  0,  0,  4,  8, 16, // Ex
+    12, 12,  8,  4,  0, 16,  8, 16, 12,  8, 16,  4,  0,  0,  8, 16, // Fx
+];
+
+/// T-cycles taken by each CB prefixed instruction, including the prefix, indexed by the second opcode byte.
+/// Instructions on registers take 8, BIT b, (HL) takes 12 and the other (HL) instructions take 16.
+#[rustfmt::skip]
+pub const CB_OPCODE_CYCLES: [u8; 256] = [
+//  x0  x1  x2  x3  x4  x5  x6  x7  x8  x9  xA  xB  xC  xD  xE  xF
+     8,  8,  8,  8,  8,  8, 16,  8,  8,  8,  8,  8,  8,  8, 16,  8, // 0x RLC/RRC
+     8,  8,  8,  8,  8,  8, 16,  8,  8,  8,  8,  8,  8,  8, 16,  8, // 1x RL/RR
+     8,  8,  8,  8,  8,  8, 16,  8,  8,  8,  8,  8,  8,  8, 16,  8, // 2x SLA/SRA
+     8,  8,  8,  8,  8,  8, 16,  8,  8,  8,  8,  8,  8,  8, 16,  8, // 3x SWAP/SRL
+     8,  8,  8,  8,  8,  8, 12,  8,  8,  8,  8,  8,  8,  8, 12,  8, // 4x BIT
+     8,  8,  8,  8,  8,  8, 12,  8,  8,  8,  8,  8,  8,  8, 12,  8, // 5x BIT
+     8,  8,  8,  8,  8,  8, 12,  8,  8,  8,  8,  8,  8,  8, 12,  8, // 6x BIT
+     8,  8,  8,  8,  8,  8, 12,  8,  8,  8,  8,  8,  8,  8, 12,  8, // 7x BIT
+     8,  8,  8,  8,  8,  8, 16,  8,  8,  8,  8,  8,  8,  8, 16,  8, // 8x RES
+     8,  8,  8,  8,  8,  8, 16,  8,  8,  8,  8,  8,  8,  8, 16,  8, // 9x RES
+     8,  8,  8,  8,  8,  8, 16,  8,  8,  8,  8,  8,  8,  8, 16,  8, // Ax RES
+     8,  8,  8,  8,  8,  8, 16,  8,  8,  8,  8,  8,  8,  8, 16,  8, // Bx RES
+     8,  8,  8,  8,  8,  8, 16,  8,  8,  8,  8,  8,  8,  8, 16,  8, // Cx SET
+     8,  8,  8,  8,  8,  8, 16,  8,  8,  8,  8,  8,  8,  8, 16,  8, // Dx SET
+     8,  8,  8,  8,  8,  8, 16,  8,  8,  8,  8,  8,  8,  8, 16,  8, // Ex SET
+     8,  8,  8,  8,  8,  8, 16,  8,  8,  8,  8,  8,  8,  8, 16,  8, // Fx SET
+];
+
@@ -1,9 +1,17 @@
+use alloc::{boxed::Box, collections::BTreeSet, format, string::String, vec::Vec};
+#[cfg(feature = "std")]
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
 use crate::gameboy_core::{
     constants::{
-        EIGHT_BIT_REGISTERS, GAME_SECTION_HEIGHT, GAME_SECTION_WIDTH,
-        INTERRUPTS_HANDLERS_ADDRESSES, SIXTEEN_BIT_REGISTERS,
+        CARTRIDGE_TYPE_ADDRESS, COLORS, EIGHT_BIT_REGISTERS, GAME_SECTION_HEIGHT, GAME_SECTION_WIDTH,
+        INTERRUPTS_HANDLERS_ADDRESSES, MEMORY_SIZE, ROM_HEADER_END, ROM_WITHOUT_MBC_SIZE, SIXTEEN_BIT_REGISTERS,
+        T_CYCLES_PER_FRAME,
     },
-    cpu_components::{CpuRegisters, MemoryBus},
+    cpu_components::{CpuRegisters, MemoryAccess, MemoryBus},
     cpu_instructions::{
         cpu_8bit_arithmetic_logical_instructions::Cpu8BitArithmeticLogicalInstructions,
         cpu_8bit_transfer_input_output_instructions::Cpu8BitTransferInputOutputInstructions,
@@ -16,15 +24,55 @@ use crate::gameboy_core::{
         cpu_rotate_shift_instructions::CpuRotateShiftInstructions,
     },
     cpu_utils,
+    disassembler::InstructionIterator,
+    emulator_error::EmulatorError,
     interrupts::InterruptsHandler,
+    mbc,
     ppu::Ppu,
     registers_contants::{*},
     timer::Timer,
 };
 
-pub struct Cpu {
+/// Maximum number of instructions `step_over` runs while waiting for a CALL to return,
+/// so a subroutine that never returns doesn't freeze the debugger.
+const STEP_OVER_MAX_INSTRUCTIONS: u64 = 1_000_000;
+
+/// Outcome of a single CPU tick, used by the debugger to know what happened.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum TickResult {
+    /// An instruction was fetched and executed.
+    Executed,
+    /// An interrupt was dispatched instead of executing an instruction.
+    InterruptHandled,
+    /// The CPU is in halt mode waiting for an interrupt.
+    Halted,
+    /// The CPU is stopped by STOP waiting for a button press.
+    Stopped,
+    /// The instruction was executed and PC landed on a breakpoint.
+    BreakpointHit(u16),
+}
+
+/// How HALT behaves, decided by IME and the pending interrupts when the opcode is executed.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum HaltMode {
+    /// The CPU stops until an interrupt is pending. If one is already pending it doesn't stop at all.
+    Normal,
+    /// HALT with IME disabled and an interrupt pending: the CPU doesn't stop, but PC is not incremented after the
+    /// next opcode fetch, so the byte after HALT is read twice.
+    HaltBug,
+}
+
+/// Handler of an opcode in the decode table. It receives the opcode since the handlers of instruction groups decode
+/// their registers or condition from it.
+type OpcodeHandler<M> = fn(&mut Cpu<M>, u8);
+
+/// The CPU is generic over the memory bus so tests can inject a mock and cartridges with an MBC can provide
+/// their own mapping. It defaults to `MemoryBus`, the flat Game Boy address space.
+/// Cloning forks the emulation: the clone continues from the same state independently of the original.
+#[derive(Clone)]
+pub struct Cpu<M: MemoryAccess = MemoryBus> {
     pub registers: CpuRegisters,
-    pub memory_bus: MemoryBus,
+    pub memory_bus: M,
     pub is_debug_mode: bool,
     pub ppu: Ppu,
     /// Total number of clock cycles since the CPU started. This value is related to the Master Clock (M) cycles.
@@ -37,14 +85,159 @@ pub struct Cpu {
     pub timer: Timer,
     pub interrupts_handler: InterruptsHandler,
     pub is_halt_mode: bool,
+    /// Set by HALT when it triggers the HALT bug, the next fetch doesn't increment PC.
+    pub is_halt_bug_pending: bool,
+    /// Set by STOP, no instruction is executed until the joypad interrupt is requested by a button press.
+    pub is_stop_mode: bool,
+    /// Game Boy Color mode, where STOP performs the speed switch requested in KEY1. Only the DMG is emulated so it
+    /// is always false for now.
+    pub cgb_mode: bool,
+    /// Copy of the loaded ROM, kept so the memory bus can be restored on a hard reset.
+    rom_binary: Vec<u8>,
+    /// Save file of the cartridge RAM, next to the ROM loaded by `load_rom_from_file`.
+    #[cfg(feature = "std")]
+    save_path: Option<PathBuf>,
+    /// Addresses where the debugger should stop, checked after each tick.
+    pub breakpoints: BTreeSet<u16>,
+    /// Number of times each opcode was executed, only counted after `enable_profiling` is called.
+    profiler: Option<Box<[u64; 256]>>,
+    /// Number of times each CB prefixed opcode was executed, only counted after `enable_profiling` is called.
+    cb_frequencies: Option<Box<[u64; 256]>>,
+}
+
+/// Defaults to the power-on state, same as `Cpu::new`.
+impl Default for Cpu {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl Cpu {
     /// Creates a new instance of the CPU with default values in the registers.
     pub fn new() -> Self {
+        Self::with_memory_bus(MemoryBus::new())
+    }
+
+    /// Start the emulator with the provided ROM binary data.
+    ///
+    /// # Errors
+    /// Returns `EmulatorError::InvalidRom` if the ROM doesn't have a full cartridge header and
+    /// `EmulatorError::UnsupportedMbc` if it is too big to be mapped without a Memory Bank Controller and its MBC is
+    /// not implemented.
+    pub fn start(rom_binary: Vec<u8>, is_debug_mode: bool) -> Result<Self, EmulatorError> {
+        Self::validate_rom(&rom_binary)?;
+
+        let mut cpu = Self::new();
+        cpu.load_rom(rom_binary);
+        cpu.initialize_memory_registers();
+        #[cfg(feature = "std")]
+        println!("LCDC Register {:0b}", cpu.memory_bus.get_lcdc_register());
+        cpu.is_debug_mode = is_debug_mode;
+        Ok(cpu)
+    }
+
+    /// Reads the ROM file and loads it, installing the Memory Bank Controller of its cartridge type.
+    /// It replaces `Cpu::start(cpu_utils::read_rom(path)?, false)` when the CPU already exists.
+    /// When the cartridge has a battery, its RAM is restored from the `.sav` file next to the ROM if there is one.
+    ///
+    /// # Errors
+    /// Returns `EmulatorError::IoError` if the ROM or the save file cannot be read, and the errors of `Cpu::start` if
+    /// the ROM can't be loaded. The CPU is left untouched when the ROM can't be loaded.
+    #[cfg(feature = "std")]
+    pub fn load_rom_from_file(&mut self, path: &Path) -> Result<(), EmulatorError> {
+        let rom_binary = fs::read(path)?;
+        Self::validate_rom(&rom_binary)?;
+
+        self.load_rom(rom_binary);
+        self.initialize_memory_registers();
+
+        let save_path = path.with_extension("sav");
+        if self.memory_bus.has_battery() && save_path.exists() {
+            self.memory_bus.load_ram(&save_path)?;
+        }
+        self.save_path = Some(save_path);
+        Ok(())
+    }
+
+    /// Writes the cartridge RAM to the `.sav` file next to the ROM, so the game progress is kept when the emulator
+    /// exits. Does nothing for cartridges without battery and for ROMs not loaded from a file.
+    ///
+    /// # Errors
+    /// Returns `EmulatorError::IoError` if the save file cannot be written.
+    #[cfg(feature = "std")]
+    pub fn save_game(&self) -> Result<(), EmulatorError> {
+        if let Some(save_path) = &self.save_path
+            && self.memory_bus.has_battery()
+        {
+            self.memory_bus.save_ram(save_path)?;
+        }
+        Ok(())
+    }
+
+    /// Checks the ROM can be loaded: it must contain the cartridge header, and unless its Memory Bank Controller is
+    /// implemented, it must fit in the 32KB ROM area.
+    fn validate_rom(rom_binary: &[u8]) -> Result<(), EmulatorError> {
+        if rom_binary.len() <= ROM_HEADER_END {
+            return Err(EmulatorError::InvalidRom(format!(
+                "the ROM has {} bytes, too small to contain the cartridge header",
+                rom_binary.len()
+            )));
+        }
+
+        if rom_binary.len() > ROM_WITHOUT_MBC_SIZE && !mbc::is_mbc_implemented(rom_binary[CARTRIDGE_TYPE_ADDRESS]) {
+            return match rom_binary[CARTRIDGE_TYPE_ADDRESS] {
+                0x00 => Err(EmulatorError::InvalidRom(format!(
+                    "the ROM has {} bytes, but cartridges without a memory bank controller have at most {} bytes",
+                    rom_binary.len(),
+                    ROM_WITHOUT_MBC_SIZE
+                ))),
+                cartridge_type => Err(EmulatorError::UnsupportedMbc(cartridge_type)),
+            };
+        }
+
+        Ok(())
+    }
+
+    /// Hard reset: brings the whole system back to its power-on state, as if the Game Boy was turned off and on again.
+    /// Registers, memory, PPU, timer and cycle counters are reinitialized and only the loaded ROM is kept, along
+    /// with the cartridge RAM when a battery keeps it.
+    pub fn reset(&mut self) {
+        let rom_binary = core::mem::take(&mut self.rom_binary);
+        let is_debug_mode = self.is_debug_mode;
+        let breakpoints = core::mem::take(&mut self.breakpoints);
+        #[cfg(feature = "std")]
+        let save_path = self.save_path.take();
+        let save_data = self.memory_bus.get_save_data();
+
+        *self = Self::new();
+        self.load_rom(rom_binary);
+        self.initialize_memory_registers();
+        if let Some(save_data) = save_data {
+            self.memory_bus.load_save_data(&save_data);
+        }
+        self.is_debug_mode = is_debug_mode;
+        self.breakpoints = breakpoints;
+        #[cfg(feature = "std")]
+        {
+            self.save_path = save_path;
+        }
+    }
+
+    fn load_rom(&mut self, rom_binary: Vec<u8>) {
+        self.memory_bus.copy_from_binary(rom_binary.clone());
+        self.rom_binary = rom_binary;
+    }
+}
+
+impl<M: MemoryAccess> Cpu<M> {
+    /// Handlers of the 256 opcodes, see `build_opcode_table`.
+    const OPCODE_TABLE: [OpcodeHandler<M>; 256] = Self::build_opcode_table();
+
+    /// Creates a new instance of the CPU using the provided memory bus, e.g. a mock in tests or a cartridge with an MBC.
+    pub fn with_memory_bus(memory_bus: M) -> Self {
         Self {
             registers: CpuRegisters::new(),
-            memory_bus: MemoryBus::new(),
+            memory_bus,
             is_debug_mode: false,
             clock_cycles: 0,
             ime: false,
@@ -55,6 +248,15 @@ impl Cpu {
             timer: Timer::new(),
             interrupts_handler: InterruptsHandler {},
             is_halt_mode: false,
+            is_halt_bug_pending: false,
+            is_stop_mode: false,
+            cgb_mode: false,
+            rom_binary: Vec::new(),
+            #[cfg(feature = "std")]
+            save_path: None,
+            breakpoints: BTreeSet::new(),
+            profiler: None,
+            cb_frequencies: None,
         }
     }
 
@@ -63,7 +265,7 @@ impl Cpu {
         self.memory_bus.write_byte(P1, 0xCF);
         self.memory_bus.write_byte(SB, 0x00);
         self.memory_bus.write_byte(SC, 0x7E);
-        self.memory_bus.write_byte(DIV, 0xAB);
+        self.memory_bus.set_div_register(0xAB);
         self.memory_bus.write_byte(TIMA, 0x00);
         self.memory_bus.write_byte(TMA, 0x00);
         self.memory_bus.write_byte(TAC, 0xF8);
@@ -95,59 +297,244 @@ impl Cpu {
         self.memory_bus.set_scx_register(0x00);
         self.memory_bus.write_byte(LY, 0x00);
         self.memory_bus.write_byte(LYC, 0x00);
-        self.memory_bus.write_byte(DMA, 0xFF);
+        // DMA ($FF46) already reads 0xFF, writing it would start an OAM DMA transfer
         self.memory_bus.set_bgp_register(0xFC);
         self.memory_bus.write_byte(OBP0, 0xE4); // Standard default: 11 10 01 00
         self.memory_bus.write_byte(OBP1, 0xE4); // Standard default: 11 10 01 00
-        self.memory_bus.write_byte(WY, 0x00);
-        self.memory_bus.write_byte(WX, 0x00);
+        self.memory_bus.set_wy_register(0x00);
+        self.memory_bus.set_wx_register(0x00);
         self.memory_bus.write_byte(IE, 0x00);
 
         // Other registers can be initialized here as needed
     }
 
-    /// Start the emulator with the provided ROM binary data.
-    pub fn start(rom_binary: Vec<u8>, is_debug_mode: bool) -> Self {
-        let mut cpu = Self::new();
-        cpu.load_rom(rom_binary);
-        cpu.initialize_memory_registers();
-        println!("LCDC Register {:0b}", cpu.memory_bus.get_lcdc_register());
-        cpu.is_debug_mode = is_debug_mode;
-        return cpu;
+    /// Soft reset: only the CPU registers and its interrupt/halt state go back to their power-on values,
+    /// WRAM, VRAM and the rest of the memory are left intact. Execution restarts from $0100.
+    pub fn soft_reset(&mut self) {
+        self.registers = CpuRegisters::new();
+        self.ime = false;
+        self.di_instruction_pending = false;
+        self.ei_instruction_pending = false;
+        self.is_halt_mode = false;
+        self.is_halt_bug_pending = false;
+        self.is_stop_mode = false;
+    }
+
+    /// Starts counting how many times each opcode is executed, useful to find which instructions to optimize.
+    /// Calling it again keeps the current counts.
+    pub fn enable_profiling(&mut self) {
+        self.profiler.get_or_insert_with(|| Box::new([0; 256]));
+        self.cb_frequencies.get_or_insert_with(|| Box::new([0; 256]));
+    }
+
+    /// Execution count of each opcode, indexed by opcode. None if profiling is not enabled.
+    pub fn get_opcode_frequencies(&self) -> Option<&[u64; 256]> {
+        self.profiler.as_deref()
+    }
+
+    /// Execution count of each CB prefixed opcode, indexed by the opcode after 0xCB. None if profiling is not enabled.
+    pub fn get_cb_opcode_frequencies(&self) -> Option<&[u64; 256]> {
+        self.cb_frequencies.as_deref()
+    }
+
+    /// Returns the N most executed opcodes with their count, sorted from the most executed.
+    /// Opcodes with the same count are sorted by opcode, and opcodes never executed are left out.
+    pub fn get_top_opcodes(&self, n: usize) -> Vec<(u8, u64)> {
+        let Some(frequencies) = self.get_opcode_frequencies() else {
+            return Vec::new();
+        };
+
+        let mut opcodes: Vec<(u8, u64)> = frequencies
+            .iter()
+            .enumerate()
+            .filter(|(_, count)| **count > 0)
+            .map(|(opcode, count)| (opcode as u8, *count))
+            .collect();
+        opcodes.sort_by(|(opcode_a, count_a), (opcode_b, count_b)| {
+            count_b.cmp(count_a).then(opcode_a.cmp(opcode_b))
+        });
+        opcodes.truncate(n);
+        opcodes
+    }
+
+    /// Returns the registers as a JSON object for external debug tools, see `cpu_utils::format_registers_as_json`.
+    pub fn serialize_registers_as_json(&self) -> String {
+        cpu_utils::format_registers_as_json(&self.registers)
+    }
+
+    /// Returns a copy of `length` bytes of memory starting at `start`, read through the memory bus.
+    /// The dump stops at 0xFFFF, it doesn't wrap around.
+    pub fn get_memory_dump(&self, start: u16, length: usize) -> Vec<u8> {
+        let end = (start as usize + length).min(MEMORY_SIZE);
+        (start as usize..end).map(|address| self.memory_bus.read_byte(address as u16)).collect()
+    }
+
+    /// Statically decodes the instructions starting at PC without executing them, see `iter_instructions_from`.
+    pub fn iter_instructions(&self) -> impl Iterator<Item = (u16, u8, Vec<u8>)> + '_ {
+        Self::iter_instructions_from(&self.memory_bus, self.registers.pc)
+    }
+
+    /// Statically decodes the instructions in memory starting at `start`, yielding (address, opcode, operand bytes).
+    /// CB prefixed instructions are yielded with 0xCB as opcode and the second opcode byte as operand.
+    /// The iteration stops after a HALT or when the address would wrap around 0xFFFF.
+    pub fn iter_instructions_from(memory_bus: &M, start: u16) -> impl Iterator<Item = (u16, u8, Vec<u8>)> + '_ {
+        InstructionIterator::new(memory_bus, start)
     }
 
     /// Perform a single CPU tick: fetch, decode, and execute one instruction.
-    pub fn tick(&mut self) {
+    pub fn tick(&mut self) -> TickResult {
+        if self.is_stop_mode {
+            if !self.is_joypad_interrupt_requested() {
+                // The timers and the PPU are stopped too, the clock still advances so run_frame returns and the
+                // frontend keeps reading the buttons
+                self.clock_cycles += 4;
+                return TickResult::Stopped;
+            }
+            self.is_stop_mode = false;
+        }
+
         self.executed_instructions_count += 1;
 
         let opcode = self.fetch_opcode();
 
         // For whatever reason Dr Game boy doensn´t log interrupts handlers addresses, this is just to match their logs
         if !INTERRUPTS_HANDLERS_ADDRESSES.contains(&self.registers.pc) && self.is_debug_mode {
-            cpu_utils::log(self, opcode).unwrap();
+            cpu_utils::log(self, opcode);
         }
 
         let interrupt_triggered = self.handle_interrupts();
         if interrupt_triggered {
-            return;
+            return TickResult::InterruptHandled;
         }
 
-        if !self.is_halt_mode {
-            self.registers.increment_pc();
+        let is_halted = self.is_halt_mode;
+        if !is_halted {
+            if self.is_halt_bug_pending {
+                // HALT bug: PC is not incremented, so this opcode will be fetched again on the next tick
+                self.is_halt_bug_pending = false;
+            } else {
+                self.registers.increment_pc();
+            }
             self.execute(opcode);
         } else {
             // When in halt mode the CPU still consumes cycles
-            self.increment_4_cycles_update_timers_and_ppu();
+            self.increment_cycles(4);
         }
 
         self.enable_ime_if_ei_instruction_pending(opcode);
         self.disable_ime_if_di_instruction_pending(opcode);
+
+        if is_halted {
+            TickResult::Halted
+        } else if self.breakpoints.contains(&self.registers.pc) {
+            TickResult::BreakpointHit(self.registers.pc)
+        } else {
+            TickResult::Executed
+        }
+    }
+
+    /// Runs instructions until the clock reaches the end of the current frame. Frames are aligned to multiples of
+    /// `T_CYCLES_PER_FRAME` cycles, so when the last instruction of a frame goes past the boundary the extra cycles
+    /// are taken from the next frame and the frame rate doesn't drift.
+    pub fn run_frame(&mut self) {
+        let end_of_frame = (self.clock_cycles / T_CYCLES_PER_FRAME + 1) * T_CYCLES_PER_FRAME;
+        while self.clock_cycles < end_of_frame {
+            self.tick();
+        }
+    }
+
+    /// Debugger step into: executes a single instruction, following CALLs into the subroutine.
+    pub fn step_into(&mut self) -> TickResult {
+        self.tick()
+    }
+
+    /// Debugger step over: when the current instruction is a CALL, it sets a one-shot breakpoint at the return
+    /// address (PC + 3) and runs until a breakpoint is hit, so the whole subroutine is executed in one step.
+    /// Any other instruction is executed with a single tick.
+    pub fn step_over(&mut self) -> TickResult {
+        let opcode = self.fetch_opcode();
+        if !Self::is_call_instruction(opcode) {
+            return self.tick();
+        }
+
+        let return_address = self.registers.pc.wrapping_add(3);
+        // Don't remove a breakpoint the user already had at the return address
+        let is_one_shot_breakpoint = self.breakpoints.insert(return_address);
+
+        let mut result = self.tick();
+        for _ in 1..STEP_OVER_MAX_INSTRUCTIONS {
+            if matches!(result, TickResult::BreakpointHit(_)) {
+                break;
+            }
+            result = self.tick();
+        }
+
+        if is_one_shot_breakpoint {
+            self.breakpoints.remove(&return_address);
+        }
+        result
+    }
+
+    /// CALL imm16 (0xCD) and CALL cc, imm16 (0xC4, 0xCC, 0xD4, 0xDC).
+    fn is_call_instruction(opcode: u8) -> bool {
+        opcode == 0b11001101 || (opcode & 0b11100111) == 0b11000100
     }
 
     fn fetch_opcode(&mut self) -> u8 {
         self.memory_bus.read_byte(self.registers.pc)
     }
 
+    /// Executes the instruction of the opcode with its handler from `OPCODE_TABLE`.
+    /// In debug builds the T-cycles it took are checked against the timing tables.
+    pub fn execute(&mut self, opcode: u8) {
+        if let Some(frequencies) = self.profiler.as_mut() {
+            frequencies[opcode as usize] += 1;
+        }
+
+        #[cfg(debug_assertions)]
+        let (expected_t_cycles, cycles_before) = (self.expected_t_cycles(opcode), self.clock_cycles);
+
+        Self::OPCODE_TABLE[opcode as usize](self, opcode);
+
+        #[cfg(debug_assertions)]
+        debug_assert_eq!(
+            self.clock_cycles - cycles_before,
+            expected_t_cycles,
+            "Opcode 0x{:02X} took a different number of T-cycles than its timing table entry",
+            opcode
+        );
+    }
+
+    /// T-cycles of the instruction about to be executed, from the timing tables of `disassembler::decode_opcode`.
+    /// After the 0xCB prefix the second opcode byte is read at PC, conditional branches use the count of the
+    /// condition as it is before executing.
+    #[cfg(debug_assertions)]
+    fn expected_t_cycles(&self, opcode: u8) -> u64 {
+        use crate::gameboy_core::disassembler;
+
+        let info = match opcode {
+            0xCB => disassembler::decode_opcode(self.memory_bus.read_byte(self.registers.pc), true),
+            _ => disassembler::decode_opcode(opcode, false),
+        };
+
+        match info.cycles_not_taken {
+            Some(cycles_not_taken) if !self.check_cc_condition(opcode) => cycles_not_taken as u64,
+            _ => info.cycles_taken as u64,
+        }
+    }
+
+    /// Builds the handlers of the 256 opcodes at compile time, so `execute` dispatches with an indexed call instead
+    /// of going through the whole match of `decode_opcode`.
+    const fn build_opcode_table() -> [OpcodeHandler<M>; 256] {
+        let mut table = [Self::unimplemented_opcode as OpcodeHandler<M>; 256];
+        let mut opcode = 0;
+        while opcode < table.len() {
+            table[opcode] = Self::decode_opcode(opcode as u8);
+            opcode += 1;
+        }
+        table
+    }
+
     // The first byte of each instruction is typically called the “opcode” (for “operation code”).
     // By noticing that some instructions perform identical operations but with different parameters, they can be grouped together;
     // for example, inc bc, inc de, inc hl, and inc sp differ only in what 16-bit register they modify.
@@ -166,210 +553,206 @@ impl Cpu {
     // imm8	The following byte
     // imm16	The following two bytes, in little-endian order
     // Table of opcodes: https://gbdev.io/pandocs/CPU_Instruction_Set.html
-    pub fn execute(&mut self, opcode: u8) {
+    /// Returns the handler of the opcode. Instruction groups share a handler that decodes its operands from the
+    /// opcode, and the CB prefix is handled by `execute_cb_prefix_instructions` which reads the second opcode byte.
+    /// A new instruction only needs its arm here, `OPCODE_TABLE` picks it up.
+    const fn decode_opcode(opcode: u8) -> OpcodeHandler<M> {
         match opcode {
-            0x00 | 0xE3 | 0xED => self.nop(), // NOP
-            0x10 => self.stop(),              // STOP
-            0x76 => self.halt(),              // HALT
+            0x00 | 0xE3 | 0xED => |cpu, _| cpu.nop(), // NOP
+            0x10 => |cpu, _| cpu.stop(),              // STOP
+            0x76 => |cpu, _| cpu.halt(),              // HALT
 
             // 8-Bit Transfer and Input/Output Instructions
-            v if (v & 0b11000111) == 0b01000110 && Cpu::destination_is_8bit_register(opcode) => {
-                self.ld_r8_hl(opcode)
+            v if (v & 0b11000111) == 0b01000110 && Self::destination_is_8bit_register(opcode) => {
+                Self::ld_r8_hl
             }
-            v if (v & 0b11111000) == 0b01110000 && Cpu::source_is_8bit_register(opcode) => {
-                self.ld_hl_r8(opcode)
+            v if (v & 0b11111000) == 0b01110000 && Self::source_is_8bit_register(opcode) => {
+                Self::ld_hl_r8
             }
             v if (v & 0b11000000) == 0b01000000
-                && Cpu::source_is_8bit_register(opcode)
-                && Cpu::destination_is_8bit_register(opcode) =>
-            {
-                self.ld_r8_r8(opcode)
+                && Self::source_is_8bit_register(opcode)
+                && Self::destination_is_8bit_register(opcode) => Self::ld_r8_r8,
+            v if (v & 0b11000111) == 0b00000110 && Self::destination_is_8bit_register(opcode) => {
+                Self::ld_r8_imm8
             }
-            v if (v & 0b11000111) == 0b00000110 && Cpu::destination_is_8bit_register(opcode) => {
-                self.ld_r8_imm8(opcode)
-            }
-            0b00110110 => self.ld_hl_imm8(),
-            0b00001010 => self.ld_a_bc(),
-            0b00011010 => self.ld_a_de(),
-            0b11110010 => self.ld_a_c(),
-            0b11100010 => self.ld_c_a(),
-            0b11110000 => self.ld_a_imm8(),
-            0b11100000 => self.ld_imm8_a(),
-            0b11111010 => self.ld_a_imm16(),
-            0b11101010 => self.ld_imm16_a(),
-            0b00101010 => self.ld_a_hli(),
-            0b00111010 => self.ld_a_hld(),
-            0b00000010 => self.ld_bc_a(),
-            0b00010010 => self.ld_de_a(),
-            0b00100010 => self.ld_hli_a(),
-            0b00110010 => self.ld_hld_a(),
+            0b00110110 => |cpu, _| cpu.ld_hl_imm8(),
+            0b00001010 => |cpu, _| cpu.ld_a_bc(),
+            0b00011010 => |cpu, _| cpu.ld_a_de(),
+            0b11110010 => |cpu, _| cpu.ld_a_c(),
+            0b11100010 => |cpu, _| cpu.ld_c_a(),
+            0b11110000 => |cpu, _| cpu.ld_a_imm8(),
+            0b11100000 => |cpu, _| cpu.ld_imm8_a(),
+            0b11111010 => |cpu, _| cpu.ld_a_imm16(),
+            0b11101010 => |cpu, _| cpu.ld_imm16_a(),
+            0b00101010 => |cpu, _| cpu.ld_a_hli(),
+            0b00111010 => |cpu, _| cpu.ld_a_hld(),
+            0b00000010 => |cpu, _| cpu.ld_bc_a(),
+            0b00010010 => |cpu, _| cpu.ld_de_a(),
+            0b00100010 => |cpu, _| cpu.ld_hli_a(),
+            0b00110010 => |cpu, _| cpu.ld_hld_a(),
 
             // 8-Bit Arithmetic and Logical Operation Instructions
-            v if (v >> 3) == 0b10000 && Cpu::source_is_8bit_register(opcode) => {
-                self.add_a_r(opcode)
-            }
-            0b11000110 => self.add_a_n(),
-            0b10000110 => self.add_a_hl(),
-            v if (v >> 3) == 0b10001 && Cpu::source_is_8bit_register(opcode) => {
-                self.adc_a_r(opcode)
+            v if (v >> 3) == 0b10000 && Self::source_is_8bit_register(opcode) => Self::add_a_r,
+            0b11000110 => |cpu, _| cpu.add_a_n(),
+            0b10000110 => |cpu, _| cpu.add_a_hl(),
+            v if (v >> 3) == 0b10001 && Self::source_is_8bit_register(opcode) => Self::adc_a_r,
+            0b11001110 => |cpu, _| cpu.adc_a_imm8(),
+            0b10001110 => |cpu, _| cpu.adc_a_hl(),
+            v if (v >> 3) == 0b10010 && Self::source_is_8bit_register(opcode) => Self::sub_a_r,
+            0b11010110 => |cpu, _| cpu.sub_a_imm8(),
+            0b10010110 => |cpu, _| cpu.sub_a_hl(),
+            v if (v >> 3) == 0b10011 && Self::source_is_8bit_register(opcode) => Self::sbc_a_r,
+            0b11011110 => |cpu, _| cpu.sbc_a_imm8(),
+            0b10011110 => |cpu, _| cpu.sbc_a_hl(),
+            v if (v >> 3) == 0b10100 && Self::source_is_8bit_register(opcode) => Self::and_a_r,
+            0b11100110 => |cpu, _| cpu.and_a_imm8(),
+            0b10100110 => |cpu, _| cpu.and_a_hl(),
+            v if (v >> 3) == 0b10110 && Self::source_is_8bit_register(opcode) => Self::or_a_r,
+            0b11110110 => |cpu, _| cpu.or_a_imm8(),
+            0b10110110 => |cpu, _| cpu.or_a_hl(),
+            v if (v >> 3) == 0b10101 && Self::source_is_8bit_register(opcode) => Self::xor_a_r,
+            0b11101110 => |cpu, _| cpu.xor_a_imm8(),
+            0b10101110 => |cpu, _| cpu.xor_a_hl(),
+            v if (v >> 3) == 0b10111 && Self::source_is_8bit_register(opcode) => Self::cp_a_r,
+            0b11111110 => |cpu, _| cpu.cp_a_imm8(),
+            0b10111110 => |cpu, _| cpu.cp_a_hl(),
+            v if (v & 0b11000111) == 0b00000100 && Self::destination_is_8bit_register(opcode) => {
+                Self::inc_r
             }
-            0b11001110 => self.adc_a_imm8(),
-            0b10001110 => self.adc_a_hl(),
-            v if (v >> 3) == 0b10010 && Cpu::source_is_8bit_register(opcode) => {
-                self.sub_a_r(opcode)
+            0b00110100 => |cpu, _| cpu.inc_hl(),
+            v if (v & 0b11000111) == 0b00000101 && Self::destination_is_8bit_register(opcode) => {
+                Self::dec_r
             }
-            0b11010110 => self.sub_a_imm8(),
-            0b10010110 => self.sub_a_hl(),
-            v if (v >> 3) == 0b10011 && Cpu::source_is_8bit_register(opcode) => {
-                self.sbc_a_r(opcode)
-            }
-            0b11011110 => self.sbc_a_imm8(),
-            0b10011110 => self.sbc_a_hl(),
-            v if (v >> 3) == 0b10100 && Cpu::source_is_8bit_register(opcode) => {
-                self.and_a_r(opcode)
-            }
-            0b11100110 => self.and_a_imm8(),
-            0b10100110 => self.and_a_hl(),
-            v if (v >> 3) == 0b10110 && Cpu::source_is_8bit_register(opcode) => self.or_a_r(opcode),
-            0b11110110 => self.or_a_imm8(),
-            0b10110110 => self.or_a_hl(),
-            v if (v >> 3) == 0b10101 && Cpu::source_is_8bit_register(opcode) => {
-                self.xor_a_r(opcode)
-            }
-            0b11101110 => self.xor_a_imm8(),
-            0b10101110 => self.xor_a_hl(),
-            v if (v >> 3) == 0b10111 && Cpu::source_is_8bit_register(opcode) => self.cp_a_r(opcode),
-            0b11111110 => self.cp_a_imm8(),
-            0b10111110 => self.cp_a_hl(),
-            v if (v & 0b11000111) == 0b00000100 && Cpu::destination_is_8bit_register(opcode) => {
-                self.inc_r(opcode)
-            }
-            0b00110100 => self.inc_hl(),
-            v if (v & 0b11000111) == 0b00000101 && Cpu::destination_is_8bit_register(opcode) => {
-                self.dec_r(opcode)
-            }
-            0b00110101 => self.dec_hl(),
+            0b00110101 => |cpu, _| cpu.dec_hl(),
 
             // 16-Bit Transfer Instructions
-            v if (v & 0b11001111) == 0b00000001 && Cpu::destination_is_16bit_register(opcode) => {
-                self.ld_r16_imm16(opcode)
+            v if (v & 0b11001111) == 0b00000001 && Self::destination_is_16bit_register(opcode) => {
+                Self::ld_r16_imm16
             }
-            0b11111001 => self.ld_sp_hl(),
-            v if (v & 0b11001111) == 0b11000101 && Cpu::destination_is_16bit_register(opcode) => {
-                self.push_r16_onto_memory_stack(opcode)
+            0b11111001 => |cpu, _| cpu.ld_sp_hl(),
+            v if (v & 0b11001111) == 0b11000101 && Self::destination_is_16bit_register(opcode) => {
+                Self::push_r16_onto_memory_stack
             }
-            v if (v & 0b11001111) == 0b11000001 && Cpu::destination_is_16bit_register(opcode) => {
-                self.pop_r16_from_memory_stack(opcode)
+            v if (v & 0b11001111) == 0b11000001 && Self::destination_is_16bit_register(opcode) => {
+                Self::pop_r16_from_memory_stack
             }
-            0b11111000 => self.ld_hl_sp_imm8(),
-            0b00001000 => self.ld_imm16_sp(),
+            0b11111000 => |cpu, _| cpu.ld_hl_sp_imm8(),
+            0b00001000 => |cpu, _| cpu.ld_imm16_sp(),
 
             // 16-Bit Arithmetic Operation Instructions
-            v if (v & 0b11001111) == 0b00001001 && Cpu::destination_is_16bit_register(opcode) => {
-                self.add_hl_r16(opcode)
+            v if (v & 0b11001111) == 0b00001001 && Self::destination_is_16bit_register(opcode) => {
+                Self::add_hl_r16
             }
-            0b11101000 => self.add_sp_imm8(),
-            v if (v & 0b11001111) == 0b00000011 && Cpu::destination_is_16bit_register(opcode) => {
-                self.inc_r16(opcode)
+            0b11101000 => |cpu, _| cpu.add_sp_imm8(),
+            v if (v & 0b11001111) == 0b00000011 && Self::destination_is_16bit_register(opcode) => {
+                Self::inc_r16
             }
-            v if (v & 0b11001111) == 0b00001011 && Cpu::destination_is_16bit_register(opcode) => {
-                self.dec_r16(opcode)
+            v if (v & 0b11001111) == 0b00001011 && Self::destination_is_16bit_register(opcode) => {
+                Self::dec_r16
             }
 
             // Rotate Shift Instructions
-            0b00000111 => self.rlca(),
-            0b00010111 => self.rla(),
-            0b00001111 => self.rrca(),
-            0b00011111 => self.rra(),
+            0b00000111 => |cpu, _| cpu.rlca(),
+            0b00010111 => |cpu, _| cpu.rla(),
+            0b00001111 => |cpu, _| cpu.rrca(),
+            0b00011111 => |cpu, _| cpu.rra(),
 
             // Bit Operations are all inside CB prefix instructions
 
             // Jump Instructions
-            0b11000011 => self.jp_imm16(),
-            v if (v & 0b11000111) == 0b11000010 => self.jp_cc_imm16(opcode),
-            0b00011000 => self.jr_imm8(),
-            v if (v & 0b11100111) == 0b00100000 => self.jr_cc_imm8(opcode),
-            0b11101001 => self.jp_hl(),
+            0b11000011 => |cpu, _| cpu.jp_imm16(),
+            v if (v & 0b11000111) == 0b11000010 => Self::jp_cc_imm16,
+            0b00011000 => |cpu, _| cpu.jr_imm8(),
+            v if (v & 0b11100111) == 0b00100000 => Self::jr_cc_imm8,
+            0b11101001 => |cpu, _| cpu.jp_hl(),
 
             // Call and Returns Instructions
-            0b11001101 => self.call_imm16(),
-            v if (v & 0b11000111) == 0b11000100 => self.call_cc_imm16(opcode),
-            v if (v & 0b11000111) == 0b11000111 => self.rst(opcode),
-            0b11001001 => self.ret(),
-            0xD9 => self.reti(),
-            v if (v & 0b11000111) == 0b11000000 => self.ret_cc(opcode),
+            0b11001101 => |cpu, _| cpu.call_imm16(),
+            v if (v & 0b11000111) == 0b11000100 => Self::call_cc_imm16,
+            v if (v & 0b11000111) == 0b11000111 => Self::rst,
+            0b11001001 => |cpu, _| cpu.ret(),
+            0xD9 => |cpu, _| cpu.reti(),
+            v if (v & 0b11000111) == 0b11000000 => Self::ret_cc,
 
             // CB prefix instructions
-            0xCB => self.execute_cb_prefix_instructions(),
+            0xCB => |cpu, _| cpu.execute_cb_prefix_instructions(),
 
             // General-Purpose Arithmetic Operations and CPU Control Instructions - Miscellaneous
-            0x27 => self.daa(),
-            0x2F => self.cpl(),
-            0xF3 => self.di(),
-            0xFB => self.ei(),
-            0x3F => self.ccf(),
-            0x37 => self.scf(),
-
-            _ => {
-                println!(
-                    "*** Unimplemented opcode: 0x{:02X} - bin: 0b{:08b} ***",
-                    opcode, opcode
-                );
-                return;
-            }
+            0x27 => |cpu, _| cpu.daa(),
+            0x2F => |cpu, _| cpu.cpl(),
+            0xF3 => |cpu, _| cpu.di(),
+            0xFB => |cpu, _| cpu.ei(),
+            0x3F => |cpu, _| cpu.ccf(),
+            0x37 => |cpu, _| cpu.scf(),
+
+            _ => Self::unimplemented_opcode,
         }
     }
 
+    #[cfg_attr(not(feature = "std"), allow(unused_variables))]
+    fn unimplemented_opcode(&mut self, opcode: u8) {
+        #[cfg(feature = "std")]
+        println!(
+            "*** Unimplemented opcode: 0x{:02X} - bin: 0b{:08b} ***",
+            opcode, opcode
+        );
+    }
+
     fn execute_cb_prefix_instructions(&mut self) {
         let cb_opcode = self.fetch_opcode();
         self.registers.increment_pc();
 
+        if let Some(frequencies) = self.cb_frequencies.as_mut() {
+            frequencies[cb_opcode as usize] += 1;
+        }
+
         match cb_opcode {
-            v if (v & 0b11111000) == 0b00000000 && Cpu::source_is_8bit_register(cb_opcode) => {
+            v if (v & 0b11111000) == 0b00000000 && Self::source_is_8bit_register(cb_opcode) => {
                 self.rlc_r8(cb_opcode)
             }
             0b00000110 => self.rlc_hl(),
-            v if (v & 0b11111000) == 0b00010000 && Cpu::source_is_8bit_register(cb_opcode) => {
+            v if (v & 0b11111000) == 0b00010000 && Self::source_is_8bit_register(cb_opcode) => {
                 self.rl_r8(cb_opcode)
             }
             0b00010110 => self.rl_hl(),
-            v if (v & 0b11111000) == 0b00001000 && Cpu::source_is_8bit_register(cb_opcode) => {
+            v if (v & 0b11111000) == 0b00001000 && Self::source_is_8bit_register(cb_opcode) => {
                 self.rrc_r8(cb_opcode)
             }
             0b00001110 => self.rrc_hl(),
-            v if (v & 0b11111000) == 0b00011000 && Cpu::source_is_8bit_register(cb_opcode) => {
+            v if (v & 0b11111000) == 0b00011000 && Self::source_is_8bit_register(cb_opcode) => {
                 self.rr_r8(cb_opcode)
             }
             0b00011110 => self.rr_hl(),
-            v if (v & 0b11111000) == 0b00100000 && Cpu::source_is_8bit_register(cb_opcode) => {
+            v if (v & 0b11111000) == 0b00100000 && Self::source_is_8bit_register(cb_opcode) => {
                 self.sla_r8(cb_opcode)
             }
             0b00100110 => self.sla_hl(),
-            v if (v & 0b11111000) == 0b00101000 && Cpu::source_is_8bit_register(cb_opcode) => {
+            v if (v & 0b11111000) == 0b00101000 && Self::source_is_8bit_register(cb_opcode) => {
                 self.sra_r8(cb_opcode)
             }
             0b00101110 => self.sra_hl(),
-            v if (v & 0b11111000) == 0b00111000 && Cpu::source_is_8bit_register(cb_opcode) => {
+            v if (v & 0b11111000) == 0b00111000 && Self::source_is_8bit_register(cb_opcode) => {
                 self.srl_r8(cb_opcode)
             }
             0b00111110 => self.srl_hl(),
-            v if (v & 0b11111000) == 0b00110000 && Cpu::source_is_8bit_register(cb_opcode) => {
+            v if (v & 0b11111000) == 0b00110000 && Self::source_is_8bit_register(cb_opcode) => {
                 self.swap_r8(cb_opcode)
             }
             0b00110110 => self.swap_hl(),
-            v if (v & 0b11000000) == 0b01000000 && Cpu::source_is_8bit_register(cb_opcode) => {
+            v if (v & 0b11000000) == 0b01000000 && Self::source_is_8bit_register(cb_opcode) => {
                 self.bit_b_r8(cb_opcode)
             }
             v if (v & 0b11000111) == 0b01000110 => self.bit_b_hl(cb_opcode),
-            v if (v & 0b11000000) == 0b11000000 && Cpu::source_is_8bit_register(cb_opcode) => {
+            v if (v & 0b11000000) == 0b11000000 && Self::source_is_8bit_register(cb_opcode) => {
                 self.set_b_r8(cb_opcode)
             }
             v if (v & 0b11000111) == 0b11000110 => self.set_b_hl(cb_opcode),
-            v if (v & 0b11000000) == 0b10000000 && Cpu::source_is_8bit_register(cb_opcode) => {
+            v if (v & 0b11000000) == 0b10000000 && Self::source_is_8bit_register(cb_opcode) => {
                 self.reset_b_r8(cb_opcode)
             }
             v if (v & 0b11000111) == 0b10000110 => self.reset_b_hl(cb_opcode),
             _ => {
+                #[cfg(feature = "std")]
                 println!(
                     "*** Unimplemented CB prefix opcode: 0x{:02X} - bin: 0b{:08b} ***",
                     cb_opcode, cb_opcode
@@ -408,12 +791,13 @@ impl Cpu {
     /// Check the condition for conditional call/jump instructions based on the opcode.
     /// Returns true if the condition is met, false otherwise.
     pub(crate) fn check_cc_condition(&self, opcode: u8) -> bool {
-        match (opcode & 0b00111000) >> 3 {
-            0 => !self.registers.flags.z,
-            1 => self.registers.flags.z,
-            0b10 => !self.registers.flags.c,
-            0b11 => self.registers.flags.c,
-            _ => false,
+        // Only bits 4-3 hold the condition, bit 5 is part of the opcode (e.g. JR NZ is 0x20 and JP NZ is 0xC2)
+        match (opcode & 0b00011000) >> 3 {
+            CC_NZ => !self.registers.flags.z,
+            CC_Z => self.registers.flags.z,
+            CC_NC => !self.registers.flags.c,
+            CC_C => self.registers.flags.c,
+            _ => unreachable!(),
         }
     }
 
@@ -430,40 +814,66 @@ impl Cpu {
         (most_significant_byte << 8) | lowest_significant_byte
     }
 
+    /// Get the byte following the opcode and move the PC past it.
+    pub(crate) fn get_imm8_and_advance_pc(&mut self) -> u8 {
+        let imm8 = self.get_imm8();
+        self.registers.increment_pc();
+        imm8
+    }
+
+    /// Get the following two bytes, in little-endian order, and move the PC past them.
+    pub(crate) fn get_imm16_and_advance_pc(&mut self) -> u16 {
+        let imm16 = self.get_imm16();
+        self.registers.increment_pc_twice();
+        imm16
+    }
+
     /// Get the destination register from the opcode.
     /// The destination register is specified by bits 3 to 5 of the opcode.
-    pub(crate) fn get_destination_register(opcode: u8) -> u8 {
+    pub(crate) const fn get_destination_register(opcode: u8) -> u8 {
         (opcode & 0b00111000) >> 3
     }
 
     /// Get the source register from the opcode.
     /// The source register is specified by bits 0 to 2 of the opcode.
-    pub(crate) fn get_source_register(opcode: u8) -> u8 {
+    pub(crate) const fn get_source_register(opcode: u8) -> u8 {
         opcode & 0b00000111
     }
 
     /// Get the 16-bit destination register from the opcode.
     /// The destination register is specified by bits 4 and 5 of the opcode.
-    pub(crate) fn get_16bit_destination_register(opcode: u8) -> u8 {
+    pub(crate) const fn get_16bit_destination_register(opcode: u8) -> u8 {
         (opcode & 0b00110000) >> 4
     }
 
     /// Check if the destination register is an 8-bit register.
-    fn destination_is_8bit_register(opcode: u8) -> bool {
-        let destination_register = Cpu::get_destination_register(opcode);
-        EIGHT_BIT_REGISTERS.contains(&destination_register)
+    const fn destination_is_8bit_register(opcode: u8) -> bool {
+        let destination_register = Self::get_destination_register(opcode);
+        Self::is_register_in(&EIGHT_BIT_REGISTERS, destination_register)
     }
 
     /// Check if the destination register is a 16-bit register.
-    fn destination_is_16bit_register(opcode: u8) -> bool {
-        let destination_register = Cpu::get_16bit_destination_register(opcode);
-        SIXTEEN_BIT_REGISTERS.contains(&destination_register)
+    const fn destination_is_16bit_register(opcode: u8) -> bool {
+        let destination_register = Self::get_16bit_destination_register(opcode);
+        Self::is_register_in(&SIXTEEN_BIT_REGISTERS, destination_register)
     }
 
     /// Check if the source register is an 8-bit register.
-    fn source_is_8bit_register(opcode: u8) -> bool {
-        let source_register = Cpu::get_source_register(opcode);
-        EIGHT_BIT_REGISTERS.contains(&source_register)
+    const fn source_is_8bit_register(opcode: u8) -> bool {
+        let source_register = Self::get_source_register(opcode);
+        Self::is_register_in(&EIGHT_BIT_REGISTERS, source_register)
+    }
+
+    /// Same as `registers.contains(&register)`, which can't be used in the const fns decoding the opcodes.
+    const fn is_register_in(registers: &[u8], register: u8) -> bool {
+        let mut index = 0;
+        while index < registers.len() {
+            if registers[index] == register {
+                return true;
+            }
+            index += 1;
+        }
+        false
     }
 
     /// Reads the content of memory specified by the contents of register pair HL
@@ -478,15 +888,15 @@ impl Cpu {
         self.memory_bus.write_byte(hl, value);
     }
 
-    fn load_rom(&mut self, rom_binary: Vec<u8>) {
-        self.memory_bus.copy_from_binary(rom_binary);
-    }
-
-    pub fn get_screen_buffer(&mut self) -> [[u8; GAME_SECTION_WIDTH]; GAME_SECTION_HEIGHT] {
-        self.ppu.get_bg_screen_buffer(&mut self.memory_bus)
+    /// Returns the last frame rendered by `update_screen` as shades, from 0 (white) to 3 (black). The frame is not
+    /// rendered again, so changes to VRAM are only seen after the next call to `update_screen`.
+    pub fn get_screen_buffer(&self) -> [[u8; GAME_SECTION_WIDTH]; GAME_SECTION_HEIGHT] {
+        self.ppu.screen.map(|row| {
+            row.map(|color| COLORS.iter().position(|&shade_color| shade_color == color).unwrap_or(0) as u8)
+        })
     }
 
-    pub(crate) fn set_debug_mode(&mut self, value: bool) {
+    pub fn set_debug_mode(&mut self, value: bool) {
         self.is_debug_mode = value;
     }
 
@@ -512,18 +922,20 @@ impl Cpu {
         self.ime = value;
     }
 
-    /// Increment clock cycles by 4 and update timers
-    pub(crate) fn increment_4_cycles_update_timers_and_ppu(&mut self) {
-        self.increment_cycles(4);
-        self.update_timers();
-        self.update_ppu();
-    }
+    /// Advances the clock by `t_cycles` T-cycles (a multiple of 4). The timers and the PPU are updated once for every
+    /// machine cycle (4 T-cycles), so none of their updates is missed whatever the length of the instruction step.
+    pub(crate) fn increment_cycles(&mut self, t_cycles: u8) {
+        debug_assert!(t_cycles.is_multiple_of(4), "{} T-cycles is not a whole number of machine cycles", t_cycles);
 
-    fn increment_cycles(&mut self, value: u8) {
-        self.clock_cycles += value as u64;
+        for _ in 0..t_cycles / 4 {
+            self.clock_cycles += 4;
+            self.memory_bus.do_oam_dma_step();
+            self.update_timers();
+            self.update_ppu();
+        }
     }
 
-    /// Calls the PPU to update the screen buffer
+    /// Calls the PPU frame barrier, the screen holds the scanlines rendered so far (blank when the LCD is off)
     pub fn update_screen(&mut self) {
         self.ppu.update_screen_buffer(&self.memory_bus);
     }
@@ -533,11 +945,16 @@ impl Cpu {
         Timer::update(self);
     }
 
+    /// A button press requests the joypad interrupt (bit 4 of IF), which is what wakes the CPU up from STOP.
+    fn is_joypad_interrupt_requested(&self) -> bool {
+        self.memory_bus.read_byte(IF) & 0b0001_0000 != 0
+    }
+
     /// Handle interrupts if any are requested
     pub fn handle_interrupts(&mut self) -> bool {
         InterruptsHandler::handle(self)
     }
-    
+
     /// Update the PPU state after every instruction execution
     pub fn update_ppu(&mut self) {
         Ppu::update_state(self);
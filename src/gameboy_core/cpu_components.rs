@@ -1,10 +1,25 @@
+use alloc::{boxed::Box, format, vec::Vec};
+use core::fmt;
+#[cfg(feature = "std")]
+use std::{io, path::Path};
+
 use crate::gameboy_core::{
-    constants::{INITIAL_PC, MEMORY_SIZE, OAM_END, OAM_START},
+    constants::{
+        EXTERNAL_RAM_END, EXTERNAL_RAM_START, INITIAL_PC, IO_REGISTERS_START, MEMORY_SIZE, OAM_END, OAM_OBJECT_COUNT,
+        OAM_SIZE, OAM_START, ROM_END, ROM_WITHOUT_MBC_SIZE, TILE_DATA_SIZE, TILE_DATA_START, TILE_MAP_SIZE, VRAM_END,
+        VRAM_START, WRAM_BANK_0_START, WRAM_BANK_COUNT, WRAM_BANK_N_START, WRAM_BANK_SIZE, WRAM_END,
+    },
     interrupts::InterruptType,
-    ppu_components::LcdcRegister,
-    registers_contants::{self, BGP, LCDC, SCX, SCY, DMA},
+    joypad::{JoypadButton, JoypadState},
+    mbc::{self, Mbc},
+    ppu::{Object, ObjectPallete},
+    ppu_components::{self, LcdcRegister},
+    registers_contants::{
+        self, BGP, DIV, DMA, HDMA1, HDMA2, HDMA3, HDMA4, HDMA5, LCDC, OBP0, OBP1, P1, SCX, SCY, SVBK, WX, WY,
+    },
 };
 
+#[derive(Debug, Clone, PartialEq)]
 pub struct CpuRegisters {
     pub a: u8,
     pub b: u8,
@@ -26,6 +41,7 @@ pub struct CpuRegisters {
 /// Bit 4: C (Carry flag)
 /// Bits 3-0: Always 0
 /// Therefore: `Z N H C 0 0 0 0`
+#[derive(Debug, Clone, PartialEq)]
 pub struct FlagsRegister {
     /// Zero Flag: True if the last operation resulted in zero
     pub z: bool, // Zero Flag
@@ -40,8 +56,89 @@ pub struct FlagsRegister {
     pub c: bool, // Carry Flag
 }
 
+/// Cloning copies the whole 64 KiB address space, the 32 KiB of WRAM banks and the cartridge ROM and RAM of the MBC,
+/// which is fine for save states and tests but too slow to do on every frame.
+#[derive(Clone)]
 pub struct MemoryBus {
     memory: [u8; MEMORY_SIZE],
+    /// Work RAM split in 8 banks of 4 KiB. Bank 0 is always mapped at $C000-$CFFF,
+    /// the bank selected by `wram_bank` is mapped at $D000-$DFFF.
+    wram: [[u8; WRAM_BANK_SIZE]; WRAM_BANK_COUNT],
+    /// Currently selected WRAM bank for $D000-$DFFF (1-7). It is never 0 since selecting bank 0 selects bank 1.
+    wram_bank: u8,
+    /// State of the GBC VRAM DMA controlled by the HDMA1-HDMA5 registers
+    hdma: HdmaState,
+    /// Buttons currently held by the player
+    pub joypad_state: JoypadState,
+    /// Bits 4 and 5 written to P1, which select the group of buttons read from P1 (D-pad and/or action buttons)
+    joypad_select: u8,
+    /// Memory Bank Controller of the cartridge, which maps $0000-$7FFF and $A000-$BFFF when present.
+    /// Without it the ROM is mapped as it is and those regions are plain memory.
+    mbc: Option<Box<dyn Mbc>>,
+    /// True while the OAM DMA started by writing DMA ($FF46) is copying to OAM
+    oam_dma_active: bool,
+    /// Address of the next byte copied by the OAM DMA
+    oam_dma_source: u16,
+    /// Machine cycles until the OAM DMA ends, one byte is copied per machine cycle
+    oam_dma_cycles_remaining: u8,
+    /// Set when the CPU writes DIV, the timer restarts counting the 256 T-cycles of DIV on its next update
+    div_reset_pending: bool,
+}
+
+/// Transfer modes of the GBC VRAM DMA, selected by bit 7 of the value written to HDMA5.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum HdmaMode {
+    /// Copies the whole block at once, the CPU is blocked until the transfer is completed.
+    GeneralPurpose,
+    /// Copies 16 bytes at the beginning of each H-Blank period (LY 0-143).
+    HBlank,
+}
+
+/// Represents the GBC VRAM DMA which copies data from ROM/RAM to VRAM in blocks of 16 bytes.
+#[derive(Clone)]
+pub struct HdmaState {
+    /// Source address, set by HDMA1 (high) and HDMA2 (low). The lower 4 bits are always 0.
+    pub source: u16,
+    /// Destination offset inside VRAM, set by HDMA3 (high) and HDMA4 (low). Only bits 12-4 are used.
+    pub destination: u16,
+    /// Number of bytes still to be copied
+    pub remaining_length: u16,
+    pub mode: HdmaMode,
+    /// True while an H-Blank DMA is in progress
+    pub is_active: bool,
+}
+
+impl Default for HdmaState {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl HdmaState {
+    pub fn new() -> Self {
+        Self {
+            source: 0,
+            destination: 0,
+            remaining_length: 0,
+            mode: HdmaMode::GeneralPurpose,
+            is_active: false,
+        }
+    }
+
+    /// Value returned when reading HDMA5: bits 0-6 hold the number of remaining 16 bytes blocks minus 1 and bit 7 is
+    /// set when no transfer is active. A stopped H-Blank DMA keeps its remaining length, and a finished transfer
+    /// reads $FF since the count wraps below 0.
+    pub fn get_hdma5_value(&self) -> u8 {
+        let remaining_blocks = ((self.remaining_length / 16).wrapping_sub(1) as u8) & 0b0111_1111;
+        if self.is_active { remaining_blocks } else { 0b1000_0000 | remaining_blocks }
+    }
+}
+
+/// Defaults to the power-on values, same as `CpuRegisters::new`.
+impl Default for CpuRegisters {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl CpuRegisters {
@@ -60,6 +157,7 @@ impl CpuRegisters {
         }
     }
 
+    #[inline(always)]
     pub fn increment_pc(&mut self) {
         self.pc = self.pc.wrapping_add(1);
     }
@@ -72,6 +170,7 @@ impl CpuRegisters {
         self.sp = self.sp.wrapping_add(1);
     }
 
+    #[inline(always)]
     pub fn set_8bit_register_value(&mut self, register: u8, value: u8) {
         match register {
             0b000 => self.b = value,
@@ -93,6 +192,7 @@ impl CpuRegisters {
     /// E 011
     /// H 100
     /// L 101
+    #[inline(always)]
     pub fn get_8bit_register_value(&self, register: u8) -> u8 {
         match register {
             0b000 => self.b,
@@ -110,14 +210,17 @@ impl CpuRegisters {
         ((self.a as u16) << 8) | self.flags.get_flags_as_u8() as u16
     }
 
+    #[inline(always)]
     pub fn get_bc(&self) -> u16 {
         ((self.b as u16) << 8) | (self.c as u16)
     }
 
+    #[inline(always)]
     pub fn get_de(&self) -> u16 {
         ((self.d as u16) << 8) | (self.e as u16)
     }
 
+    #[inline(always)]
     pub fn get_hl(&self) -> u16 {
         ((self.h as u16) << 8) | (self.l as u16)
     }
@@ -136,31 +239,39 @@ impl CpuRegisters {
 
     pub fn set_af(&mut self, value: u16) {
         self.a = (value >> 8) as u8;
-        self.flags.set_flags_from_u8((value & 0b011111111) as u8);
+        self.flags.set_flags_from_u8((value & 0xFF) as u8);
     }
 
     pub fn set_bc(&mut self, value: u16) {
         let b = (value >> 8) as u8;
-        let c = (value & 0b011111111) as u8;
+        let c = (value & 0xFF) as u8;
         self.b = b;
         self.c = c;
     }
 
     pub fn set_de(&mut self, value: u16) {
         let d = (value >> 8) as u8;
-        let e = (value & 0b011111111) as u8;
+        let e = (value & 0xFF) as u8;
         self.d = d;
         self.e = e;
     }
 
     pub fn set_hl(&mut self, value: u16) {
         let h = (value >> 8) as u8;
-        let l = (value & 0b011111111) as u8;
+        // The low register keeps only the lower 8 bits, the upper 8 go to the high register
+        let l = (value & 0xFF) as u8;
         self.h = h;
         self.l = l;
     }
 }
 
+/// Defaults to the power-on values, same as `FlagsRegister::new`.
+impl Default for FlagsRegister {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl FlagsRegister {
     /// Initializes the FlagsRegister with default values.
     /// Based on documentation on GameBoy CPU Manual (CBCPUman.pdf) where F is set to 0xB0, the initial state is Z=1, N=0, H=1, C=1
@@ -213,6 +324,7 @@ impl FlagsRegister {
     }
 
     /// This bit is set if and only if the result of an operation is zero
+    #[inline(always)]
     pub fn set_z_flag_from_u8(&mut self, result: u8) {
         self.z = result == 0;
     }
@@ -268,137 +380,553 @@ impl FlagsRegister {
     }
 }
 
+impl Default for MemoryBus {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl MemoryBus {
     pub fn new() -> Self {
+        let mut memory = [0; MEMORY_SIZE];
+        // No OAM DMA was started yet, the source register holds its power-on value
+        memory[DMA as usize] = 0xFF;
+
         Self {
-            memory: [0; MEMORY_SIZE],
+            memory,
+            wram: [[0; WRAM_BANK_SIZE]; WRAM_BANK_COUNT],
+            wram_bank: 1,
+            hdma: HdmaState::new(),
+            joypad_state: JoypadState::new(),
+            joypad_select: 0,
+            mbc: None,
+            oam_dma_active: false,
+            oam_dma_source: 0,
+            oam_dma_cycles_remaining: 0,
+            div_reset_pending: false,
         }
     }
 
+    /// Reads a byte as the CPU sees it: while an OAM DMA is running only $FF00-$FFFF can be read, the rest of the
+    /// memory reads 0xFF.
+    #[inline]
     pub fn read_byte(&self, address: u16) -> u8 {
-        // if address == LY {
-        //     // LY register always returns the current scanline (for simplicity, we return 0 here)
-        //     return 0x90;
-        // }
-
-        self.memory[address as usize]
+        if self.is_blocked_by_oam_dma(address) {
+            return 0xFF;
+        }
+        self.read_mapped_byte(address)
+    }
+
+    /// Reads a byte from the region mapped at the address, whether an OAM DMA is running or not.
+    #[inline]
+    pub fn read_mapped_byte(&self, address: u16) -> u8 {
+        // LY is not special cased here, the PPU keeps its memory cell updated on every scanline (see Ppu::update_state)
+        match address {
+            0x0000..=ROM_END if let Some(mbc) = &self.mbc => mbc.read_rom(address),
+            EXTERNAL_RAM_START..=EXTERNAL_RAM_END if let Some(mbc) = &self.mbc => mbc.read_ram(address),
+            WRAM_BANK_0_START..WRAM_BANK_N_START => {
+                self.wram[0][(address - WRAM_BANK_0_START) as usize]
+            }
+            WRAM_BANK_N_START..=WRAM_END => {
+                self.wram[self.wram_bank as usize][(address - WRAM_BANK_N_START) as usize]
+            }
+            P1 => self.joypad_state.get_p1_register(self.joypad_select),
+            // Bits 3-7 of SVBK are unused and always read as 1
+            SVBK => 0b1111_1000 | self.wram_bank,
+            // HDMA1-HDMA4 are write only
+            HDMA1..=HDMA4 => 0xFF,
+            HDMA5 => self.hdma.get_hdma5_value(),
+            _ => self.memory[address as usize],
+        }
     }
 
+    /// Writes a byte as the CPU does: while an OAM DMA is running the writes below $FF00 are ignored.
+    #[inline]
     pub fn write_byte(&mut self, address: u16, value: u8) {
-        self.memory[address as usize] = value;
-        
-        // FIX: Handle OAM DMA Transfer when writing to the DMA register ($FF46)
-        // When a game writes the high byte of a source address to $FF46,
-        // the emulator must copy 160 bytes from $XX00–$XX9F to $FE00–$FE9F
-        if address == DMA {
-            // value is the high byte of the source address
-            let source_start = ((value as u16) << 8);  // e.g., 0xC1 becomes 0xC100
-            
-            // Copy from source to OAM (0xFE00 - 0xFE9F)
-            for offset in 0..160 {
-                let source_addr = (source_start + offset as u16) as usize;
-                let dest_addr = (OAM_START + offset as u16) as usize;
-                self.memory[dest_addr] = self.memory[source_addr];
+        if self.is_blocked_by_oam_dma(address) {
+            return;
+        }
+
+        match address {
+            0x0000..=ROM_END if let Some(mbc) = &mut self.mbc => mbc.write_register(address, value),
+            EXTERNAL_RAM_START..=EXTERNAL_RAM_END if let Some(mbc) = &mut self.mbc => mbc.write_ram(address, value),
+            WRAM_BANK_0_START..WRAM_BANK_N_START => {
+                self.wram[0][(address - WRAM_BANK_0_START) as usize] = value;
+            }
+            WRAM_BANK_N_START..=WRAM_END => {
+                self.wram[self.wram_bank as usize][(address - WRAM_BANK_N_START) as usize] = value;
+            }
+            // Only the group selection bits can be written, the buttons are read only
+            P1 => self.joypad_select = value & 0b0011_0000,
+            SVBK => self.set_wram_bank(value),
+            HDMA1 => self.hdma.source = ((value as u16) << 8) | (self.hdma.source & 0x00FF),
+            HDMA2 => self.hdma.source = (self.hdma.source & 0xFF00) | (value & 0xF0) as u16,
+            HDMA3 => {
+                self.hdma.destination =
+                    (((value & 0x1F) as u16) << 8) | (self.hdma.destination & 0x00FF)
+            }
+            HDMA4 => {
+                self.hdma.destination = (self.hdma.destination & 0xFF00) | (value & 0xF0) as u16
+            }
+            HDMA5 => self.start_vram_dma(value),
+            DMA => {
+                self.memory[address as usize] = value;
+                self.start_oam_dma(value);
+            }
+            // Writing any value resets the divider
+            DIV => {
+                self.memory[address as usize] = 0;
+                self.div_reset_pending = true;
+            }
+            _ => self.memory[address as usize] = value,
+        }
+    }
+
+    /// Presses or releases a button of the joypad, the change is seen the next time P1 is read.
+    /// Pressing a released button requests the joypad interrupt, see `set_joypad_state`.
+    pub fn set_joypad_button(&mut self, button: JoypadButton, pressed: bool) {
+        let mut joypad_state = self.joypad_state;
+        joypad_state.set_button(button, pressed);
+        self.set_joypad_state(joypad_state);
+    }
+
+    /// Replaces the state of all the buttons. When a button goes from released to pressed the joypad interrupt is
+    /// requested (IF bit 4), whether IME is set or not and whatever group of buttons P1 selects. Buttons held
+    /// down don't request it again.
+    pub fn set_joypad_state(&mut self, joypad_state: JoypadState) {
+        let newly_pressed = joypad_state.get_pressed_buttons() & !self.joypad_state.get_pressed_buttons();
+        self.joypad_state = joypad_state;
+        if newly_pressed != 0 {
+            self.update_flag_in_if_register(InterruptType::Joypad, true);
+        }
+    }
+
+    /// Sets DIV without resetting it, used by the timer to increment it. Writes from the CPU always reset it to 0.
+    pub fn set_div_register(&mut self, value: u8) {
+        self.memory[DIV as usize] = value;
+    }
+
+    /// Returns true once after DIV was written, so the timer can restart its count of the cycles until the next
+    /// increment.
+    pub fn take_div_reset(&mut self) -> bool {
+        core::mem::take(&mut self.div_reset_pending)
+    }
+
+    /// Starts the OAM DMA which copies the 160 bytes from $XX00-$XX9F to OAM ($FE00-$FE9F), where XX is the value
+    /// written to DMA. Writing it again during a transfer restarts it from the new source.
+    fn start_oam_dma(&mut self, value: u8) {
+        self.oam_dma_active = true;
+        self.oam_dma_source = (value as u16) << 8;
+        self.oam_dma_cycles_remaining = OAM_SIZE as u8;
+    }
+
+    /// Copies the next byte of the OAM DMA, called once per machine cycle. Does nothing when no DMA is running.
+    pub fn do_oam_dma_step(&mut self) {
+        if !self.oam_dma_active {
+            return;
+        }
+
+        let offset = OAM_SIZE as u16 - self.oam_dma_cycles_remaining as u16;
+        // The DMA reads through the memory map so sources in banked regions (e.g. WRAM) are resolved correctly
+        self.memory[(OAM_START + offset) as usize] = self.read_mapped_byte(self.oam_dma_source + offset);
+
+        self.oam_dma_cycles_remaining -= 1;
+        if self.oam_dma_cycles_remaining == 0 {
+            self.oam_dma_active = false;
+        }
+    }
+
+    /// Returns true while the OAM DMA is copying to OAM.
+    pub fn is_oam_dma_active(&self) -> bool {
+        self.oam_dma_active
+    }
+
+    /// During an OAM DMA the CPU can only access $FF00-$FFFF: HRAM, where games copy the routine waiting for the
+    /// end of the transfer, and the IO registers, which the timer, the PPU and the interrupts keep updating.
+    #[inline]
+    fn is_blocked_by_oam_dma(&self, address: u16) -> bool {
+        self.oam_dma_active && address < IO_REGISTERS_START
+    }
+
+    /// Selects the WRAM bank mapped at $D000-$DFFF. Only bits 0-2 are used and selecting bank 0 selects bank 1.
+    pub fn set_wram_bank(&mut self, value: u8) {
+        let bank = value & 0b0000_0111;
+        self.wram_bank = if bank == 0 { 1 } else { bank };
+    }
+
+    /// Returns the WRAM bank currently mapped at $D000-$DFFF.
+    pub fn get_wram_bank(&self) -> u8 {
+        self.wram_bank
+    }
+
+    /// Starts a VRAM DMA transfer when HDMA5 is written.
+    /// Bit 7 = 0 starts a General-Purpose DMA which copies all the data at once, unless an H-Blank DMA is active,
+    /// in which case the H-Blank DMA is stopped. Bit 7 = 1 starts an H-Blank DMA which copies 16 bytes per H-Blank.
+    fn start_vram_dma(&mut self, value: u8) {
+        let is_hblank_mode = value & 0b1000_0000 != 0;
+
+        if self.hdma.is_active && !is_hblank_mode {
+            self.hdma.is_active = false;
+            return;
+        }
+
+        self.hdma.remaining_length = ((value & 0b0111_1111) as u16 + 1) * 16;
+
+        if is_hblank_mode {
+            self.hdma.mode = HdmaMode::HBlank;
+            self.hdma.is_active = true;
+        } else {
+            self.hdma.mode = HdmaMode::GeneralPurpose;
+            while self.hdma.remaining_length > 0 {
+                self.transfer_vram_dma_block();
             }
         }
     }
 
+    /// Copies the next block of 16 bytes from the HDMA source to VRAM
+    fn transfer_vram_dma_block(&mut self) {
+        for _ in 0..16 {
+            let value = self.read_byte(self.hdma.source);
+            let destination = 0x8000 | (self.hdma.destination & 0x1FFF);
+            self.memory[destination as usize] = value;
+
+            self.hdma.source = self.hdma.source.wrapping_add(1);
+            self.hdma.destination = self.hdma.destination.wrapping_add(1);
+        }
+
+        self.hdma.remaining_length -= 16;
+        if self.hdma.remaining_length == 0 {
+            self.hdma.is_active = false;
+        }
+    }
+
+    /// Called by the PPU when it enters H-Blank, copies 16 bytes if an H-Blank DMA is in progress.
+    pub fn do_hblank_dma_transfer(&mut self) {
+        if self.hdma.is_active && self.hdma.mode == HdmaMode::HBlank {
+            self.transfer_vram_dma_block();
+        }
+    }
+
+    /// Returns the state of the GBC VRAM DMA
+    pub fn get_hdma_state(&self) -> &HdmaState {
+        &self.hdma
+    }
+
+    /// Loads the ROM and creates the MBC given in its header. ROMs without an implemented MBC are mapped as they are,
+    /// only their first 32 KiB are copied to $0000-$7FFF.
     pub fn copy_from_binary(&mut self, rom_binary: Vec<u8>) {
         let start_ram_address = 0 as usize;
-        self.memory[start_ram_address..(start_ram_address + rom_binary.len())]
-            .copy_from_slice(&rom_binary);
+        let length = rom_binary.len().min(ROM_WITHOUT_MBC_SIZE);
+        self.memory[start_ram_address..(start_ram_address + length)]
+            .copy_from_slice(&rom_binary[..length]);
+        self.mbc = mbc::create_mbc(&rom_binary);
     }
 
-    /// Gets a reference to the VRAM tile data region which covers addressess $8000-$97FF
-    pub fn get_vram_tile_data(&self) -> &[u8] {
-        &self.memory[0x8000..=0x97FF]
+    /// Returns true if the cartridge has a Memory Bank Controller mapping the ROM and the cartridge RAM.
+    pub fn has_mbc(&self) -> bool {
+        self.mbc.is_some()
+    }
+
+    /// Returns true if the cartridge RAM is kept by a battery and has to be saved when the emulator exits.
+    pub fn has_battery(&self) -> bool {
+        self.mbc.as_ref().is_some_and(|mbc| mbc.has_battery())
+    }
+
+    /// Returns the content of the save file of the cartridge RAM, None for cartridges without battery.
+    pub(crate) fn get_save_data(&self) -> Option<Vec<u8>> {
+        self.mbc.as_ref().filter(|mbc| mbc.has_battery()).map(|mbc| mbc.get_save_data())
+    }
+
+    /// Restores the cartridge RAM from the content of a save file. Does nothing for cartridges without MBC.
+    pub(crate) fn load_save_data(&mut self, data: &[u8]) {
+        if let Some(mbc) = &mut self.mbc {
+            mbc.load_save_data(data);
+        }
+    }
+
+    /// Writes the cartridge RAM to a save file. Does nothing for cartridges without MBC.
+    #[cfg(feature = "std")]
+    pub fn save_ram(&self, path: &Path) -> io::Result<()> {
+        match &self.mbc {
+            Some(mbc) => mbc.save_ram(path),
+            None => Ok(()),
+        }
+    }
+
+    /// Reads the cartridge RAM from a save file. Does nothing for cartridges without MBC.
+    #[cfg(feature = "std")]
+    pub fn load_ram(&mut self, path: &Path) -> io::Result<()> {
+        match &mut self.mbc {
+            Some(mbc) => mbc.load_ram(path),
+            None => Ok(()),
+        }
     }
 
     /// Gets a mutable reference to the VRAM region
     pub fn get_vram_mut(&mut self) -> &mut [u8] {
         &mut self.memory[0x8000..=0x9FFF]
     }
+}
+
+/// Summary of a memory region shown by the Debug output of the MemoryBus, e.g. `0x8000-0x9FFF (8192 bytes, 384 non-zero)`.
+struct MemoryRegionSummary<'a> {
+    memory_bus: &'a MemoryBus,
+    start: u16,
+    end: u16,
+}
+
+impl fmt::Debug for MemoryRegionSummary<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // Read through the bus so the WRAM shows the banks currently mapped
+        let non_zero_count = (self.start..=self.end)
+            .filter(|&address| self.memory_bus.read_byte(address) != 0)
+            .count();
+
+        write!(
+            f,
+            "0x{:04X}-0x{:04X} ({} bytes, {} non-zero)",
+            self.start,
+            self.end,
+            self.end as usize - self.start as usize + 1,
+            non_zero_count
+        )
+    }
+}
+
+/// Shows a summary of each region of the memory map and the most important I/O registers instead of the 64KB of memory.
+impl fmt::Debug for MemoryBus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let region = |start: u16, end: u16| MemoryRegionSummary {
+            memory_bus: self,
+            start,
+            end,
+        };
+        let register = |address: u16| format!("0x{:02X}", self.read_byte(address));
+
+        f.debug_struct("MemoryBus")
+            .field("ROM0", &region(0x0000, 0x3FFF))
+            .field("ROMx", &region(0x4000, 0x7FFF))
+            .field("VRAM", &region(VRAM_START, VRAM_END))
+            .field("SRAM", &region(0xA000, 0xBFFF))
+            .field("WRAM", &region(WRAM_BANK_0_START, WRAM_END))
+            .field("OAM", &region(OAM_START, OAM_END))
+            .field("wram_bank", &self.wram_bank)
+            .field(
+                "IO",
+                &format_args!(
+                    "LCDC: {}, STAT: {}, LY: {}, IF: {}, IE: {}",
+                    register(LCDC),
+                    register(registers_contants::STAT),
+                    register(registers_contants::LY),
+                    register(registers_contants::IF),
+                    register(registers_contants::IE)
+                ),
+            )
+            .field("joypad_state", &self.joypad_state)
+            .finish()
+    }
+}
+
+/// Abstraction over the Game Boy address space, used by the CPU, Timer and PPU to access memory.
+/// Only `read_byte` and `write_byte` have to be implemented, the register helpers are built on top of them.
+/// It allows tests to inject a mock memory bus and cartridges with a Memory Bank Controller (MBC) to provide
+/// their own memory mapping without changing the CPU.
+pub trait MemoryAccess {
+    fn read_byte(&self, address: u16) -> u8;
+
+    fn write_byte(&mut self, address: u16, value: u8);
+
+    /// Reads a byte as the PPU sees it. The OAM DMA only blocks the CPU, so memory buses with OAM DMA support have to
+    /// read the memory here even while it runs.
+    fn read_mapped_byte(&self, address: u16) -> u8 {
+        self.read_byte(address)
+    }
+
+    /// Called by the PPU when it enters H-Blank. Memory buses without GBC VRAM DMA support can ignore it.
+    fn do_hblank_dma_transfer(&mut self) {}
+
+    /// Called by the CPU on every machine cycle to copy the next byte of the OAM DMA. Memory buses without OAM DMA
+    /// support can ignore it.
+    fn do_oam_dma_step(&mut self) {}
+
+    /// Reads all bytes from `start` to `end` (inclusive) as the PPU sees them, see `read_mapped_byte`
+    fn read_range(&self, start: u16, end: u16) -> Vec<u8> {
+        (start..=end).map(|address| self.read_mapped_byte(address)).collect()
+    }
+
+    /// Reads the `N` bytes starting at `start` as the PPU sees them, see `read_mapped_byte`. The size is known at
+    /// compile time so the PPU can copy VRAM on every scanline without allocating.
+    fn read_array<const N: usize>(&self, start: u16) -> [u8; N] {
+        core::array::from_fn(|offset| self.read_mapped_byte(start.wrapping_add(offset as u16)))
+    }
+
+    /// Gets a copy of the VRAM tile data region which covers addressess $8000-$97FF
+    fn get_vram_tile_data(&self) -> [u8; TILE_DATA_SIZE] {
+        self.read_array(TILE_DATA_START)
+    }
 
     /// Get LCDC register value
-    pub fn get_lcdc_register(&self) -> u8 {
+    fn get_lcdc_register(&self) -> u8 {
         self.read_byte(LCDC)
     }
 
     /// Set LCDC register value
-    pub fn set_lcdc_register(&mut self, value: u8) {
+    fn set_lcdc_register(&mut self, value: u8) {
         self.write_byte(LCDC, value);
     }
 
     /// Returns the background tile map area from 9800-9BFF or 9C00-9FFF based on the bg_tile_map_area flag in the LCDC register.
-    pub fn get_bg_tile_map(&self, lcdc_register: &LcdcRegister) -> &[u8] {
-        let (start, end) = lcdc_register.get_bg_tiles_map_area_address_range();
-        &self.memory[start as usize..=end as usize]
+    fn get_bg_tile_map(&self, lcdc_register: &LcdcRegister) -> [u8; TILE_MAP_SIZE] {
+        let (start, _) = lcdc_register.get_bg_tiles_map_area_address_range();
+        self.read_array(start)
+    }
+
+    /// Returns the window tile map area from 9800-9BFF or 9C00-9FFF based on the window_tile_map_area flag in the LCDC register.
+    fn get_window_tile_map(&self, lcdc_register: &LcdcRegister) -> [u8; TILE_MAP_SIZE] {
+        let (start, _) = lcdc_register.get_window_tile_map_area_address_range();
+        self.read_array(start)
+    }
+
+    /// Returns the object attribute memory (OAM) which is located from 0xFE00 to 0xFE9F.
+    /// It is a copy since the OAM is read through read_byte, but the size guarantees the 40 objects are there.
+    fn get_object_attribute_memory(&self) -> [u8; OAM_SIZE] {
+        core::array::from_fn(|offset| self.read_mapped_byte(OAM_START + offset as u16))
     }
-    
-    /// Returns the object attribute memory (OAM) which is located from 0xFE00 to 0xFE9F
-    pub fn get_object_attribute_memory(&self) -> &[u8] {
-        &self.memory[OAM_START as usize..=OAM_END as usize]
+
+    /// Parses the object at `index` (0-39) from its 4 bytes in OAM, None for indexes past the last object.
+    fn get_object(&self, index: usize) -> Option<Object> {
+        if index >= OAM_OBJECT_COUNT {
+            return None;
+        }
+
+        let address = OAM_START + (index * 4) as u16;
+        Some(Object::from_oam_entry([
+            self.read_mapped_byte(address),
+            self.read_mapped_byte(address + 1),
+            self.read_mapped_byte(address + 2),
+            self.read_mapped_byte(address + 3),
+        ]))
+    }
+
+    /// Iterates over the 40 objects in OAM, in the order they are stored in memory.
+    fn iter_objects(&self) -> impl Iterator<Item = Object> + '_ {
+        (0..OAM_OBJECT_COUNT).filter_map(|index| self.get_object(index))
     }
 
     /// Get SCY register value
-    pub fn get_scy_register(&self) -> u8 {
+    fn get_scy_register(&self) -> u8 {
         self.read_byte(SCY)
     }
 
     /// Get SCX register value
-    pub fn get_scx_register(&self) -> u8 {
+    fn get_scx_register(&self) -> u8 {
         self.read_byte(SCX)
     }
 
     /// Set SCY register value
-    pub fn set_scy_register(&mut self, value: u8) {
+    fn set_scy_register(&mut self, value: u8) {
         self.write_byte(SCY, value);
     }
 
     /// Set SCX register value
-    pub fn set_scx_register(&mut self, value: u8) {
+    fn set_scx_register(&mut self, value: u8) {
         self.write_byte(SCX, value);
     }
 
-    pub(crate) fn set_bgp_register(&mut self, value: u8) {
+    /// Get WY register value, the Y position of the window on the screen
+    fn get_wy_register(&self) -> u8 {
+        self.read_byte(WY)
+    }
+
+    /// Get WX register value, the X position of the window on the screen plus 7
+    fn get_wx_register(&self) -> u8 {
+        self.read_byte(WX)
+    }
+
+    /// Set WY register value
+    fn set_wy_register(&mut self, value: u8) {
+        self.write_byte(WY, value);
+    }
+
+    /// Set WX register value
+    fn set_wx_register(&mut self, value: u8) {
+        self.write_byte(WX, value);
+    }
+
+    /// Screen column where the window starts, which is WX - 7. WX values below 7 are clamped to the first column.
+    fn get_window_x_offset(&self) -> usize {
+        self.get_wx_register().saturating_sub(7) as usize
+    }
+
+    fn set_bgp_register(&mut self, value: u8) {
         self.write_byte(BGP, value);
     }
 
+    /// Maps the color index (0-3) of a background or window pixel to its shade in COLORS with the BGP register.
+    /// The register is read on each call, so palette changes during the frame apply to the next pixels drawn.
+    fn decode_bg_palette(&self, pixel: u8) -> usize {
+        ppu_components::decode_palette(self.read_byte(BGP), pixel)
+    }
+
+    /// Maps the color index (0-3) of an object pixel to its shade in COLORS with OBP0 (obp = 0) or OBP1 (obp = 1),
+    /// the palette selected by bit 4 of the object attributes.
+    fn decode_obp_palette(&self, obp: u8, pixel: u8) -> usize {
+        let register = if obp == ObjectPallete::OBP0 as u8 { OBP0 } else { OBP1 };
+        ppu_components::decode_palette(self.read_byte(register), pixel)
+    }
+
     /// Divider Register (DIV) - increments at a rate of 16384 Hz.
     /// Therefore, it increments every 256 CPU cycles, because the CPU runs at 4.194304 MHz.
     /// The math is 4,194,304 Hz / 16,384 Hz = 256 cycles.
-    pub(crate) fn get_div_register(&self) -> u8 {
+    fn get_div_register(&self) -> u8 {
         self.read_byte(registers_contants::DIV)
     }
 
     /// Get the TMA register value, that is located at address 0xFF06
     /// Timer Modulo (TMA) - when TIMA overflows (from 0xFF to 0x00), it is reloaded with the value in TMA.
-    pub(crate) fn get_tma_register(&self) -> u8 {
+    fn get_tma_register(&self) -> u8 {
         self.read_byte(registers_contants::TMA)
     }
 
     /// Get the TAC register value, that is located at address 0xFF07
     /// Timer Control (TAC) - controls the timer's operation, including its speed and whether it is enabled.
-    pub(crate) fn get_tac_register(&self) -> u8 {
+    fn get_tac_register(&self) -> u8 {
         self.read_byte(registers_contants::TAC)
     }
 
     /// Get the TIMA register value, that is located at address 0xFF05
     /// Timer Counter (TIMA) - increments at a rate determined by the TAC register.
-    pub(crate) fn get_tima_register(&self) -> u8 {
+    fn get_tima_register(&self) -> u8 {
         self.read_byte(registers_contants::TIMA)
     }
 
-    pub(crate) fn set_div_register(&mut self, value: u8) {
+    /// Sets DIV, memory buses which reset DIV when it is written must override it to store the value as it is.
+    fn set_div_register(&mut self, value: u8) {
         self.write_byte(registers_contants::DIV, value);
     }
 
-    pub(crate) fn set_tima_register(&mut self, value: u8) {
+    /// Returns true once after DIV was written by the CPU. Memory buses without DIV reset support can ignore it.
+    fn take_div_reset(&mut self) -> bool {
+        false
+    }
+
+    fn set_tima_register(&mut self, value: u8) {
         self.write_byte(registers_contants::TIMA, value);
     }
 
+    /// Get the IE register value, that is located at address 0xFFFF.
+    /// Only bits 0-4 are meaningful (VBlank, LCD STAT, Timer, Serial and Joypad), the upper bits are masked out.
+    fn get_ie_register(&self) -> u8 {
+        self.read_byte(registers_contants::IE) & 0x1F
+    }
+
+    /// Set the IE register value, only bits 0-4 (VBlank, LCD STAT, Timer, Serial and Joypad) are kept.
+    fn set_ie_register(&mut self, value: u8) {
+        self.write_byte(registers_contants::IE, value & 0x1F);
+    }
+
     /// Update the IF register to request or clear a specific interrupt
-    pub(crate) fn update_flag_in_if_register(
+    fn update_flag_in_if_register(
         &mut self,
         interrupt_type: InterruptType,
         value: bool,
@@ -425,3 +953,56 @@ impl MemoryBus {
         self.write_byte(registers_contants::IF, if_register);
     }
 }
+
+impl MemoryAccess for MemoryBus {
+    #[inline(always)]
+    fn read_byte(&self, address: u16) -> u8 {
+        MemoryBus::read_byte(self, address)
+    }
+
+    #[inline(always)]
+    fn write_byte(&mut self, address: u16, value: u8) {
+        MemoryBus::write_byte(self, address, value)
+    }
+
+    #[inline(always)]
+    fn read_mapped_byte(&self, address: u16) -> u8 {
+        MemoryBus::read_mapped_byte(self, address)
+    }
+
+    fn do_hblank_dma_transfer(&mut self) {
+        MemoryBus::do_hblank_dma_transfer(self)
+    }
+
+    fn do_oam_dma_step(&mut self) {
+        MemoryBus::do_oam_dma_step(self)
+    }
+
+    fn set_div_register(&mut self, value: u8) {
+        MemoryBus::set_div_register(self, value)
+    }
+
+    fn take_div_reset(&mut self) -> bool {
+        MemoryBus::take_div_reset(self)
+    }
+
+    /// Ranges inside VRAM are not banked by the MBC nor mapped to special registers, so they are copied directly
+    /// from memory instead of byte by byte.
+    fn read_range(&self, start: u16, end: u16) -> Vec<u8> {
+        if start >= VRAM_START && end <= VRAM_END && start <= end {
+            return self.memory[start as usize..=end as usize].to_vec();
+        }
+        (start..=end).map(|address| self.read_mapped_byte(address)).collect()
+    }
+
+    /// Arrays inside VRAM are copied directly from memory, like in `read_range`.
+    fn read_array<const N: usize>(&self, start: u16) -> [u8; N] {
+        let start_index = start as usize;
+        if start >= VRAM_START && start_index + N <= VRAM_END as usize + 1 {
+            let mut array = [0; N];
+            array.copy_from_slice(&self.memory[start_index..start_index + N]);
+            return array;
+        }
+        core::array::from_fn(|offset| self.read_mapped_byte(start.wrapping_add(offset as u16)))
+    }
+}
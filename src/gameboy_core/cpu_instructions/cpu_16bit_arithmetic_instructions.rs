@@ -1,4 +1,4 @@
-use crate::gameboy_core::{cpu_components::FlagsRegister, cpu_instructions::cpu_helpers::CpuAddOperation};
+use crate::gameboy_core::{cpu_components::{FlagsRegister, MemoryAccess}, cpu_instructions::cpu_helpers::CpuAddOperation};
 
 /// Trait for 16-bit arithmetic instruction operations
 pub trait Cpu16BitArithmeticInstructions {
@@ -8,12 +8,11 @@ pub trait Cpu16BitArithmeticInstructions {
     fn dec_r16(&mut self, opcode: u8);
 }
 
-impl Cpu16BitArithmeticInstructions for crate::gameboy_core::cpu::Cpu {
+impl<M: MemoryAccess> Cpu16BitArithmeticInstructions for crate::gameboy_core::cpu::Cpu<M> {
     /// Adds the contents of a 16-bit register to the contents of register pair HL and stores the results in HL.
     /// The 16-bit register can be BC, DE, HL or SP.
     fn add_hl_r16(&mut self, opcode: u8) {
-        self.increment_4_cycles_update_timers_and_ppu();
-        self.increment_4_cycles_update_timers_and_ppu();
+        self.increment_cycles(8);
         let source_register = Self::get_16bit_destination_register(opcode);
         let value = match source_register {
             0b00 => self.registers.get_bc(),
@@ -35,9 +34,9 @@ impl Cpu16BitArithmeticInstructions for crate::gameboy_core::cpu::Cpu {
 
     /// Adds the signed 8-bit immediate value to the stack pointer SP and stores the result in SP.
     fn add_sp_imm8(&mut self) {
-        self.increment_4_cycles_update_timers_and_ppu();
-        let imm8 = self.get_imm8(); // u8 (e.g., 0xFF)
-        self.increment_4_cycles_update_timers_and_ppu();
+        self.increment_cycles(4);
+        let imm8 = self.get_imm8_and_advance_pc(); // u8 (e.g., 0xFF)
+        self.increment_cycles(4);
         let sp_val = self.registers.sp;
         
         let (result, c_flag, h_flag) = sp_val.add_u8_as_signed(imm8);
@@ -77,14 +76,12 @@ impl Cpu16BitArithmeticInstructions for crate::gameboy_core::cpu::Cpu {
         self.registers.flags.set_c_flag(c_flag);
         self.registers.flags.set_h_flag(h_flag);
 
-        self.registers.increment_pc();
-        self.increment_4_cycles_update_timers_and_ppu();
-        self.increment_4_cycles_update_timers_and_ppu();
+        self.increment_cycles(8);
     } 
 
     /// Increments the contents of a 16-bit register by 1. The 16-bit register can be BC, DE, HL or SP.
     fn inc_r16(&mut self, opcode: u8) {
-        self.increment_4_cycles_update_timers_and_ppu();
+        self.increment_cycles(4);
         let source_register = Self::get_16bit_destination_register(opcode);
         let value = match source_register {
             0b00 => self.registers.get_bc(),
@@ -104,12 +101,12 @@ impl Cpu16BitArithmeticInstructions for crate::gameboy_core::cpu::Cpu {
             _ => (),
         }
 
-        self.increment_4_cycles_update_timers_and_ppu();
+        self.increment_cycles(4);
     }
 
     /// Decrements the contents of a 16-bit register by 1. The 16-bit register can be BC, DE, HL or SP.
     fn dec_r16(&mut self, opcode: u8) {
-        self.increment_4_cycles_update_timers_and_ppu();
+        self.increment_cycles(4);
         let source_register = Self::get_16bit_destination_register(opcode);
         let value = match source_register {
             0b00 => self.registers.get_bc(),
@@ -128,6 +125,6 @@ impl Cpu16BitArithmeticInstructions for crate::gameboy_core::cpu::Cpu {
             0b11 => self.registers.sp = result,
             _ => (),
         }
-        self.increment_4_cycles_update_timers_and_ppu();
+        self.increment_cycles(4);
     }
 }
@@ -1,4 +1,4 @@
-use crate::gameboy_core::cpu::Cpu;
+use crate::gameboy_core::{cpu::Cpu, cpu_components::MemoryAccess};
 
 pub trait Cpu8BitArithmeticLogicalInstructions {
     fn add_a_r(&mut self, opcode: u8);
@@ -38,7 +38,7 @@ pub trait Cpu8BitArithmeticLogicalInstructions {
     fn dec_hl(&mut self);
 }
 
-impl Cpu8BitArithmeticLogicalInstructions for Cpu {
+impl<M: MemoryAccess> Cpu8BitArithmeticLogicalInstructions for Cpu<M> {
     /// Adds the contents of register r to those of register A and stores the results in register A.
     /// Flag Z: Set if the result is 0; otherwise reset.
     ///      H: Set if there is a carry from bit 3; otherwise reset.
@@ -47,8 +47,8 @@ impl Cpu8BitArithmeticLogicalInstructions for Cpu {
     /// Example: When A = 0x3A and B = 0xC6,
     /// ADD A, B ; A ← 0, Z ← 1, H ← 1, N ← 0, CY ← 1
     fn add_a_r(&mut self, opcode: u8) {
-        self.increment_4_cycles_update_timers_and_ppu();
-        let source = Cpu::get_source_register(opcode);
+        self.increment_cycles(4);
+        let source = Self::get_source_register(opcode);
         let value = self.registers.get_8bit_register_value(source);
         let (result, carry) = self.registers.a.overflowing_add(value);
         let h_flag = crate::gameboy_core::cpu_components::FlagsRegister::calculate_h_flag_on_add(self.registers.a, value);
@@ -63,10 +63,10 @@ impl Cpu8BitArithmeticLogicalInstructions for Cpu {
     /// Example: When A = 3Ch,
     /// ADD A. FFh ; A ← 3Bh, Z ← 0, H ← 1, N ← 0, CY ← 1
     fn add_a_n(&mut self) {
-        self.increment_4_cycles_update_timers_and_ppu();
+        self.increment_cycles(4);
 
-        let value = self.get_imm8();
-        self.increment_4_cycles_update_timers_and_ppu();
+        let value = self.get_imm8_and_advance_pc();
+        self.increment_cycles(4);
 
         let (result, carry) = self.registers.a.overflowing_add(value);
         let h_flag = crate::gameboy_core::cpu_components::FlagsRegister::calculate_h_flag_on_add(self.registers.a, value);
@@ -76,17 +76,16 @@ impl Cpu8BitArithmeticLogicalInstructions for Cpu {
         self.registers.flags.set_c_flag(carry);
         self.registers.flags.set_z_flag_from_u8(result);
         self.registers.flags.set_h_flag(h_flag);
-        self.registers.increment_pc();
     }
 
     /// Adds the contents of memory specified by the contents of register pair HL to the contents of register A and stores the results in register A.
     /// Example: When A = 3Ch and (HL) = 12h,
     /// ADD A, (HL) ; A ← 4Eh, Z ← 0, H ← 0, N ← 0, CY ← 0
     fn add_a_hl(&mut self) {
-        self.increment_4_cycles_update_timers_and_ppu();
+        self.increment_cycles(4);
 
         let value = self.get_memory_value_at_hl();
-        self.increment_4_cycles_update_timers_and_ppu();
+        self.increment_cycles(4);
 
         let (result, carry) = self.registers.a.overflowing_add(value);
         let h_flag = crate::gameboy_core::cpu_components::FlagsRegister::calculate_h_flag_on_add(self.registers.a, value);
@@ -100,25 +99,25 @@ impl Cpu8BitArithmeticLogicalInstructions for Cpu {
 
     /// Adds the contents of register r and CY to the contents of register A and stores the results in register A.
     fn adc_a_r(&mut self, opcode: u8) {
-        self.increment_4_cycles_update_timers_and_ppu();
-        let source = Cpu::get_source_register(opcode);
+        self.increment_cycles(4);
+        let source = Self::get_source_register(opcode);
         let value = self.registers.get_8bit_register_value(source);
         self.adc_a_value(value);
     }
 
     /// Adds the contents of the immediate byte and CY to the contents of register A and stores the results in register A.
     fn adc_a_imm8(&mut self) {
-        self.increment_4_cycles_update_timers_and_ppu();
-        self.adc_a_value(self.get_imm8());
-        self.increment_4_cycles_update_timers_and_ppu();
-        self.registers.increment_pc();
+        self.increment_cycles(4);
+        let value = self.get_imm8_and_advance_pc();
+        self.adc_a_value(value);
+        self.increment_cycles(4);
     }
 
     /// Adds the contents of memory specified by the contents of register pair HL and CY to the contents of register A and stores the results in register A.
     fn adc_a_hl(&mut self) {
-        self.increment_4_cycles_update_timers_and_ppu();
+        self.increment_cycles(4);
         let value = self.get_memory_value_at_hl();
-        self.increment_4_cycles_update_timers_and_ppu();
+        self.increment_cycles(4);
         self.adc_a_value(value);
     }
 
@@ -145,26 +144,25 @@ impl Cpu8BitArithmeticLogicalInstructions for Cpu {
 
     /// Subtracts the contents of register r from the contents of register A and stores the results in register A.
     fn sub_a_r(&mut self, opcode: u8) {
-        self.increment_4_cycles_update_timers_and_ppu();
-        let source = Cpu::get_source_register(opcode);
+        self.increment_cycles(4);
+        let source = Self::get_source_register(opcode);
         let value = self.registers.get_8bit_register_value(source);
         self.sub_a_value(value);
     }
 
     /// Subtracts the 8-bit immediate operand n from the contents of register A and stores the results in register A.
     fn sub_a_imm8(&mut self) {
-        self.increment_4_cycles_update_timers_and_ppu();
-        let value = self.get_imm8();
-        self.increment_4_cycles_update_timers_and_ppu();
+        self.increment_cycles(4);
+        let value = self.get_imm8_and_advance_pc();
+        self.increment_cycles(4);
         self.sub_a_value(value);
-        self.registers.increment_pc();
     }
 
     /// Subtracts the contents of memory specified by the contents of register pair HL from the contents of register A and stores the results in register A.
     fn sub_a_hl(&mut self) {
-        self.increment_4_cycles_update_timers_and_ppu();
+        self.increment_cycles(4);
         let value = self.get_memory_value_at_hl();
-        self.increment_4_cycles_update_timers_and_ppu();
+        self.increment_cycles(4);
         self.sub_a_value(value);
     }
 
@@ -193,26 +191,25 @@ impl Cpu8BitArithmeticLogicalInstructions for Cpu {
 
     /// Subtracts the contents of register r and CY from the contents of register A and stores the results in register A.
     fn sbc_a_r(&mut self, opcode: u8) {
-        self.increment_4_cycles_update_timers_and_ppu();
-        let source = Cpu::get_source_register(opcode);
+        self.increment_cycles(4);
+        let source = Self::get_source_register(opcode);
         let value = self.registers.get_8bit_register_value(source);
         self.sbc_a_value(value);
     }
 
     /// Subtracts the 8-bit immediate operand n and CY from the contents of register A and stores the results in register A.
     fn sbc_a_imm8(&mut self) {
-        self.increment_4_cycles_update_timers_and_ppu();
-        let value = self.get_imm8();
-        self.increment_4_cycles_update_timers_and_ppu();
+        self.increment_cycles(4);
+        let value = self.get_imm8_and_advance_pc();
+        self.increment_cycles(4);
         self.sbc_a_value(value);
-        self.registers.increment_pc();
     }
 
     /// Subtracts the contents of memory specified by the contents of register pair HL and CY from the contents of register A and stores the results in register A.
     fn sbc_a_hl(&mut self) {
-        self.increment_4_cycles_update_timers_and_ppu();
+        self.increment_cycles(4);
         let value = self.get_memory_value_at_hl();
-        self.increment_4_cycles_update_timers_and_ppu();
+        self.increment_cycles(4);
         self.sbc_a_value(value);
     }
 
@@ -248,26 +245,25 @@ impl Cpu8BitArithmeticLogicalInstructions for Cpu {
 
     /// Takes the logical-AND for each bit of the contents of register r and register A, and stores the results in register A.
     fn and_a_r(&mut self, opcode: u8) {
-        self.increment_4_cycles_update_timers_and_ppu();
-        let source = Cpu::get_source_register(opcode);
+        self.increment_cycles(4);
+        let source = Self::get_source_register(opcode);
         let value = self.registers.get_8bit_register_value(source);
         self.and_a_value(value);
     }
 
     /// Takes the logical-AND for each bit of the contents of immediate operand and register A, and stores the results in register A.
     fn and_a_imm8(&mut self) {
-        self.increment_4_cycles_update_timers_and_ppu();
-        let value = self.get_imm8();
-        self.increment_4_cycles_update_timers_and_ppu();
+        self.increment_cycles(4);
+        let value = self.get_imm8_and_advance_pc();
+        self.increment_cycles(4);
         self.and_a_value(value);
-        self.registers.increment_pc();
     }
 
     /// Takes the logical-AND for each bit of the contents of memory specified by the contents of register pair HL and register A, and stores the results in register A.
     fn and_a_hl(&mut self) {
-        self.increment_4_cycles_update_timers_and_ppu();
+        self.increment_cycles(4);
         let value = self.get_memory_value_at_hl();
-        self.increment_4_cycles_update_timers_and_ppu();
+        self.increment_cycles(4);
         self.and_a_value(value);
     }
 
@@ -282,26 +278,25 @@ impl Cpu8BitArithmeticLogicalInstructions for Cpu {
 
     /// Takes the logical-OR for each bit of the contents of register r and register A, and stores the results in register A.
     fn or_a_r(&mut self, opcode: u8) {
-        self.increment_4_cycles_update_timers_and_ppu();
-        let source = Cpu::get_source_register(opcode);
+        self.increment_cycles(4);
+        let source = Self::get_source_register(opcode);
         let value = self.registers.get_8bit_register_value(source);
         self.or_a_value(value);
     }
 
     /// Takes the logical-OR for each bit of the contents of immediate operand and register A, and stores the results in register A.
     fn or_a_imm8(&mut self) {
-        self.increment_4_cycles_update_timers_and_ppu();
-        let value = self.get_imm8();
-        self.increment_4_cycles_update_timers_and_ppu();
+        self.increment_cycles(4);
+        let value = self.get_imm8_and_advance_pc();
+        self.increment_cycles(4);
         self.or_a_value(value);
-        self.registers.increment_pc();
     }
 
     /// Takes the logical-OR for each bit of the contents of memory specified by the contents of register pair HL and register A, and stores the results in register A.
     fn or_a_hl(&mut self) {
-        self.increment_4_cycles_update_timers_and_ppu();
+        self.increment_cycles(4);
         let value = self.get_memory_value_at_hl();
-        self.increment_4_cycles_update_timers_and_ppu();
+        self.increment_cycles(4);
         self.or_a_value(value);
     }
 
@@ -316,26 +311,25 @@ impl Cpu8BitArithmeticLogicalInstructions for Cpu {
 
     /// Takes the logical exclusive-OR for each bit of the contents of register r and register A, and stores the results in register A.
     fn xor_a_r(&mut self, opcode: u8) {
-        self.increment_4_cycles_update_timers_and_ppu();
-        let source = Cpu::get_source_register(opcode);
+        self.increment_cycles(4);
+        let source = Self::get_source_register(opcode);
         let value = self.registers.get_8bit_register_value(source);
         self.xor_a_value(value);
     }
 
     /// Takes the logical exclusive-OR for each bit of the contents of immediate operand and register A, and stores the results in register A.
     fn xor_a_imm8(&mut self) {
-        self.increment_4_cycles_update_timers_and_ppu();
-        let value = self.get_imm8();
-        self.increment_4_cycles_update_timers_and_ppu();
+        self.increment_cycles(4);
+        let value = self.get_imm8_and_advance_pc();
+        self.increment_cycles(4);
         self.xor_a_value(value);
-        self.registers.increment_pc();
     }
 
     /// Takes the logical exclusive-OR for each bit of the contents of memory specified by the contents of register pair HL and register A, and stores the results in register A.
     fn xor_a_hl(&mut self) {
-        self.increment_4_cycles_update_timers_and_ppu();
+        self.increment_cycles(4);
         let value = self.get_memory_value_at_hl();
-        self.increment_4_cycles_update_timers_and_ppu();
+        self.increment_cycles(4);
         self.xor_a_value(value);
     }
 
@@ -350,26 +344,25 @@ impl Cpu8BitArithmeticLogicalInstructions for Cpu {
 
     /// Compares the contents of register r and register A and sets the flag if they are equal.
     fn cp_a_r(&mut self, opcode: u8) {
-        self.increment_4_cycles_update_timers_and_ppu();
-        let source = Cpu::get_source_register(opcode);
+        self.increment_cycles(4);
+        let source = Self::get_source_register(opcode);
         let value = self.registers.get_8bit_register_value(source);
         self.cp_a_value(value);
     }
 
     /// Compares the contents of 8-bit immediate operand n and register A and sets the flag if they are equal.
     fn cp_a_imm8(&mut self) {
-        self.increment_4_cycles_update_timers_and_ppu();
-        let value = self.get_imm8();
-        self.increment_4_cycles_update_timers_and_ppu();
+        self.increment_cycles(4);
+        let value = self.get_imm8_and_advance_pc();
+        self.increment_cycles(4);
         self.cp_a_value(value);
-        self.registers.increment_pc();
     }
 
     /// Compares the contents of memory specified by the contents of register pair HL and register A and sets the flag if they are equal.
     fn cp_a_hl(&mut self) {
-        self.increment_4_cycles_update_timers_and_ppu();
+        self.increment_cycles(4);
         let value = self.get_memory_value_at_hl();
-        self.increment_4_cycles_update_timers_and_ppu();
+        self.increment_cycles(4);
         self.cp_a_value(value);
     }
 
@@ -395,7 +388,7 @@ impl Cpu8BitArithmeticLogicalInstructions for Cpu {
 
     /// Increments the contents of register r by 1.
     fn inc_r(&mut self, opcode: u8) {
-        self.increment_4_cycles_update_timers_and_ppu();
+        self.increment_cycles(4);
         let destination_register = Self::get_destination_register(opcode);
         let value = self.registers.get_8bit_register_value(destination_register);
 
@@ -411,9 +404,9 @@ impl Cpu8BitArithmeticLogicalInstructions for Cpu {
 
     /// Increments by 1 the contents of memory specified by register pair HL.
     fn inc_hl(&mut self) {
-        self.increment_4_cycles_update_timers_and_ppu();
+        self.increment_cycles(4);
         let value = self.get_memory_value_at_hl();
-        self.increment_4_cycles_update_timers_and_ppu();
+        self.increment_cycles(4);
 
         let (result, _carry) = value.overflowing_add(1);
         let h_flag = crate::gameboy_core::cpu_components::FlagsRegister::calculate_h_flag_on_add(value, 1);
@@ -422,12 +415,12 @@ impl Cpu8BitArithmeticLogicalInstructions for Cpu {
         self.registers.flags.set_h_flag(h_flag);
 
         self.write_memory_value_at_hl(result);
-        self.increment_4_cycles_update_timers_and_ppu();
+        self.increment_cycles(4);
     }
 
     /// Subtract 1 from the contents of register r.
     fn dec_r(&mut self, opcode: u8) {
-        self.increment_4_cycles_update_timers_and_ppu();
+        self.increment_cycles(4);
         let destination_register = Self::get_destination_register(opcode);
         let value = self.registers.get_8bit_register_value(destination_register);
 
@@ -442,9 +435,9 @@ impl Cpu8BitArithmeticLogicalInstructions for Cpu {
 
     /// Decrements by 1 the contents of memory specified by register pair HL.
     fn dec_hl(&mut self) {
-        self.increment_4_cycles_update_timers_and_ppu();
+        self.increment_cycles(4);
         let value = self.get_memory_value_at_hl();
-        self.increment_4_cycles_update_timers_and_ppu();
+        self.increment_cycles(4);
 
         let (result, _carry) = value.overflowing_sub(1);
         let h_flag = crate::gameboy_core::cpu_components::FlagsRegister::calculate_h_flag_on_sub(value, 1);
@@ -453,6 +446,6 @@ impl Cpu8BitArithmeticLogicalInstructions for Cpu {
         self.registers.flags.set_h_flag(h_flag);
 
         self.write_memory_value_at_hl(result);
-        self.increment_4_cycles_update_timers_and_ppu();
+        self.increment_cycles(4);
     }
 }
\ No newline at end of file
@@ -1,6 +1,7 @@
 use crate::gameboy_core::{
     constants::START_ADDRESS_FOR_LOAD_INSTRUCTIONS,
     cpu::Cpu,
+    cpu_components::MemoryAccess,
 };
 
 pub trait Cpu8BitTransferInputOutputInstructions {
@@ -25,23 +26,22 @@ pub trait Cpu8BitTransferInputOutputInstructions {
     fn ld_hld_a(&mut self);
 }
 
-impl Cpu8BitTransferInputOutputInstructions for Cpu {
+impl<M: MemoryAccess> Cpu8BitTransferInputOutputInstructions for Cpu<M> {
     /// Load the 8-bit immediate value into the specified 8-bit register.
     fn ld_r8_imm8(&mut self, opcode: u8) {
-        self.increment_4_cycles_update_timers_and_ppu();
+        self.increment_cycles(4);
 
-        let destination = Cpu::get_destination_register(opcode);
-        let imm8 = self.get_imm8();
-        self.increment_4_cycles_update_timers_and_ppu();
+        let destination = Self::get_destination_register(opcode);
+        let imm8 = self.get_imm8_and_advance_pc();
+        self.increment_cycles(4);
 
         self.registers.set_8bit_register_value(destination, imm8);
-        self.registers.increment_pc();
     }
 
     /// Load the contents of one 8-bit register into another 8-bit register.
     fn ld_r8_r8(&mut self, opcode: u8) {
-        let destination = Cpu::get_destination_register(opcode);
-        let source = Cpu::get_source_register(opcode);
+        let destination = Self::get_destination_register(opcode);
+        let source = Self::get_source_register(opcode);
 
         if destination != source {
             let value = self.registers.get_8bit_register_value(source);
@@ -49,61 +49,61 @@ impl Cpu8BitTransferInputOutputInstructions for Cpu {
         }
 
         // FIX: Always update PPU/timers, even when destination == source
-        self.increment_4_cycles_update_timers_and_ppu();
+        self.increment_cycles(4);
     }
 
     /// Load the contents of register HL into 8-bit register.
     fn ld_r8_hl(&mut self, opcode: u8) {
-        let destination = Cpu::get_destination_register(opcode);
+        self.increment_cycles(4);
+        let destination = Self::get_destination_register(opcode);
+
+        self.increment_cycles(4);
         let value = self.get_memory_value_at_hl();
         self.registers.set_8bit_register_value(destination, value);
-
-        self.increment_4_cycles_update_timers_and_ppu();
     }
 
     /// Stores the contents of register r in memory specified by register pair HL.
     fn ld_hl_r8(&mut self, opcode: u8) {
-        let source = Cpu::get_source_register(opcode);
+        let source = Self::get_source_register(opcode);
         let value = self.registers.get_8bit_register_value(source);
         let hl = self.registers.get_hl();
-        self.increment_4_cycles_update_timers_and_ppu();
+        self.increment_cycles(4);
 
         self.memory_bus.write_byte(hl, value);
-        self.increment_4_cycles_update_timers_and_ppu();
+        self.increment_cycles(4);
     }
 
     /// Loads 8-bit immediate data n into memory specified by register pair HL.
     fn ld_hl_imm8(&mut self) {
-        self.increment_4_cycles_update_timers_and_ppu();
+        self.increment_cycles(4);
 
-        let imm8 = self.get_imm8();
-        self.increment_4_cycles_update_timers_and_ppu();
+        let imm8 = self.get_imm8_and_advance_pc();
+        self.increment_cycles(4);
 
         let hl = self.registers.get_hl();
         self.memory_bus.write_byte(hl, imm8);
-        self.registers.increment_pc();
 
-        self.increment_4_cycles_update_timers_and_ppu();
+        self.increment_cycles(4);
     }
 
     /// Loads the contents specified by the contents of register pair BC into register A.
     fn ld_a_bc(&mut self) {
-        self.increment_4_cycles_update_timers_and_ppu();
+        self.increment_cycles(4);
 
         let bc = self.registers.get_bc();
 
-        self.increment_4_cycles_update_timers_and_ppu();
+        self.increment_cycles(4);
         let value = self.memory_bus.read_byte(bc);
         self.registers.a = value;
     }
 
     /// Loads the contents specified by the contents of register pair DE into register A.
     fn ld_a_de(&mut self) {
-        self.increment_4_cycles_update_timers_and_ppu();
+        self.increment_cycles(4);
 
         let de = self.registers.get_de();
         let value = self.memory_bus.read_byte(de);
-        self.increment_4_cycles_update_timers_and_ppu();
+        self.increment_cycles(4);
 
         self.registers.a = value;
     }
@@ -111,12 +111,12 @@ impl Cpu8BitTransferInputOutputInstructions for Cpu {
     /// Loads into register A the contents of the internal RAM, port register, or mode register at the address in
     /// the range FF00h-FFFFh specified by register C.
     fn ld_a_c(&mut self) {
-        self.increment_4_cycles_update_timers_and_ppu();
+        self.increment_cycles(4);
 
         let c_register_value = self.registers.c as u16;
         let ram_address = START_ADDRESS_FOR_LOAD_INSTRUCTIONS + c_register_value;
         let value = self.memory_bus.read_byte(ram_address);
-        self.increment_4_cycles_update_timers_and_ppu();
+        self.increment_cycles(4);
 
         self.registers.a = value;
     }
@@ -124,12 +124,12 @@ impl Cpu8BitTransferInputOutputInstructions for Cpu {
     /// Loads the contents of register A in the internal RAM, port register, or mode register at the address in the
     /// range FF00h-FFFFh specified by register C.
     fn ld_c_a(&mut self) {
-        self.increment_4_cycles_update_timers_and_ppu();
+        self.increment_cycles(4);
 
         let c_register_value = self.registers.c as u16;
         let ram_address = START_ADDRESS_FOR_LOAD_INSTRUCTIONS + c_register_value;
         self.memory_bus.write_byte(ram_address, self.registers.a);
-        self.increment_4_cycles_update_timers_and_ppu();
+        self.increment_cycles(4);
     }
 
     /// Loads into register A the contents of the internal RAM, port register, or mode register at the address in the range FF00h-FFFFh
@@ -137,17 +137,16 @@ impl Cpu8BitTransferInputOutputInstructions for Cpu {
     /// Note, however, that a 16-bit address should be specified for the mnemonic portion of n, because only the lower-order 8 bits are
     /// automatically reflected in the machine language.
     fn ld_a_imm8(&mut self) {
-        self.increment_4_cycles_update_timers_and_ppu();
+        self.increment_cycles(4);
          
-        let imm8 = self.get_imm8() as u16;
-        self.increment_4_cycles_update_timers_and_ppu();
+        let imm8 = self.get_imm8_and_advance_pc() as u16;
+        self.increment_cycles(4);
 
         let address_to_read_from = START_ADDRESS_FOR_LOAD_INSTRUCTIONS + imm8;
         let value = self.memory_bus.read_byte(address_to_read_from);
-        self.increment_4_cycles_update_timers_and_ppu();
+        self.increment_cycles(4);
 
         self.registers.a = value;
-        self.registers.increment_pc();
     }
 
     /// Loads the contents of register A to the internal RAM, port register, or mode register at the address in the range FF00h-FFFFh
@@ -155,53 +154,46 @@ impl Cpu8BitTransferInputOutputInstructions for Cpu {
     /// Note, however, that a 16-bit address should be specified for the mnemonic portion of n, because only the
     /// lower-order 8 bits are automatically reflected in the machine language.
     fn ld_imm8_a(&mut self) {
-        self.increment_4_cycles_update_timers_and_ppu();
+        self.increment_cycles(4);
 
-        let imm8 = self.get_imm8() as u16;
-        self.increment_4_cycles_update_timers_and_ppu();
+        let imm8 = self.get_imm8_and_advance_pc() as u16;
+        self.increment_cycles(4);
 
         let address_to_write = START_ADDRESS_FOR_LOAD_INSTRUCTIONS + imm8;
         self.memory_bus.write_byte(address_to_write, self.registers.a);
-        self.increment_4_cycles_update_timers_and_ppu();
-
-        self.registers.increment_pc();
+        self.increment_cycles(4);
     }
 
     /// Loads into register A the contents of the internal RAM or register specified by 16-bit immediate operand nn.
     fn ld_a_imm16(&mut self) {
-        self.increment_4_cycles_update_timers_and_ppu();
+        self.increment_cycles(4);
 
-        let imm16 = self.get_imm16();
-        self.increment_4_cycles_update_timers_and_ppu();
-        self.increment_4_cycles_update_timers_and_ppu();
+        let imm16 = self.get_imm16_and_advance_pc();
+        self.increment_cycles(8);
 
         let value = self.memory_bus.read_byte(imm16);
-        self.increment_4_cycles_update_timers_and_ppu();
+        self.increment_cycles(4);
 
         self.registers.a = value;
-        self.registers.increment_pc_twice();
     }
 
     /// Loads the contents of register A to the internal RAM or register specified by 16-bit immediate operand nn.
     fn ld_imm16_a(&mut self) {
-        self.increment_4_cycles_update_timers_and_ppu();
+        self.increment_cycles(4);
 
-        let imm16 = self.get_imm16();
-        self.increment_4_cycles_update_timers_and_ppu();
-        self.increment_4_cycles_update_timers_and_ppu();
+        let imm16 = self.get_imm16_and_advance_pc();
+        self.increment_cycles(8);
 
         self.memory_bus.write_byte(imm16, self.registers.a);
-        self.increment_4_cycles_update_timers_and_ppu();
-
-        self.registers.increment_pc_twice();
+        self.increment_cycles(4);
     }
 
     /// Loads in register A the contents of memory specified by the contents of register pair HL and simultaneously increments the contents of HL.
     fn ld_a_hli(&mut self) {
-        self.increment_4_cycles_update_timers_and_ppu();
+        self.increment_cycles(4);
 
         let value = self.get_memory_value_at_hl();
-        self.increment_4_cycles_update_timers_and_ppu();
+        self.increment_cycles(4);
 
         self.registers.a = value;
         self.registers.increment_hl();
@@ -211,10 +203,10 @@ impl Cpu8BitTransferInputOutputInstructions for Cpu {
     /// Example: When HL = 8A5Ch and (8A5Ch) = 3Ch,
     /// LD A, (HLD) ; A ← 3Ch, HL ← 8A5Bh
     fn ld_a_hld(&mut self) {
-        self.increment_4_cycles_update_timers_and_ppu();
+        self.increment_cycles(4);
 
         let value = self.get_memory_value_at_hl();
-        self.increment_4_cycles_update_timers_and_ppu();
+        self.increment_cycles(4);
 
         self.registers.a = value;
         self.registers.decrement_hl();
@@ -224,33 +216,33 @@ impl Cpu8BitTransferInputOutputInstructions for Cpu {
     /// Example: When BC = 205Fh and A = 3Fh,
     /// LD (BC) , A ; (205Fh) ← 3Fh
     fn ld_bc_a(&mut self) {
-        self.increment_4_cycles_update_timers_and_ppu();
+        self.increment_cycles(4);
 
         let bc = self.registers.get_bc();
         self.memory_bus.write_byte(bc, self.registers.a);
-        self.increment_4_cycles_update_timers_and_ppu();
+        self.increment_cycles(4);
      }
 
     /// Stores the contents of register A in the memory specified by register pair DE.
     /// Example: When DE = 205Ch and A = 00h,
     /// LD (DE) , A ; (205Ch) ← 00h
     fn ld_de_a(&mut self) {
-        self.increment_4_cycles_update_timers_and_ppu();
+        self.increment_cycles(4);
 
         let de = self.registers.get_de();
         self.memory_bus.write_byte(de, self.registers.a);
-        self.increment_4_cycles_update_timers_and_ppu();
+        self.increment_cycles(4);
     }
 
     /// Stores the contents of register A in the memory specified by register pair HL and simultaneously increments the contents of HL.
     /// Example: When HL = FFFFh and A = 56h,
     /// LD (HLI), A ; (0xFFFF) ← 56h, HL = 0000h
     fn ld_hli_a(&mut self) {
-        self.increment_4_cycles_update_timers_and_ppu();
+        self.increment_cycles(4);
 
         let hl = self.registers.get_hl();
         self.memory_bus.write_byte(hl, self.registers.a);
-        self.increment_4_cycles_update_timers_and_ppu();
+        self.increment_cycles(4);
         self.registers.increment_hl();
     }
 
@@ -258,10 +250,10 @@ impl Cpu8BitTransferInputOutputInstructions for Cpu {
     /// Example: HL = 4000h and A = 5h,
     /// LD (HLD), A ; (4000h) ← 5h, HL = 3FFFh
     fn ld_hld_a(&mut self) {
-        self.increment_4_cycles_update_timers_and_ppu();
+        self.increment_cycles(4);
         let hl = self.registers.get_hl();
         self.memory_bus.write_byte(hl, self.registers.a);
-        self.increment_4_cycles_update_timers_and_ppu();
+        self.increment_cycles(4);
 
         self.registers.decrement_hl();
     }
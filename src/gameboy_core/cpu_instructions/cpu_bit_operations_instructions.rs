@@ -1,4 +1,4 @@
-use crate::gameboy_core::cpu::Cpu;
+use crate::gameboy_core::{cpu::Cpu, cpu_components::MemoryAccess};
 
 pub trait CpuBitOperationsInstructions {
     fn reset_b_hl(&mut self, cb_opcode: u8);
@@ -9,12 +9,11 @@ pub trait CpuBitOperationsInstructions {
     fn bit_b_hl(&mut self, cb_opcode: u8);
 }
 
-impl CpuBitOperationsInstructions for Cpu {
+impl<M: MemoryAccess> CpuBitOperationsInstructions for Cpu<M> {
     /// Test bit b in 8-bit register. Set Z flag if the bit is 0.
     /// Set H flag. Reset N flag.
     fn bit_b_r8(&mut self, cb_opcode: u8) {
-        self.increment_4_cycles_update_timers_and_ppu();
-        self.increment_4_cycles_update_timers_and_ppu();
+        self.increment_cycles(8);
         let bit_index = (cb_opcode & 0b00111000) >> 3;
         let register = Self::get_source_register(cb_opcode);
         let value = self.registers.get_8bit_register_value(register);
@@ -39,11 +38,10 @@ impl CpuBitOperationsInstructions for Cpu {
     /// Test bit b in memory location pointed by HL register. Set Z flag if the bit is 0.
     /// Set H flag. Reset N flag.
     fn bit_b_hl(&mut self, cb_opcode: u8) {
-        self.increment_4_cycles_update_timers_and_ppu();
-        self.increment_4_cycles_update_timers_and_ppu();
+        self.increment_cycles(8);
         let bit_index = (cb_opcode & 0b00111000) >> 3;
         let value = self.memory_bus.read_byte(self.registers.get_hl());
-        self.increment_4_cycles_update_timers_and_ppu();
+        self.increment_cycles(4);
 
         let bit = match bit_index {
             0 => (value >> 0) & 0x01,
@@ -64,8 +62,7 @@ impl CpuBitOperationsInstructions for Cpu {
 
     /// Sets to 1 the specified bit in specified 8-bit register.
     fn set_b_r8(&mut self, cb_opcode: u8) {
-        self.increment_4_cycles_update_timers_and_ppu();
-        self.increment_4_cycles_update_timers_and_ppu();
+        self.increment_cycles(8);
         let bit_index = (cb_opcode & 0b00111000) >> 3;
         let register = Self::get_source_register(cb_opcode);
         let mut value = self.registers.get_8bit_register_value(register);
@@ -87,12 +84,11 @@ impl CpuBitOperationsInstructions for Cpu {
     
     /// Sets to 1 the specified bit in memory location pointed by HL register.
     fn set_b_hl(&mut self, cb_opcode: u8) {
-        self.increment_4_cycles_update_timers_and_ppu();
-        self.increment_4_cycles_update_timers_and_ppu();
+        self.increment_cycles(8);
         let bit_index = (cb_opcode & 0b00111000) >> 3;
         let hl = self.registers.get_hl();
         let mut value = self.memory_bus.read_byte(hl);
-        self.increment_4_cycles_update_timers_and_ppu();
+        self.increment_cycles(4);
         
         value = match bit_index {
             0 => value | 0b01,
@@ -107,13 +103,12 @@ impl CpuBitOperationsInstructions for Cpu {
         };
         
         self.memory_bus.write_byte(hl, value);
-        self.increment_4_cycles_update_timers_and_ppu();
+        self.increment_cycles(4);
     }
 
     /// Resets to 0 the specified bit in specified 8-bit register.
     fn reset_b_r8(&mut self, cb_opcode: u8) {
-        self.increment_4_cycles_update_timers_and_ppu();
-        self.increment_4_cycles_update_timers_and_ppu();
+        self.increment_cycles(8);
         let bit_index = (cb_opcode & 0b00111000) >> 3;
         let register = Self::get_source_register(cb_opcode);
         let mut value = self.registers.get_8bit_register_value(register);
@@ -135,12 +130,11 @@ impl CpuBitOperationsInstructions for Cpu {
     
     /// Sets to 1 the specified bit in memory location pointed by HL register.
     fn reset_b_hl(&mut self, cb_opcode: u8) {
-        self.increment_4_cycles_update_timers_and_ppu();
-        self.increment_4_cycles_update_timers_and_ppu();
+        self.increment_cycles(8);
         let bit_index = (cb_opcode & 0b00111000) >> 3;
         let hl = self.registers.get_hl();
         let mut value = self.memory_bus.read_byte(hl);
-        self.increment_4_cycles_update_timers_and_ppu();
+        self.increment_cycles(4);
         
         value = match bit_index {
             0 => value & 0b11111110,
@@ -155,6 +149,6 @@ impl CpuBitOperationsInstructions for Cpu {
         };
         
         self.memory_bus.write_byte(hl, value);
-        self.increment_4_cycles_update_timers_and_ppu();
+        self.increment_cycles(4);
     }
 }
\ No newline at end of file
@@ -1,4 +1,4 @@
-use crate::gameboy_core::cpu::Cpu;
+use crate::gameboy_core::{cpu::Cpu, cpu_components::MemoryAccess};
 
 /// Trait for CPU call and return instructions
 pub trait CpuCallAndReturnInstructions {
@@ -10,31 +10,27 @@ pub trait CpuCallAndReturnInstructions {
     fn reti(&mut self);
 }
 
-impl CpuCallAndReturnInstructions for Cpu {
+impl<M: MemoryAccess> CpuCallAndReturnInstructions for Cpu<M> {
     /// Pops from the memory stack the PC value pushed when the subroutine was called, returning control to the source program.
     /// In this case, the contents of the address specified by the SP are loaded in the lower-order byte of the PC,
     /// and the content of the SP is incremented by 1. The contents of the address specified by the new SP
     /// value are then loaded in the higher-order byte of the PC, and the SP is again incremented by 1. (The
     /// value of SP is 2 larger than before instruction execution.)
     fn ret(&mut self) {
-        self.increment_4_cycles_update_timers_and_ppu();
+        self.increment_cycles(4);
         self.registers.pc = self.pop_value_from_sp();
-        self.increment_4_cycles_update_timers_and_ppu();
-        self.increment_4_cycles_update_timers_and_ppu();
-        self.increment_4_cycles_update_timers_and_ppu();
+        self.increment_cycles(12);
     }
 
     /// Pushes the current value of the PC to the memory stack and loads to the PC the 16-bit immediate value.
     /// Then next instruction is fetched from the address specified by the new content of PC.
     fn call_imm16(&mut self) {
-        self.increment_4_cycles_update_timers_and_ppu();
-        self.push_value_to_sp(self.registers.pc + 2); // +2 to point to the next instruction after call
-        self.increment_4_cycles_update_timers_and_ppu();
-        self.increment_4_cycles_update_timers_and_ppu();
-        self.registers.pc = self.get_imm16();
-        self.increment_4_cycles_update_timers_and_ppu();
-        self.increment_4_cycles_update_timers_and_ppu();
-        self.increment_4_cycles_update_timers_and_ppu();
+        self.increment_cycles(4);
+        let address = self.get_imm16_and_advance_pc(); // PC now points to the next instruction after call
+        self.push_value_to_sp(self.registers.pc);
+        self.increment_cycles(8);
+        self.registers.pc = address;
+        self.increment_cycles(12);
     }
 
     /// If condition cc matches the flag, the PC value is pushed onto the stack and the PC is loaded with the 16-bit immediate value.
@@ -47,10 +43,9 @@ impl CpuCallAndReturnInstructions for Cpu {
         if self.check_cc_condition(opcode) {
             self.call_imm16();
         } else {
-            self.increment_4_cycles_update_timers_and_ppu();
+            self.increment_cycles(4);
             self.registers.increment_pc_twice();
-            self.increment_4_cycles_update_timers_and_ppu();
-            self.increment_4_cycles_update_timers_and_ppu();
+            self.increment_cycles(8);
         }
     }
 
@@ -61,11 +56,9 @@ impl CpuCallAndReturnInstructions for Cpu {
     /// by 1, and the lower-order byte of the PC is loaded in the memory address specified by that value of the SP.
     /// The RST instruction can be used to jump to 1 of 8 addresses.
     fn rst(&mut self, opcode: u8) {
-        self.increment_4_cycles_update_timers_and_ppu();
-        self.increment_4_cycles_update_timers_and_ppu();
+        self.increment_cycles(8);
         self.push_value_to_sp(self.registers.pc);
-        self.increment_4_cycles_update_timers_and_ppu();
-        self.increment_4_cycles_update_timers_and_ppu();
+        self.increment_cycles(8);
         self.registers.pc = match (opcode & 0b00111000) >> 3 {
             0 => 0x0,
             1 => 0x0008,
@@ -82,26 +75,21 @@ impl CpuCallAndReturnInstructions for Cpu {
     /// If condition cc matches the flag, pops from the memory stack the PC value pushed when the subroutine was called.
     fn ret_cc(&mut self, opcode: u8) {
         if self.check_cc_condition(opcode) {
-            self.increment_4_cycles_update_timers_and_ppu();
-            self.increment_4_cycles_update_timers_and_ppu();
+            self.increment_cycles(8);
             self.registers.pc = self.pop_value_from_sp();
-            self.increment_4_cycles_update_timers_and_ppu();
-            self.increment_4_cycles_update_timers_and_ppu();
-            self.increment_4_cycles_update_timers_and_ppu();
+            self.increment_cycles(12);
         } else {
-            self.increment_4_cycles_update_timers_and_ppu();
-            self.increment_4_cycles_update_timers_and_ppu();
+            self.increment_cycles(8);
         }
         // If condition is false, PC stays at the next instruction (already incremented by tick)
     }
 
     // Pop two bytes from stack & jump to that address then enable interrupts.
     fn reti(&mut self) {
-        self.increment_4_cycles_update_timers_and_ppu();
+        self.increment_cycles(4);
         self.registers.pc = self.pop_value_from_sp();
-        self.increment_4_cycles_update_timers_and_ppu();
-        self.increment_4_cycles_update_timers_and_ppu();
+        self.increment_cycles(8);
         self.set_ime(true);
-        self.increment_4_cycles_update_timers_and_ppu();
+        self.increment_cycles(4);
     }
 }
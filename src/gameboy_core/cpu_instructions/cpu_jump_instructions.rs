@@ -1,4 +1,4 @@
-use crate::gameboy_core::cpu::Cpu;
+use crate::gameboy_core::{cpu::Cpu, cpu_components::MemoryAccess};
 
 /// Trait for CPU jump instructions
 pub trait CpuJumpInstructions {
@@ -9,15 +9,14 @@ pub trait CpuJumpInstructions {
     fn jp_hl(&mut self);
 }
 
-impl CpuJumpInstructions for Cpu {
+impl<M: MemoryAccess> CpuJumpInstructions for Cpu<M> {
     /// Loads the 16-bit immediate value to the program counter (PC).
     fn jp_imm16(&mut self) {
-        self.increment_4_cycles_update_timers_and_ppu();
-        let imm16 = self.get_imm16();
-        self.increment_4_cycles_update_timers_and_ppu();
-        self.increment_4_cycles_update_timers_and_ppu();
+        self.increment_cycles(4);
+        let imm16 = self.get_imm16_and_advance_pc();
+        self.increment_cycles(8);
         self.registers.pc = imm16;
-        self.increment_4_cycles_update_timers_and_ppu();
+        self.increment_cycles(4);
     }
 
     /// Loads operand nn in the PC if condition cc and the flag status match.
@@ -28,10 +27,9 @@ impl CpuJumpInstructions for Cpu {
         if self.check_cc_condition(opcode) {
             self.jp_imm16();
         } else {
-            self.increment_4_cycles_update_timers_and_ppu();
+            self.increment_cycles(4);
             self.registers.increment_pc_twice();
-            self.increment_4_cycles_update_timers_and_ppu();
-            self.increment_4_cycles_update_timers_and_ppu();
+            self.increment_cycles(8);
         }
     }
 
@@ -42,45 +40,34 @@ impl CpuJumpInstructions for Cpu {
     /// Example: 0xF6 as u8 = 246
     ///          0xF6 as i8 = -10 (two's complement interpretation).
     fn jr_imm8(&mut self) {
-        self.increment_4_cycles_update_timers_and_ppu();
-        // Read the signed offset (PC is already at opcode + 1)
-        let imm8 = self.get_imm8() as i8; // Parse to i8 to handle
-        self.increment_4_cycles_update_timers_and_ppu();
-        self.registers.increment_pc(); // Move past the offset byte
+        self.increment_cycles(4);
+        // Read the signed offset (PC is already at opcode + 1) and move past it, the offset is relative to the next instruction
+        let imm8 = self.get_imm8_and_advance_pc() as i8; // Parse to i8 to handle
+        self.increment_cycles(4);
 
         // Add the signed offset to PC
         // We need to convert i8 to i16 first to handle negative numbers correctly
         self.registers.pc = (self.registers.pc as i16).wrapping_add(imm8 as i16) as u16;
 
-        self.increment_4_cycles_update_timers_and_ppu();
+        self.increment_cycles(4);
     }
 
     /// If condition cc and the flag status match, jumps -127 to +129 steps from the current address.
     /// If cc and the flag status do not match, the instruction following the current JP instruction is executed.
-    /// Note: JR cc uses bits 4-3 for the condition, different from JP cc which uses bits 5-3
+    /// The condition is in bits 4-3 of the opcode, like for JP cc, CALL cc and RET cc.
     fn jr_cc_imm8(&mut self, opcode: u8) {
-        // Extract condition from bits 4-3 (for JR cc instructions)
-        let condition = (opcode & 0b00011000) >> 3;
-        let condition_met = match condition {
-            0 => !self.registers.flags.z, // NZ
-            1 => self.registers.flags.z,  // Z
-            2 => !self.registers.flags.c, // NC
-            3 => self.registers.flags.c,  // C
-            _ => false,
-        };
-
-        if condition_met {
+        if self.check_cc_condition(opcode) {
             self.jr_imm8();
         } else {
-            self.increment_4_cycles_update_timers_and_ppu();
+            self.increment_cycles(4);
             self.registers.increment_pc(); // Move past the offset byte
-            self.increment_4_cycles_update_timers_and_ppu();
+            self.increment_cycles(4);
         }
     }
 
     /// Loads the contents of register pair HL in program counter PC.
     fn jp_hl(&mut self) {
-        self.increment_4_cycles_update_timers_and_ppu();
+        self.increment_cycles(4);
         self.registers.pc = self.registers.get_hl();
     }
 }
@@ -1,7 +1,12 @@
-use crate::gameboy_core::registers_contants::{IE, IF};
+use crate::gameboy_core::{
+    cpu::HaltMode,
+    cpu_components::MemoryAccess,
+    registers_contants::{IF, KEY1},
+};
 
 pub trait CpuMiscellaneousInstructions {
     fn is_interrupt_pending(&self) -> bool;
+    fn get_halt_mode(&self) -> HaltMode;
     fn stop(&mut self);
     fn halt(&mut self);
     fn daa(&mut self);
@@ -13,27 +18,27 @@ pub trait CpuMiscellaneousInstructions {
     fn ccf(&mut self);
 }
 
-impl CpuMiscellaneousInstructions for crate::gameboy_core::cpu::Cpu {
+impl<M: MemoryAccess> CpuMiscellaneousInstructions for crate::gameboy_core::cpu::Cpu<M> {
     /// No Operation - Do nothing for one CPU cycle.
     fn nop(&mut self) {
-        self.increment_4_cycles_update_timers_and_ppu();
+        self.increment_cycles(4);
         return;
     }
 
     /// This instruction disables interrupts but not immediately. Interrupts are disabled after instruction after DI is executed.
     fn di(&mut self) {
-        self.increment_4_cycles_update_timers_and_ppu();
+        self.increment_cycles(4);
         self.di_instruction_pending = true;
     }
 
     fn ei(&mut self) {
-        self.increment_4_cycles_update_timers_and_ppu();
+        self.increment_cycles(4);
         self.ei_instruction_pending = true;
     }
 
     /// Flips the carry flag CY. H and N flags are reset.
     fn ccf(&mut self) {
-        self.increment_4_cycles_update_timers_and_ppu();
+        self.increment_cycles(4);
         self.registers.flags.c = !self.registers.flags.c;
         self.registers.flags.h = false;
         self.registers.flags.n = false;
@@ -49,7 +54,7 @@ impl CpuMiscellaneousInstructions for crate::gameboy_core::cpu::Cpu {
     /// - If the C flag is set, subtract 0x60 from A.
     /// After the adjustment, the Z flag is set if A is zero, and the H flag is cleared.
     fn daa(&mut self) {
-        self.increment_4_cycles_update_timers_and_ppu();
+        self.increment_cycles(4);
         let mut adjustment = 0u8;
 
         if !self.registers.flags.n {
@@ -80,7 +85,7 @@ impl CpuMiscellaneousInstructions for crate::gameboy_core::cpu::Cpu {
 
     /// Inverts all bits in register A. N and H flags are set.
     fn cpl(&mut self) {
-        self.increment_4_cycles_update_timers_and_ppu();
+        self.increment_cycles(4);
         self.registers.a = !self.registers.a;
         self.registers.flags.n = true;
         self.registers.flags.h = true;
@@ -88,36 +93,50 @@ impl CpuMiscellaneousInstructions for crate::gameboy_core::cpu::Cpu {
 
     /// Sets the carry flag CY. H and N flags are reset.
     fn scf(&mut self) {
-        self.increment_4_cycles_update_timers_and_ppu();
+        self.increment_cycles(4);
         self.registers.flags.set_c_flag(true);
         self.registers.flags.h = false;
         self.registers.flags.n = false;
     }
 
+    /// Stops the CPU until an interrupt is pending. With IME disabled and an interrupt already pending it triggers
+    /// the HALT bug instead, see `HaltMode::HaltBug`.
     fn halt(&mut self) {
-        self.increment_4_cycles_update_timers_and_ppu();
-        let is_interrupt_pending = self.is_interrupt_pending();
+        self.increment_cycles(4);
 
-        if is_interrupt_pending {
-            if !self.ime {
-                // TODO: Handle the HALT bug here
-            }
-            // FIX: there is a pending interrupt, so we do not enter halt mode
-            // PPU update already called above, so just return
-            return;
+        match self.get_halt_mode() {
+            HaltMode::HaltBug => self.is_halt_bug_pending = true,
+            // FIX: if there is a pending interrupt, we do not enter halt mode
+            HaltMode::Normal if self.is_interrupt_pending() => {}
+            HaltMode::Normal => self.is_halt_mode = true,
         }
-
-        self.is_halt_mode = true;
     }
 
+    /// Stops the CPU until a button is pressed. STOP is 2 bytes long, the byte after the opcode is skipped.
+    /// On GBC, when KEY1 requests it, STOP switches the CPU speed instead.
     fn stop(&mut self) {
-        self.increment_4_cycles_update_timers_and_ppu();
+        self.increment_cycles(4);
+        self.registers.increment_pc();
+
+        if self.cgb_mode && self.memory_bus.read_byte(KEY1) & 0b0000_0001 != 0 {
+            todo!("CGB speed switch");
+        }
+
+        self.is_stop_mode = true;
     }
 
     fn is_interrupt_pending(&self) -> bool {
         let if_register = self.memory_bus.read_byte(IF);
-        let ie_register = self.memory_bus.read_byte(IE);
+        let ie_register = self.memory_bus.get_ie_register();
 
         (if_register & ie_register) != 0
     }
+
+    fn get_halt_mode(&self) -> HaltMode {
+        if !self.ime && self.is_interrupt_pending() {
+            HaltMode::HaltBug
+        } else {
+            HaltMode::Normal
+        }
+    }
 }
@@ -1,3 +1,5 @@
+use crate::gameboy_core::cpu_components::MemoryAccess;
+
 /// Trait for rotate and shift instruction operations
 pub trait CpuRotateShiftInstructions {
     fn swap_hl(&mut self);
@@ -24,13 +26,13 @@ pub trait CpuRotateShiftInstructions {
     fn sla_hl(&mut self);
 }
 
-impl CpuRotateShiftInstructions for crate::gameboy_core::cpu::Cpu {
+impl<M: MemoryAccess> CpuRotateShiftInstructions for crate::gameboy_core::cpu::Cpu<M> {
     /// Rotates the contents of register A to the left.
     /// That is, the contents of bit 0 are copied to bit 1 and the previous contents of bit 1 (the contents before the copy operation)
     /// are copied to bit 2. The same operation is repeated in sequence for the rest of the register.
     /// The contents of bit 7 are placed in both the CY flag and bit 0 of register A.
     fn rlca(&mut self) {
-        self.increment_4_cycles_update_timers_and_ppu();
+        self.increment_cycles(4);
         let rotated_value = self.rotate_left_and_update_flags(self.registers.a, false, false);
         self.registers.a = rotated_value;
     }
@@ -40,7 +42,7 @@ impl CpuRotateShiftInstructions for crate::gameboy_core::cpu::Cpu {
     /// of the register.
     /// The previous contents of the carry flag are copied to bit 0.
     fn rla(&mut self) {
-        self.increment_4_cycles_update_timers_and_ppu();
+        self.increment_cycles(4);
         let rotated_value = self.rotate_left_and_update_flags(self.registers.a, true, false);
         self.registers.a = rotated_value;
     }
@@ -50,7 +52,7 @@ impl CpuRotateShiftInstructions for crate::gameboy_core::cpu::Cpu {
     /// The same operation is repeated in sequence for the rest of the register.
     /// The contents of bit 0 are placed in both the C flag and bit 7 of register A
     fn rrca(&mut self) {
-        self.increment_4_cycles_update_timers_and_ppu();
+        self.increment_cycles(4);
         let rotated_value = self.rotate_right_and_update_flags(self.registers.a, false, false);
         self.registers.a = rotated_value;
     }
@@ -60,7 +62,7 @@ impl CpuRotateShiftInstructions for crate::gameboy_core::cpu::Cpu {
     /// The same operation is repeated in sequence for the rest of the register.
     /// The previous contents of the carry flag are copied to bit 7.
     fn rra(&mut self) {
-        self.increment_4_cycles_update_timers_and_ppu();
+        self.increment_cycles(4);
         let rotated_value = self.rotate_right_and_update_flags(self.registers.a, true, false);
         self.registers.a = rotated_value;
     }
@@ -70,8 +72,7 @@ impl CpuRotateShiftInstructions for crate::gameboy_core::cpu::Cpu {
     /// The same operation is repeated in sequence for the rest of the register.
     /// The contents of bit 7 are placed in both the CY flag and bit 0 of register B
     fn rlc_r8(&mut self, cb_opcode: u8) {
-        self.increment_4_cycles_update_timers_and_ppu();
-        self.increment_4_cycles_update_timers_and_ppu();
+        self.increment_cycles(8);
         let register = Self::get_source_register(cb_opcode);
         let value = self.registers.get_8bit_register_value(register);
 
@@ -83,15 +84,14 @@ impl CpuRotateShiftInstructions for crate::gameboy_core::cpu::Cpu {
     /// Rotates the contents of memory specified by register pair HL to the left.
     /// The contents of bit 7 are placed in both the CY flag and bit 0 of register B
     fn rlc_hl(&mut self) {
-        self.increment_4_cycles_update_timers_and_ppu();
-        self.increment_4_cycles_update_timers_and_ppu();
+        self.increment_cycles(8);
         let hl = self.registers.get_hl();
         let value = self.memory_bus.read_byte(hl);
-        self.increment_4_cycles_update_timers_and_ppu();
+        self.increment_cycles(4);
 
         let rotated_value = self.rotate_left_and_update_flags(value, false, true);
         self.memory_bus.write_byte(hl, rotated_value);
-        self.increment_4_cycles_update_timers_and_ppu();
+        self.increment_cycles(4);
     }
 
     /// Rotate the contents of 8-bit register to the left. That is, the contents of bit 0 are copied to bit 1,
@@ -99,8 +99,7 @@ impl CpuRotateShiftInstructions for crate::gameboy_core::cpu::Cpu {
     /// The same operation is repeated in sequence for the rest of the register.
     /// The previous contents of the carry (CY) flag are copied to bit 0 of register
     fn rl_r8(&mut self, cb_opcode: u8) {
-        self.increment_4_cycles_update_timers_and_ppu();
-        self.increment_4_cycles_update_timers_and_ppu();
+        self.increment_cycles(8);
         let register = Self::get_source_register(cb_opcode);
         let value = self.registers.get_8bit_register_value(register);
 
@@ -112,15 +111,14 @@ impl CpuRotateShiftInstructions for crate::gameboy_core::cpu::Cpu {
     /// Rotates the contents of memory specified by register pair HL to the left.
     /// The previous contents of the carry (CY) flag are copied to bit 0 of register B
     fn rl_hl(&mut self) {
-        self.increment_4_cycles_update_timers_and_ppu();
-        self.increment_4_cycles_update_timers_and_ppu();
+        self.increment_cycles(8);
         let hl = self.registers.get_hl();
         let value = self.memory_bus.read_byte(hl);
-        self.increment_4_cycles_update_timers_and_ppu();
+        self.increment_cycles(4);
 
         let rotated_value = self.rotate_left_and_update_flags(value, true, true);
         self.memory_bus.write_byte(hl, rotated_value);
-        self.increment_4_cycles_update_timers_and_ppu();
+        self.increment_cycles(4);
     }
 
     /// Rotates the contents of a 8-bit register to the right.
@@ -129,8 +127,7 @@ impl CpuRotateShiftInstructions for crate::gameboy_core::cpu::Cpu {
     /// The same operation is repeated in sequence for the rest of the register.
     /// The contents of bit 0 are placed in both the C flag and bit 7 of the register.
     fn rrc_r8(&mut self, cb_opcode: u8) {
-        self.increment_4_cycles_update_timers_and_ppu();
-        self.increment_4_cycles_update_timers_and_ppu();
+        self.increment_cycles(8);
         let register = Self::get_source_register(cb_opcode);
         let value = self.registers.get_8bit_register_value(register);
 
@@ -142,15 +139,14 @@ impl CpuRotateShiftInstructions for crate::gameboy_core::cpu::Cpu {
     /// Rotates the contents of memory specified by register pair HL to the right.
     /// The contents of bit 0 are placed in both the C flag and bit 7 of the register.
     fn rrc_hl(&mut self) {
-        self.increment_4_cycles_update_timers_and_ppu();
-        self.increment_4_cycles_update_timers_and_ppu();
+        self.increment_cycles(8);
         let hl = self.registers.get_hl();
         let value = self.memory_bus.read_byte(hl);
-        self.increment_4_cycles_update_timers_and_ppu();
+        self.increment_cycles(4);
 
         let rotated_value = self.rotate_right_and_update_flags(value, false, true);
         self.memory_bus.write_byte(hl, rotated_value);
-        self.increment_4_cycles_update_timers_and_ppu();
+        self.increment_cycles(4);
     }
 
     /// Rotates the contents of a 8-bit register to the right.
@@ -159,8 +155,7 @@ impl CpuRotateShiftInstructions for crate::gameboy_core::cpu::Cpu {
     /// The same operation is repeated in sequence for the rest of the register.
     /// The previous contents of the carry (CY) flag are copied to bit 7 of the register.
     fn rr_r8(&mut self, cb_opcode: u8) {
-        self.increment_4_cycles_update_timers_and_ppu();
-        self.increment_4_cycles_update_timers_and_ppu();
+        self.increment_cycles(8);
         let register = Self::get_source_register(cb_opcode);
         let value = self.registers.get_8bit_register_value(register);
 
@@ -172,15 +167,14 @@ impl CpuRotateShiftInstructions for crate::gameboy_core::cpu::Cpu {
     /// Rotates the contents of memory specified by register pair HL to the right.
     /// The previous contents of the carry (CY) flag are copied to bit 7 of the register.
     fn rr_hl(&mut self) {
-        self.increment_4_cycles_update_timers_and_ppu();
-        self.increment_4_cycles_update_timers_and_ppu();
+        self.increment_cycles(8);
         let hl = self.registers.get_hl();
         let value = self.memory_bus.read_byte(hl);
-        self.increment_4_cycles_update_timers_and_ppu();
+        self.increment_cycles(4);
 
         let rotated_value = self.rotate_right_and_update_flags(value, true, true);
         self.memory_bus.write_byte(hl, rotated_value);
-        self.increment_4_cycles_update_timers_and_ppu();
+        self.increment_cycles(4);
     }
     
     /// Shifts the contents of a 8-bit register to the left. That is, the contents of bit 0 are copied to bit 1 and the 
@@ -188,8 +182,7 @@ impl CpuRotateShiftInstructions for crate::gameboy_core::cpu::Cpu {
     /// The same operation is repeated in sequence for the rest of the operand. 
     /// The content of bit 7 is copied to CY, and bit 0 is reset.
     fn sla_r8(&mut self, cb_opcode: u8) {
-        self.increment_4_cycles_update_timers_and_ppu();
-        self.increment_4_cycles_update_timers_and_ppu();
+        self.increment_cycles(8);
         let register = Self::get_source_register(cb_opcode);
         let mut value = self.registers.get_8bit_register_value(register);
 
@@ -207,11 +200,10 @@ impl CpuRotateShiftInstructions for crate::gameboy_core::cpu::Cpu {
     /// Shifts the contents of memory specified by register pair HL to the left.
     /// The content of bit 7 is copied to CY, and bit 0 is reset.
     fn sla_hl(&mut self) {
-        self.increment_4_cycles_update_timers_and_ppu();
-        self.increment_4_cycles_update_timers_and_ppu();
+        self.increment_cycles(8);
         let hl = self.registers.get_hl();
         let mut value = self.memory_bus.read_byte(hl);
-        self.increment_4_cycles_update_timers_and_ppu();
+        self.increment_cycles(4);
 
         let bit7 = value >> 7;
         value <<= 1;
@@ -222,7 +214,7 @@ impl CpuRotateShiftInstructions for crate::gameboy_core::cpu::Cpu {
         self.registers.flags.set_h_flag(false);
         
         self.memory_bus.write_byte(hl, value);
-        self.increment_4_cycles_update_timers_and_ppu();
+        self.increment_cycles(4);
     }
 
     /// Shifts the contents of 8-bit register to the right. That is, the contents of bit 7 are copied to bit 6 and the
@@ -230,8 +222,7 @@ impl CpuRotateShiftInstructions for crate::gameboy_core::cpu::Cpu {
     /// The same operation is repeated in sequence for the rest of the operand. 
     /// The contents of bit 0 are copied to CY, and the content of bit 7 is unchanged.
     fn sra_r8(&mut self, cb_opcode: u8) {
-        self.increment_4_cycles_update_timers_and_ppu();
-        self.increment_4_cycles_update_timers_and_ppu();
+        self.increment_cycles(8);
         let register = Self::get_source_register(cb_opcode);
         let mut value = self.registers.get_8bit_register_value(register);
 
@@ -252,11 +243,10 @@ impl CpuRotateShiftInstructions for crate::gameboy_core::cpu::Cpu {
     /// Shifts the contents of memory specified by register pair HL to the right.
     /// The contents of bit 0 are copied to CY, and the content of bit 7 is unchanged.
     fn sra_hl(&mut self) {
-        self.increment_4_cycles_update_timers_and_ppu();
-        self.increment_4_cycles_update_timers_and_ppu();
+        self.increment_cycles(8);
         let hl = self.registers.get_hl();
         let mut value = self.memory_bus.read_byte(hl);
-        self.increment_4_cycles_update_timers_and_ppu();
+        self.increment_cycles(4);
 
         let bit7 = value & 0b10000000;
         let bit0 = value & 0b00000001;
@@ -269,7 +259,7 @@ impl CpuRotateShiftInstructions for crate::gameboy_core::cpu::Cpu {
         self.registers.flags.set_h_flag(false);
         
         self.memory_bus.write_byte(hl, value);
-        self.increment_4_cycles_update_timers_and_ppu();
+        self.increment_cycles(4);
     }
 
     /// Shifts the contents of operand m to the right. That is, the contents of bit 7 are copied to bit 6 and the 
@@ -277,8 +267,7 @@ impl CpuRotateShiftInstructions for crate::gameboy_core::cpu::Cpu {
     /// The same operation is repeated in sequence for the rest of the operand. 
     /// The contents of bit 0 are copied to CY, and bit 7 is reset. 
     fn srl_r8(&mut self, cb_opcode: u8) {
-        self.increment_4_cycles_update_timers_and_ppu();
-        self.increment_4_cycles_update_timers_and_ppu();
+        self.increment_cycles(8);
         let register = Self::get_source_register(cb_opcode);
         let mut value = self.registers.get_8bit_register_value(register);
 
@@ -297,11 +286,10 @@ impl CpuRotateShiftInstructions for crate::gameboy_core::cpu::Cpu {
     /// Shifts the contents of memory specified by register pair HL to the right.
     /// The contents of bit 0 are copied to CY, and bit 7 is reset
     fn srl_hl(&mut self) {
-        self.increment_4_cycles_update_timers_and_ppu();
-        self.increment_4_cycles_update_timers_and_ppu();
+        self.increment_cycles(8);
         let hl = self.registers.get_hl();
         let mut value = self.memory_bus.read_byte(hl);
-        self.increment_4_cycles_update_timers_and_ppu();
+        self.increment_cycles(4);
 
         let bit0 = value & 0b00000001;
         value >>= 1;
@@ -312,13 +300,12 @@ impl CpuRotateShiftInstructions for crate::gameboy_core::cpu::Cpu {
         self.registers.flags.set_h_flag(false);
         
         self.memory_bus.write_byte(hl, value);
-        self.increment_4_cycles_update_timers_and_ppu();
+        self.increment_cycles(4);
     }
     
     /// Shifts the contents of the lower-order and higher-order 4 bits of a 8-bit register.
     fn swap_r8(&mut self, cb_opcode: u8) {
-        self.increment_4_cycles_update_timers_and_ppu();
-        self.increment_4_cycles_update_timers_and_ppu();
+        self.increment_cycles(8);
         let register = Self::get_source_register(cb_opcode);
         let value = self.registers.get_8bit_register_value(register);
         
@@ -335,18 +322,17 @@ impl CpuRotateShiftInstructions for crate::gameboy_core::cpu::Cpu {
     
     /// Shifts the contents of the lower-order and higher-order 4 bits of a 8-bit register.
     fn swap_hl(&mut self) {
-        self.increment_4_cycles_update_timers_and_ppu();
-        self.increment_4_cycles_update_timers_and_ppu();
+        self.increment_cycles(8);
         let hl = self.registers.get_hl();
         let value = self.memory_bus.read_byte(hl);
-        self.increment_4_cycles_update_timers_and_ppu();
+        self.increment_cycles(4);
         
         let high_order_4_bits = value & 0b11110000;
         let low_order_4_bits = value & 0b00001111;
         
         let swapped_value = high_order_4_bits >> 4 | low_order_4_bits << 4;
         self.memory_bus.write_byte(hl, swapped_value);
-        self.increment_4_cycles_update_timers_and_ppu();
+        self.increment_cycles(4);
         self.registers.flags.set_z_flag_from_u8(swapped_value);
         self.registers.flags.n = false;
         self.registers.flags.set_h_flag(false);
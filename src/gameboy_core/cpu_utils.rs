@@ -1,15 +1,23 @@
+use alloc::{format, string::String, vec::Vec};
+#[cfg(feature = "std")]
 use std::{
     fs::File,
     io::{self, Read, Write},
 };
 
-use crate::gameboy_core::cpu::Cpu;
+#[cfg(feature = "std")]
+use crate::gameboy_core::emulator_error::EmulatorError;
+use crate::gameboy_core::{
+    cpu::Cpu,
+    cpu_components::{CpuRegisters, MemoryAccess},
+};
 
 /// Reads a ROM file from the specified path and returns its contents as a vector of bytes.
 ///
 /// # Errors
-/// Returns an error if the file cannot be read.
-pub fn read_rom(file_path: &str) -> io::Result<Vec<u8>> {
+/// Returns `EmulatorError::IoError` if the file cannot be read.
+#[cfg(feature = "std")]
+pub fn read_rom(file_path: &str) -> Result<Vec<u8>, EmulatorError> {
     // Open the file
     let file = File::open(file_path)?;
     let mut reader = io::BufReader::new(file);
@@ -22,12 +30,52 @@ pub fn read_rom(file_path: &str) -> io::Result<Vec<u8>> {
     Ok(buffer)
 }
 
-pub(crate) fn log(cpu: &mut Cpu, opcode: u8) -> io::Result<()> {
+/// Number of bytes in each row of a hex dump.
+const HEX_DUMP_BYTES_PER_ROW: usize = 16;
+
+/// Formats memory as a classic hex dump, 16 bytes per row with the printable ASCII characters on the right:
+/// `FF00: 00 13 02 00 48 69 00 00 00 00 00 00 00 00 00 00  ....Hi..........`
+pub fn format_hex_dump(data: &[u8], start_addr: u16) -> String {
+    let mut dump = String::new();
+
+    for (row_index, row) in data.chunks(HEX_DUMP_BYTES_PER_ROW).enumerate() {
+        let address = start_addr.wrapping_add((row_index * HEX_DUMP_BYTES_PER_ROW) as u16);
+        let hex: Vec<String> = row.iter().map(|byte| format!("{:02X}", byte)).collect();
+        let ascii: String = row
+            .iter()
+            .map(|&byte| if byte.is_ascii_graphic() || byte == b' ' { byte as char } else { '.' })
+            .collect();
+
+        // The hex column is padded so the ASCII column stays aligned on a last incomplete row
+        dump.push_str(&format!(
+            "{:04X}: {:<width$}  {}\n",
+            address,
+            hex.join(" "),
+            ascii,
+            width = HEX_DUMP_BYTES_PER_ROW * 3 - 1
+        ));
+    }
+
+    dump
+}
+
+/// Writes the CPU state to the instructions log and the Dr. Gameboy log. The log files are only written with the
+/// `debug-logging` feature, without it this compiles to nothing.
+///
+/// # Panics
+/// Panics if a log file cannot be written.
+#[cfg(feature = "debug-logging")]
+pub(crate) fn log<M: MemoryAccess>(cpu: &mut Cpu<M>, opcode: u8) {
     log_state(cpu, opcode).unwrap();
-    log_to_dr_gameboy(cpu)
+    log_to_dr_gameboy(cpu).unwrap();
 }
 
-pub(crate) fn log_state(cpu: &Cpu, opcode: u8) -> io::Result<()> {
+#[cfg(not(feature = "debug-logging"))]
+#[inline(always)]
+pub(crate) fn log<M: MemoryAccess>(_cpu: &mut Cpu<M>, _opcode: u8) {}
+
+#[cfg(feature = "debug-logging")]
+pub(crate) fn log_state<M: MemoryAccess>(cpu: &Cpu<M>, opcode: u8) -> io::Result<()> {
     if cpu.is_debug_mode {
         let file_path = "instructions_log.txt";
         let registers_state = get_registers_state_for_log(cpu, true);
@@ -47,7 +95,8 @@ pub(crate) fn log_state(cpu: &Cpu, opcode: u8) -> io::Result<()> {
 }
 
 /// Prints the CPU registers and flags register to the console
-pub fn print_state(cpu: &Cpu) {
+#[cfg(feature = "std")]
+pub fn print_state<M: MemoryAccess>(cpu: &Cpu<M>) {
     println!("\n========= Current CPU State before execute function ============");
     println!("8-bit Registers:");
     println!("  A:  0x{:02X} ({})", cpu.registers.a, cpu.registers.a);
@@ -85,9 +134,24 @@ pub fn print_state(cpu: &Cpu) {
     println!("================================================================\n");
 }
 
+/// Prints the opcode and the CPU state when the CPU is in debug mode. Only with the `debug-logging` feature, without
+/// it this compiles to nothing.
+#[cfg(feature = "debug-logging")]
+pub fn print_state_if_debug_mode<M: MemoryAccess>(cpu: &Cpu<M>, opcode: u8) {
+    if cpu.is_debug_mode {
+        println!("Opcode: 0x{:02X}", opcode);
+        print_state(cpu);
+    }
+}
+
+#[cfg(not(feature = "debug-logging"))]
+#[inline(always)]
+pub fn print_state_if_debug_mode<M: MemoryAccess>(_cpu: &Cpu<M>, _opcode: u8) {}
+
 /// Appends a line to a Dr. Gameboy log file with CPU state in the format:
 /// A:00 F:11 B:22 C:33 D:44 E:55 H:66 L:77 SP:8888 PC:9999 PCMEM:AA,BB,CC,DD
-pub fn log_to_dr_gameboy(cpu: &Cpu) -> io::Result<()> {
+#[cfg(feature = "std")]
+pub fn log_to_dr_gameboy<M: MemoryAccess>(cpu: &Cpu<M>) -> io::Result<()> {
     let file_path = "dr_gameboy_log.txt";
 
     let log_line = get_registers_state_for_log(cpu, false);
@@ -100,7 +164,37 @@ pub fn log_to_dr_gameboy(cpu: &Cpu) -> io::Result<()> {
     Ok(())
 }
 
-pub fn get_registers_state_for_log(cpu: &Cpu, detailed_display_flags: bool) -> String {
+/// Formats the registers as a JSON object for external tools, with the values as lowercase hex strings:
+/// `{"a":"01","b":"00",...,"bc":"0013",...,"pc":"0100","flags":{"z":true,"n":false,"h":true,"c":true}}`
+/// The object is always flat numbers and booleans, so it is written by hand instead of depending on serde.
+pub fn format_registers_as_json(registers: &CpuRegisters) -> String {
+    format!(
+        concat!(
+            r#"{{"a":"{:02x}","b":"{:02x}","c":"{:02x}","d":"{:02x}","e":"{:02x}","f":"{:02x}","h":"{:02x}","l":"{:02x}","#,
+            r#""bc":"{:04x}","de":"{:04x}","hl":"{:04x}","sp":"{:04x}","pc":"{:04x}","#,
+            r#""flags":{{"z":{},"n":{},"h":{},"c":{}}}}}"#
+        ),
+        registers.a,
+        registers.b,
+        registers.c,
+        registers.d,
+        registers.e,
+        registers.flags.get_flags_as_u8(),
+        registers.h,
+        registers.l,
+        registers.get_bc(),
+        registers.get_de(),
+        registers.get_hl(),
+        registers.sp,
+        registers.pc,
+        registers.flags.z,
+        registers.flags.n,
+        registers.flags.h,
+        registers.flags.c,
+    )
+}
+
+pub fn get_registers_state_for_log<M: MemoryAccess>(cpu: &Cpu<M>, detailed_display_flags: bool) -> String {
     // Get the flags register as a u8 value
     let flags_value = cpu.registers.flags.get_flags_as_u8();
     let flags_string = if detailed_display_flags {
@@ -142,16 +236,88 @@ pub fn get_registers_state_for_log(cpu: &Cpu, detailed_display_flags: bool) -> S
     log_line
 }
 
-pub(crate) fn clear_logs() -> io::Result<()> {
+/// Compares the CPU state with a line of a trace log, returns true when every register of the line matches.
+/// Both the Dr. Gameboy format written by `log_to_dr_gameboy` and the BGB format are supported:
+/// `A:01 F:B0 B:00 C:13 D:00 E:D8 H:01 L:4D SP:FFFE PC:0100 PCMEM:00,C3,13,02`
+/// `AF:01B0 BC:0013 DE:00D8 HL:014D SP:FFFE PC:0100`
+/// Fields of other kinds (e.g. LY or the cycle count) are ignored. Returns false when a register value is not valid
+/// hexadecimal or when the line has no register at all.
+pub fn compare_with_trace<M: MemoryAccess>(cpu: &Cpu<M>, trace_line: &str) -> bool {
+    let mut compared_fields = 0;
+
+    for field in trace_line.split_whitespace() {
+        let Some((name, value)) = field.split_once(':') else {
+            continue;
+        };
+
+        let is_match = match name.to_ascii_uppercase().as_str() {
+            "PCMEM" => value.split(',').enumerate().all(|(offset, byte)| {
+                u8::from_str_radix(byte, 16).is_ok_and(|byte| {
+                    cpu.memory_bus.read_byte(cpu.registers.pc.wrapping_add(offset as u16)) == byte
+                })
+            }),
+            name => match get_trace_register_value(cpu, name) {
+                Some(register_value) => u16::from_str_radix(value, 16).is_ok_and(|value| value == register_value),
+                None => continue,
+            },
+        };
+
+        if !is_match {
+            return false;
+        }
+        compared_fields += 1;
+    }
+
+    compared_fields > 0
+}
+
+/// Current value of a register named as in a trace log, None when the name is not a register.
+fn get_trace_register_value<M: MemoryAccess>(cpu: &Cpu<M>, name: &str) -> Option<u16> {
+    let registers = &cpu.registers;
+    let value = match name {
+        "A" => registers.a as u16,
+        "F" => registers.flags.get_flags_as_u8() as u16,
+        "B" => registers.b as u16,
+        "C" => registers.c as u16,
+        "D" => registers.d as u16,
+        "E" => registers.e as u16,
+        "H" => registers.h as u16,
+        "L" => registers.l as u16,
+        "AF" => registers.get_af(),
+        "BC" => registers.get_bc(),
+        "DE" => registers.get_de(),
+        "HL" => registers.get_hl(),
+        "SP" => registers.sp,
+        "PC" => registers.pc,
+        _ => return None,
+    };
+    Some(value)
+}
+
+/// Empties the instructions log, creating it if it doesn't exist. Does nothing without the `debug-logging` feature.
+#[cfg(feature = "debug-logging")]
+pub fn clear_logs() -> io::Result<()> {
     let file_path = "instructions_log.txt";
     let file = File::create(file_path)?;
     file.set_len(0)?;
     Ok(())
 }
 
-pub(crate) fn clear_dr_gameboy_log() -> io::Result<()> {
+#[cfg(all(feature = "std", not(feature = "debug-logging")))]
+pub fn clear_logs() -> io::Result<()> {
+    Ok(())
+}
+
+/// Empties the Dr. Gameboy log, creating it if it doesn't exist. Does nothing without the `debug-logging` feature.
+#[cfg(feature = "debug-logging")]
+pub fn clear_dr_gameboy_log() -> io::Result<()> {
     let file_path = "dr_gameboy_log.txt";
     let file = File::create(file_path)?;
     file.set_len(0)?;
     Ok(())
 }
+
+#[cfg(all(feature = "std", not(feature = "debug-logging")))]
+pub fn clear_dr_gameboy_log() -> io::Result<()> {
+    Ok(())
+}
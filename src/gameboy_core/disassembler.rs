@@ -0,0 +1,190 @@
+use alloc::vec::Vec;
+
+use crate::gameboy_core::{
+    constants::{CB_OPCODE_CYCLES, OPCODE_CYCLES},
+    cpu_components::MemoryAccess,
+};
+
+/// Opcode of the HALT instruction, the instruction iterator stops after it.
+const HALT_OPCODE: u8 = 0x76;
+/// Prefix of the 0xCB table of instructions (rotations, shifts and bit operations).
+const CB_PREFIX: u8 = 0xCB;
+
+/// Returns how many bytes follow the opcode of an instruction. CB prefixed instructions have the second opcode byte as
+/// their only operand. Table of opcodes: https://gbdev.io/pandocs/CPU_Instruction_Set.html
+pub fn get_operand_length(opcode: u8) -> usize {
+    match opcode {
+        // LD r16, imm16 | LD (imm16), SP | JP imm16 | JP cc, imm16 | CALL imm16 | CALL cc, imm16 | LD (imm16), A | LD A, (imm16)
+        0x01 | 0x11 | 0x21 | 0x31 | 0x08 | 0xC3 | 0xC2 | 0xCA | 0xD2 | 0xDA | 0xCD | 0xC4 | 0xCC | 0xD4 | 0xDC
+        | 0xEA | 0xFA => 2,
+        // LD r8, imm8 | LD (HL), imm8
+        v if v < 0x40 && (v & 0b11000111) == 0b00000110 => 1,
+        // ALU A, imm8
+        v if (v & 0b11000111) == 0b11000110 => 1,
+        // STOP (followed by a padding byte) | JR imm8 | JR cc, imm8 | LDH (imm8), A | LDH A, (imm8) | ADD SP, imm8 | LD HL, SP + imm8 | CB prefix
+        0x10 | 0x18 | 0x20 | 0x28 | 0x30 | 0x38 | 0xE0 | 0xF0 | 0xE8 | 0xF8 | CB_PREFIX => 1,
+        _ => 0,
+    }
+}
+
+/// Metadata of an instruction: its mnemonic, how many bytes it takes including the opcode and its T-cycles.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct OpcodeInfo {
+    pub mnemonic: &'static str,
+    pub length_bytes: u8,
+    /// T-cycles of the instruction, for conditional jumps, calls and returns the count when the branch is taken
+    pub cycles_taken: u8,
+    /// T-cycles of a conditional jump, call or return when the condition is false, None for other instructions
+    pub cycles_not_taken: Option<u8>,
+}
+
+/// Returns the metadata of an instruction. With `cb_prefix` the opcode is the byte after the 0xCB prefix and the
+/// length and cycles include the prefix. Without it, 0xCB itself is reported as the 2 bytes prefix taking 4 T-cycles.
+pub fn decode_opcode(opcode: u8, cb_prefix: bool) -> OpcodeInfo {
+    if cb_prefix {
+        return OpcodeInfo {
+            mnemonic: CB_OPCODE_MNEMONICS[opcode as usize],
+            length_bytes: 2,
+            cycles_taken: CB_OPCODE_CYCLES[opcode as usize],
+            cycles_not_taken: None,
+        };
+    }
+
+    OpcodeInfo {
+        mnemonic: OPCODE_MNEMONICS[opcode as usize],
+        length_bytes: 1 + get_operand_length(opcode) as u8,
+        cycles_taken: OPCODE_CYCLES[opcode as usize],
+        cycles_not_taken: get_cycles_not_taken(opcode),
+    }
+}
+
+/// T-cycles of the conditional instructions when the condition is false, they skip the jump, the push of the
+/// return address or the pop of the return address.
+fn get_cycles_not_taken(opcode: u8) -> Option<u8> {
+    match opcode {
+        // JR cc, imm8 | RET cc
+        0x20 | 0x28 | 0x30 | 0x38 | 0xC0 | 0xC8 | 0xD0 | 0xD8 => Some(8),
+        // JP cc, imm16 | CALL cc, imm16
+        0xC2 | 0xCA | 0xD2 | 0xDA | 0xC4 | 0xCC | 0xD4 | 0xDC => Some(12),
+        _ => None,
+    }
+}
+
+/// Mnemonic of each instruction, indexed by opcode. Operands are `imm8`/`imm16` for immediate values.
+#[rustfmt::skip]
+static OPCODE_MNEMONICS: [&str; 256] = [
+    "NOP", "LD BC, imm16", "LD (BC), A", "INC BC", "INC B", "DEC B", "LD B, imm8", "RLCA", // 00-07
+    "LD (imm16), SP", "ADD HL, BC", "LD A, (BC)", "DEC BC", "INC C", "DEC C", "LD C, imm8", "RRCA", // 08-0F
+    "STOP", "LD DE, imm16", "LD (DE), A", "INC DE", "INC D", "DEC D", "LD D, imm8", "RLA", // 10-17
+    "JR imm8", "ADD HL, DE", "LD A, (DE)", "DEC DE", "INC E", "DEC E", "LD E, imm8", "RRA", // 18-1F
+    "JR NZ, imm8", "LD HL, imm16", "LD (HL+), A", "INC HL", "INC H", "DEC H", "LD H, imm8", "DAA", // 20-27
+    "JR Z, imm8", "ADD HL, HL", "LD A, (HL+)", "DEC HL", "INC L", "DEC L", "LD L, imm8", "CPL", // 28-2F
+    "JR NC, imm8", "LD SP, imm16", "LD (HL-), A", "INC SP", "INC (HL)", "DEC (HL)", "LD (HL), imm8", "SCF", // 30-37
+    "JR C, imm8", "ADD HL, SP", "LD A, (HL-)", "DEC SP", "INC A", "DEC A", "LD A, imm8", "CCF", // 38-3F
+    "LD B, B", "LD B, C", "LD B, D", "LD B, E", "LD B, H", "LD B, L", "LD B, (HL)", "LD B, A", // 40-47
+    "LD C, B", "LD C, C", "LD C, D", "LD C, E", "LD C, H", "LD C, L", "LD C, (HL)", "LD C, A", // 48-4F
+    "LD D, B", "LD D, C", "LD D, D", "LD D, E", "LD D, H", "LD D, L", "LD D, (HL)", "LD D, A", // 50-57
+    "LD E, B", "LD E, C", "LD E, D", "LD E, E", "LD E, H", "LD E, L", "LD E, (HL)", "LD E, A", // 58-5F
+    "LD H, B", "LD H, C", "LD H, D", "LD H, E", "LD H, H", "LD H, L", "LD H, (HL)", "LD H, A", // 60-67
+    "LD L, B", "LD L, C", "LD L, D", "LD L, E", "LD L, H", "LD L, L", "LD L, (HL)", "LD L, A", // 68-6F
+    "LD (HL), B", "LD (HL), C", "LD (HL), D", "LD (HL), E", "LD (HL), H", "LD (HL), L", "HALT", "LD (HL), A", // 70-77
+    "LD A, B", "LD A, C", "LD A, D", "LD A, E", "LD A, H", "LD A, L", "LD A, (HL)", "LD A, A", // 78-7F
+    "ADD A, B", "ADD A, C", "ADD A, D", "ADD A, E", "ADD A, H", "ADD A, L", "ADD A, (HL)", "ADD A, A", // 80-87
+    "ADC A, B", "ADC A, C", "ADC A, D", "ADC A, E", "ADC A, H", "ADC A, L", "ADC A, (HL)", "ADC A, A", // 88-8F
+    "SUB B", "SUB C", "SUB D", "SUB E", "SUB H", "SUB L", "SUB (HL)", "SUB A", // 90-97
+    "SBC A, B", "SBC A, C", "SBC A, D", "SBC A, E", "SBC A, H", "SBC A, L", "SBC A, (HL)", "SBC A, A", // 98-9F
+    "AND B", "AND C", "AND D", "AND E", "AND H", "AND L", "AND (HL)", "AND A", // A0-A7
+    "XOR B", "XOR C", "XOR D", "XOR E", "XOR H", "XOR L", "XOR (HL)", "XOR A", // A8-AF
+    "OR B", "OR C", "OR D", "OR E", "OR H", "OR L", "OR (HL)", "OR A", // B0-B7
+    "CP B", "CP C", "CP D", "CP E", "CP H", "CP L", "CP (HL)", "CP A", // B8-BF
+    "RET NZ", "POP BC", "JP NZ, imm16", "JP imm16", "CALL NZ, imm16", "PUSH BC", "ADD A, imm8", "RST $00", // C0-C7
+    "RET Z", "RET", "JP Z, imm16", "PREFIX CB", "CALL Z, imm16", "CALL imm16", "ADC A, imm8", "RST $08", // C8-CF
+    "RET NC", "POP DE", "JP NC, imm16", "ILLEGAL", "CALL NC, imm16", "PUSH DE", "SUB imm8", "RST $10", // D0-D7
+    "RET C", "RETI", "JP C, imm16", "ILLEGAL", "CALL C, imm16", "ILLEGAL", "SBC A, imm8", "RST $18", // D8-DF
+    "LDH (imm8), A", "POP HL", "LDH (C), A", "ILLEGAL", "ILLEGAL", "PUSH HL", "AND imm8", "RST $20", // E0-E7
+    "ADD SP, imm8", "JP HL", "LD (imm16), A", "ILLEGAL", "ILLEGAL", "ILLEGAL", "XOR imm8", "RST $28", // E8-EF
+    "LDH A, (imm8)", "POP AF", "LDH A, (C)", "DI", "ILLEGAL", "PUSH AF", "OR imm8", "RST $30", // F0-F7
+    "LD HL, SP + imm8", "LD SP, HL", "LD A, (imm16)", "EI", "ILLEGAL", "ILLEGAL", "CP imm8", "RST $38", // F8-FF
+];
+
+/// Mnemonic of each CB prefixed instruction, indexed by the second opcode byte.
+#[rustfmt::skip]
+static CB_OPCODE_MNEMONICS: [&str; 256] = [
+    "RLC B", "RLC C", "RLC D", "RLC E", "RLC H", "RLC L", "RLC (HL)", "RLC A", // 00-07
+    "RRC B", "RRC C", "RRC D", "RRC E", "RRC H", "RRC L", "RRC (HL)", "RRC A", // 08-0F
+    "RL B", "RL C", "RL D", "RL E", "RL H", "RL L", "RL (HL)", "RL A", // 10-17
+    "RR B", "RR C", "RR D", "RR E", "RR H", "RR L", "RR (HL)", "RR A", // 18-1F
+    "SLA B", "SLA C", "SLA D", "SLA E", "SLA H", "SLA L", "SLA (HL)", "SLA A", // 20-27
+    "SRA B", "SRA C", "SRA D", "SRA E", "SRA H", "SRA L", "SRA (HL)", "SRA A", // 28-2F
+    "SWAP B", "SWAP C", "SWAP D", "SWAP E", "SWAP H", "SWAP L", "SWAP (HL)", "SWAP A", // 30-37
+    "SRL B", "SRL C", "SRL D", "SRL E", "SRL H", "SRL L", "SRL (HL)", "SRL A", // 38-3F
+    "BIT 0, B", "BIT 0, C", "BIT 0, D", "BIT 0, E", "BIT 0, H", "BIT 0, L", "BIT 0, (HL)", "BIT 0, A", // 40-47
+    "BIT 1, B", "BIT 1, C", "BIT 1, D", "BIT 1, E", "BIT 1, H", "BIT 1, L", "BIT 1, (HL)", "BIT 1, A", // 48-4F
+    "BIT 2, B", "BIT 2, C", "BIT 2, D", "BIT 2, E", "BIT 2, H", "BIT 2, L", "BIT 2, (HL)", "BIT 2, A", // 50-57
+    "BIT 3, B", "BIT 3, C", "BIT 3, D", "BIT 3, E", "BIT 3, H", "BIT 3, L", "BIT 3, (HL)", "BIT 3, A", // 58-5F
+    "BIT 4, B", "BIT 4, C", "BIT 4, D", "BIT 4, E", "BIT 4, H", "BIT 4, L", "BIT 4, (HL)", "BIT 4, A", // 60-67
+    "BIT 5, B", "BIT 5, C", "BIT 5, D", "BIT 5, E", "BIT 5, H", "BIT 5, L", "BIT 5, (HL)", "BIT 5, A", // 68-6F
+    "BIT 6, B", "BIT 6, C", "BIT 6, D", "BIT 6, E", "BIT 6, H", "BIT 6, L", "BIT 6, (HL)", "BIT 6, A", // 70-77
+    "BIT 7, B", "BIT 7, C", "BIT 7, D", "BIT 7, E", "BIT 7, H", "BIT 7, L", "BIT 7, (HL)", "BIT 7, A", // 78-7F
+    "RES 0, B", "RES 0, C", "RES 0, D", "RES 0, E", "RES 0, H", "RES 0, L", "RES 0, (HL)", "RES 0, A", // 80-87
+    "RES 1, B", "RES 1, C", "RES 1, D", "RES 1, E", "RES 1, H", "RES 1, L", "RES 1, (HL)", "RES 1, A", // 88-8F
+    "RES 2, B", "RES 2, C", "RES 2, D", "RES 2, E", "RES 2, H", "RES 2, L", "RES 2, (HL)", "RES 2, A", // 90-97
+    "RES 3, B", "RES 3, C", "RES 3, D", "RES 3, E", "RES 3, H", "RES 3, L", "RES 3, (HL)", "RES 3, A", // 98-9F
+    "RES 4, B", "RES 4, C", "RES 4, D", "RES 4, E", "RES 4, H", "RES 4, L", "RES 4, (HL)", "RES 4, A", // A0-A7
+    "RES 5, B", "RES 5, C", "RES 5, D", "RES 5, E", "RES 5, H", "RES 5, L", "RES 5, (HL)", "RES 5, A", // A8-AF
+    "RES 6, B", "RES 6, C", "RES 6, D", "RES 6, E", "RES 6, H", "RES 6, L", "RES 6, (HL)", "RES 6, A", // B0-B7
+    "RES 7, B", "RES 7, C", "RES 7, D", "RES 7, E", "RES 7, H", "RES 7, L", "RES 7, (HL)", "RES 7, A", // B8-BF
+    "SET 0, B", "SET 0, C", "SET 0, D", "SET 0, E", "SET 0, H", "SET 0, L", "SET 0, (HL)", "SET 0, A", // C0-C7
+    "SET 1, B", "SET 1, C", "SET 1, D", "SET 1, E", "SET 1, H", "SET 1, L", "SET 1, (HL)", "SET 1, A", // C8-CF
+    "SET 2, B", "SET 2, C", "SET 2, D", "SET 2, E", "SET 2, H", "SET 2, L", "SET 2, (HL)", "SET 2, A", // D0-D7
+    "SET 3, B", "SET 3, C", "SET 3, D", "SET 3, E", "SET 3, H", "SET 3, L", "SET 3, (HL)", "SET 3, A", // D8-DF
+    "SET 4, B", "SET 4, C", "SET 4, D", "SET 4, E", "SET 4, H", "SET 4, L", "SET 4, (HL)", "SET 4, A", // E0-E7
+    "SET 5, B", "SET 5, C", "SET 5, D", "SET 5, E", "SET 5, H", "SET 5, L", "SET 5, (HL)", "SET 5, A", // E8-EF
+    "SET 6, B", "SET 6, C", "SET 6, D", "SET 6, E", "SET 6, H", "SET 6, L", "SET 6, (HL)", "SET 6, A", // F0-F7
+    "SET 7, B", "SET 7, C", "SET 7, D", "SET 7, E", "SET 7, H", "SET 7, L", "SET 7, (HL)", "SET 7, A", // F8-FF
+];
+
+/// Walks the memory sequentially decoding one instruction at a time, without executing them.
+/// Yields (address, opcode, operand bytes) and stops after a HALT or when the address would wrap around 0xFFFF.
+pub struct InstructionIterator<'a, M: MemoryAccess> {
+    memory_bus: &'a M,
+    /// Address of the next instruction, None when the iteration is over
+    address: Option<u16>,
+}
+
+impl<'a, M: MemoryAccess> InstructionIterator<'a, M> {
+    pub fn new(memory_bus: &'a M, start: u16) -> Self {
+        Self {
+            memory_bus,
+            address: Some(start),
+        }
+    }
+}
+
+impl<M: MemoryAccess> Iterator for InstructionIterator<'_, M> {
+    type Item = (u16, u8, Vec<u8>);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let address = self.address?;
+        let opcode = self.memory_bus.read_byte(address);
+        let operand_length = get_operand_length(opcode) as u16;
+
+        // The operands of an instruction at the end of the memory would wrap around to 0x0000
+        let Some(last_address) = address.checked_add(operand_length) else {
+            self.address = None;
+            return None;
+        };
+
+        let operands = (1..=operand_length)
+            .map(|offset| self.memory_bus.read_byte(address + offset))
+            .collect();
+
+        self.address = if opcode == HALT_OPCODE {
+            None
+        } else {
+            last_address.checked_add(1)
+        };
+
+        Some((address, opcode, operands))
+    }
+}
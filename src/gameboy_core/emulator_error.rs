@@ -0,0 +1,62 @@
+use alloc::string::String;
+use core::fmt;
+#[cfg(feature = "std")]
+use std::io;
+
+/// Errors that can happen while loading and running a game.
+#[derive(Debug)]
+pub enum EmulatorError {
+    #[cfg(feature = "std")]
+    IoError(io::Error),
+    /// The ROM can't be run, the String describes why
+    InvalidRom(String),
+    /// The cartridge type (byte 0x0147 of the header) uses a Memory Bank Controller that is not implemented
+    UnsupportedMbc(u8),
+    /// A save file could not be loaded, the String describes why
+    #[allow(dead_code)] // Not produced yet, save files are not implemented
+    InvalidSaveState(String),
+    /// The emulator window could not be created, the String is the error reported by the window library
+    WindowError(String),
+}
+
+impl fmt::Display for EmulatorError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            #[cfg(feature = "std")]
+            EmulatorError::IoError(e) => write!(f, "I/O error: {}", e),
+            EmulatorError::InvalidRom(reason) => write!(f, "Invalid ROM: {}", reason),
+            EmulatorError::UnsupportedMbc(cartridge_type) => write!(
+                f,
+                "Unsupported cartridge type {:#04X}: its memory bank controller is not implemented",
+                cartridge_type
+            ),
+            EmulatorError::InvalidSaveState(reason) => write!(f, "Invalid save state: {}", reason),
+            EmulatorError::WindowError(reason) => write!(f, "Could not create the window: {}", reason),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for EmulatorError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            EmulatorError::IoError(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl From<io::Error> for EmulatorError {
+    fn from(e: io::Error) -> Self {
+        EmulatorError::IoError(e)
+    }
+}
+
+// minifb is not a dependency of the wasm build nor of the build without std
+#[cfg(all(feature = "std", not(target_arch = "wasm32")))]
+impl From<minifb::Error> for EmulatorError {
+    fn from(e: minifb::Error) -> Self {
+        EmulatorError::WindowError(e.to_string())
+    }
+}
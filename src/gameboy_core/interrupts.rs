@@ -3,7 +3,7 @@ use crate::gameboy_core::{
         JOYPAD_INTERRUPT_HANDLER_ADDRESS, LCD_STAT_INTERRUPT_HANDLER_ADDRESS,
         SERIAL_INTERRUPT_HANDLER_ADDRESS, TIMER_INTERRUPT_HANDLER_ADDRESS,
         VBLANK_INTERRUT_HANDLER_ADDRESS,
-    }, cpu::Cpu, cpu_instructions::cpu_miscellaneous_instructions::CpuMiscellaneousInstructions, registers_contants::{IE, IF}
+    }, cpu::Cpu, cpu_components::MemoryAccess, cpu_instructions::cpu_miscellaneous_instructions::CpuMiscellaneousInstructions, registers_contants::IF
 };
 
 pub enum InterruptType {
@@ -14,6 +14,7 @@ pub enum InterruptType {
     Joypad,
 }
 
+#[derive(Clone)]
 pub struct InterruptsHandler;
 
 /// Used to represent both IE and IF registers, since they have the same bit layout.
@@ -28,7 +29,7 @@ pub struct InterruptStatus {
 impl InterruptsHandler {
     /// Checks if any interrupts are requested by checking the IME, IF and IE registers.
     /// If an interrupt is requested, it handles it by calling the appropriate interrupt handler.
-    pub fn handle(cpu: &mut Cpu) -> bool {
+    pub fn handle<M: MemoryAccess>(cpu: &mut Cpu<M>) -> bool {
         if !cpu.ime {
             if cpu.is_halt_mode {
                 Self::check_pending_interrupts_to_exit_halt_mode(cpu);
@@ -37,40 +38,39 @@ impl InterruptsHandler {
             return false;
         }
 
+        // Only interrupts that are both requested (IF) and enabled (IE) can be dispatched
         let if_register = cpu.memory_bus.read_byte(IF);
-        let ie_register = cpu.memory_bus.read_byte(IE);
-
-        let if_register_flags = Self::get_register_flag_values(if_register);
-        let ie_register_flags = Self::get_register_flag_values(ie_register);
+        let ie_register = cpu.memory_bus.get_ie_register();
+        let pending_interrupts = Self::get_register_flag_values(if_register & ie_register);
 
         // The order of the if statements is important, as it defines the priority of the interrupts.
         // Priority order: VBlank > LCD > Timer > Serial > Joypad
 
-        if if_register_flags.vblank && ie_register_flags.vblank {
+        if pending_interrupts.vblank {
             Self::do_before_handling_interrupt(cpu, InterruptType::VBlank);
             Self::do_handle_interrupt(cpu, InterruptType::VBlank);
             return true;
         }
 
-        if if_register_flags.lcd && ie_register_flags.lcd {
+        if pending_interrupts.lcd {
             Self::do_before_handling_interrupt(cpu, InterruptType::LCD);
             Self::do_handle_interrupt(cpu, InterruptType::LCD);
             return true;
         }
 
-        if if_register_flags.timer && ie_register_flags.timer {
+        if pending_interrupts.timer {
             Self::do_before_handling_interrupt(cpu, InterruptType::Timer);
             Self::do_handle_interrupt(cpu, InterruptType::Timer);
             return true;
         }
 
-        if if_register_flags.serial && ie_register_flags.serial {
+        if pending_interrupts.serial {
             Self::do_before_handling_interrupt(cpu, InterruptType::Serial);
             Self::do_handle_interrupt(cpu, InterruptType::Serial);
             return true;
         }
 
-        if if_register_flags.joypad && ie_register_flags.joypad {
+        if pending_interrupts.joypad {
             Self::do_before_handling_interrupt(cpu, InterruptType::Joypad);
             Self::do_handle_interrupt(cpu, InterruptType::Joypad);
             return true;
@@ -89,7 +89,7 @@ impl InterruptsHandler {
     /// 1. The IME flag is reset to disable further interrupts.
     /// 2. The corresponding bit in the IF register is reset.
     /// 3. The program counter (PC) is pushed onto the stack.
-    fn do_before_handling_interrupt(cpu: &mut Cpu, interrupt_type: InterruptType) {
+    fn do_before_handling_interrupt<M: MemoryAccess>(cpu: &mut Cpu<M>, interrupt_type: InterruptType) {
         cpu.ime = false; // Disable further interrupts
 
         // Reset the corresponding bit in the IF register
@@ -108,7 +108,7 @@ impl InterruptsHandler {
     }
 
     /// Sets the PC to the interrupt handler address based on the interrupt type and increments clock cycles.
-    fn do_handle_interrupt(cpu: &mut Cpu, interrupt_type: InterruptType) {
+    fn do_handle_interrupt<M: MemoryAccess>(cpu: &mut Cpu<M>, interrupt_type: InterruptType) {
         cpu.registers.pc = match interrupt_type {
             InterruptType::VBlank => VBLANK_INTERRUT_HANDLER_ADDRESS,
             InterruptType::LCD => LCD_STAT_INTERRUPT_HANDLER_ADDRESS,
@@ -117,11 +117,7 @@ impl InterruptsHandler {
             InterruptType::Joypad => JOYPAD_INTERRUPT_HANDLER_ADDRESS,
         };
 
-        cpu.increment_4_cycles_update_timers_and_ppu();
-        cpu.increment_4_cycles_update_timers_and_ppu();
-        cpu.increment_4_cycles_update_timers_and_ppu();
-        cpu.increment_4_cycles_update_timers_and_ppu();
-        cpu.increment_4_cycles_update_timers_and_ppu();
+        cpu.increment_cycles(20);
         cpu.is_halt_mode = false;
     }
 
@@ -143,7 +139,7 @@ impl InterruptsHandler {
     }
     
     /// If any interrupts are pending (IE and IF have matching bits set), exit HALT mode even if IME is disabled.
-    fn check_pending_interrupts_to_exit_halt_mode(cpu: &mut Cpu) {
+    fn check_pending_interrupts_to_exit_halt_mode<M: MemoryAccess>(cpu: &mut Cpu<M>) {
         if cpu.is_interrupt_pending() {
             cpu.is_halt_mode = false;
         }
@@ -0,0 +1,93 @@
+/// Buttons of the Game Boy. The P1 register reads them in two groups of 4, the D-pad (Right, Left, Up, Down)
+/// and the action buttons (A, B, Select, Start), which is the order used for the bits of JoypadState.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum JoypadButton {
+    Right = 0,
+    Left = 1,
+    Up = 2,
+    Down = 3,
+    A = 4,
+    B = 5,
+    Select = 6,
+    Start = 7,
+}
+
+impl JoypadButton {
+    /// Parses the button names used in the config file, e.g. "Start".
+    pub fn from_name(name: &str) -> Option<JoypadButton> {
+        match name {
+            "Right" => Some(JoypadButton::Right),
+            "Left" => Some(JoypadButton::Left),
+            "Up" => Some(JoypadButton::Up),
+            "Down" => Some(JoypadButton::Down),
+            "A" => Some(JoypadButton::A),
+            "B" => Some(JoypadButton::B),
+            "Select" => Some(JoypadButton::Select),
+            "Start" => Some(JoypadButton::Start),
+            _ => None,
+        }
+    }
+
+    /// Returns the button with the given value (0 = Right ... 7 = Start), None for values above 7.
+    pub fn from_value(value: u8) -> Option<JoypadButton> {
+        match value {
+            0 => Some(JoypadButton::Right),
+            1 => Some(JoypadButton::Left),
+            2 => Some(JoypadButton::Up),
+            3 => Some(JoypadButton::Down),
+            4 => Some(JoypadButton::A),
+            5 => Some(JoypadButton::B),
+            6 => Some(JoypadButton::Select),
+            7 => Some(JoypadButton::Start),
+            _ => None,
+        }
+    }
+
+    fn get_mask(self) -> u8 {
+        1 << self as u8
+    }
+}
+
+/// Buttons currently held by the player, one bit per JoypadButton (1 = pressed).
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct JoypadState {
+    pressed_buttons: u8,
+}
+
+impl JoypadState {
+    pub fn new() -> Self {
+        Self { pressed_buttons: 0 }
+    }
+
+    pub fn set_button(&mut self, button: JoypadButton, pressed: bool) {
+        if pressed {
+            self.pressed_buttons |= button.get_mask();
+        } else {
+            self.pressed_buttons &= !button.get_mask();
+        }
+    }
+
+    pub fn is_pressed(&self, button: JoypadButton) -> bool {
+        self.pressed_buttons & button.get_mask() != 0
+    }
+
+    /// Returns the state of all the buttons, bit N is set when the button with value N is pressed.
+    pub fn get_pressed_buttons(&self) -> u8 {
+        self.pressed_buttons
+    }
+
+    /// Returns the value read from the P1 register with the groups selected by bits 4 and 5 of `select`:
+    /// bit 4 cleared selects the D-pad and bit 5 cleared the action buttons. Bits 0-3 are the selected buttons,
+    /// 0 when pressed, and read 0x0F when no group is selected. Bits 6 and 7 are unused and read as 1.
+    pub fn get_p1_register(&self, select: u8) -> u8 {
+        let select = select & 0b0011_0000;
+        let mut pressed = 0;
+        if select & 0b0001_0000 == 0 {
+            pressed |= self.pressed_buttons & 0x0F;
+        }
+        if select & 0b0010_0000 == 0 {
+            pressed |= self.pressed_buttons >> 4;
+        }
+        0b1100_0000 | select | (!pressed & 0x0F)
+    }
+}
@@ -0,0 +1,136 @@
+use alloc::{boxed::Box, vec, vec::Vec};
+
+use crate::gameboy_core::{
+    constants::{EXTERNAL_RAM_BANK_SIZE, EXTERNAL_RAM_START, ROM_BANK_SIZE},
+    mbc::{Mbc, fill_ram_banks, flatten_ram_banks, get_ram_bank_count, has_battery, split_rom_banks},
+};
+
+/// MBC1 memory bank controller: up to 128 ROM banks of 16 KiB and 4 external RAM banks of 8 KiB.
+/// Its registers are written at:
+/// - $0000-$1FFF: RAM enable, the RAM is enabled when the lower 4 bits of the value are 0xA.
+/// - $2000-$3FFF: lower 5 bits of the ROM bank number. Writing 0 selects bank 1.
+/// - $4000-$5FFF: 2 bits used as RAM bank number or as bits 5-6 of the ROM bank number.
+/// - $6000-$7FFF: banking mode. In mode 0 the 2 bits register only applies to $4000-$7FFF, in mode 1 it also
+///   selects the RAM bank and the bank mapped at $0000-$3FFF.
+///
+/// The zero check only looks at the 5 bits register, so banks 0x20, 0x40 and 0x60 can't be mapped at
+/// $4000-$7FFF, selecting them maps the next bank.
+#[derive(Clone)]
+pub struct Mbc1 {
+    rom_banks: Vec<[u8; ROM_BANK_SIZE]>,
+    ram_banks: Vec<[u8; EXTERNAL_RAM_BANK_SIZE]>,
+    /// Lower 5 bits of the ROM bank mapped at $4000-$7FFF, from 1 to 31.
+    rom_bank: u8,
+    /// Value of the 2 bits register, the RAM bank or the upper bits of the ROM bank depending on the banking mode.
+    ram_bank: u8,
+    ram_enabled: bool,
+    has_battery: bool,
+    /// 0 = simple banking mode, 1 = advanced banking mode.
+    banking_mode: u8,
+}
+
+impl Mbc1 {
+    pub fn new(rom_binary: &[u8]) -> Mbc1 {
+        Mbc1 {
+            rom_banks: split_rom_banks(rom_binary),
+            ram_banks: vec![[0; EXTERNAL_RAM_BANK_SIZE]; get_ram_bank_count(rom_binary)],
+            rom_bank: 1,
+            ram_bank: 0,
+            ram_enabled: false,
+            has_battery: has_battery(rom_binary),
+            banking_mode: 0,
+        }
+    }
+
+    /// Returns the ROM bank mapped at $4000-$7FFF, before being wrapped to the size of the ROM.
+    pub fn get_rom_bank(&self) -> u8 {
+        (self.ram_bank << 5) | self.rom_bank
+    }
+
+    /// Returns the RAM bank mapped at $A000-$BFFF, always 0 in banking mode 0.
+    pub fn get_ram_bank(&self) -> u8 {
+        if self.banking_mode == 0 { 0 } else { self.ram_bank }
+    }
+
+    pub fn get_banking_mode(&self) -> u8 {
+        self.banking_mode
+    }
+
+    pub fn is_ram_enabled(&self) -> bool {
+        self.ram_enabled
+    }
+
+    /// Returns the ROM bank mapped at $0000-$3FFF: bank 0, or in banking mode 1 the bank selected by the 2 bits
+    /// register (0x20, 0x40 or 0x60), which is how large ROMs map their upper banks there.
+    fn get_low_rom_bank(&self) -> u8 {
+        if self.banking_mode == 0 { 0 } else { self.ram_bank << 5 }
+    }
+
+    /// Returns the selected RAM bank, None when the cartridge has no RAM.
+    /// Cartridges with a single 8 KiB bank ignore the bank number.
+    fn get_selected_ram_bank(&self) -> Option<usize> {
+        if self.ram_banks.is_empty() {
+            None
+        } else {
+            Some(self.get_ram_bank() as usize % self.ram_banks.len())
+        }
+    }
+
+    /// Offset of an address of $A000-$BFFF in the selected RAM bank.
+    fn get_ram_offset(address: u16) -> usize {
+        address.wrapping_sub(EXTERNAL_RAM_START) as usize % EXTERNAL_RAM_BANK_SIZE
+    }
+}
+
+impl Mbc for Mbc1 {
+    fn box_clone(&self) -> Box<dyn Mbc> {
+        Box::new(self.clone())
+    }
+
+    fn has_battery(&self) -> bool {
+        self.has_battery
+    }
+
+    fn get_save_data(&self) -> Vec<u8> {
+        flatten_ram_banks(&self.ram_banks)
+    }
+
+    fn load_save_data(&mut self, data: &[u8]) {
+        fill_ram_banks(&mut self.ram_banks, data);
+    }
+
+    fn read_rom(&self, address: u16) -> u8 {
+        let offset = address as usize % ROM_BANK_SIZE;
+        let bank = if (address as usize) < ROM_BANK_SIZE {
+            self.get_low_rom_bank()
+        } else {
+            self.get_rom_bank()
+        };
+        // ROMs with fewer than 128 banks ignore the upper bits of the bank number
+        self.rom_banks[bank as usize % self.rom_banks.len()][offset]
+    }
+
+    fn write_register(&mut self, address: u16, value: u8) {
+        match address {
+            0x0000..=0x1FFF => self.ram_enabled = value & 0x0F == 0x0A,
+            0x2000..=0x3FFF => self.rom_bank = (value & 0x1F).max(1),
+            0x4000..=0x5FFF => self.ram_bank = value & 0x03,
+            _ => self.banking_mode = value & 0x01,
+        }
+    }
+
+    fn read_ram(&self, address: u16) -> u8 {
+        match self.get_selected_ram_bank() {
+            Some(bank) if self.ram_enabled => self.ram_banks[bank][Self::get_ram_offset(address)],
+            _ => 0xFF,
+        }
+    }
+
+    fn write_ram(&mut self, address: u16, value: u8) {
+        if let Some(bank) = self.get_selected_ram_bank()
+            && self.ram_enabled
+        {
+            self.ram_banks[bank][Self::get_ram_offset(address)] = value;
+        }
+    }
+}
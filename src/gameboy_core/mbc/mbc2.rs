@@ -0,0 +1,110 @@
+use alloc::{boxed::Box, vec::Vec};
+
+use crate::gameboy_core::{
+    constants::{EXTERNAL_RAM_START, ROM_BANK_SIZE},
+    mbc::{Mbc, has_battery, split_rom_banks},
+};
+
+/// MBC2 has 512 x 4 bits of RAM built in, instead of an external RAM chip.
+pub const MBC2_RAM_SIZE: usize = 512;
+
+/// MBC2 memory bank controller: up to 16 ROM banks of 16 KiB and 512 half bytes of built-in RAM.
+/// Its two registers are written at $0000-$3FFF, bit 8 of the address selects which one:
+/// - Bit 8 clear: RAM enable, the RAM is enabled when the lower 4 bits of the value are 0xA.
+/// - Bit 8 set: ROM bank number, only the lower 4 bits are used and selecting bank 0 selects bank 1.
+#[derive(Clone)]
+pub struct Mbc2 {
+    rom_banks: Vec<[u8; ROM_BANK_SIZE]>,
+    /// Only the lower 4 bits of each byte are stored.
+    ram: [u8; MBC2_RAM_SIZE],
+    /// Bank mapped at $4000-$7FFF, from 1 to 15.
+    rom_bank: u8,
+    ram_enabled: bool,
+    has_battery: bool,
+}
+
+impl Mbc2 {
+    pub fn new(rom_binary: &[u8]) -> Mbc2 {
+        Mbc2 {
+            rom_banks: split_rom_banks(rom_binary),
+            ram: [0; MBC2_RAM_SIZE],
+            rom_bank: 1,
+            ram_enabled: false,
+            has_battery: has_battery(rom_binary),
+        }
+    }
+
+    /// Returns the ROM bank mapped at $4000-$7FFF.
+    pub fn get_rom_bank(&self) -> u8 {
+        self.rom_bank
+    }
+
+    pub fn is_ram_enabled(&self) -> bool {
+        self.ram_enabled
+    }
+
+    /// Index in the RAM of an address. Only the 9 lower bits are used, so the 512 bytes at $A000-$A1FF are
+    /// repeated in the whole $A000-$BFFF region.
+    fn get_ram_index(address: u16) -> usize {
+        (address.wrapping_sub(EXTERNAL_RAM_START) & 0x01FF) as usize
+    }
+}
+
+impl Mbc for Mbc2 {
+    fn box_clone(&self) -> Box<dyn Mbc> {
+        Box::new(self.clone())
+    }
+
+    fn has_battery(&self) -> bool {
+        self.has_battery
+    }
+
+    /// The 512 half bytes are saved as one byte each.
+    fn get_save_data(&self) -> Vec<u8> {
+        self.ram.to_vec()
+    }
+
+    fn load_save_data(&mut self, data: &[u8]) {
+        for (cell, value) in self.ram.iter_mut().zip(data) {
+            *cell = value & 0x0F;
+        }
+    }
+
+    fn read_rom(&self, address: u16) -> u8 {
+        let offset = address as usize % ROM_BANK_SIZE;
+        if (address as usize) < ROM_BANK_SIZE {
+            self.rom_banks[0][offset]
+        } else {
+            // ROMs with fewer than 16 banks ignore the upper bits of the bank number
+            self.rom_banks[self.rom_bank as usize % self.rom_banks.len()][offset]
+        }
+    }
+
+    fn write_register(&mut self, address: u16, value: u8) {
+        // The registers are only mapped in $0000-$3FFF
+        if address as usize >= ROM_BANK_SIZE {
+            return;
+        }
+
+        if address & 0x0100 == 0 {
+            self.ram_enabled = value & 0x0F == 0x0A;
+        } else {
+            let bank = value & 0x0F;
+            self.rom_bank = if bank == 0 { 1 } else { bank };
+        }
+    }
+
+    fn read_ram(&self, address: u16) -> u8 {
+        if !self.ram_enabled {
+            return 0xFF;
+        }
+        // Only the lower 4 bits are stored, the upper ones read as 1
+        0xF0 | self.ram[Self::get_ram_index(address)]
+    }
+
+    fn write_ram(&mut self, address: u16, value: u8) {
+        if self.ram_enabled {
+            self.ram[Self::get_ram_index(address)] = value & 0x0F;
+        }
+    }
+}
@@ -0,0 +1,366 @@
+use alloc::{boxed::Box, vec, vec::Vec};
+#[cfg(all(feature = "std", not(target_arch = "wasm32")))]
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use crate::gameboy_core::{
+    constants::{EXTERNAL_RAM_BANK_SIZE, EXTERNAL_RAM_START, ROM_BANK_SIZE},
+    mbc::{Mbc, fill_ram_banks, flatten_ram_banks, get_ram_bank_count, has_battery, split_rom_banks},
+};
+
+/// Size of the RTC state appended to the cartridge RAM in save files: the 5 current and the 5 latched registers as
+/// 32 bits little endian values, followed by the UNIX timestamp of the save as a 64 bits value. It is the format
+/// used by most emulators, so the save files can be shared with them.
+pub const RTC_SAVE_SIZE: usize = 48;
+
+const SECONDS_PER_DAY: u64 = 24 * 60 * 60;
+/// The day counter has 9 bits, it overflows after day 511.
+const DAY_COUNTER_LIMIT: u64 = 512;
+
+/// Bits of the DH register (day_high).
+const DAY_HIGH_BIT_8: u8 = 0b0000_0001;
+const DAY_HIGH_HALT: u8 = 0b0100_0000;
+const DAY_HIGH_CARRY: u8 = 0b1000_0000;
+
+/// Values of the RTC registers, selected by writing 0x08-0x0C to the RAM bank register.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct RtcRegisters {
+    /// 0x08: seconds, 0-59
+    pub seconds: u8,
+    /// 0x09: minutes, 0-59
+    pub minutes: u8,
+    /// 0x0A: hours, 0-23
+    pub hours: u8,
+    /// 0x0B: lower 8 bits of the day counter
+    pub day_low: u8,
+    /// 0x0C: bit 0 is bit 8 of the day counter, bit 6 halts the clock and bit 7 is the day counter carry
+    pub day_high: u8,
+}
+
+impl RtcRegisters {
+    /// Returns the register selected by the RAM bank register value (0x08-0x0C).
+    fn get(&self, register: u8) -> u8 {
+        match register {
+            0x08 => self.seconds,
+            0x09 => self.minutes,
+            0x0A => self.hours,
+            0x0B => self.day_low,
+            _ => self.day_high,
+        }
+    }
+
+    /// Sets the register selected by the RAM bank register value (0x08-0x0C), the unused bits are not stored.
+    fn set(&mut self, register: u8, value: u8) {
+        match register {
+            0x08 => self.seconds = value & 0x3F,
+            0x09 => self.minutes = value & 0x3F,
+            0x0A => self.hours = value & 0x1F,
+            0x0B => self.day_low = value,
+            _ => self.day_high = value & (DAY_HIGH_BIT_8 | DAY_HIGH_HALT | DAY_HIGH_CARRY),
+        }
+    }
+
+    fn get_days(&self) -> u64 {
+        (((self.day_high & DAY_HIGH_BIT_8) as u64) << 8) | self.day_low as u64
+    }
+
+    /// Serializes the registers in the order of their numbers, each one as a 32 bits little endian value.
+    fn to_bytes(self) -> [u8; 20] {
+        let mut bytes = [0; 20];
+        for (index, value) in [self.seconds, self.minutes, self.hours, self.day_low, self.day_high].iter().enumerate() {
+            bytes[index * 4..index * 4 + 4].copy_from_slice(&(*value as u32).to_le_bytes());
+        }
+        bytes
+    }
+
+    /// Reads the registers from 5 values of 32 bits, only their lowest byte is used.
+    fn from_bytes(bytes: &[u8]) -> RtcRegisters {
+        let mut registers = RtcRegisters::default();
+        for (index, register) in (0x08..=0x0C).enumerate() {
+            registers.set(register, bytes[index * 4]);
+        }
+        registers
+    }
+}
+
+/// Real Time Clock of MBC3 cartridges. It keeps counting while the emulator is closed: the time is taken from the
+/// system clock, and the elapsed seconds are added every time the registers are latched or written.
+/// The game reads the latched copy of the registers, which only changes when the latch sequence 0x00, 0x01 is
+/// written to $6000-$7FFF.
+#[derive(Debug, Clone)]
+pub struct Rtc {
+    pub registers: RtcRegisters,
+    pub latched: RtcRegisters,
+    /// UNIX timestamp in seconds of the last time the elapsed time was added to the registers.
+    last_update_timestamp: u64,
+}
+
+impl Default for Rtc {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Rtc {
+    /// Creates the clock at day 0, 00:00:00, counting from now.
+    pub fn new() -> Rtc {
+        Rtc {
+            registers: RtcRegisters::default(),
+            latched: RtcRegisters::default(),
+            last_update_timestamp: get_unix_timestamp(),
+        }
+    }
+
+    pub fn is_halted(&self) -> bool {
+        self.registers.day_high & DAY_HIGH_HALT != 0
+    }
+
+    /// Adds the seconds elapsed since the last update according to the system clock.
+    pub fn update(&mut self) {
+        let now = get_unix_timestamp();
+        self.advance(now.saturating_sub(self.last_update_timestamp));
+        self.last_update_timestamp = now;
+    }
+
+    /// Moves the clock forward, unless it is halted. When the day counter goes past 511 it wraps around to 0 and
+    /// the carry bit is set, it stays set until the game clears it.
+    pub fn advance(&mut self, elapsed_seconds: u64) {
+        if self.is_halted() || elapsed_seconds == 0 {
+            return;
+        }
+
+        let registers = &mut self.registers;
+        let time_of_day = registers.hours as u64 * 3600 + registers.minutes as u64 * 60 + registers.seconds as u64;
+        let total_seconds = time_of_day + elapsed_seconds;
+        let mut days = registers.get_days() + total_seconds / SECONDS_PER_DAY;
+        let time_of_day = total_seconds % SECONDS_PER_DAY;
+
+        if days >= DAY_COUNTER_LIMIT {
+            registers.day_high |= DAY_HIGH_CARRY;
+            days %= DAY_COUNTER_LIMIT;
+        }
+
+        registers.seconds = (time_of_day % 60) as u8;
+        registers.minutes = (time_of_day / 60 % 60) as u8;
+        registers.hours = (time_of_day / 3600) as u8;
+        registers.day_low = days as u8;
+        registers.day_high = (registers.day_high & !DAY_HIGH_BIT_8) | (days >> 8) as u8;
+    }
+
+    /// Copies the current registers to the latched ones read by the game.
+    pub fn latch(&mut self) {
+        self.update();
+        self.latched = self.registers;
+    }
+
+    /// Writes one of the registers (0x08-0x0C), the time elapsed until now is added first.
+    pub fn write_register(&mut self, register: u8, value: u8) {
+        self.update();
+        self.registers.set(register, value);
+    }
+
+    /// Serializes the clock to the RTC_SAVE_SIZE bytes stored after the cartridge RAM in save files.
+    pub fn to_save_bytes(&self) -> [u8; RTC_SAVE_SIZE] {
+        let mut bytes = [0; RTC_SAVE_SIZE];
+        bytes[0..20].copy_from_slice(&self.registers.to_bytes());
+        bytes[20..40].copy_from_slice(&self.latched.to_bytes());
+        bytes[40..48].copy_from_slice(&self.last_update_timestamp.to_le_bytes());
+        bytes
+    }
+
+    /// Restores the clock from a save file and adds the time elapsed since it was saved.
+    /// Returns None if there are not RTC_SAVE_SIZE bytes.
+    pub fn from_save_bytes(bytes: &[u8]) -> Option<Rtc> {
+        if bytes.len() < RTC_SAVE_SIZE {
+            return None;
+        }
+
+        let mut rtc = Rtc {
+            registers: RtcRegisters::from_bytes(&bytes[0..20]),
+            latched: RtcRegisters::from_bytes(&bytes[20..40]),
+            last_update_timestamp: u64::from_le_bytes(bytes[40..48].try_into().ok()?),
+        };
+        rtc.update();
+        Some(rtc)
+    }
+}
+
+/// Seconds since the UNIX epoch, 0 if the system clock is set before it.
+#[cfg(all(feature = "std", not(target_arch = "wasm32")))]
+fn get_unix_timestamp() -> u64 {
+    SystemTime::now().duration_since(UNIX_EPOCH).map(|duration| duration.as_secs()).unwrap_or(0)
+}
+
+/// SystemTime::now panics in WebAssembly and there is no system clock without std, the clock only moves when the game
+/// writes its registers.
+#[cfg(any(not(feature = "std"), target_arch = "wasm32"))]
+fn get_unix_timestamp() -> u64 {
+    0
+}
+
+/// MBC3 memory bank controller: up to 128 ROM banks of 16 KiB, 4 external RAM banks of 8 KiB and, in the cartridge
+/// types with a timer, a Real Time Clock.
+/// Its registers are written at:
+/// - $0000-$1FFF: RAM and RTC enable, they are enabled when the lower 4 bits of the value are 0xA.
+/// - $2000-$3FFF: ROM bank number, 7 bits. Writing 0 selects bank 1.
+/// - $4000-$5FFF: 0x00-0x03 maps a RAM bank at $A000-$BFFF, 0x08-0x0C maps an RTC register instead.
+/// - $6000-$7FFF: writing 0x00 and then 0x01 latches the RTC registers.
+#[derive(Clone)]
+pub struct Mbc3 {
+    rom_banks: Vec<[u8; ROM_BANK_SIZE]>,
+    ram_banks: Vec<[u8; EXTERNAL_RAM_BANK_SIZE]>,
+    /// Bank mapped at $4000-$7FFF, from 1 to 127.
+    rom_bank: u8,
+    /// RAM bank (0x00-0x03) or RTC register (0x08-0x0C) mapped at $A000-$BFFF.
+    ram_bank: u8,
+    ram_enabled: bool,
+    has_battery: bool,
+    /// Last value written to $6000-$7FFF, the registers are latched when it goes from 0x00 to 0x01.
+    last_latch_value: u8,
+    /// None for the cartridge types without a timer.
+    rtc: Option<Rtc>,
+}
+
+impl Mbc3 {
+    pub fn new(rom_binary: &[u8], has_rtc: bool) -> Mbc3 {
+        Mbc3 {
+            rom_banks: split_rom_banks(rom_binary),
+            ram_banks: vec![[0; EXTERNAL_RAM_BANK_SIZE]; get_ram_bank_count(rom_binary)],
+            rom_bank: 1,
+            ram_bank: 0,
+            ram_enabled: false,
+            has_battery: has_battery(rom_binary),
+            last_latch_value: 0xFF,
+            rtc: has_rtc.then(Rtc::new),
+        }
+    }
+
+    /// Returns the ROM bank mapped at $4000-$7FFF.
+    pub fn get_rom_bank(&self) -> u8 {
+        self.rom_bank
+    }
+
+    /// Returns the RAM bank (0x00-0x03) or RTC register (0x08-0x0C) mapped at $A000-$BFFF.
+    pub fn get_ram_bank(&self) -> u8 {
+        self.ram_bank
+    }
+
+    pub fn is_ram_enabled(&self) -> bool {
+        self.ram_enabled
+    }
+
+    /// Returns the Real Time Clock, None when the cartridge doesn't have one.
+    pub fn get_rtc(&self) -> Option<&Rtc> {
+        self.rtc.as_ref()
+    }
+
+    pub fn get_rtc_mut(&mut self) -> Option<&mut Rtc> {
+        self.rtc.as_mut()
+    }
+
+    /// Returns the selected RAM bank, None when the cartridge has no RAM or an RTC register is selected.
+    /// Cartridges with fewer than 4 banks ignore the upper bits of the bank number.
+    fn get_selected_ram_bank(&self) -> Option<usize> {
+        if self.ram_banks.is_empty() || self.ram_bank > 0x03 {
+            None
+        } else {
+            Some(self.ram_bank as usize % self.ram_banks.len())
+        }
+    }
+
+    /// Offset of an address of $A000-$BFFF in the selected RAM bank.
+    fn get_ram_offset(address: u16) -> usize {
+        address.wrapping_sub(EXTERNAL_RAM_START) as usize % EXTERNAL_RAM_BANK_SIZE
+    }
+
+    fn is_rtc_register_selected(&self) -> bool {
+        (0x08..=0x0C).contains(&self.ram_bank)
+    }
+}
+
+impl Mbc for Mbc3 {
+    fn box_clone(&self) -> Box<dyn Mbc> {
+        Box::new(self.clone())
+    }
+
+    fn has_battery(&self) -> bool {
+        self.has_battery
+    }
+
+    /// The RAM banks are followed by the `RTC_SAVE_SIZE` bytes of the clock state when the cartridge has one.
+    fn get_save_data(&self) -> Vec<u8> {
+        let mut data = flatten_ram_banks(&self.ram_banks);
+        if let Some(rtc) = &self.rtc {
+            data.extend_from_slice(&rtc.to_save_bytes());
+        }
+        data
+    }
+
+    /// The clock is only restored when the save file contains its state, otherwise it keeps running from 0.
+    fn load_save_data(&mut self, data: &[u8]) {
+        fill_ram_banks(&mut self.ram_banks, data);
+
+        let ram_size = self.ram_banks.len() * EXTERNAL_RAM_BANK_SIZE;
+        if self.rtc.is_some()
+            && let Some(rtc) = data.get(ram_size..).and_then(Rtc::from_save_bytes)
+        {
+            self.rtc = Some(rtc);
+        }
+    }
+
+    fn read_rom(&self, address: u16) -> u8 {
+        let offset = address as usize % ROM_BANK_SIZE;
+        if (address as usize) < ROM_BANK_SIZE {
+            self.rom_banks[0][offset]
+        } else {
+            // ROMs with fewer than 128 banks ignore the upper bits of the bank number
+            self.rom_banks[self.rom_bank as usize % self.rom_banks.len()][offset]
+        }
+    }
+
+    fn write_register(&mut self, address: u16, value: u8) {
+        match address {
+            0x0000..=0x1FFF => self.ram_enabled = value & 0x0F == 0x0A,
+            0x2000..=0x3FFF => self.rom_bank = (value & 0x7F).max(1),
+            0x4000..=0x5FFF => self.ram_bank = value,
+            _ => {
+                if self.last_latch_value == 0x00
+                    && value == 0x01
+                    && let Some(rtc) = self.rtc.as_mut()
+                {
+                    rtc.latch();
+                }
+                self.last_latch_value = value;
+            }
+        }
+    }
+
+    fn read_ram(&self, address: u16) -> u8 {
+        if !self.ram_enabled {
+            return 0xFF;
+        }
+
+        if self.is_rtc_register_selected() {
+            return self.rtc.as_ref().map_or(0xFF, |rtc| rtc.latched.get(self.ram_bank));
+        }
+
+        match self.get_selected_ram_bank() {
+            Some(bank) => self.ram_banks[bank][Self::get_ram_offset(address)],
+            None => 0xFF,
+        }
+    }
+
+    fn write_ram(&mut self, address: u16, value: u8) {
+        if !self.ram_enabled {
+            return;
+        }
+
+        if self.is_rtc_register_selected() {
+            let register = self.ram_bank;
+            if let Some(rtc) = self.rtc.as_mut() {
+                rtc.write_register(register, value);
+            }
+        } else if let Some(bank) = self.get_selected_ram_bank() {
+            self.ram_banks[bank][Self::get_ram_offset(address)] = value;
+        }
+    }
+}
@@ -0,0 +1,122 @@
+use alloc::{boxed::Box, vec, vec::Vec};
+
+use crate::gameboy_core::{
+    constants::{EXTERNAL_RAM_BANK_SIZE, EXTERNAL_RAM_START, ROM_BANK_SIZE},
+    mbc::{Mbc, fill_ram_banks, flatten_ram_banks, get_ram_bank_count, has_battery, split_rom_banks},
+};
+
+/// MBC5 memory bank controller: up to 512 ROM banks of 16 KiB and 16 external RAM banks of 8 KiB.
+/// Its registers are written at:
+/// - $0000-$1FFF: RAM enable, the RAM is enabled when the lower 4 bits of the value are 0xA.
+/// - $2000-$2FFF: lower 8 bits of the ROM bank number.
+/// - $3000-$3FFF: bit 8 of the ROM bank number, in bit 0 of the value.
+/// - $4000-$5FFF: RAM bank number, from 0 to 15.
+///
+/// Unlike the other MBCs, selecting ROM bank 0 maps bank 0 at $4000-$7FFF.
+#[derive(Clone)]
+pub struct Mbc5 {
+    rom_banks: Vec<Vec<u8>>,
+    ram_banks: Vec<Vec<u8>>,
+    /// Bank mapped at $4000-$7FFF, from 0 to 511.
+    rom_bank: u16,
+    /// Bank mapped at $A000-$BFFF, from 0 to 15.
+    ram_bank: u8,
+    ram_enabled: bool,
+    has_battery: bool,
+}
+
+impl Mbc5 {
+    pub fn new(rom_binary: &[u8]) -> Mbc5 {
+        Mbc5 {
+            rom_banks: split_rom_banks(rom_binary).iter().map(|bank| bank.to_vec()).collect(),
+            ram_banks: vec![vec![0; EXTERNAL_RAM_BANK_SIZE]; get_ram_bank_count(rom_binary)],
+            rom_bank: 1,
+            ram_bank: 0,
+            ram_enabled: false,
+            has_battery: has_battery(rom_binary),
+        }
+    }
+
+    /// Returns the ROM bank mapped at $4000-$7FFF.
+    pub fn get_rom_bank(&self) -> u16 {
+        self.rom_bank
+    }
+
+    /// Returns the RAM bank mapped at $A000-$BFFF.
+    pub fn get_ram_bank(&self) -> u8 {
+        self.ram_bank
+    }
+
+    pub fn is_ram_enabled(&self) -> bool {
+        self.ram_enabled
+    }
+
+    /// Returns the selected RAM bank, None when the cartridge has no RAM.
+    /// Cartridges with fewer than 16 banks ignore the upper bits of the bank number.
+    fn get_selected_ram_bank(&self) -> Option<usize> {
+        if self.ram_banks.is_empty() {
+            None
+        } else {
+            Some(self.ram_bank as usize % self.ram_banks.len())
+        }
+    }
+
+    /// Offset of an address of $A000-$BFFF in the selected RAM bank.
+    fn get_ram_offset(address: u16) -> usize {
+        address.wrapping_sub(EXTERNAL_RAM_START) as usize % EXTERNAL_RAM_BANK_SIZE
+    }
+}
+
+impl Mbc for Mbc5 {
+    fn box_clone(&self) -> Box<dyn Mbc> {
+        Box::new(self.clone())
+    }
+
+    fn has_battery(&self) -> bool {
+        self.has_battery
+    }
+
+    fn get_save_data(&self) -> Vec<u8> {
+        flatten_ram_banks(&self.ram_banks)
+    }
+
+    fn load_save_data(&mut self, data: &[u8]) {
+        fill_ram_banks(&mut self.ram_banks, data);
+    }
+
+    fn read_rom(&self, address: u16) -> u8 {
+        let offset = address as usize % ROM_BANK_SIZE;
+        if (address as usize) < ROM_BANK_SIZE {
+            self.rom_banks[0][offset]
+        } else {
+            // ROMs with fewer than 512 banks ignore the upper bits of the bank number
+            self.rom_banks[self.rom_bank as usize % self.rom_banks.len()][offset]
+        }
+    }
+
+    fn write_register(&mut self, address: u16, value: u8) {
+        match address {
+            0x0000..=0x1FFF => self.ram_enabled = value & 0x0F == 0x0A,
+            0x2000..=0x2FFF => self.rom_bank = (self.rom_bank & 0x0100) | value as u16,
+            0x3000..=0x3FFF => self.rom_bank = (self.rom_bank & 0x00FF) | ((value as u16 & 0x01) << 8),
+            0x4000..=0x5FFF => self.ram_bank = value & 0x0F,
+            // $6000-$7FFF has no register on MBC5
+            _ => {}
+        }
+    }
+
+    fn read_ram(&self, address: u16) -> u8 {
+        match self.get_selected_ram_bank() {
+            Some(bank) if self.ram_enabled => self.ram_banks[bank][Self::get_ram_offset(address)],
+            _ => 0xFF,
+        }
+    }
+
+    fn write_ram(&mut self, address: u16, value: u8) {
+        if let Some(bank) = self.get_selected_ram_bank()
+            && self.ram_enabled
+        {
+            self.ram_banks[bank][Self::get_ram_offset(address)] = value;
+        }
+    }
+}
@@ -0,0 +1,137 @@
+use alloc::{boxed::Box, vec::Vec};
+#[cfg(feature = "std")]
+use std::{fs, io, path::Path};
+
+use crate::gameboy_core::constants::{CARTRIDGE_TYPE_ADDRESS, EXTERNAL_RAM_BANK_SIZE, RAM_SIZE_ADDRESS, ROM_BANK_SIZE};
+
+pub mod mbc1;
+pub mod mbc2;
+pub mod mbc3;
+pub mod mbc5;
+
+use mbc1::Mbc1;
+use mbc2::Mbc2;
+use mbc3::Mbc3;
+use mbc5::Mbc5;
+
+/// Memory Bank Controller of a cartridge. It maps the ROM banks at $0000-$7FFF and the cartridge RAM at
+/// $A000-$BFFF. Writes to the ROM area don't change the ROM, they set the MBC registers instead.
+pub trait Mbc {
+    /// Reads the ROM at an address in $0000-$7FFF.
+    fn read_rom(&self, address: u16) -> u8;
+
+    /// Handles a write to an address in $0000-$7FFF, which sets one of the MBC registers.
+    fn write_register(&mut self, address: u16, value: u8);
+
+    /// Reads the cartridge RAM at an address in $A000-$BFFF, 0xFF when the RAM is disabled.
+    fn read_ram(&self, address: u16) -> u8;
+
+    /// Writes the cartridge RAM at an address in $A000-$BFFF, ignored when the RAM is disabled.
+    fn write_ram(&mut self, address: u16, value: u8);
+
+    /// Copies the MBC with its ROM, RAM and registers, so the memory bus holding it as a trait object can be cloned.
+    fn box_clone(&self) -> Box<dyn Mbc>;
+
+    /// Returns true if a battery keeps the cartridge RAM when the Game Boy is turned off, so it has to be saved.
+    fn has_battery(&self) -> bool;
+
+    /// Returns the content of a save file: all the RAM banks one after the other, followed by the state of the
+    /// cartridge clock if it has one.
+    fn get_save_data(&self) -> Vec<u8>;
+
+    /// Restores the RAM from the content of a save file. Save files shorter than the RAM only restore its beginning.
+    fn load_save_data(&mut self, data: &[u8]);
+
+    /// Writes the cartridge RAM to a save file, usually the path of the ROM with the `.sav` extension.
+    #[cfg(feature = "std")]
+    fn save_ram(&self, path: &Path) -> io::Result<()> {
+        fs::write(path, self.get_save_data())
+    }
+
+    /// Reads the cartridge RAM from a save file written by `save_ram` or by another emulator.
+    #[cfg(feature = "std")]
+    fn load_ram(&mut self, path: &Path) -> io::Result<()> {
+        let data = fs::read(path)?;
+        self.load_save_data(&data);
+        Ok(())
+    }
+}
+
+impl Clone for Box<dyn Mbc> {
+    fn clone(&self) -> Self {
+        self.box_clone()
+    }
+}
+
+/// Returns true if the MBC of the cartridge type (byte 0x0147 of the header) is implemented.
+pub fn is_mbc_implemented(cartridge_type: u8) -> bool {
+    matches!(cartridge_type, 0x01..=0x03 | 0x05 | 0x06 | 0x0F..=0x13 | 0x19..=0x1E)
+}
+
+/// Creates the MBC of the cartridge type given in the ROM header. Returns None for cartridges without MBC and for
+/// the MBCs not implemented yet, whose ROM is mapped as it is.
+pub fn create_mbc(rom_binary: &[u8]) -> Option<Box<dyn Mbc>> {
+    match rom_binary.get(CARTRIDGE_TYPE_ADDRESS) {
+        // 0x01 = MBC1, 0x02 = MBC1 + RAM, 0x03 = MBC1 + RAM + Battery
+        Some(0x01..=0x03) => Some(Box::new(Mbc1::new(rom_binary))),
+        // 0x05 = MBC2, 0x06 = MBC2 + Battery
+        Some(0x05 | 0x06) => Some(Box::new(Mbc2::new(rom_binary))),
+        // 0x0F = MBC3 + Timer + Battery, 0x10 = MBC3 + Timer + RAM + Battery
+        Some(0x0F | 0x10) => Some(Box::new(Mbc3::new(rom_binary, true))),
+        // 0x11 = MBC3, 0x12 = MBC3 + RAM, 0x13 = MBC3 + RAM + Battery
+        Some(0x11..=0x13) => Some(Box::new(Mbc3::new(rom_binary, false))),
+        // 0x19-0x1E = MBC5, with or without RAM, battery and rumble motor
+        Some(0x19..=0x1E) => Some(Box::new(Mbc5::new(rom_binary))),
+        _ => None,
+    }
+}
+
+/// Splits the ROM into banks of 16 KiB. The last bank is padded with 0xFF when the ROM size is not a multiple of the
+/// bank size, and there is always at least one bank.
+pub(crate) fn split_rom_banks(rom_binary: &[u8]) -> Vec<[u8; ROM_BANK_SIZE]> {
+    let mut banks: Vec<[u8; ROM_BANK_SIZE]> = rom_binary
+        .chunks(ROM_BANK_SIZE)
+        .map(|chunk| {
+            let mut bank = [0xFF; ROM_BANK_SIZE];
+            bank[..chunk.len()].copy_from_slice(chunk);
+            bank
+        })
+        .collect();
+
+    if banks.is_empty() {
+        banks.push([0xFF; ROM_BANK_SIZE]);
+    }
+    banks
+}
+
+/// Returns true if the cartridge type given in the ROM header (byte 0x0147) has a battery keeping its RAM.
+pub(crate) fn has_battery(rom_binary: &[u8]) -> bool {
+    matches!(
+        rom_binary.get(CARTRIDGE_TYPE_ADDRESS),
+        Some(0x03 | 0x06 | 0x09 | 0x0D | 0x0F | 0x10 | 0x13 | 0x1B | 0x1E | 0x22 | 0xFF)
+    )
+}
+
+/// Concatenates the RAM banks, which is the layout of the save files.
+pub(crate) fn flatten_ram_banks<B: AsRef<[u8]>>(ram_banks: &[B]) -> Vec<u8> {
+    ram_banks.iter().flat_map(|bank| bank.as_ref().iter().copied()).collect()
+}
+
+/// Copies the content of a save file to the RAM banks, the bytes beyond the RAM size are ignored.
+pub(crate) fn fill_ram_banks<B: AsMut<[u8]>>(ram_banks: &mut [B], data: &[u8]) {
+    for (bank, chunk) in ram_banks.iter_mut().zip(data.chunks(EXTERNAL_RAM_BANK_SIZE)) {
+        bank.as_mut()[..chunk.len()].copy_from_slice(chunk);
+    }
+}
+
+/// Number of 8 KiB external RAM banks given by the RAM size in the ROM header (byte 0x0149).
+pub(crate) fn get_ram_bank_count(rom_binary: &[u8]) -> usize {
+    match rom_binary.get(RAM_SIZE_ADDRESS) {
+        Some(0x02) => 1,
+        Some(0x03) => 4,
+        Some(0x04) => 16,
+        Some(0x05) => 8,
+        // 0x00 = no RAM, 0x01 was never used by licensed cartridges
+        _ => 0,
+    }
+}
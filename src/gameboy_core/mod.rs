@@ -9,6 +9,10 @@ pub mod registers_contants;
 pub mod timer;
 pub mod interrupts;
 pub mod components;
+pub mod emulator_error;
+pub mod disassembler;
+pub mod joypad;
 
-#[cfg(test)]
-mod tests;
\ No newline at end of file
+// The tests read ROMs and write save files, they need std
+#[cfg(all(test, feature = "std"))]
+mod tests;pub mod mbc;
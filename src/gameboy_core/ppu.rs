@@ -1,12 +1,16 @@
+use alloc::{vec, vec::Vec};
+
 use crate::gameboy_core::{
     constants::{
         BG_AND_WINDOW_MAP_SCREEN_SIZE, BG_AND_WINDOW_TILE_COUNT_PER_ROW_COL, COLORS,
-        GAME_SECTION_HEIGHT, GAME_SECTION_WIDTH,
+        GAME_SECTION_HEIGHT, GAME_SECTION_WIDTH, HBLANK_START_MIN_DOTS, OAM_OBJECT_COUNT, OAM_SEARCH_DOTS, TILE_SIZE,
+        VRAM_START,
     },
-    cpu, cpu_components,
+    cpu,
+    cpu_components::MemoryAccess,
     interrupts::InterruptType,
     ppu_components::{self, Tile, TilePixelValue},
-    registers_contants::{BGP, LY, LYC, OBP0, OBP1, STAT},
+    registers_contants::{LY, LYC, STAT},
 };
 
 /// Number of T-cycles per scanline (or LCD line). LY increments every 456 T-cycles.
@@ -18,6 +22,15 @@ const VBLANK_START_LINE: u8 = 144;
 /// LY holds values from 0 to 153, so total lines are 154.
 const LY_MAX_LINES: u8 = 154;
 
+/// The PPU can only show 10 objects on each scanline.
+const MAX_OBJECTS_PER_SCANLINE: usize = 10;
+
+/// Number of tiles per row of the tile grid image when there is no other preference, like the tile data debug view.
+pub const DEFAULT_TILE_GRID_COLUMNS: usize = 16;
+
+/// Color of the 1 pixel borders between the tiles of the tile grid image.
+pub const TILE_GRID_BORDER_COLOR: u32 = 0x808080;
+
 #[derive(PartialEq)]
 enum PpuMode {
     HBlank = 0,
@@ -26,7 +39,8 @@ enum PpuMode {
     PixelTransfer = 3,
 }
 
-#[derive(Copy, Clone, Debug)]
+#[derive(Copy, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "save-states", derive(serde::Serialize, serde::Deserialize))]
 pub struct Object {
     pub y: u8,
     pub x: u8,
@@ -34,7 +48,33 @@ pub struct Object {
     pub attributes: ObjectAttributes,
 }
 
-#[derive(Copy, Clone, Debug)]
+impl Object {
+    /// Parses an object from its 4 bytes in OAM: Y position, X position, tile index and attribute flags.
+    pub fn from_oam_entry(entry: [u8; 4]) -> Object {
+        let [y, x, tile_index, flags] = entry;
+
+        Object {
+            y,
+            x,
+            tile_index: tile_index as usize,
+            attributes: ObjectAttributes {
+                priority: (flags & 0b1000_0000) != 0,
+                y_flip: (flags & 0b0100_0000) != 0,
+                x_flip: (flags & 0b0010_0000) != 0,
+                // FIX: Bit 4 selects the object palette, not bit 6 (which is y_flip)
+                // Bit 7 = priority, Bit 6 = y_flip, Bit 5 = x_flip, Bit 4 = palette selection
+                pallete: if (flags & 0b0001_0000) != 0 {
+                    ObjectPallete::OBP1
+                } else {
+                    ObjectPallete::OBP0
+                },
+            },
+        }
+    }
+}
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "save-states", derive(serde::Serialize, serde::Deserialize))]
 pub struct ObjectAttributes {
     pub priority: bool,
     pub y_flip: bool,
@@ -42,18 +82,58 @@ pub struct ObjectAttributes {
     pub pallete: ObjectPallete,
 }
 
-#[derive(Copy, Clone, Debug)]
+#[derive(Copy, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "save-states", derive(serde::Serialize, serde::Deserialize))]
 pub enum ObjectPallete {
     OBP0 = 0,
     OBP1 = 1,
 }
 
+#[derive(Clone, PartialEq)]
+#[cfg_attr(feature = "save-states", derive(serde::Serialize, serde::Deserialize))]
 pub struct Ppu {
+    #[cfg_attr(feature = "save-states", serde(with = "screen_buffer_serde"))]
     pub screen: [[u32; GAME_SECTION_WIDTH]; GAME_SECTION_HEIGHT], // 144 rows of 160 pixels
     pub dots: u16,
     pub objects_to_be_rendered: Vec<Object>,
+    #[cfg_attr(feature = "save-states", serde(with = "screen_buffer_serde"))]
     pub bg_screen_buffer: [[u32; GAME_SECTION_WIDTH]; GAME_SECTION_HEIGHT],
     pub need_to_render_line: bool,
+    /// State of LCDC bit 7 seen on the last update, used to detect when the LCD is turned on or off.
+    pub is_lcd_enabled: bool,
+    /// Row of the window drawn on the next scanline where the window is visible. It only advances on the scanlines
+    /// showing the window, so a window hidden for a few lines continues where it stopped. Reset on each frame.
+    pub window_internal_line: u8,
+    /// Trace of the mode changes as (dot in the scanline, new mode), only recorded after `enable_mode_trace` is called.
+    #[cfg_attr(feature = "save-states", serde(skip))]
+    pub ppu_mode_changes: Option<Vec<(u16, u8)>>,
+}
+
+/// Serializes the 144x160 screen buffers as a flat list of pixels, row after row. Serde only implements the
+/// traits for arrays of up to 32 items, and a flat list is read the same way by every format.
+#[cfg(feature = "save-states")]
+mod screen_buffer_serde {
+    use alloc::vec::Vec;
+    use serde::{Deserialize, Deserializer, Serialize, Serializer, de::Error};
+
+    use crate::gameboy_core::constants::{GAME_SECTION_HEIGHT, GAME_SECTION_WIDTH};
+
+    type ScreenBuffer = [[u32; GAME_SECTION_WIDTH]; GAME_SECTION_HEIGHT];
+
+    pub fn serialize<S: Serializer>(buffer: &ScreenBuffer, serializer: S) -> Result<S::Ok, S::Error> {
+        buffer.as_flattened().serialize(serializer)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<ScreenBuffer, D::Error> {
+        let pixels = Vec::<u32>::deserialize(deserializer)?;
+        if pixels.len() != GAME_SECTION_WIDTH * GAME_SECTION_HEIGHT {
+            return Err(D::Error::invalid_length(pixels.len(), &"160x144 pixels"));
+        }
+
+        let mut buffer = [[0; GAME_SECTION_WIDTH]; GAME_SECTION_HEIGHT];
+        buffer.as_flattened_mut().copy_from_slice(&pixels);
+        Ok(buffer)
+    }
 }
 
 impl Ppu {
@@ -64,40 +144,100 @@ impl Ppu {
             objects_to_be_rendered: Vec::new(),
             bg_screen_buffer: [[0; GAME_SECTION_WIDTH]; GAME_SECTION_HEIGHT],
             need_to_render_line: false,
+            is_lcd_enabled: false,
+            window_internal_line: 0,
+            ppu_mode_changes: None,
         }
     }
 
-    /// Generates the screen buffer representing the visible 160x144 pixel screen.
-    /// This will build the Background first, then apply the Window (if enabled), and finally render the Objects - Sprites (if enabled).
-    pub fn update_screen_buffer(&mut self, memory_bus: &cpu_components::MemoryBus) {
-        self.screen = self.get_bg_screen_buffer_as_colors(memory_bus);
+    /// Starts recording the mode changes in `ppu_mode_changes`. Calling it again keeps the recorded changes.
+    pub fn enable_mode_trace(&mut self) {
+        self.ppu_mode_changes.get_or_insert_with(Vec::new);
+    }
+
+    /// Returns true if the LCD and PPU are enabled (bit 7 of the LCDC register).
+    pub fn get_lcdc_enabled(&self, memory_bus: &impl MemoryAccess) -> bool {
+        ppu_components::LcdcRegister::get_lcdc_register(memory_bus).lcd_ppu_enabled
+    }
+
+    /// Frame barrier: returns the visible 160x144 pixel screen. It is not rendered again here, each scanline is
+    /// rendered by `step_scanline` when the PPU enters H-Blank, so the screen holds the last frame drawn so far.
+    /// When the LCD is off the screen is blank (white).
+    pub fn update_screen_buffer(
+        &mut self,
+        memory_bus: &impl MemoryAccess,
+    ) -> &[[u32; GAME_SECTION_WIDTH]; GAME_SECTION_HEIGHT] {
+        if !self.get_lcdc_enabled(memory_bus) {
+            self.screen = [[0xFFFFFF; GAME_SECTION_WIDTH]; GAME_SECTION_HEIGHT];
+        }
+
+        &self.screen
+    }
+
+    /// Renders the scanline LY into the screen: the Background first, then the Window (if enabled), and finally the
+    /// Objects - Sprites (if enabled) selected for the line. Lines outside the screen (V-Blank) are ignored.
+    pub fn step_scanline(&mut self, memory_bus: &impl MemoryAccess, ly: u8) {
+        if ly >= VBLANK_START_LINE {
+            return;
+        }
+
+        if ly == 0 {
+            self.window_internal_line = 0;
+        }
+
+        let ly_usize = ly as usize;
+        let lcdc = ppu_components::LcdcRegister::get_lcdc_register(memory_bus);
+
+        let mut bg_color_indexes = self.render_background_line_to_screen_buffer(memory_bus, ly_usize, &lcdc);
+
+        let mut line = self.screen[ly_usize];
+        self.render_window_for_scanline(ly, &mut line, &mut bg_color_indexes, memory_bus);
+        self.render_sprites_for_scanline(ly, &mut line, &bg_color_indexes, memory_bus);
+        self.screen[ly_usize] = line;
     }
 
     /// Generates the background screen buffer representing the visible 160x144 pixel screen in color values.
     /// Where the color is an u32 representing the RGB value.
     pub fn get_bg_screen_buffer_as_colors(
         &self,
-        memory_bus: &cpu_components::MemoryBus,
+        memory_bus: &impl MemoryAccess,
+        lcdc_register: &ppu_components::LcdcRegister,
     ) -> [[u32; GAME_SECTION_WIDTH]; GAME_SECTION_HEIGHT] {
-        let lcdc_register = ppu_components::LcdcRegister::get_lcdc_register(memory_bus);
-
         // When Bit 0 is cleared, both background and window become blank (white), and the Window Display Bit is ignored in that case.
         // Only objects may still be displayed (if enabled in Bit 1).
         if lcdc_register.bg_window_enable == false {
             return [[0xFFFFFF; GAME_SECTION_WIDTH]; GAME_SECTION_HEIGHT];
         }
 
-        let bg_screen_buffer = self.get_bg_screen_buffer(memory_bus);
+        let bg_screen_buffer = self.get_bg_screen_buffer(memory_bus, lcdc_register);
         let mut color_screen_buffer = [[0u32; GAME_SECTION_WIDTH]; GAME_SECTION_HEIGHT];
 
         for row in 0..GAME_SECTION_HEIGHT {
             for col in 0..GAME_SECTION_WIDTH {
                 let pixel_value = bg_screen_buffer[row][col];
-                let color = COLORS[pixel_value as usize];
+                let color = COLORS[memory_bus.decode_bg_palette(pixel_value)];
                 color_screen_buffer[row][col] = color;
             }
         }
 
+        // The window is drawn over the background from the row WY, as if it was visible on all those rows
+        if lcdc_register.window_enable {
+            let tiles = self.get_tiles(memory_bus);
+            let window_tiles = self.get_bg_and_window_tiles(&tiles, lcdc_register);
+            let window_tile_map = self.get_window_tile_map_as_grid_32x32(memory_bus, lcdc_register);
+            let wy = memory_bus.get_wy_register() as usize;
+            let wx = memory_bus.get_wx_register();
+
+            for (window_line, row) in (wy..GAME_SECTION_HEIGHT).enumerate() {
+                let window_row = Self::get_window_row_color_indexes(&window_tile_map, &window_tiles, window_line, wx);
+                for (col, color_index) in window_row.into_iter().enumerate() {
+                    if let Some(color_index) = color_index {
+                        color_screen_buffer[row][col] = COLORS[memory_bus.decode_bg_palette(color_index)];
+                    }
+                }
+            }
+        }
+
         color_screen_buffer
     }
 
@@ -105,31 +245,63 @@ impl Ppu {
     /// This will build the Background only returning it in a color pallete value only.
     pub fn get_bg_screen_buffer(
         &self,
-        memory_bus: &cpu_components::MemoryBus,
+        memory_bus: &impl MemoryAccess,
+        lcdc_register: &ppu_components::LcdcRegister,
     ) -> [[u8; GAME_SECTION_WIDTH]; GAME_SECTION_HEIGHT] {
-        let lcdc_register = ppu_components::LcdcRegister::get_lcdc_register(memory_bus);
         let tiles = self.get_tiles(memory_bus);
 
-        let bg_buffer = self.get_entire_bg_buffer(memory_bus, &tiles, &lcdc_register);
+        let bg_buffer = self.get_entire_bg_buffer(memory_bus, &tiles, lcdc_register);
         let screen_buffer = self.get_visible_bg_buffer(&bg_buffer, memory_bus);
 
         screen_buffer
     }
 
+    /// Returns the 40 objects (sprites) stored in OAM, in the same order they are stored in memory.
+    pub fn get_objects(memory_bus: &impl MemoryAccess) -> [Object; OAM_OBJECT_COUNT] {
+        Ppu::get_all_40_objects(memory_bus)
+    }
+
     /// Returns the entire set of Tiles from VRAM.
     /// Tiles are used to build the background, window, and objects (sprites).
-    pub fn get_tiles_data(&self, memory_bus: &cpu_components::MemoryBus) -> [Tile; 384] {
+    pub fn get_tiles_data(&self, memory_bus: &impl MemoryAccess) -> [Tile; 384] {
         self.get_tiles(memory_bus)
     }
 
+    /// Packs the 384 tiles into a single image with `cols` tiles per row (at least 1), separated by 1 pixel borders of
+    /// TILE_GRID_BORDER_COLOR. Each pixel value is used as index in the palette, whose colors are 0xRRGGBB values.
+    /// Returns the pixels row by row at 1x scale, the image is `cols * 9 - 1` pixels wide and has as many rows of
+    /// tiles as needed, so the last row can be partially filled with the border color.
+    pub fn get_all_tiles_as_rgba_grid(tiles: &[Tile; 384], palette: &[u32; 4], cols: usize) -> Vec<u32> {
+        let cols = cols.max(1);
+        let rows = tiles.len().div_ceil(cols);
+        let cell_size = TILE_SIZE + 1;
+        let width = cols * cell_size - 1;
+        let height = rows * cell_size - 1;
+        let mut grid = vec![TILE_GRID_BORDER_COLOR; width * height];
+
+        for (tile_index, tile) in tiles.iter().enumerate() {
+            let top = (tile_index / cols) * cell_size;
+            let left = (tile_index % cols) * cell_size;
+
+            for (tile_row, row_pixels) in tile.pixels.iter().enumerate() {
+                let row_start = (top + tile_row) * width + left;
+                for (tile_col, pixel) in row_pixels.iter().enumerate() {
+                    grid[row_start + tile_col] = palette[*pixel as usize];
+                }
+            }
+        }
+
+        grid
+    }
+
     /// Returns the visible portion of the background buffer based on the SCX and SCY scroll values and to fit the 160x144 screen.
     /// The PPU calculates the bottom-right coordinates of the viewport with those formulas:
     /// bottom := (SCY + 143) % 256 and right := (SCX + 159) % 256.
     /// As suggested by the modulo operations, in case the values are larger than 255 they will “wrap around” towards the top-left corner of the tilemap.
-    fn get_visible_bg_buffer(
+    pub(crate) fn get_visible_bg_buffer(
         &self,
         bg_buffer: &[[u8; BG_AND_WINDOW_MAP_SCREEN_SIZE]; BG_AND_WINDOW_MAP_SCREEN_SIZE],
-        memory_bus: &cpu_components::MemoryBus,
+        memory_bus: &impl MemoryAccess,
     ) -> [[u8; GAME_SECTION_WIDTH]; GAME_SECTION_HEIGHT] {
         let scy = memory_bus.get_scy_register() as usize;
         let scx = memory_bus.get_scx_register() as usize;
@@ -151,7 +323,7 @@ impl Ppu {
     /// into a 32x32 grid, and then mapping each tile's pixels into the correct positions in the 256x256 buffer.
     pub fn get_entire_bg_buffer(
         &self,
-        memory_bus: &cpu_components::MemoryBus,
+        memory_bus: &impl MemoryAccess,
         tiles: &[Tile; 384],
         lcdc_register: &ppu_components::LcdcRegister,
     ) -> [[u8; BG_AND_WINDOW_MAP_SCREEN_SIZE]; BG_AND_WINDOW_MAP_SCREEN_SIZE] {
@@ -186,7 +358,7 @@ impl Ppu {
     /// A tile (or character) has 8×8 pixels and has a color depth of 2 bits per pixel,
     /// allowing each pixel to use one of 4 colors or gray shades.
     /// Tiles can be displayed as part of the Background/Window maps, and/or as objects (movable sprites).
-    pub fn get_tiles(&self, memory_bus: &cpu_components::MemoryBus) -> [Tile; 384] {
+    pub fn get_tiles(&self, memory_bus: &impl MemoryAccess) -> [Tile; 384] {
         let mut tiles: [Tile; 384] = [Tile::new(); 384];
         // Tile data is stored in VRAM in the memory area at $8000-$97FF;
         // Each tile is 16 bytes (2 bytes per row, 8 rows)
@@ -264,12 +436,24 @@ impl Ppu {
     /// To accomplish this, it reads the tile map from memory and then parses to a 2D array by calculating row and column indices.
     fn get_bg_tile_map_as_grid_32x32(
         &self,
-        memory_bus: &cpu_components::MemoryBus,
+        memory_bus: &impl MemoryAccess,
+        lcdc: &ppu_components::LcdcRegister,
+    ) -> [[u8; 32]; 32] {
+        Self::tile_map_to_grid_32x32(&memory_bus.get_bg_tile_map(lcdc))
+    }
+
+    /// Converts the window tile map, selected by LCDC bit 6, from a flat vector to a 32x32 grid.
+    pub fn get_window_tile_map_as_grid_32x32(
+        &self,
+        memory_bus: &impl MemoryAccess,
         lcdc: &ppu_components::LcdcRegister,
     ) -> [[u8; 32]; 32] {
-        let tile_map_vec = memory_bus.get_bg_tile_map(lcdc).to_vec();
+        Self::tile_map_to_grid_32x32(&memory_bus.get_window_tile_map(lcdc))
+    }
+
+    fn tile_map_to_grid_32x32(tile_map: &[u8]) -> [[u8; 32]; 32] {
         let mut tile_map_grid = [[0u8; 32]; 32];
-        for (i, &value) in tile_map_vec.iter().enumerate() {
+        for (i, &value) in tile_map.iter().enumerate() {
             let row = i / 32;
             let col = i % 32;
             tile_map_grid[row][col] = value;
@@ -279,7 +463,17 @@ impl Ppu {
 
     /// Increases the LY register based on the number of T-cycles (dots) executed and, updates PPU mode and interrupts accordingly.
     /// This method should be called every instruction execution to keep the PPU state updated.
-    pub(crate) fn update_state(cpu: &mut cpu::Cpu) {
+    /// While the LCD is off (LCDC bit 7 cleared) the PPU is stopped: LY is held at 0, dots do not advance and the mode is 0.
+    pub(crate) fn update_state<M: MemoryAccess>(cpu: &mut cpu::Cpu<M>) {
+        if !cpu.ppu.get_lcdc_enabled(&cpu.memory_bus) {
+            if cpu.ppu.is_lcd_enabled {
+                Ppu::turn_lcd_off(cpu);
+            }
+            return;
+        }
+
+        // When the LCD is turned back on the PPU starts again from the beginning of LY 0, which was set when it was turned off
+        cpu.ppu.is_lcd_enabled = true;
         cpu.ppu.dots += 4;
 
         if cpu.ppu.dots >= T_CYCLES_PER_SCANLINE {
@@ -295,43 +489,47 @@ impl Ppu {
                 cpu.memory_bus
                     .update_flag_in_if_register(InterruptType::VBlank, true);
 
-                // Set mode to 1 (V-Blank)
+                // Set mode to 1 (V-Blank). All the scanlines of the frame have been rendered at this point.
                 Ppu::set_ppu_mode_flag_in_stat(cpu, PpuMode::VBlank);
-                
-                // FIX: Update the cached background buffer at V-Blank
-                // This ensures the screen is ready for the next frame and helps with any pending changes
-                cpu.ppu.update_screen_buffer(&cpu.memory_bus);
             } else if ly == 0 {
                 // This handles V-Blank Exit (transition from V-Blank to OAM Search)
                 Ppu::set_ppu_mode_flag_in_stat(cpu, PpuMode::OamSearch);
             }
             
             // FIX: Define objects for the NEW scanline now that LY has been incremented
-            // This ensures that when render_line() is called during Pixel Transfer,
+            // This ensures that when step_scanline() is called at the start of H-Blank,
             // the correct objects for the current scanline are available
             if ly < VBLANK_START_LINE {
                 Ppu::define_objects_to_be_rendered(cpu, ly);
             }
-        } else {
+        } else if cpu.memory_bus.read_byte(LY) < VBLANK_START_LINE {
             // Update the PPU mode based on the current number of dots (T-cycles) in the scanline.
-            // each dot represents a T-cycle
+            // each dot represents a T-cycle. During V-Blank (LY 144-153) the mode stays 1 for the whole line.
             match cpu.ppu.dots {
                 // OAM Search lasts 80 dots
-                0..=79 => {
+                0..OAM_SEARCH_DOTS => {
                     Ppu::set_ppu_mode_flag_in_stat(cpu, PpuMode::OamSearch);
                     cpu.ppu.need_to_render_line = false;
                 }
                 // Drawing pixels lasts at least 172 dots, there are cases where it can take longer but I'm using the simplest approach for now
-                80..=251 => {
+                OAM_SEARCH_DOTS..HBLANK_START_MIN_DOTS => {
                     let current_ppu_mode = Ppu::get_ppu_mode_flag_from_stat(cpu);
                     if current_ppu_mode != PpuMode::PixelTransfer {
-                        Ppu::render_line(cpu);
                         Ppu::set_ppu_mode_flag_in_stat(cpu, PpuMode::PixelTransfer);
                         cpu.ppu.need_to_render_line = true;
                     }
                 }
                 // Greater than or equal to 252 dots means the rest of the scanline (H-Blank)
                 _ => {
+                    let current_ppu_mode = Ppu::get_ppu_mode_flag_from_stat(cpu);
+                    if current_ppu_mode != PpuMode::HBlank {
+                        // The line is drawn once Pixel Transfer is over, with the registers as they are at that point
+                        let ly = cpu.memory_bus.read_byte(LY);
+                        cpu.ppu.step_scanline(&cpu.memory_bus, ly);
+
+                        // GBC H-Blank DMA copies 16 bytes at the start of each H-Blank
+                        cpu.memory_bus.do_hblank_dma_transfer();
+                    }
                     Ppu::set_ppu_mode_flag_in_stat(cpu, PpuMode::HBlank);
                 }
             }
@@ -340,15 +538,37 @@ impl Ppu {
         Self::compare_lyc(cpu);
     }
 
+    /// Stops the PPU when the LCD is turned off: LY and dots go back to 0, the mode is set to 0 (H-Blank) and the screen is blank.
+    /// On real hardware the LCD must only be turned off during V-Blank, doing it outside V-Blank may damage the screen,
+    /// so this is asserted in debug builds to catch emulation (or game) bugs.
+    fn turn_lcd_off<M: MemoryAccess>(cpu: &mut cpu::Cpu<M>) {
+        let ly = cpu.memory_bus.read_byte(LY);
+        debug_assert!(
+            ly >= VBLANK_START_LINE,
+            "The LCD should only be turned off during V-Blank, but LY was {}",
+            ly
+        );
+
+        cpu.ppu.is_lcd_enabled = false;
+        cpu.ppu.dots = 0;
+        cpu.memory_bus.write_byte(LY, 0);
+        Ppu::set_ppu_mode_flag_in_stat(cpu, PpuMode::HBlank);
+        cpu.ppu.screen = [[0xFFFFFF; GAME_SECTION_WIDTH]; GAME_SECTION_HEIGHT];
+    }
+
     /// Compares the LY and LYC registers and sets or clears the matching flag in the STAT register (bit 2).
-    fn compare_lyc(cpu: &mut cpu::Cpu) {
+    /// The LCD STAT interrupt is requested when the flag goes from 0 to 1 and the LYC source is enabled (STAT bit 6),
+    /// so it fires once when LY reaches LYC and not on every update of that line.
+    fn compare_lyc<M: MemoryAccess>(cpu: &mut cpu::Cpu<M>) {
         let ly = cpu.memory_bus.read_byte(LY);
         let lyc: u8 = cpu.memory_bus.read_byte(LYC);
         let mut stat = cpu.memory_bus.read_byte(STAT);
         if ly == lyc {
+            if stat & 0b00000100 == 0 && stat & 0b01000000 != 0 {
+                cpu.memory_bus
+                    .update_flag_in_if_register(InterruptType::LCD, true);
+            }
             stat |= 0b00000100; // Set the LY=LYC flag
-            cpu.memory_bus
-                .update_flag_in_if_register(InterruptType::LCD, true);
         } else {
             stat &= 0b11111011; // Clear the LY=LYC flag
         }
@@ -357,14 +577,36 @@ impl Ppu {
     }
 
     /// Sets the PPU mode flag in the STAT register.
-    fn set_ppu_mode_flag_in_stat(cpu: &mut cpu::Cpu, mode: PpuMode) {
+    /// When the mode changes the LCD STAT interrupt is requested if the source of the new mode is enabled in STAT:
+    /// bit 3 for H-Blank, bit 4 for V-Blank and bit 5 for OAM Search. Pixel Transfer has no interrupt source.
+    fn set_ppu_mode_flag_in_stat<M: MemoryAccess>(cpu: &mut cpu::Cpu<M>, mode: PpuMode) {
         let mut stat = cpu.memory_bus.read_byte(STAT);
-        stat = (stat & 0b11111100) | (mode as u8);
+        let interrupt_enable_bit = match mode {
+            PpuMode::HBlank => Some(0b00001000),
+            PpuMode::VBlank => Some(0b00010000),
+            PpuMode::OamSearch => Some(0b00100000),
+            PpuMode::PixelTransfer => None,
+        };
+        let mode = mode as u8;
+        if stat & 0b00000011 != mode {
+            if let Some(ppu_mode_changes) = cpu.ppu.ppu_mode_changes.as_mut() {
+                ppu_mode_changes.push((cpu.ppu.dots, mode));
+            }
+            // Turning the LCD off also sets mode 0, but it doesn't request an interrupt
+            if let Some(enable_bit) = interrupt_enable_bit
+                && stat & enable_bit != 0
+                && cpu.ppu.is_lcd_enabled
+            {
+                cpu.memory_bus
+                    .update_flag_in_if_register(InterruptType::LCD, true);
+            }
+        }
+        stat = (stat & 0b11111100) | mode;
         cpu.memory_bus.write_byte(STAT, stat);
     }
 
     /// Gets the current PPU mode flag from the STAT register.
-    fn get_ppu_mode_flag_from_stat(cpu: &cpu::Cpu) -> PpuMode {
+    fn get_ppu_mode_flag_from_stat<M: MemoryAccess>(cpu: &cpu::Cpu<M>) -> PpuMode {
         let stat = cpu.memory_bus.read_byte(STAT);
         match stat & 0b00000011 {
             0 => PpuMode::HBlank,
@@ -379,100 +621,66 @@ impl Ppu {
 
     /// Sets the 10 objects (sprites) to be rendered for the current scanline (LY)
     /// and stores them in the PPU.
-    fn define_objects_to_be_rendered(cpu: &mut cpu::Cpu, ly: u8) {
-        let ly_isize = ly as isize;
-        let objects = Ppu::get_all_40_objects(&cpu.memory_bus);
+    fn define_objects_to_be_rendered<M: MemoryAccess>(cpu: &mut cpu::Cpu<M>, ly: u8) {
         let lcdc_register = ppu_components::LcdcRegister::get_lcdc_register(&cpu.memory_bus);
-        let object_height: isize = if lcdc_register.obj_size { 16 } else { 8 };
-        
-        let objects_to_be_rendered = objects
-            .iter()
-            .filter_map(|object| {
-                let start_object_screen_y = (object.y as isize) - Self::Y_OFFSET;
-                let end_object_screen_y: isize = start_object_screen_y + object_height;
-                let start_object_screen_x = (object.x as isize) - 8;
-                let end_object_screen_x: isize = start_object_screen_x + 8;
-                
-                if (start_object_screen_y <= ly_isize && ly_isize < end_object_screen_y)
-                    && (end_object_screen_x >= 0 && start_object_screen_x < GAME_SECTION_WIDTH as isize)
-                {
-                    Some(*object)
-                } else {
-                    None
-                }
-            })
-            .collect::<Vec<Object>>();
+        let object_height = lcdc_register.obj_size();
+        let oam = Ppu::get_all_40_objects(&cpu.memory_bus);
 
-        cpu.ppu.objects_to_be_rendered = if objects_to_be_rendered.len() <= 10 {
-            objects_to_be_rendered
-        } else {
-            objects_to_be_rendered[..10].to_vec()
-        };
+        cpu.ppu.objects_to_be_rendered = Ppu::objects_on_scanline(&oam, ly, object_height).copied().collect();
     }
 
-    /// Get all 40 objects (sprites) from OAM (Object Attribute Memory).
-    fn get_all_40_objects(memory_bus: &cpu_components::MemoryBus) -> [Object; 40] {
-        let oam_memory = memory_bus.get_object_attribute_memory();
-
-        let objects = oam_memory
-            .chunks(4)
-            .map(|obj| Object {
-                y: obj[0],
-                x: obj[1],
-                tile_index: obj[2] as usize,
-                attributes: ObjectAttributes {
-                    priority: (obj[3] & 0b1000_0000) != 0,
-                    y_flip: (obj[3] & 0b0100_0000) != 0,
-                    x_flip: (obj[3] & 0b0010_0000) != 0,
-                    // FIX: Bit 4 selects the object palette, not bit 6 (which is y_flip)
-                    // Bit 7 = priority, Bit 6 = y_flip, Bit 5 = x_flip, Bit 4 = palette selection
-                    pallete: if (obj[3] & 0b0001_0000) != 0 {
-                        ObjectPallete::OBP1
-                    } else {
-                        ObjectPallete::OBP0
-                    },
-                },
-            })
-            .collect::<Vec<Object>>()
-            .try_into()
-            .unwrap();
+    /// Returns the objects shown on the scanline LY, in the order they have to be drawn.
+    /// Like the hardware, the first 10 objects in OAM whose rows cover LY are selected, the X position is not taken
+    /// into account, so objects outside the screen horizontally still count. They are then sorted so the ones with
+    /// the highest priority come last and are drawn over the others: the lower the X the higher the priority, and
+    /// for the same X the object stored first in OAM wins.
+    pub fn objects_on_scanline<'a>(
+        oam: &'a [Object; OAM_OBJECT_COUNT],
+        ly: u8,
+        sprite_height: u8,
+    ) -> impl Iterator<Item = &'a Object> + 'a {
+        let line = ly as u16 + Self::Y_OFFSET as u16;
+        let mut objects: Vec<(usize, &Object)> = oam
+            .iter()
+            .enumerate()
+            .filter(|(_, object)| line >= object.y as u16 && line < object.y as u16 + sprite_height as u16)
+            .take(MAX_OBJECTS_PER_SCANLINE)
+            .collect();
 
-        objects
+        objects.sort_by_key(|&(oam_index, object)| (core::cmp::Reverse(object.x), core::cmp::Reverse(oam_index)));
+        objects.into_iter().map(|(_, object)| object)
     }
 
-    fn render_line(cpu: &mut cpu::Cpu) {
-        let ly = cpu.memory_bus.read_byte(LY);
-        if ly >= 144 {
-            return;
-        }
-
-        let ly_usize = ly as usize;
-
-        Ppu::render_background_line_to_screen_buffer(cpu, ly_usize);
-
-        let lcdc = ppu_components::LcdcRegister::get_lcdc_register(&cpu.memory_bus);
-
-        Ppu::render_window_line_to_screen_buffer(cpu, ly_usize, &lcdc);
-
-        Ppu::render_objects_line_to_screen_buffer(cpu, ly_usize, &lcdc);
+    /// Get all 40 objects (sprites) from OAM (Object Attribute Memory).
+    fn get_all_40_objects(memory_bus: &impl MemoryAccess) -> [Object; OAM_OBJECT_COUNT] {
+        let mut objects = memory_bus.iter_objects();
+        core::array::from_fn(|_| objects.next().expect("OAM always holds 40 objects"))
     }
 
-    fn render_background_line_to_screen_buffer(cpu: &mut cpu::Cpu, ly_usize: usize) {
+    /// Renders the background pixels of the scanline into the screen and returns their color indexes (0-3) before the
+    /// palette is applied, which the objects need to know if they are hidden behind the background.
+    fn render_background_line_to_screen_buffer(
+        &mut self,
+        memory_bus: &impl MemoryAccess,
+        ly_usize: usize,
+        lcdc_register: &ppu_components::LcdcRegister,
+    ) -> [u8; GAME_SECTION_WIDTH] {
+        let mut bg_color_indexes = [0u8; GAME_SECTION_WIDTH];
+
         // FIX: Only calculate the single scanline instead of the entire 144x160 background buffer
         // This is much more efficient than regenerating all 23,040 pixels just to render 160
-        let lcdc_register = ppu_components::LcdcRegister::get_lcdc_register(&cpu.memory_bus);
 
         // When Bit 0 is cleared, both background and window become blank (white)
         if lcdc_register.bg_window_enable == false {
-            cpu.ppu.screen[ly_usize] = [0xFFFFFF; GAME_SECTION_WIDTH];
-            return;
+            self.screen[ly_usize] = [0xFFFFFF; GAME_SECTION_WIDTH];
+            return bg_color_indexes;
         }
 
-        let tiles = cpu.ppu.get_tiles(&cpu.memory_bus);
-        let bg_tile_map = cpu.ppu.get_bg_tile_map_as_grid_32x32(&cpu.memory_bus, &lcdc_register);
-        let bg_tiles = cpu.ppu.get_bg_and_window_tiles(&tiles, &lcdc_register);
-        let scy = cpu.memory_bus.get_scy_register() as usize;
-        let scx = cpu.memory_bus.get_scx_register() as usize;
+        let tiles = self.get_tiles(memory_bus);
+        let bg_tile_map = self.get_bg_tile_map_as_grid_32x32(memory_bus, lcdc_register);
+        let bg_tiles = self.get_bg_and_window_tiles(&tiles, lcdc_register);
+        let scy = memory_bus.get_scy_register() as usize;
+        let scx = memory_bus.get_scx_register() as usize;
 
         // Calculate which row in the 256x256 background map we need
         let bg_row = (scy + ly_usize) % BG_AND_WINDOW_MAP_SCREEN_SIZE;
@@ -480,7 +688,8 @@ impl Ppu {
         let tile_pixel_row = bg_row % 8;
 
         // Render this scanline pixel by pixel
-        for screen_col in 0..GAME_SECTION_WIDTH {
+        let screen_row = self.screen[ly_usize].iter_mut();
+        for (screen_col, (color_index, pixel)) in bg_color_indexes.iter_mut().zip(screen_row).enumerate() {
             let bg_col = (scx + screen_col) % BG_AND_WINDOW_MAP_SCREEN_SIZE;
             let tile_map_col = bg_col / 8;
             let tile_pixel_col = bg_col % 8;
@@ -488,118 +697,139 @@ impl Ppu {
             let tile_index = bg_tile_map[tile_map_row][tile_map_col] as usize;
             let tile = &bg_tiles[tile_index];
 
-            let color_pallete_value = match tile.pixels[tile_pixel_row][tile_pixel_col] {
-                TilePixelValue::Zero => 0,
-                TilePixelValue::One => 1,
-                TilePixelValue::Two => 2,
-                TilePixelValue::Three => 3,
-            };
-
-            let bgp_register = cpu.memory_bus.read_byte(BGP);
-            let palette_index = ((bgp_register >> (color_pallete_value * 2)) & 0b11) as usize;
-            let color = COLORS[palette_index];
-            cpu.ppu.screen[ly_usize][screen_col] = color;
+            let color_pallete_value = tile.pixels[tile_pixel_row][tile_pixel_col] as u8;
+            *color_index = color_pallete_value;
+            *pixel = COLORS[memory_bus.decode_bg_palette(color_pallete_value)];
         }
+
+        bg_color_indexes
     }
-        
-    fn render_window_line_to_screen_buffer(_cpu: &mut cpu::Cpu, _ly_usize: usize, lcdc: &ppu_components::LcdcRegister) {
-        if lcdc.window_enable {
+
+    /// Draws the window over the background pixels of the scanline LY, and replaces their color indexes with the
+    /// window ones so the objects are hidden behind the window too. The top-left pixel of the window is shown at
+    /// the screen column WX - 7 and the row WY, the row of the window drawn is `window_internal_line`.
+    /// On DMG the window is hidden with the background when LCDC bit 0 is cleared.
+    pub fn render_window_for_scanline(
+        &mut self,
+        ly: u8,
+        line: &mut [u32; GAME_SECTION_WIDTH],
+        bg_color_indexes: &mut [u8; GAME_SECTION_WIDTH],
+        memory_bus: &impl MemoryAccess,
+    ) {
+        let lcdc = ppu_components::LcdcRegister::get_lcdc_register(memory_bus);
+        let wy = memory_bus.get_wy_register();
+        let wx = memory_bus.get_wx_register();
+        // WX values from 167 put the window past the right border
+        if !lcdc.bg_window_enable || !lcdc.window_enable || ly < wy || wx as usize >= GAME_SECTION_WIDTH + 7 {
+            return;
         }
+
+        let tiles = self.get_tiles(memory_bus);
+        let window_tiles = self.get_bg_and_window_tiles(&tiles, &lcdc);
+        let window_tile_map = self.get_window_tile_map_as_grid_32x32(memory_bus, &lcdc);
+
+        let window_line = self.window_internal_line as usize;
+        let window_row = Self::get_window_row_color_indexes(&window_tile_map, &window_tiles, window_line, wx);
+        for (col, color_index) in window_row.into_iter().enumerate() {
+            if let Some(color_index) = color_index {
+                bg_color_indexes[col] = color_index;
+                line[col] = COLORS[memory_bus.decode_bg_palette(color_index)];
+            }
+        }
+
+        self.window_internal_line = self.window_internal_line.wrapping_add(1);
     }
-    
-    fn render_objects_line_to_screen_buffer(cpu: &mut cpu::Cpu, ly_usize: usize, lcdc: &ppu_components::LcdcRegister) {
-        if lcdc.obj_enable {
-            let tiles = cpu.ppu.get_tiles(&cpu.memory_bus);
-            let objects = &cpu.ppu.objects_to_be_rendered;
-            let obp0_register = cpu.memory_bus.read_byte(OBP0);
-            let obp1_register = cpu.memory_bus.read_byte(OBP1);
-            
-            for object in objects {
-                // FIX: Use signed arithmetic to properly handle sprites with y < Y_OFFSET (partially off-screen top)
-                let start_object_screen_y = (object.y as isize) - Self::Y_OFFSET;
-                // FIX: Use signed arithmetic to properly handle sprites with x < 8 (partially off-screen left)
-                let start_object_screen_x = (object.x as isize) - 8;
-                
-                // Determine the object height (8x8 or 8x16 based on LCDC)
-                let object_height: isize = if lcdc.obj_size { 16 } else { 8 };
-                
-                // Calculate which row of the sprite we're on
-                let ly_isize = ly_usize as isize;
-                let mut tile_row = (ly_isize - start_object_screen_y) as usize;
-                
-                // Handle y-flip: if y_flip is set, flip the tile row index
-                if object.attributes.y_flip {
-                    tile_row = (object_height as usize) - 1 - tile_row;
-                }
-                
-                // Get the correct tile for 8x16 objects
-                let tile_index = if lcdc.obj_size && tile_row >= 8 {
-                    // For 8x16 sprites, if we're in the lower half (rows 8-15), use the next tile
-                    (object.tile_index | 1) as usize // Set bit 0 to get the second tile
-                } else {
-                    object.tile_index as usize
+
+    /// Returns the color indexes (0-3) of the row `window_line` of the window at each screen column, None for the
+    /// columns at the left of the window (screen column + 7 < WX).
+    fn get_window_row_color_indexes(
+        window_tile_map: &[[u8; 32]; 32],
+        window_tiles: &[Tile; 256],
+        window_line: usize,
+        wx: u8,
+    ) -> [Option<u8>; GAME_SECTION_WIDTH] {
+        let tile_map_row = (window_line / 8) % BG_AND_WINDOW_TILE_COUNT_PER_ROW_COL;
+
+        core::array::from_fn(|screen_col| {
+            let window_col = (screen_col + 7).checked_sub(wx as usize)?;
+            let tile_index = window_tile_map[tile_map_row][window_col / 8] as usize;
+            Some(window_tiles[tile_index].pixels[window_line % 8][window_col % 8] as u8)
+        })
+    }
+
+    /// Draws the objects selected for the scanline LY (`objects_to_be_rendered`) over the background and window
+    /// pixels of the line. `bg_color_indexes` holds the color indexes (0-3) of those pixels before the palette is
+    /// applied: the objects with the priority bit set are hidden behind the indexes 1-3 and only drawn over index 0.
+    /// The color 0 of the objects is transparent. Where objects overlap, the pixel of the object with the highest
+    /// priority is kept even when it is hidden behind the background, so the objects below it don't show through.
+    pub fn render_sprites_for_scanline(
+        &self,
+        ly: u8,
+        bg_line: &mut [u32; GAME_SECTION_WIDTH],
+        bg_color_indexes: &[u8; GAME_SECTION_WIDTH],
+        memory_bus: &impl MemoryAccess,
+    ) {
+        let lcdc = ppu_components::LcdcRegister::get_lcdc_register(memory_bus);
+        if !lcdc.obj_enable {
+            return;
+        }
+
+        let object_height = lcdc.obj_size();
+        // Set once an object has a pixel at the column, the objects with a lower priority can't draw there
+        let mut is_column_taken = [false; GAME_SECTION_WIDTH];
+
+        // The objects are sorted so the one with the highest priority comes last
+        for object in self.objects_to_be_rendered.iter().rev() {
+            let Some(row_pixels) = Self::get_object_row_pixels(object, ly, object_height, memory_bus) else {
+                continue;
+            };
+
+            for (tile_col, &color_index) in row_pixels.iter().enumerate() {
+                // X is the screen position + 8, so objects can be partially hidden on the left
+                let Some(screen_x) = (object.x as usize + tile_col).checked_sub(8) else {
+                    continue;
                 };
-                
-                let actual_tile_row = tile_row % 8; // Get row within the tile (0-7)
-                
-                // FIX: Bounds check to prevent out-of-bounds access
-                // Sprite tiles are stored in 0x8000-0x8FFF (384 tiles max)
-                if tile_index >= tiles.len() {
-                    continue;  // Skip invalid sprite tile
+                if screen_x >= GAME_SECTION_WIDTH || color_index == 0 || is_column_taken[screen_x] {
+                    continue;
                 }
-                
-                let tile = tiles[tile_index];
-                
-                for tile_col in 0..8 {
-                    let screen_x = start_object_screen_x + tile_col as isize;
-                    if screen_x < 0 || screen_x >= GAME_SECTION_WIDTH as isize {
-                        continue; // Skip pixels outside the screen bounds
-                    }
 
-                    // Handle x-flip: if x_flip is set, flip the tile column index
-                    let actual_tile_col = if object.attributes.x_flip {
-                        7 - tile_col
-                    } else {
-                        tile_col
-                    };
-                    
-                    let pixel_value = match tile.pixels[actual_tile_row][actual_tile_col] {
-                        TilePixelValue::Zero => 0,
-                        TilePixelValue::One => 1,
-                        TilePixelValue::Two => 2,
-                        TilePixelValue::Three => 3,
-                    };
-                    
-                    if pixel_value == 0 {
-                        continue; // Color 0 is transparent for sprites
-                    }
-
-                    // FIX: Apply the object palette register to get the actual color
-                    // Palette registers map 2-bit pixel values (0-3) to actual colors (0-3)
-                    let palette_register = match object.attributes.pallete {
-                        ObjectPallete::OBP0 => obp0_register,
-                        ObjectPallete::OBP1 => obp1_register,
-                    };
-                    
-                    // Extract the color from the palette: each 2 bits represent one color mapping
-                    // Bits 7-6 = color 3, Bits 5-4 = color 2, Bits 3-2 = color 1, Bits 1-0 = color 0
-                    let palette_index = ((palette_register >> (pixel_value * 2)) & 0b11) as usize;
-                    let color = COLORS[palette_index];
-                    
-                    // FIX: Respect object priority
-                    // If priority bit is set, only render if background is color 0 (white)
-                    if object.attributes.priority {
-                        // Get the background color at this position
-                        let bg_color = cpu.ppu.screen[ly_usize][screen_x as usize];
-                        // Only render if background is white (color 0 = 0xFFFFFF)
-                        if bg_color != COLORS[0] {
-                            continue; // Skip rendering, background has priority
-                        }
-                    }
-                    
-                    cpu.ppu.screen[ly_usize][screen_x as usize] = color;
-                } 
-            } 
+                is_column_taken[screen_x] = true;
+                if object.attributes.priority && bg_color_indexes[screen_x] != 0 {
+                    continue;
+                }
+                bg_line[screen_x] = COLORS[memory_bus.decode_obp_palette(object.attributes.pallete as u8, color_index)];
+            }
         }
     }
+
+    /// Returns the color indexes (0-3) of the 8 pixels of the object on the scanline LY, from left to right with the X
+    /// and Y flips applied. None when the object doesn't cover the scanline.
+    fn get_object_row_pixels(
+        object: &Object,
+        ly: u8,
+        object_height: u8,
+        memory_bus: &impl MemoryAccess,
+    ) -> Option<[u8; 8]> {
+        // Y is the screen position + 16, so objects can be partially hidden at the top
+        let row = (ly as usize + Self::Y_OFFSET as usize)
+            .checked_sub(object.y as usize)
+            .filter(|&row| row < object_height as usize)?;
+        let row = if object.attributes.y_flip { object_height as usize - 1 - row } else { row };
+
+        // 8x16 objects ignore bit 0 of the tile index: the top 8 rows use the even tile, the bottom 8 rows the odd one
+        let tile_index = match (object_height, row) {
+            (16, 0..8) => object.tile_index & 0xFE,
+            (16, _) => object.tile_index | 0x01,
+            _ => object.tile_index,
+        };
+        // Objects always use the $8000 addressing mode, each tile row is stored in 2 bytes
+        let address = VRAM_START + (tile_index * 16 + (row % 8) * 2) as u16;
+        let low_byte = memory_bus.read_mapped_byte(address);
+        let high_byte = memory_bus.read_mapped_byte(address + 1);
+
+        Some(core::array::from_fn(|col| {
+            let bit = if object.attributes.x_flip { col } else { 7 - col };
+            (((high_byte >> bit) & 0x01) << 1) | ((low_byte >> bit) & 0x01)
+        }))
+    }
 }
@@ -1,6 +1,6 @@
 use core::fmt;
 
-use crate::gameboy_core::{constants::*, cpu_components};
+use crate::gameboy_core::{constants::*, cpu_components::MemoryAccess};
 
 #[derive(Copy,Clone,fmt::Debug)]
 pub enum TilePixelValue {
@@ -21,8 +21,38 @@ impl Tile {
             pixels: [[TilePixelValue::Zero; 8]; 8],
         }
     }
+
+    /// Renders the tile to a flat RGBA byte array (8x8 pixels, 4 bytes each), row by row.
+    /// Each pixel value is used as index in the palette, whose colors are 0xRRGGBB values. Alpha is always 0xFF.
+    pub fn to_rgba_buffer(&self, palette: &[u32; 4]) -> [u8; 256] {
+        let mut buffer = [0u8; 256];
+        for (pixel_index, pixel) in self.pixels.iter().flatten().enumerate() {
+            let [_, red, green, blue] = palette[*pixel as usize].to_be_bytes();
+            buffer[pixel_index * 4..pixel_index * 4 + 4].copy_from_slice(&[red, green, blue, 0xFF]);
+        }
+        buffer
+    }
+
+    /// Renders the tile to a flat RGB byte array (8x8 pixels, 3 bytes each), row by row.
+    /// Each pixel value is used as index in the palette, whose colors are 0xRRGGBB values.
+    pub fn to_rgb_buffer(&self, palette: &[u32; 4]) -> [u8; 192] {
+        let mut buffer = [0u8; 192];
+        for (pixel_index, pixel) in self.pixels.iter().flatten().enumerate() {
+            let [_, red, green, blue] = palette[*pixel as usize].to_be_bytes();
+            buffer[pixel_index * 3..pixel_index * 3 + 3].copy_from_slice(&[red, green, blue]);
+        }
+        buffer
+    }
+}
+
+/// Maps a color index (0-3) of a tile pixel to its shade (0-3), the index in COLORS, with a palette register (BGP,
+/// OBP0 or OBP1). Each shade takes 2 bits of the register: bits 7-6 = color 3, bits 5-4 = color 2,
+/// bits 3-2 = color 1 and bits 1-0 = color 0. E.g. 0xE4 (11 10 01 00) keeps the indexes and 0x1B reverses them.
+pub fn decode_palette(palette_register: u8, color_index: u8) -> usize {
+    ((palette_register >> ((color_index & 0b11) * 2)) & 0b11) as usize
 }
 
+#[derive(Debug, Clone, Copy, PartialEq)]
 pub struct LcdcRegister {
     /// This bit controls whether the LCD is on and the PPU is active. 
     /// Setting it to 0 turns both off, which grants immediate and full access to VRAM, OAM, etc.
@@ -64,9 +94,12 @@ pub struct LcdcRegister {
 
 impl LcdcRegister {
     /// Reads the LCDC register from the memory bus and returns an instance of LcdcRegister with the corresponding flags set.
-    pub fn get_lcdc_register(memory_bus: &cpu_components::MemoryBus) -> Self {
-        let lcdc_value = memory_bus.get_lcdc_register();
-        
+    pub fn get_lcdc_register(memory_bus: &impl MemoryAccess) -> Self {
+        Self::new_from_byte(memory_bus.get_lcdc_register())
+    }
+
+    /// Parses the value of the LCDC register, bit 7 is lcd_ppu_enabled and bit 0 bg_window_enable.
+    pub fn new_from_byte(lcdc_value: u8) -> Self {
         Self {
             lcd_ppu_enabled: (lcdc_value & 0b1000_0000) != 0,
             window_tile_map_area: (lcdc_value & 0b0100_0000) != 0,
@@ -78,6 +111,24 @@ impl LcdcRegister {
             bg_window_enable: (lcdc_value & 0b0000_0001) != 0,
         }
     }
+
+    /// Packs the flags back into the LCDC register value, the inverse of new_from_byte.
+    pub fn to_byte(&self) -> u8 {
+        ((self.lcd_ppu_enabled as u8) << 7)
+            | ((self.window_tile_map_area as u8) << 6)
+            | ((self.window_enable as u8) << 5)
+            | ((self.bg_window_tile_data_area as u8) << 4)
+            | ((self.bg_tile_map_area as u8) << 3)
+            | ((self.obj_size as u8) << 2)
+            | ((self.obj_enable as u8) << 1)
+            | (self.bg_window_enable as u8)
+    }
+
+    /// Height of the objects in pixels: 8, or 16 when LCDC bit 2 is set and each object shows 2 stacked tiles.
+    pub fn obj_size(&self) -> u8 {
+        if self.obj_size { 16 } else { 8 }
+    }
+
     /// Returns the memory address range the BG and Window use to pick up tiles.
     /// When bg_window_tiles is true, returns the address range from 0x8000 to 0x8FFF.
     /// When false, returns the address range from 0x8800 to 0x97FF.
@@ -86,7 +86,37 @@ pub const WY: u16 = 0xFF4A;
 /// WX specifies the X position of the window on the screen.
 pub const WX: u16 = 0xFF4B;
 
+/// KEY1 prepares the CPU speed switch (GBC only). Bit 0 is set to request the switch, which happens on the next STOP,
+/// and bit 7 reads the current speed (0 = normal, 1 = double).
+pub const KEY1: u16 = 0xFF4D;
+
+/// HDMA1 and HDMA2 hold the high and low bytes of the VRAM DMA source address (GBC only).
+/// The lower 4 bits of the address are ignored, so transfers are always aligned to 16 bytes.
+pub const HDMA1: u16 = 0xFF51;
+pub const HDMA2: u16 = 0xFF52;
+
+/// HDMA3 and HDMA4 hold the high and low bytes of the VRAM DMA destination address (GBC only).
+/// Only bits 12-4 are used, the destination is always inside VRAM ($8000-$9FF0).
+pub const HDMA3: u16 = 0xFF53;
+pub const HDMA4: u16 = 0xFF54;
+
+/// HDMA5 starts a VRAM DMA transfer. Bit 7 selects the mode (0 = General-Purpose DMA, 1 = H-Blank DMA)
+/// and bits 0-6 hold the transfer length divided by 16, minus 1.
+pub const HDMA5: u16 = 0xFF55;
+
+/// SVBK selects the Work RAM bank mapped at $D000-$DFFF (GBC only). Bits 0-2 hold the bank number,
+/// writing 0 selects bank 1.
+pub const SVBK: u16 = 0xFF70;
+
 /// The IE flag is used to control interrupts.
 pub const IE: u16 = 0xFFFF;
 
-
+/// Condition codes of the conditional JP, JR, CALL and RET instructions, encoded in bits 4-3 of the opcode.
+/// NZ: the Z flag is reset.
+pub const CC_NZ: u8 = 0b00;
+/// Z: the Z flag is set.
+pub const CC_Z: u8 = 0b01;
+/// NC: the C flag is reset.
+pub const CC_NC: u8 = 0b10;
+/// C: the C flag is set.
+pub const CC_C: u8 = 0b11;
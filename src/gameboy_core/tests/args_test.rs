@@ -0,0 +1,50 @@
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    use crate::args::{Args, parse_args_from};
+
+    #[test]
+    fn test_parse_args_with_only_the_rom() {
+        let args = parse_args_from(["gameboy_emulator_rust", "games/Tetris.gb"]).unwrap();
+
+        assert_eq!(
+            args,
+            Args {
+                rom: Some(PathBuf::from("games/Tetris.gb")),
+                debug: false,
+                scale: None,
+                no_audio: false,
+            }
+        );
+    }
+
+    #[test]
+    fn test_parse_args_with_all_options() {
+        let args = parse_args_from(["gameboy_emulator_rust", "--debug", "--scale", "2", "--no-audio", "rom.gb"]).unwrap();
+
+        assert_eq!(args.rom, Some(PathBuf::from("rom.gb")));
+        assert!(args.debug);
+        assert_eq!(args.scale, Some(2));
+        assert!(args.no_audio);
+
+        let args = parse_args_from(["gameboy_emulator_rust", "rom.gb", "-d"]).unwrap();
+        assert!(args.debug, "-d is the short form of --debug");
+    }
+
+    #[test]
+    fn test_parse_args_without_rom() {
+        let args = parse_args_from(["gameboy_emulator_rust", "--scale", "2"]).unwrap();
+
+        assert_eq!(args.rom, None, "The ROM picker is shown when no ROM is given");
+        assert_eq!(args.scale, Some(2));
+    }
+
+    #[test]
+    fn test_parse_args_rejects_invalid_arguments() {
+        assert!(parse_args_from(["gameboy_emulator_rust", "rom.gb", "--scale", "0"]).is_err());
+        assert!(parse_args_from(["gameboy_emulator_rust", "rom.gb", "--scale", "5"]).is_err());
+        assert!(parse_args_from(["gameboy_emulator_rust", "rom.gb", "--scale", "two"]).is_err());
+        assert!(parse_args_from(["gameboy_emulator_rust", "rom.gb", "--unknown"]).is_err());
+    }
+}
@@ -1,6 +1,15 @@
 #[cfg(test)]
 mod tests {
-    use crate::gameboy_core::cpu::Cpu;
+    use crate::gameboy_core::{
+        constants::VBLANK_INTERRUT_HANDLER_ADDRESS,
+        cpu::Cpu,
+        cpu_components::MemoryAccess,
+        registers_contants::IF,
+        tests::{
+            cpu_state_snapshot::{CpuStateSnapshot, assert_cpu_state_eq},
+            mock_cartridge::cpu_with_code,
+        },
+    };
 
     #[test]
     fn test_di_instruction() {
@@ -44,6 +53,82 @@ mod tests {
         assert_eq!(cpu.di_instruction_pending, false, "di_instruction_pending should be reset to false");
     }
 
+    #[test]
+    fn test_ei_enables_ime_after_the_next_instruction() {
+        // EI; NOP; NOP
+        let mut cpu = cpu_with_code(&[0xFB, 0x00, 0x00]);
+        cpu.ime = false;
+
+        cpu.tick();
+        assert!(!cpu.ime, "IME should not be enabled by EI itself");
+        assert!(cpu.ei_instruction_pending);
+
+        cpu.tick();
+        assert!(cpu.ime, "IME should be enabled after the NOP that follows EI");
+        assert!(!cpu.ei_instruction_pending);
+        assert_eq!(cpu.registers.pc, 0x0102);
+    }
+
+    #[test]
+    fn test_interrupt_is_not_serviced_during_the_instruction_after_ei() {
+        // EI; INC A; NOP
+        let mut cpu = cpu_with_code(&[0xFB, 0x3C, 0x00]);
+        cpu.registers.a = 0;
+        cpu.registers.sp = 0xDFFE;
+        cpu.ime = false;
+        cpu.memory_bus.set_ie_register(0x01);
+        cpu.memory_bus.write_byte(IF, 0x01);
+
+        cpu.tick();
+        cpu.tick();
+        assert_eq!(cpu.registers.a, 1, "The instruction after EI should be executed before the interrupt");
+        assert_eq!(cpu.registers.pc, 0x0102);
+
+        cpu.tick();
+        assert_eq!(cpu.registers.pc, VBLANK_INTERRUT_HANDLER_ADDRESS);
+        assert!(!cpu.ime);
+        assert_eq!(cpu.pop_value_from_sp(), 0x0102);
+    }
+
+    /// Creates a CPU about to execute RETI, with IME disabled and the return address 0x1234 on the stack.
+    fn create_cpu_before_reti() -> Cpu {
+        let mut cpu = cpu_with_code(&[0xD9]);
+        cpu.registers.sp = 0xDFFC;
+        cpu.memory_bus.write_byte(0xDFFC, 0x34);
+        cpu.memory_bus.write_byte(0xDFFD, 0x12);
+        cpu.ime = false;
+        cpu
+    }
+
+    #[test]
+    fn test_reti_returns_and_enables_ime_immediately() {
+        let mut cpu = create_cpu_before_reti();
+        let clock_cycles = cpu.clock_cycles;
+
+        cpu.tick();
+
+        assert_eq!(cpu.registers.pc, 0x1234);
+        assert_eq!(cpu.registers.sp, 0xDFFE);
+        assert!(cpu.ime, "RETI doesn't have the delay of EI");
+        assert!(!cpu.ei_instruction_pending);
+        assert_eq!(cpu.clock_cycles - clock_cycles, 16);
+    }
+
+    #[test]
+    fn test_interrupt_pending_during_reti_is_serviced_on_the_next_tick() {
+        let mut cpu = create_cpu_before_reti();
+        cpu.memory_bus.set_ie_register(0x01);
+        cpu.memory_bus.write_byte(IF, 0x01);
+
+        cpu.tick();
+        assert_eq!(cpu.registers.pc, 0x1234);
+
+        cpu.tick();
+        assert_eq!(cpu.registers.pc, VBLANK_INTERRUT_HANDLER_ADDRESS);
+        assert!(!cpu.ime);
+        assert_eq!(cpu.pop_value_from_sp(), 0x1234, "no instruction is executed at the return address first");
+    }
+
     #[test]
     fn test_call_imm16_basic() {
         let mut cpu = Cpu::new();
@@ -1252,5 +1337,73 @@ mod tests {
             );
         }
     }
-}
 
+    #[test]
+    fn test_call_then_ret_snapshots() {
+        // 0x0100: CALL 0x0200 / 0x0103: NOP ... 0x0200: RET
+        let mut cpu = cpu_with_code(&[0xCD, 0x00, 0x02, 0x00]);
+        cpu.memory_bus.write_byte(0x0200, 0xC9);
+        cpu.registers.sp = 0xFFFE;
+        let before_call = CpuStateSnapshot::from_cpu(&cpu);
+
+        cpu.tick();
+        let after_call = CpuStateSnapshot { pc: 0x0200, sp: 0xFFFC, ..before_call };
+        assert_cpu_state_eq(&cpu, &after_call);
+
+        cpu.tick();
+        let after_ret = CpuStateSnapshot { pc: 0x0103, sp: 0xFFFE, ..before_call };
+        assert_cpu_state_eq(&cpu, &after_ret);
+    }
+
+    #[test]
+    fn test_call_cc_not_taken_snapshot_only_moves_pc() {
+        // CALL Z,0x0200 with Z reset doesn't touch the stack
+        let mut cpu = cpu_with_code(&[0xCC, 0x00, 0x02]);
+        cpu.registers.sp = 0xFFFE;
+        cpu.registers.flags.z = false;
+        let before_call = CpuStateSnapshot::from_cpu(&cpu);
+
+        cpu.tick();
+
+        let expected = CpuStateSnapshot { pc: 0x0103, ..before_call };
+        assert_cpu_state_eq(&cpu, &expected);
+        assert_eq!(cpu.registers, expected.to_registers());
+    }
+
+    #[test]
+    fn test_ret_cc_every_condition_via_tick() {
+        // (opcode, z flag, c flag, is taken)
+        let scenarios = [
+            (0xC0, false, false, true), // RET NZ
+            (0xC0, true, false, false),
+            (0xC8, true, false, true), // RET Z
+            (0xC8, false, false, false),
+            (0xD0, false, false, true), // RET NC
+            (0xD0, false, true, false),
+            (0xD8, false, true, true), // RET C
+            (0xD8, false, false, false),
+        ];
+
+        for (opcode, z_flag, c_flag, is_taken) in scenarios {
+            let mut cpu = cpu_with_code(&[opcode]);
+            cpu.registers.sp = 0xFFFC;
+            cpu.memory_bus.write_byte(0xFFFC, 0x03);
+            cpu.memory_bus.write_byte(0xFFFD, 0x80);
+            cpu.registers.flags.z = z_flag;
+            cpu.registers.flags.c = c_flag;
+            let before_ret = CpuStateSnapshot::from_cpu(&cpu);
+
+            cpu.tick();
+
+            let expected = if is_taken {
+                CpuStateSnapshot { pc: 0x8003, sp: 0xFFFE, ..before_ret }
+            } else {
+                CpuStateSnapshot { pc: before_ret.pc + 1, ..before_ret }
+            };
+            assert_eq!(
+                CpuStateSnapshot::from_cpu(&cpu), expected,
+                "RET cc 0x{:02X} with Z={} C={}", opcode, z_flag, c_flag
+            );
+        }
+    }
+}
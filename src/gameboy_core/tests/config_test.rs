@@ -0,0 +1,79 @@
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    use crate::{args::parse_args_from, config::Config};
+
+    fn create_custom_config() -> Config {
+        let mut config = Config {
+            scale: 2,
+            palette_index: 3,
+            audio_volume: 0.5,
+            save_directory: PathBuf::from("/tmp/gameboy_saves"),
+            scanline_opacity: 0.75,
+            ..Config::default()
+        };
+        config.key_bindings.insert("A".to_string(), "K".to_string());
+        config
+    }
+
+    #[test]
+    fn test_config_round_trip_through_toml() {
+        let config = create_custom_config();
+
+        let content = toml::to_string(&config).unwrap();
+        let parsed: Config = toml::from_str(&content).unwrap();
+
+        assert_eq!(parsed, config);
+    }
+
+    #[test]
+    fn test_config_round_trip_through_file() {
+        let path = std::env::temp_dir()
+            .join(format!("gameboy_emulator_config_test_{}", std::process::id()))
+            .join("config.toml");
+        let config = create_custom_config();
+
+        config.save_to_file(&path).unwrap();
+        let loaded = Config::load_from_file(&path).unwrap();
+        std::fs::remove_dir_all(path.parent().unwrap()).unwrap();
+
+        assert_eq!(loaded, config);
+    }
+
+    #[test]
+    fn test_missing_entries_take_default_values() {
+        let config: Config = toml::from_str("palette_index = 4").unwrap();
+
+        assert_eq!(config.palette_index, 4);
+        assert_eq!(config.scale, Config::default().scale);
+        assert_eq!(config.key_bindings, Config::default().key_bindings);
+    }
+
+    #[test]
+    fn test_load_from_invalid_file_returns_error() {
+        let path = std::env::temp_dir().join(format!("gameboy_emulator_invalid_config_{}.toml", std::process::id()));
+        std::fs::write(&path, "scale = \"big\"").unwrap();
+
+        let result = Config::load_from_file(&path);
+        std::fs::remove_file(&path).unwrap();
+
+        assert!(result.unwrap_err().to_string().starts_with("Invalid config file"));
+        assert!(Config::load_from_file(&path).unwrap_err().to_string().starts_with("Could not access the config file"));
+    }
+
+    #[test]
+    fn test_command_line_arguments_take_precedence_over_config() {
+        let mut config = create_custom_config();
+        config.merge_args(&parse_args_from(["gameboy_emulator_rust", "rom.gb", "--scale", "4", "--no-audio"]).unwrap());
+
+        assert_eq!(config.scale, 4);
+        assert_eq!(config.audio_volume, 0.0);
+        assert_eq!(config.palette_index, 3, "Values not given as arguments are kept");
+
+        let mut config = create_custom_config();
+        config.merge_args(&parse_args_from(["gameboy_emulator_rust", "rom.gb"]).unwrap());
+
+        assert_eq!(config, create_custom_config(), "Config values are kept when there are no arguments");
+    }
+}
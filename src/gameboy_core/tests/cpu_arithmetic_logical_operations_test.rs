@@ -206,8 +206,11 @@ mod tests {
         cpu.registers.c = 0xCD; // low byte
 
         let opcode = 0b11000101; // PUSH BC (0xC5)
+        let cycles_before = cpu.clock_cycles;
         cpu.execute(opcode);
 
+        assert_eq!(cpu.clock_cycles - cycles_before, 16, "PUSH rr should take 16 T-cycles");
+
         // After push, SP should be decremented by 2
         assert_eq!(cpu.registers.sp, 0xFFFC, "SP should be decremented by 2 after PUSH");
 
@@ -233,8 +236,11 @@ mod tests {
         cpu.memory_bus.write_byte(0xFFFD, 0x3C); // high byte (B)
 
         let opcode = 0b11000001; // POP BC (0xC1)
+        let cycles_before = cpu.clock_cycles;
         cpu.execute(opcode);
 
+        assert_eq!(cpu.clock_cycles - cycles_before, 12, "POP rr should take 12 T-cycles");
+
         // After pop, SP should be incremented by 2
         assert_eq!(cpu.registers.sp, 0xFFFE, "SP should be incremented by 2 after POP");
 
@@ -243,6 +249,38 @@ mod tests {
         assert_eq!(cpu.registers.c, 0x5F, "C should contain low byte popped from stack");
     }
 
+    #[test]
+    fn test_push_af_onto_memory_stack() {
+        let mut cpu = Cpu::new();
+        cpu.registers.sp = 0xFFFE;
+        cpu.registers.a = 0x12;
+        cpu.registers.flags.set_flags_from_u8(0b1010_0000); // Z and H set
+
+        let cycles_before = cpu.clock_cycles;
+        cpu.execute(0xF5); // PUSH AF
+
+        assert_eq!(cpu.clock_cycles - cycles_before, 16, "PUSH AF should take 16 T-cycles");
+        assert_eq!(cpu.registers.sp, 0xFFFC);
+        assert_eq!(cpu.memory_bus.read_byte(0xFFFC), 0b1010_0000, "Memory at 0xFFFC should contain F");
+        assert_eq!(cpu.memory_bus.read_byte(0xFFFD), 0x12, "Memory at 0xFFFD should contain A");
+    }
+
+    #[test]
+    fn test_pop_af_from_memory_stack() {
+        let mut cpu = Cpu::new();
+        cpu.registers.sp = 0xFFFC;
+        cpu.memory_bus.write_byte(0xFFFC, 0b0101_1111); // N and C set, the lower 4 bits of F are always 0
+        cpu.memory_bus.write_byte(0xFFFD, 0x3C);
+
+        let cycles_before = cpu.clock_cycles;
+        cpu.execute(0xF1); // POP AF
+
+        assert_eq!(cpu.clock_cycles - cycles_before, 12, "POP AF should take 12 T-cycles");
+        assert_eq!(cpu.registers.sp, 0xFFFE);
+        assert_eq!(cpu.registers.a, 0x3C);
+        assert_eq!(cpu.registers.flags.get_flags_as_u8(), 0b0101_0000);
+    }
+
     #[test]
     fn test_add_hl_r16_examples() {
         // Example 1: When HL = 8A23h, BC = 0605h,
@@ -292,6 +330,51 @@ mod tests {
         assert_eq!(cpu.registers.flags.z, false, "Z flag should be 0");
     }
 
+    /// Runs ADD SP, imm8 with all the flags set before, so it can be checked Z and N are always cleared.
+    /// Returns the new SP and the H and C flags.
+    fn execute_add_sp_imm8(sp: u16, imm8: u8) -> (u16, bool, bool) {
+        let mut cpu = Cpu::new();
+        cpu.registers.sp = sp;
+        cpu.registers.flags.set_flags_from_u8(0xF0);
+        cpu.memory_bus.write_byte(cpu.registers.pc, imm8);
+
+        cpu.execute(0xE8); // ADD SP, imm8
+
+        assert!(!cpu.registers.flags.z, "Z flag should always be 0 (SP=0x{:04X}, imm8=0x{:02X})", sp, imm8);
+        assert!(!cpu.registers.flags.n, "N flag should always be 0 (SP=0x{:04X}, imm8=0x{:02X})", sp, imm8);
+        (cpu.registers.sp, cpu.registers.flags.h, cpu.registers.flags.c)
+    }
+
+    #[test]
+    fn test_add_sp_imm8_positive_offset_with_half_carry() {
+        // 0x0F + 0x01 carries from bit 3 of the low byte
+        assert_eq!(execute_add_sp_imm8(0x000F, 0x01), (0x0010, true, false));
+        // 0xFF + 0x01 carries from bit 3 and bit 7 of the low byte
+        assert_eq!(execute_add_sp_imm8(0x00FF, 0x01), (0x0100, true, true));
+    }
+
+    #[test]
+    fn test_add_sp_imm8_positive_offset_without_half_carry() {
+        assert_eq!(execute_add_sp_imm8(0x1000, 0x10), (0x1010, false, false));
+        assert_eq!(execute_add_sp_imm8(0xC0F0, 0x7F), (0xC16F, false, true));
+    }
+
+    #[test]
+    fn test_add_sp_imm8_negative_offset() {
+        // 0xFE is -2, the flags come from the unsigned low byte addition 0xF8 + 0xFE
+        assert_eq!(execute_add_sp_imm8(0xFFF8, 0xFE), (0xFFF6, true, true));
+        // 0x80 is -128, 0x00 + 0x80 doesn't carry
+        assert_eq!(execute_add_sp_imm8(0xD000, 0x80), (0xCF80, false, false));
+    }
+
+    #[test]
+    fn test_add_sp_imm8_wraps_around() {
+        // SP = 0x0000 - 1 wraps to 0xFFFF
+        assert_eq!(execute_add_sp_imm8(0x0000, 0xFF), (0xFFFF, false, false));
+        // SP = 0xFFFF + 1 wraps to 0x0000, Z is still 0
+        assert_eq!(execute_add_sp_imm8(0xFFFF, 0x01), (0x0000, true, true));
+    }
+
     #[test]
     fn test_inc_r16() {
         // INC DE ; DE ← 2360h
@@ -0,0 +1,197 @@
+#[cfg(test)]
+mod tests {
+    use crate::gameboy_core::{
+        cpu::{Cpu, TickResult},
+        registers_contants::IF,
+        tests::mock_cartridge::cpu_with_code,
+    };
+
+    const CCF: u8 = 0x3F;
+    const SCF: u8 = 0x37;
+    const ADD_A_B: u8 = 0x80;
+    const SUB_B: u8 = 0x90;
+    const DAA: u8 = 0x27;
+    const STOP: u8 = 0x10;
+    const INC_A: u8 = 0x3C;
+
+    #[test]
+    fn test_ccf_clears_carry_when_set() {
+        let mut cpu = cpu_with_code(&[CCF]);
+        cpu.registers.flags.c = true;
+
+        cpu.tick();
+
+        assert!(!cpu.registers.flags.c);
+    }
+
+    #[test]
+    fn test_ccf_sets_carry_when_cleared() {
+        let mut cpu = cpu_with_code(&[CCF]);
+        cpu.registers.flags.c = false;
+
+        cpu.tick();
+
+        assert!(cpu.registers.flags.c);
+    }
+
+    #[test]
+    fn test_ccf_always_clears_n_and_h_and_keeps_z() {
+        for flags in 0u8..16 {
+            let mut cpu = cpu_with_code(&[CCF]);
+            cpu.registers.flags.set_flags_from_u8(flags << 4);
+            let initial_clock_cycles = cpu.clock_cycles;
+
+            cpu.tick();
+
+            let z = flags & 0b1000 != 0;
+            let c = flags & 0b0001 != 0;
+            assert_eq!(cpu.registers.flags.z, z, "Z should be kept for flags {:04b}", flags);
+            assert!(!cpu.registers.flags.n, "N should be cleared for flags {:04b}", flags);
+            assert!(!cpu.registers.flags.h, "H should be cleared for flags {:04b}", flags);
+            assert_eq!(cpu.registers.flags.c, !c, "C should be flipped for flags {:04b}", flags);
+            assert_eq!(cpu.clock_cycles - initial_clock_cycles, 4);
+        }
+    }
+
+    #[test]
+    fn test_scf_then_ccf_clears_carry() {
+        let mut cpu = cpu_with_code(&[SCF, CCF]);
+        cpu.registers.flags.c = false;
+
+        cpu.tick();
+        assert!(cpu.registers.flags.c, "SCF should set the carry");
+
+        cpu.tick();
+        assert!(!cpu.registers.flags.c, "CCF should clear the carry set by SCF");
+    }
+
+    #[test]
+    fn test_add_then_ccf_keeps_zero_flag_from_add() {
+        // 0xF0 + 0x10 = 0x00 with carry, so Z and C are set by the ADD
+        let mut cpu = cpu_with_code(&[ADD_A_B, CCF]);
+        cpu.registers.a = 0xF0;
+        cpu.registers.b = 0x10;
+
+        cpu.tick();
+        assert!(cpu.registers.flags.z);
+        assert!(cpu.registers.flags.c);
+
+        cpu.tick();
+        assert_eq!(cpu.registers.a, 0x00);
+        assert!(cpu.registers.flags.z, "Z should keep the value set by ADD");
+        assert!(!cpu.registers.flags.c);
+        assert!(!cpu.registers.flags.n);
+        assert!(!cpu.registers.flags.h);
+    }
+    /// Runs `opcode` (ADD A, B or SUB B) followed by DAA and returns the CPU after both instructions.
+    fn run_bcd_operation_and_daa(opcode: u8, a: u8, b: u8) -> Cpu {
+        let mut cpu = cpu_with_code(&[opcode, DAA]);
+        cpu.registers.a = a;
+        cpu.registers.b = b;
+
+        cpu.tick();
+        let initial_clock_cycles = cpu.clock_cycles;
+        cpu.tick();
+        assert_eq!(cpu.clock_cycles - initial_clock_cycles, 4, "DAA should take 4 T-cycles");
+
+        cpu
+    }
+
+    #[test]
+    fn test_daa_after_addition_without_carry() {
+        // 0x12 + 0x34 = 0x46 is already a valid BCD number
+        let cpu = run_bcd_operation_and_daa(ADD_A_B, 0x12, 0x34);
+
+        assert_eq!(cpu.registers.a, 0x46);
+        assert!(!cpu.registers.flags.z);
+        assert!(!cpu.registers.flags.n);
+        assert!(!cpu.registers.flags.h);
+        assert!(!cpu.registers.flags.c);
+    }
+
+    #[test]
+    fn test_daa_pan_docs_example() {
+        // 0x45 + 0x38 = 0x7D in binary, 45 + 38 = 83 in BCD
+        let cpu = run_bcd_operation_and_daa(ADD_A_B, 0x45, 0x38);
+
+        assert_eq!(cpu.registers.a, 0x83);
+        assert!(!cpu.registers.flags.c);
+    }
+
+    #[test]
+    fn test_daa_after_addition_with_half_carry() {
+        // 0x19 + 0x28 = 0x41 with H set, the lower digit overflowed: 19 + 28 = 47
+        let cpu = run_bcd_operation_and_daa(ADD_A_B, 0x19, 0x28);
+
+        assert_eq!(cpu.registers.a, 0x47);
+        assert!(!cpu.registers.flags.h, "H should always be cleared");
+        assert!(!cpu.registers.flags.c);
+    }
+
+    #[test]
+    fn test_daa_after_addition_overflowing_99_sets_carry_and_zero() {
+        // 99 + 1 = 100, A keeps the last two digits and the carry is the hundreds digit
+        let cpu = run_bcd_operation_and_daa(ADD_A_B, 0x99, 0x01);
+
+        assert_eq!(cpu.registers.a, 0x00);
+        assert!(cpu.registers.flags.z);
+        assert!(cpu.registers.flags.c);
+    }
+
+    #[test]
+    fn test_daa_after_subtraction_with_half_borrow() {
+        // 0x10 - 0x01 = 0x0F with H set: 10 - 1 = 9
+        let cpu = run_bcd_operation_and_daa(SUB_B, 0x10, 0x01);
+
+        assert_eq!(cpu.registers.a, 0x09);
+        assert!(cpu.registers.flags.n, "N should be kept");
+        assert!(!cpu.registers.flags.h);
+        assert!(!cpu.registers.flags.c);
+    }
+
+    #[test]
+    fn test_daa_after_subtraction_with_borrow() {
+        // 0x00 - 0x01 = 0xFF with H and C set: 0 - 1 = 99 borrowing from the hundreds
+        let cpu = run_bcd_operation_and_daa(SUB_B, 0x00, 0x01);
+
+        assert_eq!(cpu.registers.a, 0x99);
+        assert!(cpu.registers.flags.n);
+        assert!(cpu.registers.flags.c, "The borrow should be kept in C");
+    }
+    #[test]
+    fn test_stop_does_not_execute_instructions_while_stopped() {
+        // STOP 0x00; INC A
+        let mut cpu = cpu_with_code(&[STOP, 0x00, INC_A]);
+        cpu.registers.a = 0;
+
+        assert_eq!(cpu.tick(), TickResult::Executed);
+        assert!(cpu.is_stop_mode);
+        assert_eq!(cpu.registers.pc, 0x0102, "The byte after STOP should be skipped");
+
+        for _ in 0..1000 {
+            let clock_cycles = cpu.clock_cycles;
+            assert_eq!(cpu.tick(), TickResult::Stopped);
+            assert!(cpu.clock_cycles > clock_cycles, "The clock should advance so a frame can finish");
+        }
+        assert_eq!(cpu.registers.pc, 0x0102);
+        assert_eq!(cpu.registers.a, 0);
+    }
+
+    #[test]
+    fn test_stop_resumes_when_the_joypad_interrupt_is_requested() {
+        // STOP 0x00; INC A
+        let mut cpu = cpu_with_code(&[STOP, 0x00, INC_A]);
+        cpu.registers.a = 0;
+        cpu.ime = false;
+
+        cpu.tick();
+        assert_eq!(cpu.tick(), TickResult::Stopped);
+
+        cpu.memory_bus.write_byte(IF, 0b0001_0000);
+        assert_eq!(cpu.tick(), TickResult::Executed);
+
+        assert!(!cpu.is_stop_mode);
+        assert_eq!(cpu.registers.a, 1);
+        assert_eq!(cpu.registers.pc, 0x0103);
+    }
+}
@@ -0,0 +1,97 @@
+use crate::gameboy_core::{
+    cpu::Cpu,
+    cpu_components::{CpuRegisters, FlagsRegister, MemoryAccess},
+};
+
+/// Expected values of every register and flag of the CPU, so a test can check the whole state in one assertion
+/// after a sequence of instructions.
+///
+/// ```ignore
+/// let expected = CpuStateSnapshot { pc: 0x0200, sp: 0xFFFC, ..CpuStateSnapshot::from_cpu(&cpu) };
+/// assert_cpu_state_eq(&cpu, &expected);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CpuStateSnapshot {
+    pub a: u8,
+    pub b: u8,
+    pub c: u8,
+    pub d: u8,
+    pub e: u8,
+    pub h: u8,
+    pub l: u8,
+    pub sp: u16,
+    pub pc: u16,
+    pub z_flag: bool,
+    pub n_flag: bool,
+    pub h_flag: bool,
+    pub c_flag: bool,
+}
+
+impl CpuStateSnapshot {
+    pub fn from_registers(registers: &CpuRegisters) -> CpuStateSnapshot {
+        CpuStateSnapshot {
+            a: registers.a,
+            b: registers.b,
+            c: registers.c,
+            d: registers.d,
+            e: registers.e,
+            h: registers.h,
+            l: registers.l,
+            sp: registers.sp,
+            pc: registers.pc,
+            z_flag: registers.flags.z,
+            n_flag: registers.flags.n,
+            h_flag: registers.flags.h,
+            c_flag: registers.flags.c,
+        }
+    }
+
+    pub fn from_cpu<M: MemoryAccess>(cpu: &Cpu<M>) -> CpuStateSnapshot {
+        Self::from_registers(&cpu.registers)
+    }
+
+    /// Builds the registers holding the values of the snapshot.
+    pub fn to_registers(self) -> CpuRegisters {
+        CpuRegisters {
+            a: self.a,
+            b: self.b,
+            c: self.c,
+            d: self.d,
+            e: self.e,
+            h: self.h,
+            l: self.l,
+            sp: self.sp,
+            pc: self.pc,
+            flags: FlagsRegister {
+                z: self.z_flag,
+                h: self.h_flag,
+                n: self.n_flag,
+                c: self.c_flag,
+            },
+        }
+    }
+}
+
+/// Asserts that every register and flag of the CPU has the value of the snapshot.
+#[track_caller]
+pub fn assert_cpu_state_eq<M: MemoryAccess>(cpu: &Cpu<M>, expected: &CpuStateSnapshot) {
+    assert_eq!(&CpuStateSnapshot::from_cpu(cpu), expected, "CPU state doesn't match the snapshot");
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::gameboy_core::cpu_components::CpuRegisters;
+
+    use super::CpuStateSnapshot;
+
+    #[test]
+    fn test_snapshot_round_trips_through_registers() {
+        let mut registers = CpuRegisters::new();
+        registers.set_hl(0xC0DE);
+        registers.flags.c = true;
+
+        let snapshot = CpuStateSnapshot::from_registers(&registers);
+
+        assert_eq!(snapshot.to_registers(), registers);
+    }
+}
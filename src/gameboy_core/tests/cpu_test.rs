@@ -0,0 +1,584 @@
+#[cfg(test)]
+mod tests {
+    use crate::gameboy_core::{
+        constants::{CB_OPCODE_CYCLES, INITIAL_PC, OPCODE_CYCLES, T_CYCLES_PER_FRAME},
+        cpu::{Cpu, TickResult},
+        cpu_components::{CpuRegisters, FlagsRegister, MemoryAccess, MemoryBus},
+        disassembler::decode_opcode,
+        registers_contants::{CC_C, CC_NC, CC_NZ, CC_Z, LY, SVBK},
+        tests::mock_cartridge::{MockCartridge, cpu_with_code},
+    };
+
+    /// Creates a CPU with a small ROM where the first byte is 0x3C (INC A) and the last one 0xAA.
+    fn create_cpu_with_rom() -> Cpu {
+        let mut rom_binary = vec![0u8; 0x200];
+        rom_binary[0] = 0x3C;
+        rom_binary[0x1FF] = 0xAA;
+        Cpu::start(rom_binary, false).unwrap()
+    }
+
+    /// Changes registers, memory and counters so it is possible to check what a reset restores.
+    fn dirty_cpu_state(cpu: &mut Cpu) {
+        cpu.registers.a = 0x42;
+        cpu.registers.set_bc(0x1234);
+        cpu.registers.sp = 0xC000;
+        cpu.registers.pc = 0x0150;
+        cpu.registers.flags.set_flags_from_u8(0x00);
+        cpu.ime = true;
+        cpu.is_halt_mode = true;
+        cpu.clock_cycles = 1000;
+        cpu.executed_instructions_count = 250;
+        cpu.ppu.dots = 200;
+        cpu.memory_bus.write_byte(0xC100, 0x11);
+        cpu.memory_bus.write_byte(0x8000, 0x22);
+        cpu.memory_bus.write_byte(SVBK, 3);
+        cpu.memory_bus.write_byte(LY, 0x50);
+    }
+
+    fn assert_power_on_registers(cpu: &Cpu) {
+        assert_eq!(cpu.registers.get_af(), 0x01B0);
+        assert_eq!(cpu.registers.get_bc(), 0x0013);
+        assert_eq!(cpu.registers.get_de(), 0x00D8);
+        assert_eq!(cpu.registers.get_hl(), 0x014D);
+        assert_eq!(cpu.registers.sp, 0xFFFE);
+        assert_eq!(cpu.registers.pc, INITIAL_PC);
+        assert!(!cpu.ime);
+        assert!(!cpu.is_halt_mode);
+    }
+
+    #[test]
+    fn test_reset_restores_power_on_state_and_keeps_rom() {
+        let mut cpu = create_cpu_with_rom();
+        dirty_cpu_state(&mut cpu);
+
+        cpu.reset();
+
+        assert_power_on_registers(&cpu);
+        assert_eq!(cpu.clock_cycles, 0);
+        assert_eq!(cpu.executed_instructions_count, 0);
+        assert_eq!(cpu.ppu.dots, 0);
+        assert_eq!(cpu.memory_bus.read_byte(0xC100), 0, "WRAM should be cleared");
+        assert_eq!(cpu.memory_bus.read_byte(0x8000), 0, "VRAM should be cleared");
+        assert_eq!(cpu.memory_bus.get_wram_bank(), 1);
+        assert_eq!(cpu.memory_bus.read_byte(LY), 0);
+        assert_eq!(cpu.memory_bus.get_lcdc_register(), 0x93, "IO registers should be back to their initial values");
+        assert_eq!(cpu.memory_bus.read_byte(0x0000), 0x3C, "ROM should be kept");
+        assert_eq!(cpu.memory_bus.read_byte(0x01FF), 0xAA, "ROM should be kept");
+    }
+
+    #[test]
+    fn test_soft_reset_restores_registers_and_keeps_memory() {
+        let mut cpu = create_cpu_with_rom();
+        dirty_cpu_state(&mut cpu);
+
+        cpu.soft_reset();
+
+        assert_power_on_registers(&cpu);
+        assert_eq!(cpu.memory_bus.read_byte(0xC100), 0x11, "WRAM should be kept");
+        assert_eq!(cpu.memory_bus.read_byte(0x8000), 0x22, "VRAM should be kept");
+        assert_eq!(cpu.memory_bus.read_byte(0x0000), 0x3C);
+    }
+
+    /// Creates a CPU with a program at 0x0100 that calls a subroutine at 0x0200:
+    /// 0x0100: CALL 0x0200, 0x0103: INC B, 0x0200: INC A, 0x0201: INC A, 0x0202: RET
+    fn create_cpu_with_call_program() -> Cpu {
+        let mut cpu = cpu_with_code(&[0xCD, 0x00, 0x02, 0x04]);
+        MockCartridge::new().write_code(0x0200, &[0x3C, 0x3C, 0xC9]).install_into(&mut cpu.memory_bus);
+        cpu.registers.a = 0;
+        cpu.registers.b = 0;
+        cpu
+    }
+
+    #[test]
+    fn test_step_into_enters_the_called_subroutine() {
+        let mut cpu = create_cpu_with_call_program();
+
+        let result = cpu.step_into();
+
+        assert_eq!(result, TickResult::Executed);
+        assert_eq!(cpu.registers.pc, 0x0200);
+        assert_eq!(cpu.registers.sp, 0xFFFC);
+    }
+
+    #[test]
+    fn test_step_over_runs_the_whole_call_and_stops_after_it() {
+        let mut cpu = create_cpu_with_call_program();
+
+        let result = cpu.step_over();
+
+        assert_eq!(result, TickResult::BreakpointHit(0x0103));
+        assert_eq!(cpu.registers.pc, 0x0103);
+        assert_eq!(cpu.registers.a, 2, "The subroutine body should have been executed");
+        assert_eq!(cpu.registers.b, 0, "The instruction after the CALL should not be executed yet");
+        assert_eq!(cpu.registers.sp, 0xFFFE);
+        assert!(cpu.breakpoints.is_empty(), "The one-shot breakpoint should be removed");
+    }
+
+    #[test]
+    fn test_step_over_executes_a_single_instruction_when_it_is_not_a_call() {
+        let mut cpu = create_cpu_with_call_program();
+        cpu.registers.pc = 0x0103;
+
+        let result = cpu.step_over();
+
+        assert_eq!(result, TickResult::Executed);
+        assert_eq!(cpu.registers.pc, 0x0104);
+        assert_eq!(cpu.registers.b, 1);
+    }
+
+    #[test]
+    fn test_step_over_stops_at_a_breakpoint_inside_the_subroutine() {
+        let mut cpu = create_cpu_with_call_program();
+        cpu.breakpoints.insert(0x0201);
+
+        let result = cpu.step_over();
+
+        assert_eq!(result, TickResult::BreakpointHit(0x0201));
+        assert_eq!(cpu.registers.a, 1);
+        assert!(cpu.breakpoints.contains(&0x0201), "User breakpoints should be kept");
+        assert!(!cpu.breakpoints.contains(&0x0103));
+    }
+
+    /// Runs a program at 0x0100 with known instruction counts: 4x INC A, 3x NOP, 2x SWAP A, 1x BIT 0, A, 1x DEC B
+    fn run_program_with_profiling() -> Cpu {
+        let program = [0x3C, 0x00, 0x3C, 0xCB, 0x37, 0x00, 0x3C, 0xCB, 0x47, 0x05, 0xCB, 0x37, 0x3C, 0x00];
+        let mut cpu = cpu_with_code(&program);
+
+        cpu.enable_profiling();
+        for _ in 0..11 {
+            cpu.tick();
+        }
+        cpu
+    }
+
+    #[test]
+    fn test_profiler_counts_executed_opcodes() {
+        let cpu = run_program_with_profiling();
+
+        let frequencies = cpu.get_opcode_frequencies().unwrap();
+        assert_eq!(frequencies[0x3C], 4);
+        assert_eq!(frequencies[0x00], 3);
+        assert_eq!(frequencies[0xCB], 3);
+        assert_eq!(frequencies[0x05], 1);
+        assert_eq!(frequencies.iter().sum::<u64>(), 11);
+
+        let cb_frequencies = cpu.get_cb_opcode_frequencies().unwrap();
+        assert_eq!(cb_frequencies[0x37], 2);
+        assert_eq!(cb_frequencies[0x47], 1);
+        assert_eq!(cb_frequencies.iter().sum::<u64>(), 3);
+    }
+
+    #[test]
+    fn test_get_top_opcodes_sorted_by_count() {
+        let cpu = run_program_with_profiling();
+
+        assert_eq!(cpu.get_top_opcodes(3), vec![(0x3C, 4), (0x00, 3), (0xCB, 3)]);
+        assert_eq!(cpu.get_top_opcodes(10), vec![(0x3C, 4), (0x00, 3), (0xCB, 3), (0x05, 1)]);
+    }
+
+    #[test]
+    fn test_profiler_is_disabled_by_default() {
+        let mut cpu = create_cpu_with_call_program();
+        cpu.tick();
+
+        assert!(cpu.get_opcode_frequencies().is_none());
+        assert!(cpu.get_cb_opcode_frequencies().is_none());
+        assert!(cpu.get_top_opcodes(5).is_empty());
+    }
+
+    #[test]
+    fn test_default_cpu_has_power_on_values() {
+        let cpu = Cpu::default();
+
+        assert_eq!(cpu.registers.a, 0x01);
+        assert_eq!(cpu.registers.get_af(), 0x01B0);
+        assert_eq!(cpu.registers.get_bc(), 0x0013);
+        assert_eq!(cpu.registers.get_de(), 0x00D8);
+        assert_eq!(cpu.registers.get_hl(), 0x014D);
+        assert_eq!(cpu.registers.sp, 0xFFFE);
+        assert_eq!(cpu.registers.pc, INITIAL_PC);
+        assert_eq!(cpu.memory_bus.get_wram_bank(), 1);
+        assert_eq!(cpu.clock_cycles, 0);
+        assert!(!cpu.ime);
+    }
+
+    #[test]
+    fn test_flags_from_every_byte_keep_only_the_upper_nibble() {
+        for byte in 0..=0xFFu8 {
+            let mut flags = FlagsRegister::new();
+            flags.set_flags_from_u8(byte);
+
+            assert_eq!(flags.get_flags_as_u8(), byte & 0xF0, "Wrong flags for 0x{:02X}", byte);
+        }
+    }
+
+    #[test]
+    fn test_flags_to_byte_and_back_keep_every_flag() {
+        for combination in 0..16u8 {
+            let mut flags = FlagsRegister::new();
+            flags.z = combination & 0b1000 != 0;
+            flags.n = combination & 0b0100 != 0;
+            flags.h = combination & 0b0010 != 0;
+            flags.c = combination & 0b0001 != 0;
+
+            let byte = flags.get_flags_as_u8();
+            assert_eq!(byte & 0x0F, 0, "Bits 0-3 should never be set, got 0x{:02X}", byte);
+
+            let mut restored_flags = FlagsRegister::new();
+            restored_flags.set_flags_from_u8(byte);
+            assert_eq!(
+                (restored_flags.z, restored_flags.n, restored_flags.h, restored_flags.c),
+                (flags.z, flags.n, flags.h, flags.c),
+                "Wrong flags after restoring 0x{:02X}",
+                byte
+            );
+        }
+    }
+
+    #[test]
+    fn test_default_registers_match_new() {
+        let registers = CpuRegisters::default();
+        let flags = FlagsRegister::default();
+
+        assert_eq!(registers.get_af(), CpuRegisters::new().get_af());
+        assert_eq!(registers.pc, INITIAL_PC);
+        assert_eq!(flags.get_flags_as_u8(), 0xB0);
+        assert_eq!(MemoryBus::default().read_byte(0xC000), 0);
+    }
+
+    #[test]
+    fn test_cloned_registers_are_independent() {
+        let mut registers = CpuRegisters::new();
+        let snapshot = registers.clone();
+
+        registers.a = 0x99;
+        registers.flags.z = false;
+
+        assert_eq!(registers.get_af(), 0x9930);
+        assert_eq!(snapshot.a, 0x01);
+        assert!(snapshot.flags.z);
+    }
+
+    #[test]
+    fn test_set_16bit_registers_split_high_and_low_bytes() {
+        let mut registers = CpuRegisters::new();
+
+        registers.set_hl(0x01FF);
+        assert_eq!(registers.h, 0x01);
+        assert_eq!(registers.l, 0xFF);
+
+        registers.set_bc(0xABCD);
+        registers.set_de(0x1234);
+        assert_eq!((registers.b, registers.c), (0xAB, 0xCD));
+        assert_eq!((registers.d, registers.e), (0x12, 0x34));
+    }
+
+    #[test]
+    fn test_cloned_cpu_runs_independently_from_the_same_state() {
+        // ADD A,B / LD (0xC000),A / JR -6
+        let mut cpu = cpu_with_code(&[0x80, 0xEA, 0x00, 0xC0, 0x18, 0xFA]);
+        cpu.registers.b = 1;
+        for _ in 0..10 {
+            cpu.tick();
+        }
+
+        let mut fork = cpu.clone();
+        assert_eq!(fork.registers, cpu.registers);
+        assert_eq!(fork.clock_cycles, cpu.clock_cycles);
+        fork.registers.b = 2;
+
+        for _ in 0..100 {
+            cpu.tick();
+            fork.tick();
+        }
+
+        assert_eq!(fork.registers.pc, cpu.registers.pc, "both run the same code");
+        assert_eq!(fork.clock_cycles, cpu.clock_cycles);
+        assert_ne!(fork.registers.a, cpu.registers.a);
+        assert_eq!(cpu.memory_bus.read_byte(0xC000), cpu.registers.a);
+        assert_eq!(fork.memory_bus.read_byte(0xC000), fork.registers.a, "the clone has its own memory");
+    }
+
+    #[test]
+    fn test_serialize_registers_as_json_of_the_power_on_state() {
+        let cpu = Cpu::new();
+
+        assert_eq!(
+            cpu.serialize_registers_as_json(),
+            concat!(
+                r#"{"a":"01","b":"00","c":"13","d":"00","e":"d8","f":"b0","h":"01","l":"4d","#,
+                r#""bc":"0013","de":"00d8","hl":"014d","sp":"fffe","pc":"0100","#,
+                r#""flags":{"z":true,"n":false,"h":true,"c":true}}"#
+            )
+        );
+    }
+
+    #[test]
+    fn test_serialized_registers_are_valid_json() {
+        let mut cpu = Cpu::new();
+        cpu.registers.set_hl(0xABCD);
+        cpu.registers.flags.set_flags_from_u8(0x40);
+
+        let json: serde_json::Value = serde_json::from_str(&cpu.serialize_registers_as_json()).unwrap();
+
+        assert_eq!(json["hl"], "abcd");
+        assert_eq!(json["f"], "40");
+        assert_eq!(json["flags"]["n"], true);
+        assert_eq!(json["flags"]["z"], false);
+    }
+
+    #[test]
+    fn test_get_memory_dump() {
+        let mut cpu = create_cpu_with_rom();
+        cpu.memory_bus.write_byte(0xC000, 0x12);
+        cpu.memory_bus.write_byte(0xC001, 0x34);
+
+        assert_eq!(cpu.get_memory_dump(0x0000, 2), vec![0x3C, 0x00]);
+        assert_eq!(cpu.get_memory_dump(0xBFFF, 3), vec![0x00, 0x12, 0x34]);
+        assert_eq!(cpu.get_memory_dump(0xFFFE, 4).len(), 2, "The dump should stop at 0xFFFF");
+        assert!(cpu.get_memory_dump(0xC000, 0).is_empty());
+    }
+
+    #[test]
+    fn test_get_imm16_and_advance_pc_reads_little_endian() {
+        let mut cpu = cpu_with_code(&[0x34, 0x12, 0xCD]);
+
+        assert_eq!(cpu.get_imm16_and_advance_pc(), 0x1234, "the low byte comes first");
+        assert_eq!(cpu.registers.pc, INITIAL_PC + 2, "PC should advance by exactly 2");
+        assert_eq!(cpu.get_imm8_and_advance_pc(), 0xCD);
+        assert_eq!(cpu.registers.pc, INITIAL_PC + 3, "PC should advance by exactly 1");
+    }
+
+    #[test]
+    fn test_instructions_with_immediates_leave_pc_after_them() {
+        // LD BC,0x1234 / LD A,0x56 / LD (0xC000),A / CALL 0x0200
+        let mut cpu = cpu_with_code(&[0x01, 0x34, 0x12, 0x3E, 0x56, 0xEA, 0x00, 0xC0, 0xCD, 0x00, 0x02]);
+        cpu.registers.sp = 0xFFFE;
+
+        cpu.tick();
+        assert_eq!(cpu.registers.get_bc(), 0x1234);
+        assert_eq!(cpu.registers.pc, INITIAL_PC + 3);
+        cpu.tick();
+        assert_eq!(cpu.registers.a, 0x56);
+        assert_eq!(cpu.registers.pc, INITIAL_PC + 5);
+        cpu.tick();
+        assert_eq!(cpu.memory_bus.read_byte(0xC000), 0x56);
+        assert_eq!(cpu.registers.pc, INITIAL_PC + 8);
+        cpu.tick();
+        assert_eq!(cpu.registers.pc, 0x0200);
+        assert_eq!(cpu.pop_value_from_sp(), INITIAL_PC + 11, "CALL should push the address after its operand");
+    }
+
+    #[test]
+    fn test_check_cc_condition_for_every_opcode_and_flags() {
+        let mut cpu = create_cpu_with_rom();
+
+        for flags in 0..=0xFu8 {
+            cpu.registers.flags.set_flags_from_u8(flags << 4);
+            let (z, c) = (cpu.registers.flags.z, cpu.registers.flags.c);
+
+            for opcode in 0..=0xFFu8 {
+                let expected = match (opcode >> 3) & 0b11 {
+                    CC_NZ => !z,
+                    CC_Z => z,
+                    CC_NC => !c,
+                    CC_C => c,
+                    _ => unreachable!(),
+                };
+                assert_eq!(
+                    cpu.check_cc_condition(opcode),
+                    expected,
+                    "Wrong condition for opcode 0x{:02X} with flags 0x{:02X}",
+                    opcode,
+                    flags << 4
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_check_cc_condition_for_conditional_instructions() {
+        let mut cpu = create_cpu_with_rom();
+        // Z reset, C set
+        cpu.registers.flags.set_flags_from_u8(0b0001_0000);
+
+        // JR cc, JP cc, CALL cc and RET cc with the conditions NZ, Z, NC and C
+        for base_opcode in [0x20, 0xC2, 0xC4, 0xC0] {
+            assert!(cpu.check_cc_condition(base_opcode), "NZ for 0x{:02X}", base_opcode);
+            assert!(!cpu.check_cc_condition(base_opcode + 0x08), "Z for 0x{:02X}", base_opcode);
+            assert!(!cpu.check_cc_condition(base_opcode + 0x10), "NC for 0x{:02X}", base_opcode);
+            assert!(cpu.check_cc_condition(base_opcode + 0x18), "C for 0x{:02X}", base_opcode);
+        }
+    }
+
+    #[test]
+    fn test_clock_cycles_for_a_sequence_of_mixed_instructions() {
+        let program: [(u16, &[u8], u64); 11] = [
+            (0x0100, &[0x00], 4),              // NOP
+            (0x0101, &[0x06, 0x12], 8),        // LD B, 0x12
+            (0x0103, &[0x3C], 4),              // INC A
+            (0x0104, &[0x21, 0x00, 0xC0], 12), // LD HL, 0xC000
+            (0x0107, &[0xC5], 16),             // PUSH BC
+            (0x0108, &[0xD1], 12),             // POP DE
+            (0x0109, &[0xCD, 0x20, 0x01], 24), // CALL 0x0120
+            (0x0120, &[0xC9], 16),             // RET
+            (0x010C, &[0xC3, 0x30, 0x01], 16), // JP 0x0130
+            (0x0130, &[0xCB, 0x37], 8),        // SWAP A
+            (0x0132, &[0x36, 0x55], 12),       // LD (HL), 0x55
+        ];
+        let mut rom_binary = vec![0u8; 0x200];
+        for (address, bytes, _) in program {
+            rom_binary[address as usize..address as usize + bytes.len()].copy_from_slice(bytes);
+        }
+        let mut cpu = Cpu::start(rom_binary, false).unwrap();
+
+        for (address, _, t_cycles) in program {
+            assert_eq!(cpu.registers.pc, address);
+            let cycles_before = cpu.clock_cycles;
+            cpu.tick();
+            assert_eq!(cpu.clock_cycles - cycles_before, t_cycles, "Wrong T-cycles for the instruction at 0x{:04X}", address);
+        }
+
+        assert_eq!(cpu.clock_cycles, program.iter().map(|(_, _, t_cycles)| t_cycles).sum::<u64>());
+        assert_eq!(cpu.memory_bus.read_byte(0xC000), 0x55);
+    }
+
+    #[test]
+    fn test_illegal_opcodes_run_as_nop_take_the_cycles_of_their_table_entry() {
+        let mut rom_binary = vec![0u8; 0x200];
+        rom_binary[0x0100] = 0xE3;
+        rom_binary[0x0101] = 0xED;
+        let mut cpu = Cpu::start(rom_binary, false).unwrap();
+
+        cpu.tick();
+        cpu.tick();
+
+        assert_eq!(cpu.registers.pc, 0x0102);
+        assert_eq!(cpu.clock_cycles, OPCODE_CYCLES[0xE3] as u64 + OPCODE_CYCLES[0xED] as u64);
+        assert_eq!(cpu.clock_cycles, 8);
+    }
+
+    #[test]
+    fn test_opcode_cycle_tables_totals() {
+        let total: u32 = OPCODE_CYCLES.iter().map(|&cycles| cycles as u32).sum();
+        let cb_total: u32 = CB_OPCODE_CYCLES.iter().map(|&cycles| cycles as u32).sum();
+
+        assert_eq!(total, 1816 + 2 * 4, "1816 in the Pan Docs table, plus 0xE3 and 0xED run as NOP");
+        // 224 register instructions of 8 T-cycles, 8 BIT b, (HL) of 12 and 24 other (HL) instructions of 16
+        assert_eq!(cb_total, 224 * 8 + 8 * 12 + 24 * 16);
+        assert_eq!(OPCODE_CYCLES.iter().filter(|&&cycles| cycles == 0).count(), 9, "11 illegal opcodes but 0xE3, 0xED");
+    }
+
+    /// Executes the instruction at 0xC000 from a known state, with the flags set so conditional branches are taken.
+    /// Returns the T-cycles it took.
+    fn execute_instruction_and_get_cycles(cpu: &mut Cpu, bytes: &[u8]) -> u64 {
+        for (offset, byte) in bytes.iter().enumerate() {
+            cpu.memory_bus.write_byte(0xC000 + offset as u16, *byte);
+        }
+        cpu.registers.pc = 0xC001;
+        cpu.registers.sp = 0xDFF0;
+        cpu.registers.set_bc(0xC180);
+        cpu.registers.set_de(0xC200);
+        cpu.registers.set_hl(0xC300);
+        cpu.is_halt_mode = false;
+
+        let condition = (bytes[0] >> 3) & 0b11;
+        cpu.registers.flags.z = condition == CC_Z;
+        cpu.registers.flags.c = condition == CC_C;
+
+        let cycles_before = cpu.clock_cycles;
+        cpu.execute(bytes[0]);
+        cpu.clock_cycles - cycles_before
+    }
+
+    #[test]
+    fn test_instructions_take_the_cycles_of_the_timing_table() {
+        let mut cpu = Cpu::new();
+
+        for opcode in 0..=0xFFu8 {
+            if OPCODE_CYCLES[opcode as usize] == 0 || opcode == 0xCB {
+                continue;
+            }
+            let cycles = execute_instruction_and_get_cycles(&mut cpu, &[opcode, 0x00, 0xC0]);
+            assert_eq!(cycles, OPCODE_CYCLES[opcode as usize] as u64, "Wrong T-cycles for opcode 0x{:02X}", opcode);
+        }
+
+        for cb_opcode in 0..=0xFFu8 {
+            let cycles = execute_instruction_and_get_cycles(&mut cpu, &[0xCB, cb_opcode]);
+            assert_eq!(
+                cycles,
+                CB_OPCODE_CYCLES[cb_opcode as usize] as u64,
+                "Wrong T-cycles for opcode 0xCB 0x{:02X}",
+                cb_opcode
+            );
+        }
+    }
+
+    #[test]
+    fn test_conditional_instructions_not_taken_take_fewer_cycles() {
+        let mut cpu = Cpu::new();
+        let conditional_opcodes: Vec<u8> =
+            (0..=0xFFu8).filter(|&opcode| decode_opcode(opcode, false).cycles_not_taken.is_some()).collect();
+        // JR cc, JP cc, CALL cc and RET cc with the 4 conditions each
+        assert_eq!(conditional_opcodes.len(), 16);
+
+        for opcode in conditional_opcodes {
+            let info = decode_opcode(opcode, false);
+            cpu.memory_bus.write_byte(0xC000, opcode);
+            cpu.memory_bus.write_byte(0xC001, 0x00);
+            cpu.memory_bus.write_byte(0xC002, 0xC0);
+            cpu.registers.pc = 0xC001;
+            cpu.registers.sp = 0xDFF0;
+
+            // Flags set so the condition is false
+            let condition = (opcode >> 3) & 0b11;
+            cpu.registers.flags.z = condition == CC_NZ;
+            cpu.registers.flags.c = condition == CC_NC;
+
+            let cycles_before = cpu.clock_cycles;
+            cpu.execute(opcode);
+
+            assert_eq!(
+                cpu.clock_cycles - cycles_before,
+                info.cycles_not_taken.unwrap() as u64,
+                "Wrong T-cycles for {} (0x{:02X}) not taken",
+                info.mnemonic,
+                opcode
+            );
+            assert!(info.cycles_not_taken.unwrap() < info.cycles_taken);
+            assert_eq!(
+                cpu.registers.pc,
+                0xC000 + info.length_bytes as u16,
+                "{} not taken should skip its operands",
+                info.mnemonic
+            );
+            assert_eq!(cpu.registers.sp, 0xDFF0, "{} not taken should not use the stack", info.mnemonic);
+        }
+    }
+
+    #[test]
+    fn test_run_frame_runs_exactly_one_frame_of_cycles() {
+        // Memory is empty, so the CPU only runs NOPs of 4 T-cycles
+        let mut cpu = Cpu::new();
+
+        for _ in 0..3 {
+            cpu.run_frame();
+        }
+
+        assert_eq!(cpu.clock_cycles, T_CYCLES_PER_FRAME * 3);
+    }
+
+    #[test]
+    fn test_run_frame_takes_the_extra_cycles_of_the_previous_frame_into_account() {
+        // JP 0x0100 takes 16 T-cycles, so the first one goes 12 T-cycles past the end of the frame
+        let mut cpu = cpu_with_code(&[0xC3, 0x00, 0x01]);
+        cpu.clock_cycles = T_CYCLES_PER_FRAME - 4;
+
+        cpu.run_frame();
+        assert_eq!(cpu.clock_cycles, T_CYCLES_PER_FRAME + 12);
+
+        cpu.run_frame();
+        assert!(cpu.clock_cycles >= T_CYCLES_PER_FRAME * 2);
+        assert!(cpu.clock_cycles < T_CYCLES_PER_FRAME * 2 + 16, "The second frame should end on its boundary");
+    }
+}
@@ -471,6 +471,44 @@ mod tests {
         assert_eq!(cpu.registers.flags.c, false, "CY flag should be 0");
     }
 
+    /// Runs LDHL SP, imm8 with all the flags set before, checking SP is unchanged and Z and N are cleared.
+    /// Returns HL and the H and C flags.
+    fn execute_ldhl_sp_imm8(sp: u16, imm8: u8) -> (u16, bool, bool) {
+        let mut cpu = Cpu::new();
+        cpu.registers.sp = sp;
+        cpu.registers.flags.set_flags_from_u8(0xF0);
+        cpu.memory_bus.write_byte(cpu.registers.pc, imm8);
+
+        cpu.execute(0xF8); // LDHL SP, imm8
+
+        assert_eq!(cpu.registers.sp, sp, "SP should remain unchanged after LDHL SP, imm8");
+        assert!(!cpu.registers.flags.z, "Z flag should always be 0");
+        assert!(!cpu.registers.flags.n, "N flag should always be 0");
+        (cpu.registers.get_hl(), cpu.registers.flags.h, cpu.registers.flags.c)
+    }
+
+    #[test]
+    fn test_ldhl_sp_imm8_negative_offset() {
+        // 0xFE is -2, the flags come from the unsigned low byte addition 0xF8 + 0xFE = 0x1F6
+        assert_eq!(execute_ldhl_sp_imm8(0xFFF8, 0xFE), (0xFFF6, true, true));
+        // 0xFF is -1, 0x00 + 0xFF doesn't carry from any bit
+        assert_eq!(execute_ldhl_sp_imm8(0xFF00, 0xFF), (0xFEFF, false, false));
+    }
+
+    #[test]
+    fn test_ldhl_sp_imm8_half_carry() {
+        // 0x08 + 0x08 carries from bit 3 only
+        assert_eq!(execute_ldhl_sp_imm8(0xFF08, 0x08), (0xFF10, true, false));
+    }
+
+    #[test]
+    fn test_ldhl_sp_imm8_carry() {
+        // 0xF0 + 0x10 carries from bit 7 only, the lower nibbles are 0 so there is no half carry
+        assert_eq!(execute_ldhl_sp_imm8(0xFFF0, 0x10), (0x0000, false, true));
+        // 0xFF + 0x01 carries from both bits
+        assert_eq!(execute_ldhl_sp_imm8(0xC0FF, 0x01), (0xC100, true, true));
+    }
+
     #[test]
     fn test_and_a_r() {
         let mut cpu = Cpu::new();
@@ -0,0 +1,101 @@
+#[cfg(test)]
+mod tests {
+    use crate::gameboy_core::{
+        cpu::Cpu,
+        cpu_utils::{self, compare_with_trace, format_hex_dump},
+        tests::mock_cartridge::cpu_with_code,
+    };
+
+    #[test]
+    fn test_format_hex_dump_full_rows() {
+        let mut data = [0u8; 32];
+        data[1] = 0x13;
+        data[2] = 0x02;
+        data[4..6].copy_from_slice(b"Hi");
+        data[16..32].copy_from_slice(b"Game Boy ROM~\x7F\xFF!");
+
+        let dump = format_hex_dump(&data, 0xFF00);
+
+        assert_eq!(
+            dump,
+            "FF00: 00 13 02 00 48 69 00 00 00 00 00 00 00 00 00 00  ....Hi..........\n\
+             FF10: 47 61 6D 65 20 42 6F 79 20 52 4F 4D 7E 7F FF 21  Game Boy ROM~..!\n"
+        );
+    }
+
+    #[test]
+    fn test_format_hex_dump_pads_the_last_row() {
+        let dump = format_hex_dump(&[0x41, 0x42, 0x0A], 0x0100);
+
+        assert_eq!(dump, format!("0100: 41 42 0A{}  AB.\n", " ".repeat(13 * 3)));
+    }
+
+    #[test]
+    fn test_format_hex_dump_empty() {
+        assert_eq!(format_hex_dump(&[], 0x0000), "");
+    }
+
+    #[test]
+    fn test_compare_with_trace_follows_a_known_trace() {
+        let rom = cpu_utils::read_rom("files/roms/tests/blarggs_test_roms/06-ld r,r.gb").unwrap();
+        let mut cpu = Cpu::start(rom, false).unwrap();
+        // State before each of the first instructions: NOP, JP 0213, LD HL,4000, JP 0200, LD B,A
+        let trace = [
+            "A:01 F:B0 B:00 C:13 D:00 E:D8 H:01 L:4D SP:FFFE PC:0100 PCMEM:00,C3,13,02",
+            "A:01 F:B0 B:00 C:13 D:00 E:D8 H:01 L:4D SP:FFFE PC:0101 PCMEM:C3,13,02,CE",
+            "A:01 F:B0 B:00 C:13 D:00 E:D8 H:01 L:4D SP:FFFE PC:0213 PCMEM:21,00,40,C3",
+            "A:01 F:B0 B:00 C:13 D:00 E:D8 H:40 L:00 SP:FFFE PC:0216 PCMEM:C3,00,02,00",
+            "A:01 F:B0 B:00 C:13 D:00 E:D8 H:40 L:00 SP:FFFE PC:0200 PCMEM:47,11,00,C0",
+        ];
+
+        for (index, trace_line) in trace.iter().enumerate() {
+            let captured_line = cpu_utils::get_registers_state_for_log(&cpu, false);
+            assert_eq!(captured_line.trim_end(), *trace_line, "Wrong state before instruction {}", index);
+            assert!(compare_with_trace(&cpu, &captured_line), "Captured trace line {} doesn't match", index);
+            cpu.tick();
+        }
+
+        assert!(compare_with_trace(&cpu, "A:01 F:B0 B:01 C:13 D:00 E:D8 H:40 L:00 SP:FFFE PC:0201"));
+    }
+
+    #[test]
+    fn test_compare_with_trace_bgb_format() {
+        let cpu = Cpu::new();
+
+        assert!(compare_with_trace(&cpu, "AF:01B0 BC:0013 DE:00D8 HL:014D SP:FFFE PC:0100 LY:00 (cy: 0)"));
+        assert!(compare_with_trace(&cpu, "af:01b0 bc:0013"));
+        assert!(!compare_with_trace(&cpu, "AF:01B0 BC:0013 DE:00D8 HL:014D SP:FFFC PC:0100"));
+    }
+
+    #[test]
+    fn test_compare_with_trace_detects_mismatches() {
+        // LD A, 0x42
+        let cpu = cpu_with_code(&[0x3E, 0x42]);
+
+        assert!(compare_with_trace(&cpu, "A:01 PC:0100 PCMEM:3E,42"));
+        assert!(!compare_with_trace(&cpu, "A:02 PC:0100"));
+        assert!(!compare_with_trace(&cpu, "A:01 PC:0100 PCMEM:3E,43"));
+        assert!(!compare_with_trace(&cpu, "A:XY PC:0100"));
+        assert!(!compare_with_trace(&cpu, "LY:00 unrelated text"));
+        assert!(!compare_with_trace(&cpu, ""));
+    }
+
+    #[cfg(not(feature = "debug-logging"))]
+    #[test]
+    fn test_log_functions_do_nothing_without_debug_logging() {
+        let mut cpu = cpu_with_code(&[0x00]);
+        cpu.is_debug_mode = true;
+
+        assert!(cpu_utils::clear_logs().is_ok());
+        assert!(cpu_utils::clear_dr_gameboy_log().is_ok());
+        cpu_utils::log(&mut cpu, 0x00);
+        cpu_utils::print_state_if_debug_mode(&cpu, 0x00);
+    }
+
+    #[test]
+    fn test_print_state_if_debug_mode_outside_debug_mode() {
+        let cpu = cpu_with_code(&[0x00]);
+
+        cpu_utils::print_state_if_debug_mode(&cpu, 0x00);
+    }
+}
@@ -0,0 +1,100 @@
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use crate::gameboy_core::components::debug_overlay::{
+        DebugOverlay, FPS_AVERAGE_FRAMES, FrameStats, OVERLAY_LINE_HEIGHT, OVERLAY_PADDING, OVERLAY_TEXT_COLOR,
+        render_debug_overlay,
+    };
+
+    const WIDTH: usize = 64;
+    const HEIGHT: usize = 40;
+    const BACKGROUND: u32 = 0x808080;
+    const DARKENED_BACKGROUND: u32 = 0x333333;
+
+    fn render(overlay: &DebugOverlay) -> Vec<u32> {
+        let mut buffer = vec![BACKGROUND; WIDTH * HEIGHT];
+        render_debug_overlay(overlay, &mut buffer, WIDTH);
+        buffer
+    }
+
+    #[test]
+    fn test_overlay_lines() {
+        let overlay = DebugOverlay {
+            fps: 59.73,
+            frame_time_ms: 4.25,
+            cpu_cycles_per_frame: 70224,
+            current_ly: 144,
+        };
+
+        assert_eq!(overlay.get_lines(), ["FPS 59.7", "MS 4.2", "CYC 70224", "LY 144"]);
+    }
+
+    #[test]
+    fn test_render_debug_overlay_draws_known_pixels() {
+        let buffer = render(&DebugOverlay {
+            fps: 60.0,
+            frame_time_ms: 1.0,
+            cpu_cycles_per_frame: 70224,
+            current_ly: 7,
+        });
+        let pixel = |row: usize, col: usize| buffer[(OVERLAY_PADDING + row) * WIDTH + OVERLAY_PADDING + col];
+
+        // "F" of the first line: full top row, only the left column on the second row
+        assert_eq!(pixel(0, 0), OVERLAY_TEXT_COLOR);
+        assert_eq!(pixel(0, 2), OVERLAY_TEXT_COLOR);
+        assert_eq!(pixel(1, 0), OVERLAY_TEXT_COLOR);
+        assert_eq!(pixel(1, 1), DARKENED_BACKGROUND);
+        // Spacing column between "F" and "P"
+        assert_eq!(pixel(0, 3), DARKENED_BACKGROUND);
+        // "." of "60.0" is the 7th character, only its bottom middle pixel is set
+        assert_eq!(pixel(4, 6 * 4 + 1), OVERLAY_TEXT_COLOR);
+        assert_eq!(pixel(3, 6 * 4 + 1), DARKENED_BACKGROUND);
+        // "7" of "LY 7" in the fourth line: full top row, only the right column below
+        let ly_row = 3 * OVERLAY_LINE_HEIGHT;
+        assert_eq!(pixel(ly_row, 3 * 4), OVERLAY_TEXT_COLOR);
+        assert_eq!(pixel(ly_row + 2, 3 * 4), DARKENED_BACKGROUND);
+        assert_eq!(pixel(ly_row + 2, 3 * 4 + 2), OVERLAY_TEXT_COLOR);
+    }
+
+    #[test]
+    fn test_render_debug_overlay_only_darkens_the_top_left_corner() {
+        let buffer = render(&DebugOverlay::default());
+
+        // "FPS 0.0" is the longest line: 7 characters of 3 pixels plus 6 spacings, and 4 lines of text
+        let background_width = 7 * 4 - 1 + 2 * OVERLAY_PADDING;
+        let background_height = 4 * OVERLAY_LINE_HEIGHT - 2 + 2 * OVERLAY_PADDING;
+
+        assert_eq!(buffer[0], DARKENED_BACKGROUND);
+        assert_eq!(buffer[background_width - 1], DARKENED_BACKGROUND);
+        assert_eq!(buffer[background_width], BACKGROUND);
+        assert_eq!(buffer[(background_height - 1) * WIDTH], DARKENED_BACKGROUND);
+        assert_eq!(buffer[background_height * WIDTH], BACKGROUND);
+        assert_eq!(buffer[HEIGHT * WIDTH - 1], BACKGROUND);
+    }
+
+    #[test]
+    fn test_render_debug_overlay_is_clipped_to_small_buffers() {
+        let mut buffer = vec![BACKGROUND; 8 * 4];
+
+        render_debug_overlay(&DebugOverlay::default(), &mut buffer, 8);
+
+        assert!(buffer.iter().all(|&pixel| pixel != BACKGROUND));
+    }
+
+    #[test]
+    fn test_fps_is_averaged_over_the_last_frames() {
+        let mut frame_stats = FrameStats::new();
+        assert_eq!(frame_stats.get_fps(), 0.0);
+
+        frame_stats.push_frame_duration(Duration::from_millis(10));
+        frame_stats.push_frame_duration(Duration::from_millis(30));
+        assert!((frame_stats.get_fps() - 50.0).abs() < 0.01);
+
+        // The two first frames are dropped once the window is full
+        for _ in 0..FPS_AVERAGE_FRAMES {
+            frame_stats.push_frame_duration(Duration::from_millis(25));
+        }
+        assert!((frame_stats.get_fps() - 40.0).abs() < 0.01);
+    }
+}
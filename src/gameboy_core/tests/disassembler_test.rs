@@ -0,0 +1,196 @@
+#[cfg(test)]
+mod tests {
+    use crate::gameboy_core::{
+        cpu::Cpu,
+        cpu_components::MemoryBus,
+        disassembler::{OpcodeInfo, decode_opcode, get_operand_length},
+    };
+
+    fn create_memory_bus_with_program(start: u16, program: &[u8]) -> MemoryBus {
+        let mut memory_bus = MemoryBus::new();
+        for (offset, byte) in program.iter().enumerate() {
+            memory_bus.write_byte(start + offset as u16, *byte);
+        }
+        memory_bus
+    }
+
+    #[test]
+    fn test_iter_instructions_from_decodes_operands_and_stops_at_halt() {
+        let program = [
+            0x00, // NOP
+            0x3E, 0x42, // LD A, 0x42
+            0x21, 0x34, 0x12, // LD HL, 0x1234
+            0xCB, 0x7C, // BIT 7, H
+            0x20, 0xFB, // JR NZ, -5
+            0xCD, 0x00, 0xC1, // CALL 0xC100
+            0xE0, 0x80, // LDH (0x80), A
+            0x76, // HALT
+            0x3C, // INC A, after the HALT so it is not decoded
+        ];
+        let memory_bus = create_memory_bus_with_program(0xC000, &program);
+
+        let instructions: Vec<(u16, u8, Vec<u8>)> = Cpu::iter_instructions_from(&memory_bus, 0xC000).collect();
+
+        assert_eq!(
+            instructions,
+            vec![
+                (0xC000, 0x00, vec![]),
+                (0xC001, 0x3E, vec![0x42]),
+                (0xC003, 0x21, vec![0x34, 0x12]),
+                (0xC006, 0xCB, vec![0x7C]),
+                (0xC008, 0x20, vec![0xFB]),
+                (0xC00A, 0xCD, vec![0x00, 0xC1]),
+                (0xC00D, 0xE0, vec![0x80]),
+                (0xC00F, 0x76, vec![]),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_iter_instructions_starts_at_pc() {
+        let mut cpu = Cpu::with_memory_bus(create_memory_bus_with_program(0xC000, &[0x04, 0x05, 0x76]));
+        cpu.registers.pc = 0xC001;
+
+        let addresses: Vec<u16> = cpu.iter_instructions().map(|(address, _, _)| address).collect();
+
+        assert_eq!(addresses, vec![0xC001, 0xC002]);
+    }
+
+    #[test]
+    fn test_iter_instructions_stops_when_the_address_wraps() {
+        // LD BC, imm16 at 0xFFFE would need a byte at 0x10000
+        let memory_bus = create_memory_bus_with_program(0xFFFC, &[0x00, 0x00, 0x01]);
+
+        let instructions: Vec<(u16, u8, Vec<u8>)> = Cpu::iter_instructions_from(&memory_bus, 0xFFFC).collect();
+
+        assert_eq!(instructions, vec![(0xFFFC, 0x00, vec![]), (0xFFFD, 0x00, vec![])]);
+
+        // The last instruction at 0xFFFF is decoded, but the iteration does not continue at 0x0000
+        let memory_bus = create_memory_bus_with_program(0xFFFF, &[0x00]);
+        let instructions: Vec<(u16, u8, Vec<u8>)> = Cpu::iter_instructions_from(&memory_bus, 0xFFFE).collect();
+        assert_eq!(instructions.len(), 2);
+    }
+
+    #[test]
+    fn test_get_operand_length() {
+        for opcode in [0x00, 0x04, 0x40, 0x76, 0x80, 0xC1, 0xC9, 0xE9, 0xF3, 0xFB] {
+            assert_eq!(get_operand_length(opcode), 0, "Wrong length for {:#04X}", opcode);
+        }
+        for opcode in [0x06, 0x0E, 0x10, 0x18, 0x36, 0x38, 0xC6, 0xCB, 0xE0, 0xE8, 0xF0, 0xF8, 0xFE] {
+            assert_eq!(get_operand_length(opcode), 1, "Wrong length for {:#04X}", opcode);
+        }
+        for opcode in [0x01, 0x08, 0x31, 0xC2, 0xC3, 0xCD, 0xDC, 0xEA, 0xFA] {
+            assert_eq!(get_operand_length(opcode), 2, "Wrong length for {:#04X}", opcode);
+        }
+    }
+    fn info(mnemonic: &'static str, length_bytes: u8, cycles_taken: u8, cycles_not_taken: Option<u8>) -> OpcodeInfo {
+        OpcodeInfo {
+            mnemonic,
+            length_bytes,
+            cycles_taken,
+            cycles_not_taken,
+        }
+    }
+
+    #[test]
+    fn test_decode_opcode() {
+        let expected = [
+            (0x00, info("NOP", 1, 4, None)),
+            (0x01, info("LD BC, imm16", 3, 12, None)),
+            (0x02, info("LD (BC), A", 1, 8, None)),
+            (0x06, info("LD B, imm8", 2, 8, None)),
+            (0x08, info("LD (imm16), SP", 3, 20, None)),
+            (0x09, info("ADD HL, BC", 1, 8, None)),
+            (0x10, info("STOP", 2, 4, None)),
+            (0x18, info("JR imm8", 2, 12, None)),
+            (0x20, info("JR NZ, imm8", 2, 12, Some(8))),
+            (0x22, info("LD (HL+), A", 1, 8, None)),
+            (0x27, info("DAA", 1, 4, None)),
+            (0x38, info("JR C, imm8", 2, 12, Some(8))),
+            (0x34, info("INC (HL)", 1, 12, None)),
+            (0x36, info("LD (HL), imm8", 2, 12, None)),
+            (0x3A, info("LD A, (HL-)", 1, 8, None)),
+            (0x41, info("LD B, C", 1, 4, None)),
+            (0x46, info("LD B, (HL)", 1, 8, None)),
+            (0x70, info("LD (HL), B", 1, 8, None)),
+            (0x76, info("HALT", 1, 4, None)),
+            (0x7F, info("LD A, A", 1, 4, None)),
+            (0x86, info("ADD A, (HL)", 1, 8, None)),
+            (0x90, info("SUB B", 1, 4, None)),
+            (0xAF, info("XOR A", 1, 4, None)),
+            (0xBE, info("CP (HL)", 1, 8, None)),
+            (0xC0, info("RET NZ", 1, 20, Some(8))),
+            (0xC1, info("POP BC", 1, 12, None)),
+            (0xC3, info("JP imm16", 3, 16, None)),
+            (0xC4, info("CALL NZ, imm16", 3, 24, Some(12))),
+            (0xC5, info("PUSH BC", 1, 16, None)),
+            (0xC9, info("RET", 1, 16, None)),
+            (0xCA, info("JP Z, imm16", 3, 16, Some(12))),
+            (0xCB, info("PREFIX CB", 2, 4, None)),
+            (0xCD, info("CALL imm16", 3, 24, None)),
+            (0xCE, info("ADC A, imm8", 2, 8, None)),
+            (0xD3, info("ILLEGAL", 1, 0, None)),
+            (0xD9, info("RETI", 1, 16, None)),
+            (0xDF, info("RST $18", 1, 16, None)),
+            (0xE0, info("LDH (imm8), A", 2, 12, None)),
+            (0xE2, info("LDH (C), A", 1, 8, None)),
+            (0xE8, info("ADD SP, imm8", 2, 16, None)),
+            (0xE9, info("JP HL", 1, 4, None)),
+            (0xEA, info("LD (imm16), A", 3, 16, None)),
+            (0xF1, info("POP AF", 1, 12, None)),
+            (0xF3, info("DI", 1, 4, None)),
+            (0xF8, info("LD HL, SP + imm8", 2, 12, None)),
+            (0xFA, info("LD A, (imm16)", 3, 16, None)),
+            (0xFB, info("EI", 1, 4, None)),
+            (0xFE, info("CP imm8", 2, 8, None)),
+        ];
+
+        for (opcode, expected_info) in expected {
+            assert_eq!(
+                decode_opcode(opcode, false),
+                expected_info,
+                "Wrong info for {:#04X}",
+                opcode
+            );
+        }
+    }
+
+    #[test]
+    fn test_decode_cb_prefixed_opcode() {
+        let expected = [
+            (0x00, info("RLC B", 2, 8, None)),
+            (0x06, info("RLC (HL)", 2, 16, None)),
+            (0x11, info("RL C", 2, 8, None)),
+            (0x1F, info("RR A", 2, 8, None)),
+            (0x27, info("SLA A", 2, 8, None)),
+            (0x2E, info("SRA (HL)", 2, 16, None)),
+            (0x37, info("SWAP A", 2, 8, None)),
+            (0x3F, info("SRL A", 2, 8, None)),
+            (0x46, info("BIT 0, (HL)", 2, 12, None)),
+            (0x7C, info("BIT 7, H", 2, 8, None)),
+            (0x87, info("RES 0, A", 2, 8, None)),
+            (0xBE, info("RES 7, (HL)", 2, 16, None)),
+            (0xC0, info("SET 0, B", 2, 8, None)),
+            (0xFE, info("SET 7, (HL)", 2, 16, None)),
+        ];
+
+        for (opcode, expected_info) in expected {
+            assert_eq!(
+                decode_opcode(opcode, true),
+                expected_info,
+                "Wrong info for 0xCB {:#04X}",
+                opcode
+            );
+        }
+    }
+
+    #[test]
+    fn test_decode_opcode_length_matches_operand_length() {
+        for opcode in 0..=0xFF {
+            assert_eq!(
+                decode_opcode(opcode, false).length_bytes as usize,
+                1 + get_operand_length(opcode)
+            );
+        }
+    }
+}
@@ -0,0 +1,74 @@
+#[cfg(test)]
+mod tests {
+    use std::{io, path::Path};
+
+    use crate::gameboy_core::{cpu::Cpu, cpu_utils, emulator_error::EmulatorError};
+
+    #[test]
+    fn test_display_of_each_error_variant() {
+        let io_error = EmulatorError::from(io::Error::new(io::ErrorKind::NotFound, "file not found"));
+        assert_eq!(io_error.to_string(), "I/O error: file not found");
+
+        let invalid_rom = EmulatorError::InvalidRom("the ROM is empty".to_string());
+        assert_eq!(invalid_rom.to_string(), "Invalid ROM: the ROM is empty");
+
+        let unsupported_mbc = EmulatorError::UnsupportedMbc(0x13);
+        assert_eq!(
+            unsupported_mbc.to_string(),
+            "Unsupported cartridge type 0x13: its memory bank controller is not implemented"
+        );
+
+        let invalid_save_state = EmulatorError::InvalidSaveState("wrong size".to_string());
+        assert_eq!(invalid_save_state.to_string(), "Invalid save state: wrong size");
+
+        let window_error = EmulatorError::WindowError("no display".to_string());
+        assert_eq!(window_error.to_string(), "Could not create the window: no display");
+    }
+
+    #[test]
+    fn test_read_rom_of_missing_file_returns_io_error() {
+        let result = cpu_utils::read_rom("files/roms/missing_rom.gb");
+
+        assert!(matches!(result, Err(EmulatorError::IoError(_))));
+    }
+
+    #[test]
+    fn test_load_rom_from_missing_file_returns_io_error() {
+        let mut cpu = Cpu::new();
+
+        let result = cpu.load_rom_from_file(Path::new("files/roms/missing_rom.gb"));
+
+        assert!(matches!(result, Err(EmulatorError::IoError(_))));
+    }
+
+    #[test]
+    fn test_load_rom_from_file_loads_the_rom() {
+        let mut cpu = Cpu::new();
+
+        cpu.load_rom_from_file(Path::new("files/roms/tests/nintendo_logo.gb")).unwrap();
+
+        let rom = cpu_utils::read_rom("files/roms/tests/nintendo_logo.gb").unwrap();
+        assert_eq!(cpu.memory_bus.read_byte(0x0104), rom[0x0104], "the Nintendo logo of the header is mapped");
+    }
+
+    /// Returns the error of starting the CPU with the ROM, the CPU itself is dropped right away since it is big
+    fn get_start_error(rom_binary: Vec<u8>) -> Option<EmulatorError> {
+        Cpu::start(rom_binary, false).err()
+    }
+
+    #[test]
+    fn test_start_rejects_invalid_roms() {
+        let error = get_start_error(vec![0; 0x100]);
+        assert!(matches!(error, Some(EmulatorError::InvalidRom(_))), "ROM without a full header");
+
+        let mut rom_binary = vec![0; 0x10000];
+        let error = get_start_error(rom_binary.clone());
+        assert!(matches!(error, Some(EmulatorError::InvalidRom(_))), "ROM only cartridge bigger than 32KB");
+
+        rom_binary[0x0147] = 0x20; // MBC6
+        let error = get_start_error(rom_binary);
+        assert!(matches!(error, Some(EmulatorError::UnsupportedMbc(0x20))));
+
+        assert!(get_start_error(vec![0; 0x8000]).is_none());
+    }
+}
@@ -0,0 +1,88 @@
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use crate::{
+        config::Config,
+        gameboy_core::{
+            components::gamepad::{GamepadButton, GamepadEvent, GamepadMapping},
+            joypad::{JoypadButton, JoypadState},
+        },
+    };
+
+    #[test]
+    fn test_default_mapping() {
+        let mapping = GamepadMapping::default();
+
+        assert_eq!(mapping.get_joypad_button(GamepadButton::South), Some(JoypadButton::A));
+        assert_eq!(mapping.get_joypad_button(GamepadButton::West), Some(JoypadButton::B));
+        assert_eq!(mapping.get_joypad_button(GamepadButton::Start), Some(JoypadButton::Start));
+        assert_eq!(mapping.get_joypad_button(GamepadButton::Select), Some(JoypadButton::Select));
+        assert_eq!(mapping.get_joypad_button(GamepadButton::DPadUp), Some(JoypadButton::Up));
+        assert_eq!(mapping.get_joypad_button(GamepadButton::DPadRight), Some(JoypadButton::Right));
+        assert_eq!(mapping.get_joypad_button(GamepadButton::East), None);
+    }
+
+    #[test]
+    fn test_default_config_bindings_match_default_mapping() {
+        let mapping = GamepadMapping::from_bindings(&Config::default().gamepad_bindings);
+
+        assert_eq!(mapping, GamepadMapping::default());
+    }
+
+    #[test]
+    fn test_button_events_press_and_release_joypad_buttons() {
+        let mapping = GamepadMapping::default();
+        let mut joypad_state = JoypadState::new();
+
+        mapping.apply_event(GamepadEvent::ButtonPressed(GamepadButton::South), &mut joypad_state);
+        mapping.apply_event(GamepadEvent::ButtonPressed(GamepadButton::DPadLeft), &mut joypad_state);
+        assert!(joypad_state.is_pressed(JoypadButton::A));
+        assert!(joypad_state.is_pressed(JoypadButton::Left));
+        assert_eq!(joypad_state.get_pressed_buttons(), 0b0001_0010);
+
+        mapping.apply_event(GamepadEvent::ButtonReleased(GamepadButton::South), &mut joypad_state);
+        assert!(!joypad_state.is_pressed(JoypadButton::A));
+        assert!(joypad_state.is_pressed(JoypadButton::Left));
+
+        // Unmapped buttons are ignored
+        mapping.apply_event(GamepadEvent::ButtonPressed(GamepadButton::North), &mut joypad_state);
+        assert_eq!(joypad_state.get_pressed_buttons(), 0b0000_0010);
+    }
+
+    #[test]
+    fn test_dpad_axis_events() {
+        let mapping = GamepadMapping::default();
+        let mut joypad_state = JoypadState::new();
+
+        mapping.apply_event(GamepadEvent::DPadXChanged(1.0), &mut joypad_state);
+        mapping.apply_event(GamepadEvent::DPadYChanged(-1.0), &mut joypad_state);
+        assert!(joypad_state.is_pressed(JoypadButton::Right));
+        assert!(joypad_state.is_pressed(JoypadButton::Down));
+        assert!(!joypad_state.is_pressed(JoypadButton::Left));
+        assert!(!joypad_state.is_pressed(JoypadButton::Up));
+
+        mapping.apply_event(GamepadEvent::DPadXChanged(-1.0), &mut joypad_state);
+        mapping.apply_event(GamepadEvent::DPadYChanged(0.2), &mut joypad_state);
+        assert!(joypad_state.is_pressed(JoypadButton::Left));
+        assert!(!joypad_state.is_pressed(JoypadButton::Right));
+        assert!(!joypad_state.is_pressed(JoypadButton::Down), "Values under the threshold release the axis");
+        assert!(!joypad_state.is_pressed(JoypadButton::Up));
+    }
+
+    #[test]
+    fn test_config_bindings_override_default_mapping() {
+        let bindings: HashMap<String, String> = [("A", "East"), ("B", "South"), ("Start", "Unknown"), ("Turbo", "North")]
+            .iter()
+            .map(|(button, gamepad_button)| (button.to_string(), gamepad_button.to_string()))
+            .collect();
+
+        let mapping = GamepadMapping::from_bindings(&bindings);
+
+        assert_eq!(mapping.get_joypad_button(GamepadButton::East), Some(JoypadButton::A));
+        assert_eq!(mapping.get_joypad_button(GamepadButton::South), Some(JoypadButton::B));
+        assert_eq!(mapping.get_joypad_button(GamepadButton::West), None, "The old B button should be unmapped");
+        assert_eq!(mapping.get_joypad_button(GamepadButton::Start), Some(JoypadButton::Start));
+        assert_eq!(mapping.get_joypad_button(GamepadButton::North), None);
+    }
+}
@@ -0,0 +1,265 @@
+#[cfg(test)]
+mod tests {
+    use crate::gameboy_core::{
+        constants::{
+            JOYPAD_INTERRUPT_HANDLER_ADDRESS, LCD_STAT_INTERRUPT_HANDLER_ADDRESS, SERIAL_INTERRUPT_HANDLER_ADDRESS,
+            TIMER_INTERRUPT_HANDLER_ADDRESS, VBLANK_INTERRUT_HANDLER_ADDRESS,
+        },
+        cpu::{Cpu, TickResult},
+        cpu_components::MemoryAccess,
+        registers_contants::{IE, IF, LCDC, LY, P1},
+        tests::mock_cartridge::cpu_with_code,
+    };
+
+    #[test]
+    fn test_ie_register_only_keeps_lower_5_bits() {
+        let mut cpu = Cpu::new();
+
+        cpu.memory_bus.set_ie_register(0xFF);
+        assert_eq!(cpu.memory_bus.get_ie_register(), 0x1F);
+        assert_eq!(cpu.memory_bus.read_byte(IE), 0x1F);
+
+        // Writing the whole byte directly is still possible, but the upper bits are ignored when reading IE
+        cpu.memory_bus.write_byte(IE, 0xE4);
+        assert_eq!(cpu.memory_bus.get_ie_register(), 0x04);
+    }
+
+    #[test]
+    fn test_only_enabled_interrupt_is_dispatched() {
+        let mut cpu = Cpu::new();
+        cpu.registers.pc = 0xC000;
+        cpu.registers.sp = 0xDFFE;
+        cpu.ime = true;
+
+        // Only the timer interrupt is enabled, but both VBlank and Timer are requested
+        cpu.memory_bus.set_ie_register(0x04);
+        cpu.memory_bus.write_byte(IF, 0b0000_0101);
+
+        cpu.tick();
+
+        assert_eq!(cpu.registers.pc, TIMER_INTERRUPT_HANDLER_ADDRESS);
+        assert_ne!(cpu.registers.pc, VBLANK_INTERRUT_HANDLER_ADDRESS);
+        assert!(!cpu.ime);
+        let if_register = cpu.memory_bus.read_byte(IF);
+        assert_eq!(if_register & 0b0000_0100, 0, "Timer flag should be cleared");
+        assert_eq!(if_register & 0b0000_0001, 0b0000_0001, "VBlank flag should still be requested");
+        assert_eq!(cpu.pop_value_from_sp(), 0xC000, "Return address should be pushed to the stack");
+    }
+
+    /// Requests only the interrupt of the IF bit with IME enabled and checks that one tick dispatches it to the vector.
+    fn assert_interrupt_is_dispatched_to(if_bit: u8, vector: u16) {
+        let mut cpu = Cpu::new();
+        cpu.registers.pc = 0xC000;
+        cpu.registers.sp = 0xDFFE;
+        cpu.ime = true;
+        cpu.memory_bus.set_ie_register(0x1F);
+        cpu.memory_bus.write_byte(IF, if_bit);
+        let clock_cycles = cpu.clock_cycles;
+
+        assert_eq!(cpu.tick(), TickResult::InterruptHandled);
+
+        assert_eq!(cpu.registers.pc, vector);
+        assert!(!cpu.ime, "IME should be disabled by the dispatch");
+        assert_eq!(cpu.memory_bus.read_byte(IF) & 0x1F, 0, "The IF bit should be cleared");
+        assert_eq!(cpu.clock_cycles - clock_cycles, 20, "The dispatch should take 20 T-cycles");
+        assert_eq!(cpu.pop_value_from_sp(), 0xC000, "Return address should be pushed to the stack");
+    }
+
+    #[test]
+    fn test_vblank_interrupt_is_dispatched_to_0x40() {
+        assert_interrupt_is_dispatched_to(0b0000_0001, VBLANK_INTERRUT_HANDLER_ADDRESS);
+    }
+
+    #[test]
+    fn test_lcd_stat_interrupt_is_dispatched_to_0x48() {
+        assert_interrupt_is_dispatched_to(0b0000_0010, LCD_STAT_INTERRUPT_HANDLER_ADDRESS);
+    }
+
+    #[test]
+    fn test_timer_interrupt_is_dispatched_to_0x50() {
+        assert_interrupt_is_dispatched_to(0b0000_0100, TIMER_INTERRUPT_HANDLER_ADDRESS);
+    }
+
+    #[test]
+    fn test_serial_interrupt_is_dispatched_to_0x58() {
+        assert_interrupt_is_dispatched_to(0b0000_1000, SERIAL_INTERRUPT_HANDLER_ADDRESS);
+    }
+
+    #[test]
+    fn test_joypad_interrupt_is_dispatched_to_0x60() {
+        assert_interrupt_is_dispatched_to(0b0001_0000, JOYPAD_INTERRUPT_HANDLER_ADDRESS);
+    }
+
+    #[test]
+    fn test_pending_interrupts_are_dispatched_by_priority() {
+        let mut cpu = Cpu::new();
+        cpu.registers.pc = 0xC000;
+        cpu.registers.sp = 0xDFFE;
+        cpu.memory_bus.set_ie_register(0x1F);
+        cpu.memory_bus.write_byte(IF, 0b0001_1010);
+
+        for vector in [LCD_STAT_INTERRUPT_HANDLER_ADDRESS, SERIAL_INTERRUPT_HANDLER_ADDRESS, JOYPAD_INTERRUPT_HANDLER_ADDRESS] {
+            cpu.ime = true;
+            cpu.tick();
+            assert_eq!(cpu.registers.pc, vector);
+        }
+    }
+
+    #[test]
+    fn test_no_interrupt_is_dispatched_when_none_is_enabled() {
+        let mut cpu = Cpu::new();
+        cpu.registers.pc = 0xC000;
+        cpu.ime = true;
+        cpu.memory_bus.set_ie_register(0x00);
+        cpu.memory_bus.write_byte(IF, 0b0001_1111);
+        // NOP
+        cpu.memory_bus.write_byte(0xC000, 0x00);
+
+        cpu.tick();
+
+        assert_eq!(cpu.registers.pc, 0xC001);
+        assert!(cpu.ime);
+    }
+
+    #[test]
+    fn test_halt_without_pending_interrupt_enters_halt_mode() {
+        // HALT; INC A
+        let mut cpu = cpu_with_code(&[0x76, 0x3C]);
+        cpu.memory_bus.set_ie_register(0x04);
+
+        cpu.tick();
+
+        assert!(cpu.is_halt_mode);
+        assert!(!cpu.is_halt_bug_pending);
+        assert_eq!(cpu.registers.pc, 0x0101);
+    }
+
+    #[test]
+    fn test_halt_bug_reads_the_next_byte_twice() {
+        // HALT; INC A; NOP
+        let mut cpu = cpu_with_code(&[0x76, 0x3C, 0x00]);
+        cpu.registers.a = 0;
+        cpu.ime = false;
+        cpu.memory_bus.set_ie_register(0x04);
+        cpu.memory_bus.write_byte(IF, 0x04);
+
+        cpu.tick();
+        assert!(!cpu.is_halt_mode, "The CPU should not halt when the HALT bug happens");
+        assert!(cpu.is_halt_bug_pending);
+        assert_eq!(cpu.registers.pc, 0x0101);
+
+        cpu.tick();
+        assert_eq!(cpu.registers.pc, 0x0101, "PC should not be incremented after the first fetch of INC A");
+        assert_eq!(cpu.registers.a, 1);
+        assert!(!cpu.is_halt_bug_pending);
+
+        cpu.tick();
+        assert_eq!(cpu.registers.pc, 0x0102);
+        assert_eq!(cpu.registers.a, 2, "INC A should be executed twice");
+
+        cpu.tick();
+        assert_eq!(cpu.registers.pc, 0x0103);
+    }
+
+    #[test]
+    fn test_halt_with_ime_and_pending_interrupt_does_not_trigger_halt_bug() {
+        let mut cpu = Cpu::new();
+        cpu.ime = true;
+        cpu.memory_bus.set_ie_register(0x04);
+        cpu.memory_bus.write_byte(IF, 0x04);
+
+        // HALT is executed directly, through tick the pending interrupt would be dispatched before it
+        cpu.execute(0x76);
+
+        assert!(!cpu.is_halt_mode, "The pending interrupt should be serviced instead of halting");
+        assert!(!cpu.is_halt_bug_pending);
+    }
+
+    #[test]
+    fn test_halt_keeps_the_ppu_running_and_resumes_when_an_interrupt_is_requested() {
+        // HALT; INC A
+        let mut cpu = cpu_with_code(&[0x76, 0x3C]);
+        cpu.registers.a = 0;
+        cpu.ime = false;
+        cpu.memory_bus.set_ie_register(0x04);
+        // The PPU only advances with the LCD on
+        cpu.memory_bus.write_byte(LCDC, 0x91);
+
+        cpu.tick();
+        assert!(cpu.is_halt_mode);
+
+        for _ in 0..3 {
+            let clock_cycles = cpu.clock_cycles;
+            let dots = cpu.ppu.dots;
+
+            assert_eq!(cpu.tick(), TickResult::Halted);
+            assert_eq!(cpu.clock_cycles, clock_cycles + 4);
+            assert_eq!(cpu.ppu.dots, dots + 4, "The PPU should keep running while the CPU is halted");
+            assert_eq!(cpu.registers.pc, 0x0101);
+        }
+
+        // With IME disabled the CPU leaves halt mode and continues after HALT without dispatching the interrupt
+        cpu.memory_bus.write_byte(IF, 0x04);
+        assert_eq!(cpu.tick(), TickResult::Executed);
+        assert!(!cpu.is_halt_mode);
+        assert_eq!(cpu.registers.a, 1);
+        assert_eq!(cpu.registers.pc, 0x0102);
+    }
+
+    #[test]
+    fn test_halt_with_ime_dispatches_the_interrupt_that_wakes_the_cpu() {
+        // HALT; INC A
+        let mut cpu = cpu_with_code(&[0x76, 0x3C]);
+        cpu.registers.sp = 0xDFFE;
+        cpu.ime = true;
+        cpu.memory_bus.set_ie_register(0x04);
+
+        cpu.tick();
+        assert_eq!(cpu.tick(), TickResult::Halted);
+
+        cpu.memory_bus.write_byte(IF, 0x04);
+        assert_eq!(cpu.tick(), TickResult::InterruptHandled);
+
+        assert!(!cpu.is_halt_mode);
+        assert_eq!(cpu.registers.pc, TIMER_INTERRUPT_HANDLER_ADDRESS);
+        assert_eq!(cpu.pop_value_from_sp(), 0x0101, "RETI should return to the instruction after HALT");
+    }
+
+    #[test]
+    fn test_halt_without_interrupts_stays_halted() {
+        // HALT; INC A
+        let mut cpu = cpu_with_code(&[0x76, 0x3C]);
+        cpu.registers.a = 0;
+        // Only the joypad interrupt is enabled and no button is pressed
+        cpu.memory_bus.set_ie_register(0x10);
+
+        cpu.tick();
+        for _ in 0..100_000 {
+            assert_eq!(cpu.tick(), TickResult::Halted);
+        }
+
+        assert!(cpu.is_halt_mode);
+        assert_eq!(cpu.registers.pc, 0x0101);
+        assert_eq!(cpu.registers.a, 0);
+    }
+
+    #[test]
+    fn test_vblank_interrupt_is_requested_in_the_if_register() {
+        assert_eq!(IF, 0xFF0F);
+
+        let mut cpu = Cpu::new();
+        cpu.initialize_memory_registers();
+        cpu.memory_bus.write_byte(IF, 0x00);
+        cpu.memory_bus.write_byte(P1, 0x20);
+
+        while cpu.memory_bus.read_byte(LY) != 144 {
+            cpu.update_ppu();
+        }
+
+        assert_eq!(cpu.memory_bus.read_byte(0xFF0F) & 0b0000_0001, 0b0000_0001, "VBlank flag should be requested");
+        assert_eq!(cpu.memory_bus.read_byte(P1), 0xEF, "The joypad register should be left untouched");
+
+        cpu.memory_bus.write_byte(0xFF0F, 0x00);
+        assert_eq!(cpu.memory_bus.read_byte(0xFF0F) & 0b0000_0001, 0, "VBlank flag should be cleared");
+    }
+}
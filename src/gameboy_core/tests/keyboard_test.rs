@@ -0,0 +1,101 @@
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use minifb::Key;
+
+    use crate::{
+        config::Config,
+        gameboy_core::{
+            components::keyboard::{self, InputMapping, KeyboardInput},
+            cpu_components::MemoryBus,
+            joypad::JoypadButton,
+        },
+    };
+
+    /// Keys that went down and up since the last window update.
+    #[derive(Default)]
+    struct MockKeyboard {
+        pressed: Vec<Key>,
+        released: Vec<Key>,
+    }
+
+    impl KeyboardInput for MockKeyboard {
+        fn is_key_pressed(&self, key: Key) -> bool {
+            self.pressed.contains(&key)
+        }
+
+        fn is_key_released(&self, key: Key) -> bool {
+            self.released.contains(&key)
+        }
+    }
+
+    #[test]
+    fn test_default_mapping() {
+        let mapping = InputMapping::default();
+
+        assert_eq!(mapping.get_key(JoypadButton::Up), Some(Key::Up));
+        assert_eq!(mapping.get_key(JoypadButton::Right), Some(Key::Right));
+        assert_eq!(mapping.get_key(JoypadButton::A), Some(Key::Z));
+        assert_eq!(mapping.get_key(JoypadButton::B), Some(Key::X));
+        assert_eq!(mapping.get_key(JoypadButton::Start), Some(Key::Enter));
+        assert_eq!(mapping.get_key(JoypadButton::Select), Some(Key::Backspace));
+    }
+
+    #[test]
+    fn test_default_config_bindings_match_default_mapping() {
+        let mapping = InputMapping::from_bindings(&Config::default().key_bindings);
+
+        assert_eq!(mapping, InputMapping::default());
+    }
+
+    #[test]
+    fn test_config_bindings_override_default_mapping() {
+        let bindings: HashMap<String, String> = [("A", "k"), ("B", "Space"), ("Start", "Unknown"), ("Turbo", "T")]
+            .iter()
+            .map(|(button, key)| (button.to_string(), key.to_string()))
+            .collect();
+
+        let mapping = InputMapping::from_bindings(&bindings);
+
+        assert_eq!(mapping.get_key(JoypadButton::A), Some(Key::K));
+        assert_eq!(mapping.get_key(JoypadButton::B), Some(Key::Space));
+        assert_eq!(mapping.get_key(JoypadButton::Start), Some(Key::Enter));
+        assert_eq!(keyboard::key_from_name("7"), Some(Key::Key7));
+        assert_eq!(keyboard::key_from_name("AB"), None);
+    }
+
+    #[test]
+    fn test_poll_input_presses_and_releases_buttons() {
+        let mapping = InputMapping::default();
+        let mut memory_bus = MemoryBus::new();
+
+        let keyboard = MockKeyboard {
+            pressed: vec![Key::Z, Key::Left],
+            ..Default::default()
+        };
+        keyboard::poll_input(&keyboard, &mut memory_bus, &mapping);
+        assert_eq!(memory_bus.joypad_state.get_pressed_buttons(), 0b0001_0010);
+
+        // Held keys don't change anything, released keys release their button
+        keyboard::poll_input(&MockKeyboard::default(), &mut memory_bus, &mapping);
+        assert_eq!(memory_bus.joypad_state.get_pressed_buttons(), 0b0001_0010);
+        let keyboard = MockKeyboard {
+            released: vec![Key::Z],
+            ..Default::default()
+        };
+        keyboard::poll_input(&keyboard, &mut memory_bus, &mapping);
+        assert!(!memory_bus.joypad_state.is_pressed(JoypadButton::A));
+        assert!(memory_bus.joypad_state.is_pressed(JoypadButton::Left));
+    }
+
+    #[test]
+    fn test_poll_input_keeps_the_buttons_held_on_a_controller() {
+        let mut memory_bus = MemoryBus::new();
+        memory_bus.set_joypad_button(JoypadButton::Start, true);
+
+        keyboard::poll_input(&MockKeyboard::default(), &mut memory_bus, &InputMapping::default());
+
+        assert!(memory_bus.joypad_state.is_pressed(JoypadButton::Start));
+    }
+}
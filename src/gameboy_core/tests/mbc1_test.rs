@@ -0,0 +1,226 @@
+#[cfg(test)]
+mod tests {
+    use std::{fs, path::PathBuf};
+
+    use crate::gameboy_core::{
+        constants::{CARTRIDGE_TYPE_ADDRESS, EXTERNAL_RAM_BANK_SIZE, RAM_SIZE_ADDRESS, ROM_BANK_SIZE},
+        cpu::Cpu,
+        mbc::{self, Mbc, mbc1::Mbc1},
+    };
+
+    /// Creates an MBC1 ROM with the given number of banks and RAM size code. The first byte of each bank is its number.
+    fn create_mbc1_rom(bank_count: usize, ram_size: u8) -> Vec<u8> {
+        let mut rom_binary = vec![0; bank_count * ROM_BANK_SIZE];
+        for bank in 0..bank_count {
+            rom_binary[bank * ROM_BANK_SIZE] = bank as u8;
+        }
+        rom_binary[CARTRIDGE_TYPE_ADDRESS] = 0x03; // MBC1 + RAM + Battery
+        rom_binary[RAM_SIZE_ADDRESS] = ram_size;
+        rom_binary
+    }
+
+    #[test]
+    fn test_rom_bank_switching() {
+        let mut mbc1 = Mbc1::new(&create_mbc1_rom(32, 0x00));
+        assert_eq!(mbc1.get_rom_bank(), 1);
+        assert_eq!(mbc1.read_rom(0x4000), 1);
+
+        mbc1.write_register(0x2000, 0x05);
+        assert_eq!(mbc1.read_rom(0x4000), 5);
+        mbc1.write_register(0x3FFF, 0x1F);
+        assert_eq!(mbc1.read_rom(0x4000), 31);
+        assert_eq!(mbc1.read_rom(0x0000), 0, "bank 0 is always mapped at $0000-$3FFF in mode 0");
+
+        // Only the lower 5 bits are used
+        mbc1.write_register(0x2000, 0xE3);
+        assert_eq!(mbc1.get_rom_bank(), 3);
+    }
+
+    #[test]
+    fn test_selecting_rom_bank_0_selects_bank_1() {
+        let mut mbc1 = Mbc1::new(&create_mbc1_rom(4, 0x00));
+        mbc1.write_register(0x2000, 0x02);
+
+        mbc1.write_register(0x2000, 0x00);
+        assert_eq!(mbc1.get_rom_bank(), 1);
+        assert_eq!(mbc1.read_rom(0x4000), 1);
+
+        // The zero check only uses the lower 5 bits
+        mbc1.write_register(0x2000, 0x20);
+        assert_eq!(mbc1.read_rom(0x4000), 1);
+    }
+
+    #[test]
+    fn test_upper_rom_bank_bits() {
+        let mut mbc1 = Mbc1::new(&create_mbc1_rom(128, 0x00));
+
+        mbc1.write_register(0x2000, 0x01);
+        mbc1.write_register(0x4000, 0x02);
+        assert_eq!(mbc1.get_rom_bank(), 0x41);
+        assert_eq!(mbc1.read_rom(0x4000), 0x41);
+
+        // Bank 0x40 can't be mapped at $4000-$7FFF, 0x41 is mapped instead
+        mbc1.write_register(0x2000, 0x00);
+        assert_eq!(mbc1.read_rom(0x4000), 0x41);
+
+        // Only the lower 2 bits of the register are used
+        mbc1.write_register(0x5FFF, 0xFF);
+        mbc1.write_register(0x2000, 0x1F);
+        assert_eq!(mbc1.read_rom(0x4000), 0x7F);
+    }
+
+    #[test]
+    fn test_banking_mode_1_maps_upper_banks_at_0x0000() {
+        let mut mbc1 = Mbc1::new(&create_mbc1_rom(128, 0x00));
+        mbc1.write_register(0x4000, 0x01);
+        assert_eq!(mbc1.read_rom(0x0000), 0, "mode 0 always maps bank 0");
+
+        mbc1.write_register(0x6000, 0x01);
+        assert_eq!(mbc1.get_banking_mode(), 1);
+        assert_eq!(mbc1.read_rom(0x0000), 0x20);
+
+        mbc1.write_register(0x7FFF, 0x00);
+        assert_eq!(mbc1.get_banking_mode(), 0);
+        assert_eq!(mbc1.read_rom(0x0000), 0);
+    }
+
+    #[test]
+    fn test_ram_enable() {
+        let mut mbc1 = Mbc1::new(&create_mbc1_rom(4, 0x02));
+        mbc1.write_ram(0xA000, 0x12);
+        assert_eq!(mbc1.read_ram(0xA000), 0xFF, "the RAM is disabled at power on");
+
+        mbc1.write_register(0x1FFF, 0x3A);
+        assert!(mbc1.is_ram_enabled(), "only the lower 4 bits are checked");
+        mbc1.write_ram(0xA000, 0x12);
+        assert_eq!(mbc1.read_ram(0xA000), 0x12);
+
+        mbc1.write_register(0x0000, 0x00);
+        assert!(!mbc1.is_ram_enabled());
+        assert_eq!(mbc1.read_ram(0xA000), 0xFF);
+    }
+
+    #[test]
+    fn test_ram_banks_are_only_switched_in_banking_mode_1() {
+        let mut mbc1 = Mbc1::new(&create_mbc1_rom(4, 0x03)); // 4 RAM banks
+        mbc1.write_register(0x0000, 0x0A);
+        mbc1.write_ram(0xA000, 0x11);
+
+        // In mode 0 bank 0 is always mapped
+        mbc1.write_register(0x4000, 0x02);
+        assert_eq!(mbc1.get_ram_bank(), 0);
+        assert_eq!(mbc1.read_ram(0xA000), 0x11);
+
+        mbc1.write_register(0x6000, 0x01);
+        assert_eq!(mbc1.get_ram_bank(), 2);
+        assert_eq!(mbc1.read_ram(0xA000), 0x00);
+        mbc1.write_ram(0xBFFF, 0x22);
+
+        mbc1.write_register(0x4000, 0x00);
+        assert_eq!(mbc1.read_ram(0xA000), 0x11);
+        mbc1.write_register(0x4000, 0x02);
+        assert_eq!(mbc1.read_ram(0xBFFF), 0x22);
+    }
+
+    #[test]
+    fn test_cartridge_without_ram_reads_0xff() {
+        let mut mbc1 = Mbc1::new(&create_mbc1_rom(2, 0x00));
+        mbc1.write_register(0x0000, 0x0A);
+        mbc1.write_ram(0xA000, 0x12);
+        assert_eq!(mbc1.read_ram(0xA000), 0xFF);
+    }
+
+    #[test]
+    fn test_start_accepts_mbc1_roms() {
+        for cartridge_type in 0x01..=0x03 {
+            assert!(mbc::is_mbc_implemented(cartridge_type));
+        }
+
+        // 64 KiB is too big to be mapped without an MBC
+        let mut cpu = Cpu::start(create_mbc1_rom(4, 0x02), false).expect("MBC1 ROMs are supported");
+        assert!(cpu.memory_bus.has_mbc());
+        cpu.memory_bus.write_byte(0x2000, 0x03);
+        assert_eq!(cpu.memory_bus.read_byte(0x4000), 3);
+
+        cpu.memory_bus.write_byte(0x0000, 0x0A);
+        cpu.memory_bus.write_byte(0xA000, 0x55);
+        assert_eq!(cpu.memory_bus.read_byte(0xA000), 0x55);
+    }
+
+    /// Path of a file in a temporary folder unique to the test.
+    fn get_temp_path(test_name: &str, file_name: &str) -> PathBuf {
+        let folder = std::env::temp_dir().join(format!("gameboy_emulator_{}_{}", test_name, std::process::id()));
+        fs::create_dir_all(&folder).unwrap();
+        folder.join(file_name)
+    }
+
+    #[test]
+    fn test_save_and_load_ram_round_trip() {
+        let path = get_temp_path("mbc1_save", "game.sav");
+        let mut mbc1 = Mbc1::new(&create_mbc1_rom(4, 0x02)); // 8 KiB of RAM
+        assert!(mbc1.has_battery());
+        mbc1.write_register(0x0000, 0x0A);
+        for address in 0xA000..=0xBFFF_u16 {
+            mbc1.write_ram(address, (address % 251) as u8);
+        }
+
+        mbc1.save_ram(&path).unwrap();
+        assert_eq!(fs::metadata(&path).unwrap().len(), EXTERNAL_RAM_BANK_SIZE as u64);
+
+        let mut loaded = Mbc1::new(&create_mbc1_rom(4, 0x02));
+        loaded.load_ram(&path).unwrap();
+        fs::remove_dir_all(path.parent().unwrap()).unwrap();
+
+        loaded.write_register(0x0000, 0x0A);
+        for address in 0xA000..=0xBFFF_u16 {
+            assert_eq!(loaded.read_ram(address), (address % 251) as u8, "address {:04X}", address);
+        }
+    }
+
+    #[test]
+    fn test_save_file_is_a_dump_of_all_ram_banks() {
+        let mut mbc1 = Mbc1::new(&create_mbc1_rom(4, 0x03));
+        mbc1.write_register(0x0000, 0x0A);
+        mbc1.write_register(0x6000, 0x01);
+        for bank in 0..4 {
+            mbc1.write_register(0x4000, bank);
+            mbc1.write_ram(0xA000, bank + 1);
+        }
+
+        let data = mbc1.get_save_data();
+        assert_eq!(data.len(), 4 * EXTERNAL_RAM_BANK_SIZE);
+        for bank in 0..4 {
+            assert_eq!(data[bank * EXTERNAL_RAM_BANK_SIZE], bank as u8 + 1);
+        }
+
+        // Without battery there is nothing to save
+        let mut rom_binary = create_mbc1_rom(4, 0x03);
+        rom_binary[CARTRIDGE_TYPE_ADDRESS] = 0x02;
+        assert!(!Mbc1::new(&rom_binary).has_battery());
+    }
+
+    #[test]
+    fn test_save_file_next_to_the_rom_is_loaded_and_written() {
+        let rom_path = get_temp_path("cpu_save_game", "game.gb");
+        let save_path = rom_path.with_extension("sav");
+        fs::write(&rom_path, create_mbc1_rom(4, 0x02)).unwrap();
+        let mut save_data = vec![0; EXTERNAL_RAM_BANK_SIZE];
+        save_data[0] = 0x42;
+        fs::write(&save_path, &save_data).unwrap();
+
+        let mut cpu = Cpu::new();
+        cpu.load_rom_from_file(&rom_path).unwrap();
+        cpu.memory_bus.write_byte(0x0000, 0x0A);
+        assert_eq!(cpu.memory_bus.read_byte(0xA000), 0x42);
+
+        cpu.memory_bus.write_byte(0xA001, 0x24);
+        cpu.reset();
+        cpu.memory_bus.write_byte(0x0000, 0x0A);
+        assert_eq!(cpu.memory_bus.read_byte(0xA001), 0x24, "the battery keeps the RAM on a hard reset");
+
+        cpu.save_game().unwrap();
+        let saved = fs::read(&save_path).unwrap();
+        fs::remove_dir_all(rom_path.parent().unwrap()).unwrap();
+        assert_eq!(saved[..2], [0x42, 0x24]);
+    }
+}
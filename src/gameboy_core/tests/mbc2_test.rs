@@ -0,0 +1,158 @@
+#[cfg(test)]
+mod tests {
+    use crate::gameboy_core::{
+        constants::{CARTRIDGE_TYPE_ADDRESS, ROM_BANK_SIZE},
+        cpu::Cpu,
+        cpu_components::MemoryBus,
+        mbc::{
+            self, Mbc,
+            mbc2::{MBC2_RAM_SIZE, Mbc2},
+        },
+    };
+
+    /// Creates an MBC2 ROM with the given number of banks, the first byte of each bank is its number.
+    fn create_mbc2_rom(bank_count: usize) -> Vec<u8> {
+        let mut rom_binary = vec![0; bank_count * ROM_BANK_SIZE];
+        for bank in 0..bank_count {
+            rom_binary[bank * ROM_BANK_SIZE] = bank as u8;
+        }
+        rom_binary[CARTRIDGE_TYPE_ADDRESS] = 0x06; // MBC2 + Battery
+        rom_binary
+    }
+
+    #[test]
+    fn test_rom_bank_switching() {
+        let mut mbc2 = Mbc2::new(&create_mbc2_rom(16));
+        assert_eq!(mbc2.get_rom_bank(), 1);
+        assert_eq!(mbc2.read_rom(0x0000), 0, "bank 0 is always mapped at $0000-$3FFF");
+        assert_eq!(mbc2.read_rom(0x4000), 1);
+
+        // Bit 8 of the address set selects the ROM bank
+        mbc2.write_register(0x2100, 0x05);
+        assert_eq!(mbc2.get_rom_bank(), 5);
+        assert_eq!(mbc2.read_rom(0x4000), 5);
+        assert_eq!(mbc2.read_rom(0x0000), 0);
+
+        // Only the lower 4 bits are used and bank 0 selects bank 1
+        mbc2.write_register(0x3F00, 0xF7);
+        assert_eq!(mbc2.get_rom_bank(), 7);
+        mbc2.write_register(0x2100, 0x00);
+        assert_eq!(mbc2.get_rom_bank(), 1);
+        mbc2.write_register(0x2100, 0x10);
+        assert_eq!(mbc2.get_rom_bank(), 1);
+    }
+
+    #[test]
+    fn test_rom_bank_wraps_around_the_rom_size() {
+        let mut mbc2 = Mbc2::new(&create_mbc2_rom(4));
+        mbc2.write_register(0x2100, 0x06);
+        assert_eq!(mbc2.read_rom(0x4000), 2);
+    }
+
+    #[test]
+    fn test_ram_enable() {
+        let mut mbc2 = Mbc2::new(&create_mbc2_rom(2));
+        assert!(!mbc2.is_ram_enabled());
+        mbc2.write_ram(0xA000, 0x05);
+        assert_eq!(mbc2.read_ram(0xA000), 0xFF, "the RAM is disabled at power on");
+
+        // Bit 8 of the address clear enables the RAM when the value is 0x0A
+        mbc2.write_register(0x0000, 0x0A);
+        assert!(mbc2.is_ram_enabled());
+        mbc2.write_ram(0xA000, 0x05);
+        assert_eq!(mbc2.read_ram(0xA000), 0xF5);
+
+        // A write with bit 8 set selects the ROM bank and leaves the RAM enabled
+        mbc2.write_register(0x0100, 0x00);
+        assert!(mbc2.is_ram_enabled());
+        assert_eq!(mbc2.get_rom_bank(), 1);
+
+        mbc2.write_register(0x3E00, 0x00);
+        assert!(!mbc2.is_ram_enabled());
+        assert_eq!(mbc2.read_ram(0xA000), 0xFF);
+
+        // Only the lower 4 bits of the value are checked
+        mbc2.write_register(0x0000, 0xFA);
+        assert!(mbc2.is_ram_enabled());
+        assert_eq!(mbc2.read_ram(0xA000), 0xF5, "the RAM keeps its contents while disabled");
+    }
+
+    #[test]
+    fn test_bit_8_of_the_address_selects_the_register_in_the_whole_range() {
+        let mut mbc2 = Mbc2::new(&create_mbc2_rom(16));
+
+        for page in 0x00..0x40u16 {
+            let address = page << 8;
+            mbc2.write_register(address, 0x0A);
+            mbc2.write_register(address, 0x03);
+
+            if address & 0x0100 == 0 {
+                assert!(!mbc2.is_ram_enabled(), "{:#06X} should write the RAM enable register", address);
+                assert_eq!(mbc2.get_rom_bank(), 1, "{:#06X} should not change the ROM bank", address);
+            } else {
+                assert_eq!(mbc2.get_rom_bank(), 3, "{:#06X} should write the ROM bank register", address);
+                mbc2.write_register(0x2100, 0x01);
+            }
+        }
+    }
+
+    #[test]
+    fn test_writes_above_0x3fff_are_ignored() {
+        let mut mbc2 = Mbc2::new(&create_mbc2_rom(16));
+
+        for address in [0x4000, 0x4100, 0x5F00, 0x7FFF] {
+            mbc2.write_register(address, 0x0A);
+            mbc2.write_register(address, 0x05);
+        }
+
+        assert!(!mbc2.is_ram_enabled());
+        assert_eq!(mbc2.get_rom_bank(), 1);
+    }
+
+    #[test]
+    fn test_ram_stores_half_bytes_and_echoes() {
+        let mut mbc2 = Mbc2::new(&create_mbc2_rom(2));
+        mbc2.write_register(0x0000, 0x0A);
+
+        mbc2.write_ram(0xA1FF, 0xAB);
+        assert_eq!(mbc2.read_ram(0xA1FF), 0xFB, "only the lower 4 bits are stored, the upper ones read as 1");
+
+        // The 512 half-bytes are repeated through $A000-$BFFF
+        assert_eq!(mbc2.read_ram(0xA1FF + MBC2_RAM_SIZE as u16), 0xFB);
+        assert_eq!(mbc2.read_ram(0xBFFF), 0xFB);
+        mbc2.write_ram(0xB000, 0x03);
+        assert_eq!(mbc2.read_ram(0xA000), 0xF3);
+    }
+
+    #[test]
+    fn test_create_mbc() {
+        assert!(mbc::create_mbc(&create_mbc2_rom(2)).is_some());
+        assert!(mbc::create_mbc(&vec![0; 0x8000]).is_none(), "ROM only cartridge");
+        assert!(mbc::is_mbc_implemented(0x05));
+        assert!(!mbc::is_mbc_implemented(0x00));
+    }
+
+    #[test]
+    fn test_memory_bus_routes_the_rom_and_ram_areas_to_the_mbc() {
+        let mut memory_bus = MemoryBus::new();
+        memory_bus.copy_from_binary(create_mbc2_rom(8));
+        assert!(memory_bus.has_mbc());
+
+        memory_bus.write_byte(0x2100, 0x03);
+        assert_eq!(memory_bus.read_byte(0x4000), 3);
+        assert_eq!(memory_bus.read_byte(0x2100), 0x00, "writes to the ROM area don't change the ROM");
+
+        memory_bus.write_byte(0xA010, 0x09);
+        assert_eq!(memory_bus.read_byte(0xA010), 0xFF);
+        memory_bus.write_byte(0x0000, 0x0A);
+        memory_bus.write_byte(0xA010, 0x09);
+        assert_eq!(memory_bus.read_byte(0xA010), 0xF9);
+    }
+
+    #[test]
+    fn test_start_accepts_mbc2_roms_bigger_than_32kb() {
+        let cpu = Cpu::start(create_mbc2_rom(16), false).expect("MBC2 ROMs are supported");
+        assert!(cpu.memory_bus.has_mbc());
+        assert_eq!(cpu.memory_bus.read_byte(0x4000), 1);
+    }
+}
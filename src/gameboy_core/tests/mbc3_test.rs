@@ -0,0 +1,264 @@
+#[cfg(test)]
+mod tests {
+    use crate::gameboy_core::{
+        constants::{CARTRIDGE_TYPE_ADDRESS, EXTERNAL_RAM_BANK_SIZE, RAM_SIZE_ADDRESS, ROM_BANK_SIZE},
+        cpu::Cpu,
+        mbc::{
+            self, Mbc,
+            mbc3::{Mbc3, RTC_SAVE_SIZE, Rtc, RtcRegisters},
+        },
+    };
+
+    const SECONDS_PER_DAY: u64 = 24 * 60 * 60;
+
+    /// Creates an MBC3 ROM with the given number of banks and RAM size code. The first byte of each bank is its number.
+    fn create_mbc3_rom(bank_count: usize, ram_size: u8) -> Vec<u8> {
+        let mut rom_binary = vec![0; bank_count * ROM_BANK_SIZE];
+        for bank in 0..bank_count {
+            rom_binary[bank * ROM_BANK_SIZE] = bank as u8;
+        }
+        rom_binary[CARTRIDGE_TYPE_ADDRESS] = 0x10; // MBC3 + Timer + RAM + Battery
+        rom_binary[RAM_SIZE_ADDRESS] = ram_size;
+        rom_binary
+    }
+
+    /// Creates an MBC3 with RTC and 4 RAM banks, with the RAM and the RTC enabled.
+    fn create_enabled_mbc3() -> Mbc3 {
+        let mut mbc3 = Mbc3::new(&create_mbc3_rom(4, 0x03), true);
+        mbc3.write_register(0x0000, 0x0A);
+        mbc3
+    }
+
+    fn latch(mbc3: &mut Mbc3) {
+        mbc3.write_register(0x6000, 0x00);
+        mbc3.write_register(0x6000, 0x01);
+    }
+
+    /// Reads an RTC register (0x08-0x0C) through the external RAM area.
+    fn read_rtc_register(mbc3: &mut Mbc3, register: u8) -> u8 {
+        mbc3.write_register(0x4000, register);
+        mbc3.read_ram(0xA000)
+    }
+
+    #[test]
+    fn test_rom_bank_switching() {
+        let mut mbc3 = Mbc3::new(&create_mbc3_rom(128, 0x00), false);
+        assert_eq!(mbc3.read_rom(0x4000), 1);
+
+        mbc3.write_register(0x2000, 0x7F);
+        assert_eq!(mbc3.get_rom_bank(), 0x7F);
+        assert_eq!(mbc3.read_rom(0x4000), 0x7F);
+        assert_eq!(mbc3.read_rom(0x0000), 0);
+
+        mbc3.write_register(0x3FFF, 0x00);
+        assert_eq!(mbc3.get_rom_bank(), 1, "bank 0 selects bank 1");
+    }
+
+    #[test]
+    fn test_ram_banks() {
+        let mut mbc3 = create_enabled_mbc3();
+        mbc3.write_ram(0xA000, 0x11);
+
+        mbc3.write_register(0x4000, 0x03);
+        assert_eq!(mbc3.get_ram_bank(), 3);
+        assert_eq!(mbc3.read_ram(0xA000), 0x00);
+        mbc3.write_ram(0xBFFF, 0x33);
+
+        mbc3.write_register(0x4000, 0x00);
+        assert_eq!(mbc3.read_ram(0xA000), 0x11);
+        mbc3.write_register(0x4000, 0x03);
+        assert_eq!(mbc3.read_ram(0xBFFF), 0x33);
+
+        mbc3.write_register(0x0000, 0x00);
+        assert_eq!(mbc3.read_ram(0xBFFF), 0xFF, "the RAM is disabled");
+    }
+
+    #[test]
+    fn test_rtc_registers_are_read_from_the_latched_copy() {
+        let mut mbc3 = create_enabled_mbc3();
+        mbc3.get_rtc_mut().unwrap().registers.day_high = 0x40; // Halted so the clock doesn't move during the test
+        mbc3.write_register(0x4000, 0x09);
+        mbc3.write_ram(0xA000, 42);
+
+        assert_eq!(read_rtc_register(&mut mbc3, 0x09), 0, "the new value is not latched yet");
+
+        latch(&mut mbc3);
+        assert_eq!(read_rtc_register(&mut mbc3, 0x09), 42);
+        // RTC registers don't touch the RAM banks
+        mbc3.write_register(0x4000, 0x00);
+        assert_eq!(mbc3.read_ram(0xA000), 0x00);
+    }
+
+    #[test]
+    fn test_latch_needs_0x00_then_0x01() {
+        let mut mbc3 = create_enabled_mbc3();
+        mbc3.get_rtc_mut().unwrap().registers.day_high = 0x40;
+        mbc3.write_register(0x4000, 0x08);
+        mbc3.write_ram(0xA000, 30);
+
+        mbc3.write_register(0x6000, 0x01);
+        assert_eq!(read_rtc_register(&mut mbc3, 0x08), 0, "0x01 alone doesn't latch");
+        mbc3.write_register(0x6000, 0x00);
+        mbc3.write_register(0x7FFF, 0x02);
+        mbc3.write_register(0x6000, 0x01);
+        assert_eq!(read_rtc_register(&mut mbc3, 0x08), 0, "the 0x00 must be followed by 0x01");
+
+        latch(&mut mbc3);
+        assert_eq!(read_rtc_register(&mut mbc3, 0x08), 30);
+    }
+
+    #[test]
+    fn test_rtc_counts_seconds_minutes_hours_and_days() {
+        let mut rtc = Rtc::new();
+
+        rtc.advance(SECONDS_PER_DAY + 3600 + 2 * 60 + 3);
+
+        assert_eq!(
+            rtc.registers,
+            RtcRegisters {
+                seconds: 3,
+                minutes: 2,
+                hours: 1,
+                day_low: 1,
+                day_high: 0
+            }
+        );
+
+        rtc.advance(255 * SECONDS_PER_DAY);
+        assert_eq!(rtc.registers.day_low, 0x00);
+        assert_eq!(rtc.registers.day_high, 0x01, "bit 0 of DH is bit 8 of the day counter");
+    }
+
+    #[test]
+    fn test_day_counter_overflows_after_day_511() {
+        let mut rtc = Rtc::new();
+        rtc.registers = RtcRegisters {
+            seconds: 59,
+            minutes: 59,
+            hours: 23,
+            day_low: 0xFF,
+            day_high: 0x01,
+        };
+
+        rtc.advance(1);
+
+        assert_eq!(
+            rtc.registers,
+            RtcRegisters {
+                seconds: 0,
+                minutes: 0,
+                hours: 0,
+                day_low: 0,
+                day_high: 0x80
+            },
+            "the day counter wraps to 0 and sets the carry"
+        );
+
+        // The carry stays set until the game clears it
+        rtc.advance(SECONDS_PER_DAY);
+        assert_eq!(rtc.registers.day_high, 0x80);
+        assert_eq!(rtc.registers.day_low, 1);
+    }
+
+    #[test]
+    fn test_halt_bit_stops_the_rtc() {
+        let mut mbc3 = create_enabled_mbc3();
+        mbc3.write_register(0x4000, 0x0C);
+        mbc3.write_ram(0xA000, 0x40);
+
+        let rtc = mbc3.get_rtc_mut().unwrap();
+        assert!(rtc.is_halted());
+        rtc.advance(SECONDS_PER_DAY);
+        assert_eq!(rtc.registers.day_low, 0);
+        assert_eq!(rtc.registers.seconds, 0);
+
+        mbc3.write_ram(0xA000, 0x00);
+        let rtc = mbc3.get_rtc_mut().unwrap();
+        assert!(!rtc.is_halted());
+        rtc.advance(10);
+        assert_eq!(rtc.registers.seconds, 10);
+    }
+
+    #[test]
+    fn test_rtc_save_bytes_round_trip() {
+        let mut rtc = Rtc::new();
+        rtc.registers = RtcRegisters {
+            seconds: 1,
+            minutes: 2,
+            hours: 3,
+            day_low: 4,
+            day_high: 0x41,
+        };
+        rtc.latched = RtcRegisters {
+            seconds: 5,
+            minutes: 6,
+            hours: 7,
+            day_low: 8,
+            day_high: 0x80,
+        };
+
+        let bytes = rtc.to_save_bytes();
+        assert_eq!(bytes.len(), RTC_SAVE_SIZE);
+        assert_eq!(bytes[4..8], [2, 0, 0, 0], "registers are saved as 32 bits little endian values");
+
+        // The saved clock is halted, so no time is added when it is restored
+        let restored = Rtc::from_save_bytes(&bytes).unwrap();
+        assert_eq!(restored.registers, rtc.registers);
+        assert_eq!(restored.latched, rtc.latched);
+
+        assert!(Rtc::from_save_bytes(&bytes[..RTC_SAVE_SIZE - 1]).is_none());
+    }
+
+    #[test]
+    fn test_save_data_contains_the_ram_banks_and_the_rtc() {
+        let mut mbc3 = create_enabled_mbc3();
+        mbc3.write_register(0x4000, 0x02);
+        mbc3.write_ram(0xA000, 0x77);
+        mbc3.get_rtc_mut().unwrap().registers = RtcRegisters {
+            seconds: 10,
+            minutes: 20,
+            hours: 5,
+            day_low: 3,
+            day_high: 0x40,
+        };
+
+        let data = mbc3.get_save_data();
+        assert_eq!(data.len(), 4 * EXTERNAL_RAM_BANK_SIZE + RTC_SAVE_SIZE);
+        assert_eq!(data[2 * EXTERNAL_RAM_BANK_SIZE], 0x77);
+
+        let mut loaded = Mbc3::new(&create_mbc3_rom(4, 0x03), true);
+        loaded.load_save_data(&data);
+        assert_eq!(loaded.get_rtc().unwrap().registers, mbc3.get_rtc().unwrap().registers);
+        loaded.write_register(0x0000, 0x0A);
+        loaded.write_register(0x4000, 0x02);
+        assert_eq!(loaded.read_ram(0xA000), 0x77);
+
+        // Save files without the RTC state only restore the RAM
+        let mut without_rtc = Mbc3::new(&create_mbc3_rom(4, 0x03), true);
+        without_rtc.load_save_data(&data[..4 * EXTERNAL_RAM_BANK_SIZE]);
+        assert_eq!(without_rtc.get_rtc().unwrap().registers.minutes, 0);
+    }
+
+    #[test]
+    fn test_cartridge_without_timer_reads_0xff_from_rtc_registers() {
+        let mut mbc3 = Mbc3::new(&create_mbc3_rom(4, 0x03), false);
+        mbc3.write_register(0x0000, 0x0A);
+        assert!(mbc3.get_rtc().is_none());
+
+        mbc3.write_register(0x4000, 0x08);
+        mbc3.write_ram(0xA000, 0x12);
+        assert_eq!(mbc3.read_ram(0xA000), 0xFF);
+    }
+
+    #[test]
+    fn test_start_accepts_mbc3_roms() {
+        for cartridge_type in 0x0F..=0x13 {
+            assert!(mbc::is_mbc_implemented(cartridge_type));
+        }
+
+        let mut cpu = Cpu::start(create_mbc3_rom(8, 0x03), false).expect("MBC3 ROMs are supported");
+        assert!(cpu.memory_bus.has_mbc());
+        cpu.memory_bus.write_byte(0x2000, 0x05);
+        assert_eq!(cpu.memory_bus.read_byte(0x4000), 5);
+    }
+}
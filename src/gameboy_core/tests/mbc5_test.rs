@@ -0,0 +1,164 @@
+#[cfg(test)]
+mod tests {
+    use crate::gameboy_core::{
+        constants::{CARTRIDGE_TYPE_ADDRESS, RAM_SIZE_ADDRESS, ROM_BANK_SIZE},
+        cpu::Cpu,
+        mbc::{self, Mbc, mbc5::Mbc5},
+    };
+
+    /// Creates an MBC5 ROM with the given number of banks and RAM size code. The first two bytes of each bank are
+    /// its number, low byte first.
+    fn create_mbc5_rom(bank_count: usize, ram_size: u8) -> Vec<u8> {
+        let mut rom_binary = vec![0; bank_count * ROM_BANK_SIZE];
+        for bank in 0..bank_count {
+            rom_binary[bank * ROM_BANK_SIZE] = bank as u8;
+            rom_binary[bank * ROM_BANK_SIZE + 1] = (bank >> 8) as u8;
+        }
+        rom_binary[CARTRIDGE_TYPE_ADDRESS] = 0x1B; // MBC5 + RAM + Battery
+        rom_binary[RAM_SIZE_ADDRESS] = ram_size;
+        rom_binary
+    }
+
+    /// Bank number written at the start of the bank mapped at $4000-$7FFF.
+    fn read_switchable_bank_number(mbc5: &Mbc5) -> u16 {
+        u16::from_le_bytes([mbc5.read_rom(0x4000), mbc5.read_rom(0x4001)])
+    }
+
+    #[test]
+    fn test_rom_bank_switching() {
+        let mut mbc5 = Mbc5::new(&create_mbc5_rom(32, 0x00));
+        assert_eq!(mbc5.get_rom_bank(), 1);
+        assert_eq!(read_switchable_bank_number(&mbc5), 1);
+
+        mbc5.write_register(0x2000, 0x1F);
+        assert_eq!(read_switchable_bank_number(&mbc5), 31);
+        assert_eq!(mbc5.read_rom(0x0000), 0, "bank 0 is always mapped at $0000-$3FFF");
+
+        // Bank 0 can be mapped at $4000-$7FFF
+        mbc5.write_register(0x2FFF, 0x00);
+        assert_eq!(mbc5.get_rom_bank(), 0);
+        assert_eq!(read_switchable_bank_number(&mbc5), 0);
+    }
+
+    #[test]
+    fn test_switching_to_bank_256_uses_the_high_bit_register() {
+        let mut mbc5 = Mbc5::new(&create_mbc5_rom(512, 0x00));
+
+        mbc5.write_register(0x2000, 0x00);
+        mbc5.write_register(0x3000, 0x01);
+        assert_eq!(mbc5.get_rom_bank(), 256);
+        assert_eq!(read_switchable_bank_number(&mbc5), 256, "first bank of the second half of the ROM");
+
+        // The low byte register keeps the high bit
+        mbc5.write_register(0x2000, 0xFF);
+        assert_eq!(read_switchable_bank_number(&mbc5), 511);
+
+        // Only bit 0 of the high bit register is used
+        mbc5.write_register(0x3000, 0xFE);
+        assert_eq!(read_switchable_bank_number(&mbc5), 255);
+    }
+
+    #[test]
+    fn test_all_512_rom_banks_are_reachable() {
+        let mut mbc5 = Mbc5::new(&create_mbc5_rom(512, 0x00));
+
+        for bank in 0..512u16 {
+            mbc5.write_register(0x2000, bank as u8);
+            mbc5.write_register(0x3000, (bank >> 8) as u8);
+            assert_eq!(mbc5.get_rom_bank(), bank);
+            assert_eq!(read_switchable_bank_number(&mbc5), bank, "Wrong bank mapped at $4000 for bank {}", bank);
+        }
+    }
+
+    #[test]
+    fn test_high_bit_register_is_independent_of_the_low_byte() {
+        let mut mbc5 = Mbc5::new(&create_mbc5_rom(512, 0x00));
+        mbc5.write_register(0x2000, 0x42);
+
+        mbc5.write_register(0x3FFF, 0x01);
+        assert_eq!(mbc5.get_rom_bank(), 0x142, "the high bit keeps the low byte");
+        mbc5.write_register(0x3000, 0x00);
+        assert_eq!(mbc5.get_rom_bank(), 0x042);
+
+        // $3000-$3FFF doesn't change the low byte and $2000-$2FFF doesn't change the high bit
+        mbc5.write_register(0x3000, 0xFF);
+        mbc5.write_register(0x2FFF, 0x07);
+        assert_eq!(mbc5.get_rom_bank(), 0x107);
+    }
+
+    #[test]
+    fn test_each_of_the_16_ram_banks_holds_its_own_data() {
+        let mut mbc5 = Mbc5::new(&create_mbc5_rom(2, 0x04)); // 16 RAM banks
+        mbc5.write_register(0x0000, 0x0A);
+
+        for bank in 0..16u8 {
+            mbc5.write_register(0x4000, bank);
+            mbc5.write_ram(0xA000, bank + 0x10);
+        }
+
+        for bank in 0..16u8 {
+            mbc5.write_register(0x4000, bank);
+            assert_eq!(mbc5.read_ram(0xA000), bank + 0x10, "Wrong data in RAM bank {}", bank);
+        }
+    }
+
+    #[test]
+    fn test_ram_banks() {
+        let mut mbc5 = Mbc5::new(&create_mbc5_rom(2, 0x04)); // 16 RAM banks
+        mbc5.write_ram(0xA000, 0x12);
+        assert_eq!(mbc5.read_ram(0xA000), 0xFF, "the RAM is disabled at power on");
+
+        mbc5.write_register(0x0000, 0x0A);
+        assert!(mbc5.is_ram_enabled());
+        mbc5.write_ram(0xA000, 0x12);
+
+        mbc5.write_register(0x4000, 0x0F);
+        assert_eq!(mbc5.get_ram_bank(), 15);
+        assert_eq!(mbc5.read_ram(0xA000), 0x00);
+        mbc5.write_ram(0xBFFF, 0x34);
+
+        mbc5.write_register(0x5FFF, 0x00);
+        assert_eq!(mbc5.read_ram(0xA000), 0x12);
+        mbc5.write_register(0x4000, 0x0F);
+        assert_eq!(mbc5.read_ram(0xBFFF), 0x34);
+
+        mbc5.write_register(0x1FFF, 0x00);
+        assert!(!mbc5.is_ram_enabled());
+        assert_eq!(mbc5.read_ram(0xBFFF), 0xFF);
+    }
+
+    #[test]
+    fn test_cartridge_without_ram_reads_0xff() {
+        let mut mbc5 = Mbc5::new(&create_mbc5_rom(2, 0x00));
+        mbc5.write_register(0x0000, 0x0A);
+        mbc5.write_ram(0xA000, 0x12);
+        assert_eq!(mbc5.read_ram(0xA000), 0xFF);
+    }
+
+    #[test]
+    fn test_start_accepts_mbc5_roms() {
+        assert!(mbc::is_mbc_implemented(0x19));
+        assert!(mbc::is_mbc_implemented(0x1E));
+
+        let mut cpu = Cpu::start(create_mbc5_rom(4, 0x02), false).expect("MBC5 ROMs are supported");
+        assert!(cpu.memory_bus.has_mbc());
+        cpu.memory_bus.write_byte(0x2000, 0x03);
+        assert_eq!(cpu.memory_bus.read_byte(0x4000), 3);
+    }
+
+    #[test]
+    fn test_cloned_memory_bus_has_its_own_mbc() {
+        let mut cpu = Cpu::start(create_mbc5_rom(4, 0x02), false).unwrap();
+        cpu.memory_bus.write_byte(0x0000, 0x0A);
+        cpu.memory_bus.write_byte(0xA000, 0x11);
+
+        let mut memory_bus = cpu.memory_bus.clone();
+        memory_bus.write_byte(0x2000, 0x03);
+        memory_bus.write_byte(0xA000, 0x22);
+
+        assert_eq!(cpu.memory_bus.read_byte(0x4000), 1, "the original keeps its ROM bank");
+        assert_eq!(cpu.memory_bus.read_byte(0xA000), 0x11, "the original keeps its RAM");
+        assert_eq!(memory_bus.read_byte(0x4000), 3);
+        assert_eq!(memory_bus.read_byte(0xA000), 0x22);
+    }
+}
@@ -0,0 +1,562 @@
+#[cfg(test)]
+mod tests {
+    use crate::gameboy_core::{
+        constants::{OAM_OBJECT_COUNT, OAM_SIZE, OAM_START},
+        cpu::Cpu,
+        cpu_components::{MemoryAccess, MemoryBus},
+        joypad::JoypadButton,
+        ppu::{Object, ObjectPallete, Ppu},
+        registers_contants::{BGP, DMA, HDMA1, HDMA2, HDMA3, HDMA4, HDMA5, IE, IF, LY, OBP0, OBP1, P1, SVBK, WX, WY},
+    };
+
+    /// Memory bus that records every write to the IE register and forwards all accesses to a real `MemoryBus`.
+    struct MockMemoryBus {
+        inner: MemoryBus,
+        ie_writes: Vec<u8>,
+    }
+
+    impl MemoryAccess for MockMemoryBus {
+        fn read_byte(&self, address: u16) -> u8 {
+            self.inner.read_byte(address)
+        }
+
+        fn write_byte(&mut self, address: u16, value: u8) {
+            if address == IE {
+                self.ie_writes.push(value);
+            }
+            self.inner.write_byte(address, value);
+        }
+    }
+
+    #[test]
+    fn test_wram_bank_defaults_to_bank_1() {
+        let memory_bus = MemoryBus::new();
+
+        assert_eq!(memory_bus.get_wram_bank(), 1, "WRAM bank 1 should be selected at power on");
+        assert_eq!(memory_bus.read_byte(SVBK) & 0b0000_0111, 1);
+    }
+
+    #[test]
+    fn test_wram_each_bank_holds_its_own_data() {
+        let mut memory_bus = MemoryBus::new();
+
+        // Write a different value at the same address of every switchable bank (1-7)
+        for bank in 1..8u8 {
+            memory_bus.write_byte(SVBK, bank);
+            memory_bus.write_byte(0xD000, 0x10 + bank);
+            memory_bus.write_byte(0xDFFF, 0x20 + bank);
+        }
+
+        // Read them back, every bank must keep its own value
+        for bank in 1..8u8 {
+            memory_bus.write_byte(SVBK, bank);
+            assert_eq!(memory_bus.get_wram_bank(), bank);
+            assert_eq!(memory_bus.read_byte(0xD000), 0x10 + bank, "Wrong value at 0xD000 for bank {}", bank);
+            assert_eq!(memory_bus.read_byte(0xDFFF), 0x20 + bank, "Wrong value at 0xDFFF for bank {}", bank);
+        }
+    }
+
+    #[test]
+    fn test_wram_bank_0_is_fixed_at_c000() {
+        let mut memory_bus = MemoryBus::new();
+        memory_bus.write_byte(0xC000, 0xAA);
+        memory_bus.write_byte(0xCFFF, 0xBB);
+
+        // Switching the bank must not affect $C000-$CFFF
+        for bank in 0..8u8 {
+            memory_bus.write_byte(SVBK, bank);
+            assert_eq!(memory_bus.read_byte(0xC000), 0xAA);
+            assert_eq!(memory_bus.read_byte(0xCFFF), 0xBB);
+        }
+    }
+
+    #[test]
+    fn test_wram_selecting_bank_0_selects_bank_1() {
+        let mut memory_bus = MemoryBus::new();
+
+        memory_bus.write_byte(SVBK, 1);
+        memory_bus.write_byte(0xD123, 0x42);
+
+        memory_bus.write_byte(SVBK, 3);
+        assert_eq!(memory_bus.read_byte(0xD123), 0x00, "Bank 3 should not see bank 1 data");
+
+        memory_bus.write_byte(SVBK, 0);
+        assert_eq!(memory_bus.get_wram_bank(), 1, "Selecting bank 0 should fall back to bank 1");
+        assert_eq!(memory_bus.read_byte(0xD123), 0x42);
+    }
+
+    #[test]
+    fn test_wram_bank_only_uses_lower_3_bits_of_svbk() {
+        let mut memory_bus = MemoryBus::new();
+
+        memory_bus.write_byte(SVBK, 0b1111_1010);
+        assert_eq!(memory_bus.get_wram_bank(), 2);
+
+        // Bank bits set to 0 with upper bits set still selects bank 1
+        memory_bus.write_byte(SVBK, 0b1111_1000);
+        assert_eq!(memory_bus.get_wram_bank(), 1);
+    }
+
+    /// Writes the source and destination addresses of a VRAM DMA transfer
+    fn set_hdma_addresses(memory_bus: &mut MemoryBus, source: u16, destination: u16) {
+        memory_bus.write_byte(HDMA1, (source >> 8) as u8);
+        memory_bus.write_byte(HDMA2, source as u8);
+        memory_bus.write_byte(HDMA3, (destination >> 8) as u8);
+        memory_bus.write_byte(HDMA4, destination as u8);
+    }
+
+    #[test]
+    fn test_gdma_copies_all_data_at_once() {
+        let mut memory_bus = MemoryBus::new();
+        for offset in 0..0x40u16 {
+            memory_bus.write_byte(0xC000 + offset, offset as u8 + 1);
+        }
+
+        set_hdma_addresses(&mut memory_bus, 0xC000, 0x8800);
+        // Bit 7 = 0 (General-Purpose DMA), length = (0x03 + 1) * 16 = 0x40 bytes
+        memory_bus.write_byte(HDMA5, 0x03);
+
+        for offset in 0..0x40u16 {
+            assert_eq!(memory_bus.read_byte(0x8800 + offset), offset as u8 + 1, "Wrong value at offset {:#X}", offset);
+        }
+        assert_eq!(memory_bus.read_byte(0x8840), 0, "GDMA should not copy more than the requested length");
+        assert_eq!(memory_bus.read_byte(HDMA5), 0xFF, "HDMA5 should read 0xFF once the transfer is completed");
+    }
+
+    #[test]
+    fn test_hdma_ignores_lower_4_bits_of_addresses() {
+        let mut memory_bus = MemoryBus::new();
+        memory_bus.write_byte(0xC100, 0xAB);
+
+        set_hdma_addresses(&mut memory_bus, 0xC10F, 0x010F);
+        memory_bus.write_byte(HDMA5, 0x00);
+
+        assert_eq!(memory_bus.read_byte(0x8100), 0xAB);
+    }
+
+    #[test]
+    fn test_hblank_dma_copies_16_bytes_per_hblank() {
+        let mut memory_bus = MemoryBus::new();
+        for offset in 0..0x30u16 {
+            memory_bus.write_byte(0xC000 + offset, 0xA0 + offset as u8);
+        }
+
+        set_hdma_addresses(&mut memory_bus, 0xC000, 0x8000);
+        // Bit 7 = 1 (H-Blank DMA), length = 3 blocks of 16 bytes
+        memory_bus.write_byte(HDMA5, 0x82);
+
+        assert_eq!(memory_bus.read_byte(0x8000), 0, "H-Blank DMA should not copy anything before the first H-Blank");
+        assert_eq!(memory_bus.read_byte(HDMA5), 0x02, "Bit 7 should be 0 while the transfer is active");
+
+        memory_bus.do_hblank_dma_transfer();
+        assert_eq!(memory_bus.read_byte(0x800F), 0xAF);
+        assert_eq!(memory_bus.read_byte(0x8010), 0);
+        assert_eq!(memory_bus.read_byte(HDMA5), 0x01);
+
+        memory_bus.do_hblank_dma_transfer();
+        memory_bus.do_hblank_dma_transfer();
+        assert_eq!(memory_bus.read_byte(0x802F), 0xCF);
+        assert_eq!(memory_bus.read_byte(HDMA5), 0xFF);
+        assert!(!memory_bus.get_hdma_state().is_active);
+    }
+
+    #[test]
+    fn test_hblank_dma_can_be_stopped() {
+        let mut memory_bus = MemoryBus::new();
+        memory_bus.write_byte(0xC010, 0x55);
+
+        set_hdma_addresses(&mut memory_bus, 0xC000, 0x8000);
+        memory_bus.write_byte(HDMA5, 0x81);
+        memory_bus.do_hblank_dma_transfer();
+
+        // Writing bit 7 = 0 while an H-Blank DMA is active stops it
+        memory_bus.write_byte(HDMA5, 0x00);
+        assert_eq!(memory_bus.read_byte(HDMA5), 0x80, "Bit 7 is set and the last block is still remaining");
+
+        memory_bus.do_hblank_dma_transfer();
+        assert_eq!(memory_bus.read_byte(0x8010), 0, "No data should be copied after the H-Blank DMA is stopped");
+    }
+
+    #[test]
+    fn test_hdma5_reads_the_remaining_length_after_a_stopped_hblank_dma() {
+        let mut memory_bus = MemoryBus::new();
+        assert_eq!(memory_bus.read_byte(HDMA5), 0xFF, "No transfer was started");
+
+        set_hdma_addresses(&mut memory_bus, 0xC000, 0x8000);
+        // 8 blocks of 16 bytes, 2 of them copied before the transfer is stopped
+        memory_bus.write_byte(HDMA5, 0x87);
+        memory_bus.do_hblank_dma_transfer();
+        memory_bus.do_hblank_dma_transfer();
+        assert_eq!(memory_bus.read_byte(HDMA5), 0x05);
+
+        memory_bus.write_byte(HDMA5, 0x00);
+        assert!(!memory_bus.get_hdma_state().is_active);
+        assert_eq!(memory_bus.read_byte(HDMA5), 0x80 | 0x05, "6 blocks remain, bit 7 tells the transfer is stopped");
+        assert_eq!(memory_bus.get_hdma_state().remaining_length, 6 * 16);
+    }
+
+    /// Fills $C000-$C09F with values that are different from the offset, so they can't be confused with the OAM ones.
+    fn fill_oam_dma_source(memory_bus: &mut MemoryBus) {
+        for offset in 0..OAM_SIZE as u16 {
+            memory_bus.write_byte(0xC000 + offset, 0xFF - offset as u8);
+        }
+    }
+
+    #[test]
+    fn test_oam_dma_copies_one_byte_per_machine_cycle() {
+        let mut memory_bus = MemoryBus::new();
+        fill_oam_dma_source(&mut memory_bus);
+        assert_eq!(memory_bus.read_byte(DMA), 0xFF);
+
+        memory_bus.write_byte(DMA, 0xC0);
+        assert!(memory_bus.is_oam_dma_active());
+        assert_eq!(memory_bus.read_byte(DMA), 0xC0);
+
+        for _ in 0..OAM_SIZE {
+            assert!(memory_bus.is_oam_dma_active());
+            memory_bus.do_oam_dma_step();
+        }
+        assert!(!memory_bus.is_oam_dma_active());
+
+        for offset in 0..OAM_SIZE as u16 {
+            assert_eq!(memory_bus.read_byte(OAM_START + offset), 0xFF - offset as u8, "Wrong value at offset {}", offset);
+        }
+        assert_eq!(memory_bus.read_byte(OAM_START + OAM_SIZE as u16), 0, "Nothing is copied after OAM");
+    }
+
+    #[test]
+    fn test_oam_dma_blocks_memory_except_ff00_to_ffff() {
+        let mut memory_bus = MemoryBus::new();
+        fill_oam_dma_source(&mut memory_bus);
+        memory_bus.write_byte(0xFF80, 0x12);
+
+        memory_bus.write_byte(DMA, 0xC0);
+
+        assert_eq!(memory_bus.read_byte(0xC000), 0xFF, "WRAM is not readable during the DMA");
+        assert_eq!(memory_bus.read_byte(0x0100), 0xFF, "ROM is not readable during the DMA");
+        memory_bus.write_byte(0xC000, 0x00);
+        assert_eq!(memory_bus.read_byte(0xFF80), 0x12, "HRAM is readable during the DMA");
+        memory_bus.write_byte(0xFFFE, 0x34);
+        assert_eq!(memory_bus.read_byte(0xFFFE), 0x34);
+
+        for _ in 0..OAM_SIZE {
+            memory_bus.do_oam_dma_step();
+        }
+        assert_eq!(memory_bus.read_byte(0xC000), 0xFF, "the write during the DMA was ignored");
+        assert_eq!(memory_bus.read_byte(0xC001), 0xFE);
+    }
+
+    #[test]
+    fn test_oam_dma_takes_160_machine_cycles_of_the_cpu() {
+        let mut cpu = Cpu::new();
+        fill_oam_dma_source(&mut cpu.memory_bus);
+        cpu.memory_bus.write_byte(DMA, 0xC0);
+
+        for _ in 1..OAM_SIZE {
+            cpu.increment_cycles(4);
+        }
+        assert!(cpu.memory_bus.is_oam_dma_active());
+        assert_eq!(cpu.memory_bus.read_byte(OAM_START + 1), 0xFF, "OAM is blocked until the DMA ends");
+
+        cpu.increment_cycles(4);
+        assert!(!cpu.memory_bus.is_oam_dma_active());
+        assert_eq!(cpu.memory_bus.read_byte(OAM_START + 1), 0xFE);
+        assert_eq!(cpu.memory_bus.read_byte(OAM_START + 0x9F), 0x60);
+    }
+
+    #[test]
+    fn test_hblank_dma_is_driven_by_ppu_hblank() {
+        let mut cpu = Cpu::new();
+        cpu.memory_bus.set_lcdc_register(0x91);
+        cpu.memory_bus.write_byte(LY, 0);
+        for offset in 0..0x20u16 {
+            cpu.memory_bus.write_byte(0xC000 + offset, 0x11);
+        }
+
+        set_hdma_addresses(&mut cpu.memory_bus, 0xC000, 0x8000);
+        cpu.memory_bus.write_byte(HDMA5, 0x81);
+
+        // Run one full scanline (456 dots, 4 dots per update)
+        for _ in 0..(456 / 4) {
+            Ppu::update_state(&mut cpu);
+        }
+
+        assert_eq!(cpu.memory_bus.read_byte(0x800F), 0x11, "First block should be copied during the first H-Blank");
+        assert_eq!(cpu.memory_bus.read_byte(0x8010), 0, "Only one block should be copied per H-Blank");
+        assert_eq!(cpu.memory_bus.read_byte(HDMA5), 0x00);
+    }
+
+    #[test]
+    fn test_mock_memory_bus_intercepts_writes_to_ie() {
+        let mock = MockMemoryBus {
+            inner: MemoryBus::new(),
+            ie_writes: Vec::new(),
+        };
+        let mut cpu = Cpu::with_memory_bus(mock);
+        cpu.registers.pc = 0xC000;
+        cpu.registers.a = 0x05;
+
+        // LDH (0xFF), A
+        cpu.memory_bus.write_byte(0xC000, 0xE0);
+        cpu.memory_bus.write_byte(0xC001, 0xFF);
+        // LD A, 0x1F
+        cpu.memory_bus.write_byte(0xC002, 0x3E);
+        cpu.memory_bus.write_byte(0xC003, 0x1F);
+        // LD (0xFFFF), A
+        cpu.memory_bus.write_byte(0xC004, 0xEA);
+        cpu.memory_bus.write_byte(0xC005, 0xFF);
+        cpu.memory_bus.write_byte(0xC006, 0xFF);
+
+        cpu.tick();
+        cpu.tick();
+        cpu.tick();
+
+        assert_eq!(cpu.memory_bus.ie_writes, vec![0x05, 0x1F]);
+        assert_eq!(cpu.memory_bus.inner.read_byte(IE), 0x1F);
+        assert_eq!(cpu.registers.pc, 0xC007);
+    }
+
+    /// Fills OAM so object i has Y = i, X = i + 8, tile index = 2 * i and the attribute flags 0b1001_0000 on odd objects
+    fn create_memory_bus_with_objects() -> MemoryBus {
+        let mut memory_bus = MemoryBus::new();
+        for index in 0..OAM_OBJECT_COUNT as u16 {
+            let address = OAM_START + index * 4;
+            memory_bus.write_byte(address, index as u8);
+            memory_bus.write_byte(address + 1, index as u8 + 8);
+            memory_bus.write_byte(address + 2, index as u8 * 2);
+            memory_bus.write_byte(address + 3, if index % 2 == 1 { 0b1001_0000 } else { 0 });
+        }
+        memory_bus
+    }
+
+    #[test]
+    fn test_get_object_attribute_memory_returns_the_160_oam_bytes() {
+        let memory_bus = create_memory_bus_with_objects();
+
+        let oam = memory_bus.get_object_attribute_memory();
+
+        assert_eq!(oam.len(), 160);
+        assert_eq!(oam[0..4], [0, 8, 0, 0]);
+        assert_eq!(oam[156..160], [39, 47, 78, 0b1001_0000]);
+    }
+
+    #[test]
+    fn test_iter_objects_parses_all_40_entries() {
+        let memory_bus = create_memory_bus_with_objects();
+
+        let objects: Vec<Object> = memory_bus.iter_objects().collect();
+
+        assert_eq!(objects.len(), OAM_OBJECT_COUNT);
+        for (index, object) in objects.iter().enumerate() {
+            assert_eq!(object.y as usize, index);
+            assert_eq!(object.x as usize, index + 8);
+            assert_eq!(object.tile_index, index * 2);
+            assert_eq!(object.attributes.priority, index % 2 == 1, "Wrong priority for object {}", index);
+            assert_eq!(matches!(object.attributes.pallete, ObjectPallete::OBP1), index % 2 == 1);
+            assert!(!object.attributes.x_flip && !object.attributes.y_flip);
+        }
+    }
+
+    #[test]
+    fn test_get_object_at_the_oam_edges() {
+        let memory_bus = create_memory_bus_with_objects();
+
+        let first = memory_bus.get_object(0).unwrap();
+        assert_eq!((first.y, first.x, first.tile_index), (0, 8, 0));
+
+        let last = memory_bus.get_object(39).unwrap();
+        assert_eq!((last.y, last.x, last.tile_index), (39, 47, 78));
+        assert!(last.attributes.priority);
+
+        assert!(memory_bus.get_object(40).is_none());
+        assert!(memory_bus.get_object(usize::MAX).is_none());
+    }
+
+    #[test]
+    fn test_window_position_registers() {
+        let mut memory_bus = MemoryBus::new();
+
+        memory_bus.set_wy_register(0x40);
+        memory_bus.set_wx_register(0x57);
+
+        assert_eq!(memory_bus.get_wy_register(), 0x40);
+        assert_eq!(memory_bus.get_wx_register(), 0x57);
+        assert_eq!(memory_bus.read_byte(WY), 0x40);
+        assert_eq!(memory_bus.read_byte(WX), 0x57);
+
+        memory_bus.write_byte(WX, 0x0A);
+        assert_eq!(memory_bus.get_wx_register(), 0x0A);
+    }
+
+    #[test]
+    fn test_window_x_offset_subtracts_7_from_wx() {
+        let mut memory_bus = MemoryBus::new();
+
+        for (wx, expected_offset) in [(7, 0), (8, 1), (0x57, 80), (166, 159), (0xFF, 248)] {
+            memory_bus.set_wx_register(wx);
+            assert_eq!(memory_bus.get_window_x_offset(), expected_offset, "Wrong offset for WX = {}", wx);
+        }
+
+        // WX below 7 doesn't wrap around, the window starts at the first column
+        for wx in 0..7 {
+            memory_bus.set_wx_register(wx);
+            assert_eq!(memory_bus.get_window_x_offset(), 0, "Wrong offset for WX = {}", wx);
+        }
+    }
+
+    #[test]
+    fn test_debug_output_summarizes_the_memory_map() {
+        let mut memory_bus = MemoryBus::new();
+        memory_bus.set_lcdc_register(0x91);
+        memory_bus.write_byte(LY, 0x90);
+        memory_bus.write_byte(0x8000, 0xFF);
+        memory_bus.write_byte(0x8001, 0x3C);
+
+        let output = format!("{:?}", memory_bus);
+
+        assert!(output.contains("LCDC: 0x91"), "{}", output);
+        assert!(output.contains("LY: 0x90"), "{}", output);
+        assert!(output.contains("VRAM: 0x8000-0x9FFF (8192 bytes, 2 non-zero)"), "{}", output);
+        for region in ["ROM0", "ROMx", "SRAM", "WRAM", "OAM", "STAT", "IF", "IE"] {
+            assert!(output.contains(region), "{} is missing in {}", region, output);
+        }
+        assert!(output.len() < 1000, "The whole memory should not be printed");
+    }
+
+    #[test]
+    fn test_decode_bg_palette() {
+        let mut memory_bus = MemoryBus::new();
+
+        memory_bus.write_byte(BGP, 0xE4);
+        let shades: Vec<usize> = (0..4).map(|pixel| memory_bus.decode_bg_palette(pixel)).collect();
+        assert_eq!(shades, [0, 1, 2, 3], "0xE4 keeps the color indexes");
+
+        memory_bus.write_byte(BGP, 0x1B);
+        let shades: Vec<usize> = (0..4).map(|pixel| memory_bus.decode_bg_palette(pixel)).collect();
+        assert_eq!(shades, [3, 2, 1, 0], "0x1B reverses the color indexes");
+    }
+
+    #[test]
+    fn test_decode_obp_palette_reads_the_selected_register() {
+        let mut memory_bus = MemoryBus::new();
+        memory_bus.write_byte(OBP0, 0xE4);
+        memory_bus.write_byte(OBP1, 0x1B);
+
+        for pixel in 0..4 {
+            assert_eq!(memory_bus.decode_obp_palette(ObjectPallete::OBP0 as u8, pixel), pixel as usize);
+            assert_eq!(memory_bus.decode_obp_palette(ObjectPallete::OBP1 as u8, pixel), 3 - pixel as usize);
+        }
+    }
+
+    /// Memory bus with Right, Down, A and Start pressed.
+    fn create_memory_bus_with_pressed_buttons() -> MemoryBus {
+        let mut memory_bus = MemoryBus::new();
+        for button in [JoypadButton::Right, JoypadButton::Down, JoypadButton::A, JoypadButton::Start] {
+            memory_bus.set_joypad_button(button, true);
+        }
+        memory_bus
+    }
+
+    #[test]
+    fn test_p1_reads_the_d_pad_when_bit_4_is_cleared() {
+        let mut memory_bus = create_memory_bus_with_pressed_buttons();
+
+        memory_bus.write_byte(P1, 0x20);
+
+        // Right (bit 0) and Down (bit 3) read 0
+        assert_eq!(memory_bus.read_byte(P1), 0b1110_0110);
+    }
+
+    #[test]
+    fn test_p1_reads_the_action_buttons_when_bit_5_is_cleared() {
+        let mut memory_bus = create_memory_bus_with_pressed_buttons();
+
+        memory_bus.write_byte(P1, 0x10);
+
+        // A (bit 0) and Start (bit 3) read 0
+        assert_eq!(memory_bus.read_byte(P1), 0b1101_0110);
+
+        memory_bus.set_joypad_button(JoypadButton::Start, false);
+        memory_bus.set_joypad_button(JoypadButton::B, true);
+        assert_eq!(memory_bus.read_byte(P1), 0b1101_1100, "released buttons read 1 again");
+    }
+
+    #[test]
+    fn test_p1_reads_0x0f_when_no_group_is_selected() {
+        let mut memory_bus = create_memory_bus_with_pressed_buttons();
+
+        memory_bus.write_byte(P1, 0xFF);
+        assert_eq!(memory_bus.read_byte(P1), 0xFF);
+        assert_eq!(memory_bus.read_byte(P1) & 0x0F, 0x0F);
+
+        // Both groups selected, a bit is 0 when the button of either group is pressed
+        memory_bus.write_byte(P1, 0x00);
+        assert_eq!(memory_bus.read_byte(P1), 0b1100_0110);
+    }
+
+    #[test]
+    fn test_pressing_a_button_requests_the_joypad_interrupt_once() {
+        let mut memory_bus = MemoryBus::new();
+        memory_bus.write_byte(IF, 0);
+        // Only the D-pad is selected, the interrupt is requested for the action buttons too
+        memory_bus.write_byte(P1, 0x20);
+
+        memory_bus.set_joypad_button(JoypadButton::A, true);
+        assert_eq!(memory_bus.read_byte(IF) & 0b0001_0000, 0b0001_0000);
+
+        // Holding or releasing the button doesn't request it again
+        memory_bus.write_byte(IF, 0);
+        memory_bus.set_joypad_button(JoypadButton::A, true);
+        assert_eq!(memory_bus.read_byte(IF) & 0b0001_0000, 0);
+        memory_bus.set_joypad_button(JoypadButton::A, false);
+        assert_eq!(memory_bus.read_byte(IF) & 0b0001_0000, 0);
+    }
+
+    #[test]
+    fn test_read_range_goes_through_the_mbc_but_is_not_blocked_by_the_oam_dma() {
+        use crate::gameboy_core::constants::{CARTRIDGE_TYPE_ADDRESS, RAM_SIZE_ADDRESS, ROM_BANK_SIZE};
+        let mut rom_binary = vec![0; 4 * ROM_BANK_SIZE];
+        rom_binary[3 * ROM_BANK_SIZE] = 0x33;
+        rom_binary[CARTRIDGE_TYPE_ADDRESS] = 0x03; // MBC1 + RAM + Battery
+        rom_binary[RAM_SIZE_ADDRESS] = 0x02;
+        let mut memory_bus = MemoryBus::new();
+        memory_bus.copy_from_binary(rom_binary);
+        memory_bus.write_byte(0x2000, 0x03);
+        memory_bus.write_byte(0x0000, 0x0A);
+        memory_bus.write_byte(0xA000, 0x55);
+        memory_bus.write_byte(0x8000, 0x77);
+
+        assert_eq!(memory_bus.read_range(0x4000, 0x4001), [0x33, 0x00], "the ROM bank selected in the MBC is read");
+        assert_eq!(memory_bus.read_range(0xA000, 0xA000), [0x55]);
+        assert_eq!(memory_bus.read_range(0x8000, 0x8000), [0x77]);
+
+        memory_bus.write_byte(DMA, 0xC0);
+        assert_eq!(memory_bus.read_byte(0x8000), 0xFF, "VRAM is blocked for the CPU during the OAM DMA");
+        assert_eq!(memory_bus.read_range(0x8000, 0x8000), [0x77], "but not for the PPU");
+        assert_eq!(memory_bus.read_range(0x4000, 0x4000), [0x33]);
+        assert_eq!(memory_bus.get_vram_tile_data()[0], 0x77);
+    }
+
+    #[test]
+    fn test_tile_map_arrays_hold_the_tile_map_area_selected_in_lcdc() {
+        use crate::gameboy_core::{
+            constants::{TILE_DATA_END, TILE_MAP_AREA_0_START, TILE_MAP_AREA_1_END, TILE_MAP_AREA_1_START},
+            ppu_components::LcdcRegister,
+        };
+        let mut memory_bus = MemoryBus::new();
+        memory_bus.write_byte(TILE_DATA_END, 0x11);
+        memory_bus.write_byte(TILE_MAP_AREA_0_START, 0x22);
+        memory_bus.write_byte(TILE_MAP_AREA_1_START, 0x33);
+        memory_bus.write_byte(TILE_MAP_AREA_1_END, 0x44);
+        let mock_memory_bus = MockMemoryBus { inner: memory_bus.clone(), ie_writes: Vec::new() };
+
+        let lcdc = LcdcRegister::new_from_byte(0b0000_1000);
+        assert_eq!(memory_bus.get_vram_tile_data().last(), Some(&0x11));
+        assert_eq!(memory_bus.get_bg_tile_map(&LcdcRegister::new_from_byte(0))[0], 0x22);
+        assert_eq!(memory_bus.get_bg_tile_map(&lcdc)[0], 0x33);
+        assert_eq!(memory_bus.get_bg_tile_map(&lcdc).last(), Some(&0x44));
+
+        assert_eq!(mock_memory_bus.get_vram_tile_data(), memory_bus.get_vram_tile_data());
+        assert_eq!(mock_memory_bus.get_bg_tile_map(&lcdc), memory_bus.get_bg_tile_map(&lcdc));
+    }
+}
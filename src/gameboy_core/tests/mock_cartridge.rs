@@ -0,0 +1,89 @@
+use crate::gameboy_core::{constants::INITIAL_PC, cpu::Cpu, cpu_components::MemoryBus};
+
+/// Cartridge built in tests without a ROM file: code is placed at given addresses and then written to the memory
+/// bus. The rest of the ROM area is left as it is, so several blocks of code can be placed at different addresses.
+///
+/// ```ignore
+/// MockCartridge::new()
+///     .write_code(0x0100, &[0x00, 0xC3, 0x00, 0x01])
+///     .install_into(&mut cpu.memory_bus);
+/// ```
+#[derive(Debug, Default)]
+pub struct MockCartridge {
+    code_blocks: Vec<(u16, Vec<u8>)>,
+}
+
+impl MockCartridge {
+    pub fn new() -> MockCartridge {
+        MockCartridge { code_blocks: Vec::new() }
+    }
+
+    /// Places the bytes starting at the address. Blocks written later overwrite the earlier ones where they overlap.
+    pub fn write_code(&mut self, address: u16, bytes: &[u8]) -> &mut Self {
+        self.code_blocks.push((address, bytes.to_vec()));
+        self
+    }
+
+    /// Writes all the code blocks to the memory bus.
+    pub fn install_into(&self, memory_bus: &mut MemoryBus) {
+        for (address, bytes) in &self.code_blocks {
+            for (offset, &byte) in bytes.iter().enumerate() {
+                memory_bus.write_byte(address.wrapping_add(offset as u16), byte);
+            }
+        }
+    }
+}
+
+/// Creates a CPU in its power-on state with the bytes at 0x0100 and PC pointing to them, so the first `tick` executes
+/// the first instruction.
+pub fn cpu_with_code(bytes: &[u8]) -> Cpu {
+    let mut cpu = Cpu::new();
+    MockCartridge::new().write_code(INITIAL_PC, bytes).install_into(&mut cpu.memory_bus);
+    cpu.registers.pc = INITIAL_PC;
+    cpu
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::gameboy_core::{constants::INITIAL_PC, cpu::Cpu};
+
+    use super::{MockCartridge, cpu_with_code};
+
+    #[test]
+    fn test_install_into_writes_every_code_block() {
+        let mut cpu = Cpu::new();
+
+        MockCartridge::new()
+            .write_code(0x0100, &[0x00, 0xC3, 0x00, 0x02])
+            .write_code(0x0200, &[0x3C, 0xC9])
+            .install_into(&mut cpu.memory_bus);
+
+        assert_eq!(cpu.get_memory_dump(0x0100, 4), vec![0x00, 0xC3, 0x00, 0x02]);
+        assert_eq!(cpu.get_memory_dump(0x0200, 2), vec![0x3C, 0xC9]);
+    }
+
+    #[test]
+    fn test_later_code_blocks_overwrite_earlier_ones() {
+        let mut cpu = Cpu::new();
+
+        MockCartridge::new()
+            .write_code(0x0100, &[0x01, 0x02, 0x03])
+            .write_code(0x0101, &[0xFF])
+            .install_into(&mut cpu.memory_bus);
+
+        assert_eq!(cpu.get_memory_dump(0x0100, 3), vec![0x01, 0xFF, 0x03]);
+    }
+
+    #[test]
+    fn test_cpu_with_code_executes_the_code_from_0x0100() {
+        // LD A, 0x42; INC A
+        let mut cpu = cpu_with_code(&[0x3E, 0x42, 0x3C]);
+        assert_eq!(cpu.registers.pc, INITIAL_PC);
+
+        cpu.tick();
+        cpu.tick();
+
+        assert_eq!(cpu.registers.a, 0x43);
+        assert_eq!(cpu.registers.pc, 0x0103);
+    }
+}
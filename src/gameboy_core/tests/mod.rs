@@ -5,3 +5,29 @@ pub mod cpu_bit_operations_instructions_test;
 pub mod call_and_return_instructions_test;
 pub mod cpu_jump_instructions_test;
 pub mod ppu_test;
+pub mod memory_bus_test;
+pub mod cpu_test;
+pub mod screen_test;
+pub mod tile_map_viewer_test;
+pub mod interrupts_test;
+pub mod palette_test;
+pub mod args_test;
+pub mod config_test;
+pub mod emulator_error_test;
+pub mod disassembler_test;
+pub mod ppu_components_test;
+pub mod gamepad_test;
+pub mod recent_roms_test;
+pub mod cpu_utils_test;
+pub mod mock_cartridge;
+pub mod cpu_miscellaneous_instructions_test;
+pub mod debug_overlay_test;
+pub mod wasm_test;
+pub mod mbc1_test;
+pub mod mbc2_test;
+pub mod mbc3_test;
+pub mod mbc5_test;
+pub mod cpu_state_snapshot;
+pub mod stack_property_test;
+pub mod timer_test;
+pub mod keyboard_test;
@@ -0,0 +1,49 @@
+#[cfg(test)]
+mod tests {
+    use crate::gameboy_core::{
+        components::palette::{BUILTIN_PALETTES, Palette},
+        constants::{COLORS, GAME_SECTION_HEIGHT, GAME_SECTION_WIDTH},
+    };
+
+    #[test]
+    fn test_builtin_palettes_map_each_shade_to_distinct_colors() {
+        assert!(BUILTIN_PALETTES.len() >= 5);
+        assert_eq!(Palette::default().colors, COLORS, "The default palette should be grayscale");
+
+        for palette in BUILTIN_PALETTES {
+            let mapped_colors = COLORS.map(|color| palette.map_color(color));
+
+            assert_eq!(mapped_colors, palette.colors, "Wrong colors for palette {}", palette.name);
+            for (shade, color) in mapped_colors.iter().enumerate() {
+                assert!(
+                    !mapped_colors[shade + 1..].contains(color),
+                    "Palette {} has repeated colors", palette.name
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_builtin_palettes_have_unique_names() {
+        for (index, palette) in BUILTIN_PALETTES.iter().enumerate() {
+            assert!(BUILTIN_PALETTES[index + 1..].iter().all(|other| other.name != palette.name));
+        }
+    }
+
+    #[test]
+    fn test_apply_palette_to_game_screen() {
+        let palette = BUILTIN_PALETTES[1];
+        let mut screen = [[COLORS[0]; GAME_SECTION_WIDTH]; GAME_SECTION_HEIGHT];
+        screen[0][1] = COLORS[1];
+        screen[10][20] = COLORS[2];
+        screen[GAME_SECTION_HEIGHT - 1][GAME_SECTION_WIDTH - 1] = COLORS[3];
+
+        let mapped_screen = palette.apply(&screen);
+
+        assert_eq!(mapped_screen[0][0], palette.colors[0]);
+        assert_eq!(mapped_screen[0][1], palette.colors[1]);
+        assert_eq!(mapped_screen[10][20], palette.colors[2]);
+        assert_eq!(mapped_screen[GAME_SECTION_HEIGHT - 1][GAME_SECTION_WIDTH - 1], palette.colors[3]);
+        assert_eq!(palette.map_color(0x123456), 0x123456, "Colors outside COLORS should not change");
+    }
+}
@@ -0,0 +1,146 @@
+#[cfg(test)]
+mod tests {
+    use crate::gameboy_core::{
+        constants::{TILE_MAP_AREA_0_END, TILE_MAP_AREA_0_START, TILE_MAP_AREA_1_END, TILE_MAP_AREA_1_START},
+        ppu_components::{LcdcRegister, Tile, TilePixelValue},
+    };
+
+    const IDENTITY_PALETTE: [u32; 4] = [0xFFFFFF, 0xAAAAAA, 0x555555, 0x000000];
+    const INVERTED_PALETTE: [u32; 4] = [0x000000, 0x555555, 0xAAAAAA, 0xFFFFFF];
+    const PIXEL_VALUES: [TilePixelValue; 4] =
+        [TilePixelValue::Zero, TilePixelValue::One, TilePixelValue::Two, TilePixelValue::Three];
+
+    /// Creates a tile where each pixel value is (row + col) % 4, so every row has the four values
+    fn create_tile_with_all_pixel_values() -> Tile {
+        let mut tile = Tile::new();
+        for row in 0..8 {
+            for col in 0..8 {
+                tile.pixels[row][col] = PIXEL_VALUES[(row + col) % 4];
+            }
+        }
+        tile
+    }
+
+    fn get_rgb_bytes(color: u32) -> [u8; 3] {
+        [(color >> 16) as u8, (color >> 8) as u8, color as u8]
+    }
+
+    #[test]
+    fn test_to_rgba_buffer_with_identity_and_inverted_palettes() {
+        let tile = create_tile_with_all_pixel_values();
+
+        for palette in [IDENTITY_PALETTE, INVERTED_PALETTE] {
+            let buffer = tile.to_rgba_buffer(&palette);
+
+            for row in 0..8 {
+                for col in 0..8 {
+                    let index = (row * 8 + col) * 4;
+                    let [red, green, blue] = get_rgb_bytes(palette[(row + col) % 4]);
+                    assert_eq!(buffer[index..index + 4], [red, green, blue, 0xFF], "Wrong pixel ({}, {})", row, col);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_to_rgb_buffer_with_identity_and_inverted_palettes() {
+        let tile = create_tile_with_all_pixel_values();
+
+        for palette in [IDENTITY_PALETTE, INVERTED_PALETTE] {
+            let buffer = tile.to_rgb_buffer(&palette);
+
+            for row in 0..8 {
+                for col in 0..8 {
+                    let index = (row * 8 + col) * 3;
+                    assert_eq!(buffer[index..index + 3], get_rgb_bytes(palette[(row + col) % 4]), "Wrong pixel ({}, {})", row, col);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_each_pixel_value_maps_to_its_palette_color() {
+        // Distinct color channels make sure red, green and blue are not swapped
+        let palette = [0x102030, 0x405060, 0x708090, 0xA0B0C0];
+
+        for (value_index, pixel_value) in PIXEL_VALUES.iter().enumerate() {
+            let mut tile = Tile::new();
+            tile.pixels = [[*pixel_value; 8]; 8];
+
+            let rgba = tile.to_rgba_buffer(&palette);
+            let rgb = tile.to_rgb_buffer(&palette);
+            let [red, green, blue] = get_rgb_bytes(palette[value_index]);
+
+            assert!(rgba.chunks(4).all(|pixel| pixel == [red, green, blue, 0xFF]));
+            assert!(rgb.chunks(3).all(|pixel| pixel == [red, green, blue]));
+        }
+    }
+
+    /// Returns the LCDC flags from bit 7 to bit 0
+    fn get_lcdc_flags(lcdc: &LcdcRegister) -> [bool; 8] {
+        [
+            lcdc.lcd_ppu_enabled,
+            lcdc.window_tile_map_area,
+            lcdc.window_enable,
+            lcdc.bg_window_tile_data_area,
+            lcdc.bg_tile_map_area,
+            lcdc.obj_size,
+            lcdc.obj_enable,
+            lcdc.bg_window_enable,
+        ]
+    }
+
+    #[test]
+    fn test_lcdc_new_from_byte_parses_every_bit() {
+        for value in 0..=0xFFu8 {
+            let flags = get_lcdc_flags(&LcdcRegister::new_from_byte(value));
+
+            for (index, flag) in flags.iter().enumerate() {
+                let bit = 7 - index;
+                assert_eq!(*flag, value & (1 << bit) != 0, "Wrong flag for bit {} of 0x{:02X}", bit, value);
+            }
+        }
+    }
+
+    #[test]
+    fn test_lcdc_to_byte_is_the_inverse_of_new_from_byte() {
+        for value in 0..=0xFFu8 {
+            let lcdc = LcdcRegister::new_from_byte(value);
+
+            assert_eq!(lcdc.to_byte(), value);
+            assert_eq!(LcdcRegister::new_from_byte(lcdc.to_byte()), lcdc);
+        }
+    }
+
+    #[test]
+    fn test_lcdc_single_flags() {
+        let lcdc = LcdcRegister::new_from_byte(0b1000_0000);
+        assert!(lcdc.lcd_ppu_enabled);
+        assert!(!lcdc.bg_window_enable);
+
+        let lcdc = LcdcRegister::new_from_byte(0b0000_0100);
+        assert!(lcdc.obj_size);
+        assert_eq!(lcdc.obj_size(), 16);
+        assert_eq!(LcdcRegister::new_from_byte(0b1111_1011).obj_size(), 8);
+        assert_eq!(get_lcdc_flags(&lcdc).iter().filter(|flag| **flag).count(), 1);
+
+        // Initial value after the boot ROM: LCD on, BG tile data at 0x8000 and BG on
+        let lcdc = LcdcRegister::new_from_byte(0x91);
+        assert_eq!(get_lcdc_flags(&lcdc), [true, false, false, true, false, false, false, true]);
+    }
+
+    #[test]
+    fn test_window_tile_map_area_is_selected_by_bit_6() {
+        let lcdc = LcdcRegister::new_from_byte(0b0000_0000);
+        assert_eq!(lcdc.get_window_tile_map_area_address_range(), (TILE_MAP_AREA_0_START, TILE_MAP_AREA_0_END));
+        assert_eq!((TILE_MAP_AREA_0_START, TILE_MAP_AREA_0_END), (0x9800, 0x9BFF));
+
+        let lcdc = LcdcRegister::new_from_byte(0b0100_0000);
+        assert_eq!(lcdc.get_window_tile_map_area_address_range(), (TILE_MAP_AREA_1_START, TILE_MAP_AREA_1_END));
+        assert_eq!((TILE_MAP_AREA_1_START, TILE_MAP_AREA_1_END), (0x9C00, 0x9FFF));
+
+        // Bit 3 selects the background tile map and doesn't change the window one
+        let lcdc = LcdcRegister::new_from_byte(0b0000_1000);
+        assert_eq!(lcdc.get_window_tile_map_area_address_range(), (TILE_MAP_AREA_0_START, TILE_MAP_AREA_0_END));
+    }
+}
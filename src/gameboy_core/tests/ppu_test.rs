@@ -165,15 +165,16 @@ pub fn setup_nintendo_display(cpu: &mut Cpu) {
 
 #[cfg(test)]
 mod tests {
-    use crate::gameboy_core::{constants::{BG_AND_WINDOW_MAP_SCREEN_SIZE, GAME_SECTION_HEIGHT, GAME_SECTION_WIDTH, TILE_MAP_AREA_0_START}, ppu_components, registers_contants::{BGP, LCDC}};
+    use crate::gameboy_core::{constants::{BG_AND_WINDOW_MAP_SCREEN_SIZE, GAME_SECTION_HEIGHT, GAME_SECTION_WIDTH, TILE_MAP_AREA_0_START}, cpu_components::MemoryAccess, ppu_components, registers_contants::{BGP, LCDC, LY, LYC, STAT}};
     use minifb::{Key, Window, WindowOptions};
 
     #[test]
     fn render_nintendo_logo_tiles_in_bg_screen() {
         let mut cpu = crate::gameboy_core::cpu::Cpu::start(
-            crate::cpu_utils::read_rom("files/roms/tests/nintendo_logo.gb").unwrap(),
+            crate::gameboy_core::cpu_utils::read_rom("files/roms/tests/nintendo_logo.gb").unwrap(),
             true,
-        );
+        )
+        .unwrap();
         super::setup_nintendo_display(&mut cpu);
 
         // Verify LCD is enabled
@@ -203,9 +204,10 @@ mod tests {
     #[test]
     fn render_nintendo_logo_tiles_in_visible_screen() {
         let mut cpu = crate::gameboy_core::cpu::Cpu::start(
-            crate::cpu_utils::read_rom("files/roms/tests/nintendo_logo.gb").unwrap(),
+            crate::gameboy_core::cpu_utils::read_rom("files/roms/tests/nintendo_logo.gb").unwrap(),
             true,
-        );
+        )
+        .unwrap();
         cpu.memory_bus.set_scx_register(40);
         super::setup_nintendo_display(&mut cpu);
 
@@ -223,7 +225,11 @@ mod tests {
         assert_eq!(cpu.memory_bus.read_byte(start_pos + 3), 0); // Empty
         assert_eq!(cpu.memory_bus.read_byte(start_pos + 14), 6); // O
 
-        // Get screen buffer and render it
+        // Run the PPU until the whole frame has been rendered, then get the screen buffer and show it
+        while cpu.memory_bus.read_byte(LY) != 144 {
+            cpu.update_ppu();
+        }
+        cpu.update_screen();
         let screen_buffer = cpu.get_screen_buffer();
         render_visible_screen_with_minifb(&screen_buffer);
     }
@@ -325,4 +331,1297 @@ mod tests {
                 .unwrap();
         }
     }
+
+    #[test]
+    fn test_ly_increments_after_456_t_cycles() {
+        let mut cpu = crate::gameboy_core::cpu::Cpu::new();
+        cpu.memory_bus.set_lcdc_register(0x91);
+        cpu.memory_bus.write_byte(LY, 0);
+        // Run NOPs (4 T-cycles each) from WRAM
+        cpu.registers.pc = 0xC000;
+
+        for _ in 0..113 {
+            cpu.tick();
+        }
+        assert_eq!(cpu.clock_cycles, 452);
+        assert_eq!(cpu.memory_bus.read_byte(LY), 0, "LY should not change before 456 T-cycles");
+
+        cpu.tick();
+        assert_eq!(cpu.clock_cycles, 456);
+        assert_eq!(cpu.memory_bus.read_byte(LY), 1, "LY should increment after 456 T-cycles");
+    }
+
+    /// Creates a CPU with the LCD enabled and the PPU at the beginning of V-Blank
+    fn create_cpu_in_vblank() -> crate::gameboy_core::cpu::Cpu {
+        let mut cpu = crate::gameboy_core::cpu::Cpu::new();
+        cpu.memory_bus.set_lcdc_register(0x91);
+        cpu.memory_bus.write_byte(LY, 143);
+        cpu.ppu.dots = 452;
+        crate::gameboy_core::ppu::Ppu::update_state(&mut cpu);
+        assert_eq!(cpu.memory_bus.read_byte(LY), 144);
+        cpu
+    }
+
+    #[test]
+    fn test_lcd_off_stops_ppu_and_holds_ly_at_0() {
+        let mut cpu = create_cpu_in_vblank();
+        cpu.ppu.dots = 100;
+
+        cpu.memory_bus.set_lcdc_register(0x11);
+        assert!(!cpu.ppu.get_lcdc_enabled(&cpu.memory_bus));
+
+        // Run for 2 full scanlines, nothing should change while the LCD is off
+        for _ in 0..(456 * 2 / 4) {
+            crate::gameboy_core::ppu::Ppu::update_state(&mut cpu);
+        }
+
+        assert_eq!(cpu.memory_bus.read_byte(LY), 0);
+        assert_eq!(cpu.ppu.dots, 0);
+        assert_eq!(cpu.memory_bus.read_byte(STAT) & 0b11, 0, "PPU mode should be 0 while the LCD is off");
+        assert!(cpu.ppu.screen.iter().all(|row| row.iter().all(|&pixel| pixel == 0xFFFFFF)));
+    }
+
+    #[test]
+    fn test_lcd_restarts_from_ly_0_when_turned_back_on() {
+        let mut cpu = create_cpu_in_vblank();
+        cpu.memory_bus.set_lcdc_register(0x11);
+        crate::gameboy_core::ppu::Ppu::update_state(&mut cpu);
+
+        cpu.memory_bus.set_lcdc_register(0x91);
+        assert!(cpu.ppu.get_lcdc_enabled(&cpu.memory_bus));
+        for _ in 0..(456 / 4) {
+            crate::gameboy_core::ppu::Ppu::update_state(&mut cpu);
+        }
+
+        assert_eq!(cpu.memory_bus.read_byte(LY), 1);
+    }
+
+    /// Fills tile map 0 ($9800) with tile 1 and tile map 1 ($9C00) with tile 2, except the last entry of each map
+    /// which is 0xAA and 0xBB.
+    fn fill_both_tile_maps(cpu: &mut crate::gameboy_core::cpu::Cpu) {
+        for offset in 0..0x400 {
+            cpu.memory_bus.write_byte(TILE_MAP_AREA_0_START + offset, 1);
+            cpu.memory_bus.write_byte(TILE_MAP_AREA_0_START + 0x400 + offset, 2);
+        }
+        cpu.memory_bus.write_byte(0x9BFF, 0xAA);
+        cpu.memory_bus.write_byte(0x9FFF, 0xBB);
+    }
+
+    #[test]
+    fn test_get_window_tile_map_as_grid_32x32_reads_map_0_when_bit_6_is_cleared() {
+        let mut cpu = crate::gameboy_core::cpu::Cpu::new();
+        fill_both_tile_maps(&mut cpu);
+        // Bit 3 selects map 1 for the background, it must not be used for the window
+        let lcdc = ppu_components::LcdcRegister::new_from_byte(0b1000_1000);
+
+        let grid = cpu.ppu.get_window_tile_map_as_grid_32x32(&cpu.memory_bus, &lcdc);
+
+        assert_eq!(grid[0][0], 1);
+        assert_eq!(grid[15][20], 1);
+        assert_eq!(grid[31][31], 0xAA);
+    }
+
+    #[test]
+    fn test_get_window_tile_map_as_grid_32x32_reads_map_1_when_bit_6_is_set() {
+        let mut cpu = crate::gameboy_core::cpu::Cpu::new();
+        fill_both_tile_maps(&mut cpu);
+        let lcdc = ppu_components::LcdcRegister::new_from_byte(0b1100_0000);
+
+        let grid = cpu.ppu.get_window_tile_map_as_grid_32x32(&cpu.memory_bus, &lcdc);
+
+        assert_eq!(grid[0][0], 2);
+        assert_eq!(grid[15][20], 2);
+        assert_eq!(grid[31][31], 0xBB);
+    }
+
+    /// Fills both tile maps with fill_both_tile_maps and writes tile 1 with color 3 and tile 2 with color 1 in the
+    /// tile data at $8000, so each tile map renders in a single color.
+    fn create_cpu_with_a_color_in_each_tile_map() -> crate::gameboy_core::cpu::Cpu {
+        let mut cpu = crate::gameboy_core::cpu::Cpu::new();
+        fill_both_tile_maps(&mut cpu);
+        for row in 0..8 {
+            // Tile 1: both bit planes set, tile 2: only the low bit plane set
+            cpu.memory_bus.write_byte(0x8010 + row * 2, 0xFF);
+            cpu.memory_bus.write_byte(0x8010 + row * 2 + 1, 0xFF);
+            cpu.memory_bus.write_byte(0x8020 + row * 2, 0xFF);
+            cpu.memory_bus.write_byte(0x8020 + row * 2 + 1, 0x00);
+        }
+        cpu
+    }
+
+    #[test]
+    fn test_get_bg_screen_buffer_reads_map_0_when_bit_3_is_cleared() {
+        let mut cpu = create_cpu_with_a_color_in_each_tile_map();
+        // Bit 6 selects map 1 for the window, it must not be used for the background
+        cpu.memory_bus.set_lcdc_register(0b1101_0001);
+        let lcdc = ppu_components::LcdcRegister::get_lcdc_register(&cpu.memory_bus);
+
+        let screen_buffer = cpu.ppu.get_bg_screen_buffer(&cpu.memory_bus, &lcdc);
+        assert!(screen_buffer.iter().flatten().all(|&pixel| pixel == 3), "tile 1 everywhere");
+
+        // Scrolled to the last tile of the map, $9BFF has tile 0xAA which is empty
+        cpu.memory_bus.set_scx_register(248);
+        cpu.memory_bus.set_scy_register(248);
+        let screen_buffer = cpu.ppu.get_bg_screen_buffer(&cpu.memory_bus, &lcdc);
+        assert_eq!(screen_buffer[7][7], 0);
+        assert_eq!(screen_buffer[8][8], 3);
+    }
+
+    #[test]
+    fn test_get_bg_screen_buffer_reads_map_1_when_bit_3_is_set() {
+        let mut cpu = create_cpu_with_a_color_in_each_tile_map();
+        cpu.memory_bus.set_lcdc_register(0b1001_1001);
+        let lcdc = ppu_components::LcdcRegister::get_lcdc_register(&cpu.memory_bus);
+
+        let screen_buffer = cpu.ppu.get_bg_screen_buffer(&cpu.memory_bus, &lcdc);
+        assert!(screen_buffer.iter().flatten().all(|&pixel| pixel == 1), "tile 2 everywhere");
+
+        // Scrolled to the last tile of the map, $9FFF has tile 0xBB which is empty
+        cpu.memory_bus.set_scx_register(248);
+        cpu.memory_bus.set_scy_register(248);
+        let screen_buffer = cpu.ppu.get_bg_screen_buffer(&cpu.memory_bus, &lcdc);
+        assert_eq!(screen_buffer[7][7], 0);
+        assert_eq!(screen_buffer[8][8], 1);
+    }
+
+    /// Creates the 40 OAM objects hidden above the screen (Y = 0), with tile index set to the OAM index so the
+    /// selected objects can be identified.
+    fn create_hidden_oam() -> [crate::gameboy_core::ppu::Object; 40] {
+        std::array::from_fn(|index| crate::gameboy_core::ppu::Object::from_oam_entry([0, 50, index as u8, 0]))
+    }
+
+    fn get_tile_indexes_on_scanline(oam: &[crate::gameboy_core::ppu::Object; 40], ly: u8, sprite_height: u8) -> Vec<usize> {
+        crate::gameboy_core::ppu::Ppu::objects_on_scanline(oam, ly, sprite_height)
+            .map(|object| object.tile_index)
+            .collect()
+    }
+
+    #[test]
+    fn test_objects_on_scanline_selects_the_first_10_objects_of_oam_on_the_line() {
+        let mut oam = create_hidden_oam();
+        // 15 objects cover LY 20 (screen Y 20 is Y 36 in OAM), with X going down so the last ones are the leftmost
+        for (position, oam_index) in [1, 3, 4, 7, 9, 12, 15, 18, 20, 22, 25, 28, 31, 34, 39].iter().enumerate() {
+            oam[*oam_index].y = 36 - (position % 8) as u8;
+            oam[*oam_index].x = 100 - position as u8 * 5;
+        }
+        // Objects on other lines: ending right before LY 20 and starting right after it
+        oam[0].y = 28;
+        oam[2].y = 37;
+
+        let selected = get_tile_indexes_on_scanline(&oam, 20, 8);
+
+        // Only the first 10 in OAM order are kept, and the one with the lowest X is drawn last
+        assert_eq!(selected, vec![1, 3, 4, 7, 9, 12, 15, 18, 20, 22]);
+    }
+
+    #[test]
+    fn test_objects_on_scanline_are_sorted_so_lower_x_is_drawn_last() {
+        let mut oam = create_hidden_oam();
+        for (oam_index, x) in [(5, 40), (6, 10), (7, 80), (8, 10), (9, 0)] {
+            oam[oam_index].y = 16;
+            oam[oam_index].x = x;
+        }
+
+        let selected = get_tile_indexes_on_scanline(&oam, 0, 8);
+
+        // For the same X the object stored first in OAM has priority, so it is drawn after the other one
+        assert_eq!(selected, vec![7, 5, 8, 6, 9]);
+    }
+
+    #[test]
+    fn test_objects_on_scanline_uses_the_sprite_height() {
+        let mut oam = create_hidden_oam();
+        // Rows 0-7 in 8x8 mode and 0-15 in 8x16 mode
+        oam[10].y = 16;
+
+        assert_eq!(get_tile_indexes_on_scanline(&oam, 7, 8), vec![10]);
+        assert!(get_tile_indexes_on_scanline(&oam, 8, 8).is_empty());
+        assert_eq!(get_tile_indexes_on_scanline(&oam, 15, 16), vec![10]);
+        assert!(get_tile_indexes_on_scanline(&oam, 16, 16).is_empty());
+    }
+
+    #[test]
+    fn test_get_screen_buffer_returns_the_frame_rendered_by_the_ppu() {
+        let mut cpu = crate::gameboy_core::cpu::Cpu::new();
+        // LCD on, tile data at $8000, tile map at $9800 (filled with tile 0), background on
+        cpu.memory_bus.set_lcdc_register(0x91);
+        cpu.memory_bus.write_byte(BGP, 0xE4);
+        // Tile 0: the first row uses color 1, the last one color 3 and the others color 0
+        cpu.memory_bus.write_byte(0x8000, 0xFF);
+        cpu.memory_bus.write_byte(0x8001, 0x00);
+        cpu.memory_bus.write_byte(0x800E, 0xFF);
+        cpu.memory_bus.write_byte(0x800F, 0xFF);
+
+        while cpu.memory_bus.read_byte(LY) != 144 {
+            cpu.update_ppu();
+        }
+        cpu.ppu.update_screen_buffer(&cpu.memory_bus);
+        let screen_buffer = cpu.get_screen_buffer();
+
+        for (row_index, row) in screen_buffer.iter().enumerate() {
+            let expected_shade = match row_index % 8 {
+                0 => 1,
+                7 => 3,
+                _ => 0,
+            };
+            assert!(row.iter().all(|&shade| shade == expected_shade), "Wrong shade in row {}", row_index);
+        }
+        for (row, colors_row) in screen_buffer.iter().zip(cpu.ppu.screen.iter()) {
+            for (&shade, &color) in row.iter().zip(colors_row.iter()) {
+                assert_eq!(crate::gameboy_core::constants::COLORS[shade as usize], color);
+            }
+        }
+
+        // The frame barrier doesn't render the frame again, the changes show up when the PPU draws the next frame
+        cpu.memory_bus.write_byte(0x8001, 0xFF);
+        cpu.ppu.update_screen_buffer(&cpu.memory_bus);
+        assert_eq!(cpu.get_screen_buffer(), screen_buffer);
+    }
+
+    #[test]
+    fn test_update_screen_buffer_is_white_when_lcd_is_off() {
+        let mut cpu = crate::gameboy_core::cpu::Cpu::new();
+        cpu.memory_bus.set_lcdc_register(0x11);
+        // BGP maps every color to black, so the screen would be black if the LCD was on
+        cpu.memory_bus.write_byte(BGP, 0xFF);
+
+        cpu.ppu.update_screen_buffer(&cpu.memory_bus);
+
+        assert!(cpu.ppu.screen.iter().all(|row| row.iter().all(|&pixel| pixel == 0xFFFFFF)));
+    }
+
+    #[test]
+    #[cfg(debug_assertions)]
+    #[should_panic(expected = "The LCD should only be turned off during V-Blank")]
+    fn test_lcd_off_outside_vblank_panics_in_debug_builds() {
+        let mut cpu = crate::gameboy_core::cpu::Cpu::new();
+        cpu.memory_bus.set_lcdc_register(0x91);
+        cpu.memory_bus.write_byte(LY, 0);
+        crate::gameboy_core::ppu::Ppu::update_state(&mut cpu);
+
+        cpu.memory_bus.set_lcdc_register(0x11);
+        crate::gameboy_core::ppu::Ppu::update_state(&mut cpu);
+    }
+
+    #[test]
+    fn test_mode_changes_during_a_full_frame() {
+        use crate::gameboy_core::constants::{HBLANK_START_MIN_DOTS, OAM_SEARCH_DOTS};
+
+        let mut cpu = crate::gameboy_core::cpu::Cpu::new();
+        cpu.memory_bus.set_lcdc_register(0x91);
+        cpu.memory_bus.write_byte(LY, 0);
+        cpu.ppu.enable_mode_trace();
+
+        // 154 lines of 456 dots, the PPU advances 4 dots per update
+        let mut vblank_updates = 0;
+        for _ in 0..(154 * 456 / 4) {
+            crate::gameboy_core::ppu::Ppu::update_state(&mut cpu);
+            if cpu.memory_bus.read_byte(STAT) & 0b11 == 1 {
+                vblank_updates += 1;
+            }
+        }
+
+        let mode_changes = cpu.ppu.ppu_mode_changes.as_ref().unwrap();
+        let mut expected_mode_changes = Vec::new();
+        for _ in 0..144 {
+            expected_mode_changes.extend([(4, 2), (OAM_SEARCH_DOTS, 3), (HBLANK_START_MIN_DOTS, 0)]);
+        }
+        // V-Blank starts at the beginning of LY 144 and the next frame at the beginning of LY 0
+        expected_mode_changes.extend([(0, 1), (0, 2)]);
+
+        assert_eq!(*mode_changes, expected_mode_changes);
+        assert_eq!(vblank_updates, 10 * 456 / 4, "The PPU should stay in V-Blank for 10 lines");
+        assert_eq!(cpu.memory_bus.read_byte(LY), 0);
+    }
+
+    /// Creates a CPU with the LCD on at the start of LY, in mode 0 with the given STAT interrupt sources and IF cleared.
+    fn create_cpu_with_stat_sources(ly: u8, stat: u8) -> crate::gameboy_core::cpu::Cpu {
+        use crate::gameboy_core::registers_contants::IF;
+        let mut cpu = crate::gameboy_core::cpu::Cpu::new();
+        cpu.memory_bus.set_lcdc_register(0x91);
+        cpu.memory_bus.write_byte(LY, ly);
+        cpu.memory_bus.write_byte(LYC, 0xFF);
+        cpu.memory_bus.write_byte(STAT, stat & 0b11111000);
+        cpu.memory_bus.write_byte(IF, 0);
+        cpu
+    }
+
+    fn is_lcd_interrupt_requested(cpu: &crate::gameboy_core::cpu::Cpu) -> bool {
+        cpu.memory_bus.read_byte(crate::gameboy_core::registers_contants::IF) & 0b10 != 0
+    }
+
+    #[test]
+    fn test_stat_hblank_interrupt() {
+        use crate::gameboy_core::ppu::Ppu;
+        let mut cpu = create_cpu_with_stat_sources(0, 0b0000_1000);
+
+        while cpu.ppu.dots < 248 {
+            Ppu::update_state(&mut cpu);
+        }
+        assert!(!is_lcd_interrupt_requested(&cpu), "the line is still in modes 2 and 3");
+
+        Ppu::update_state(&mut cpu);
+        assert_eq!(cpu.memory_bus.read_byte(STAT) & 0b11, 0);
+        assert!(is_lcd_interrupt_requested(&cpu));
+
+        let mut cpu = create_cpu_with_stat_sources(0, 0);
+        while cpu.ppu.dots < 252 {
+            Ppu::update_state(&mut cpu);
+        }
+        assert!(!is_lcd_interrupt_requested(&cpu), "the H-Blank source is disabled");
+    }
+
+    #[test]
+    fn test_stat_vblank_interrupt() {
+        use crate::gameboy_core::ppu::Ppu;
+        for (stat, expected) in [(0b0001_0000, true), (0b0000_0000, false)] {
+            let mut cpu = create_cpu_with_stat_sources(143, stat);
+            cpu.ppu.dots = 452;
+
+            Ppu::update_state(&mut cpu);
+
+            assert_eq!(cpu.memory_bus.read_byte(STAT) & 0b11, 1);
+            assert_eq!(is_lcd_interrupt_requested(&cpu), expected, "STAT {:08b}", stat);
+        }
+    }
+
+    #[test]
+    fn test_stat_oam_interrupt() {
+        use crate::gameboy_core::ppu::Ppu;
+        for (stat, expected) in [(0b0010_0000, true), (0b0000_0000, false)] {
+            let mut cpu = create_cpu_with_stat_sources(0, stat);
+
+            Ppu::update_state(&mut cpu);
+
+            assert_eq!(cpu.memory_bus.read_byte(STAT) & 0b11, 2);
+            assert_eq!(is_lcd_interrupt_requested(&cpu), expected, "STAT {:08b}", stat);
+        }
+    }
+
+    #[test]
+    fn test_stat_lyc_interrupt_fires_once_when_ly_reaches_lyc() {
+        use crate::gameboy_core::{ppu::Ppu, registers_contants::IF};
+        let mut cpu = create_cpu_with_stat_sources(4, 0b0100_0000);
+        cpu.memory_bus.write_byte(LYC, 5);
+        cpu.ppu.dots = 452;
+
+        Ppu::update_state(&mut cpu);
+        assert_eq!(cpu.memory_bus.read_byte(LY), 5);
+        assert_ne!(cpu.memory_bus.read_byte(STAT) & 0b100, 0, "the LY=LYC flag is set");
+        assert!(is_lcd_interrupt_requested(&cpu));
+
+        // The rest of the line keeps LY = LYC without requesting it again
+        cpu.memory_bus.write_byte(IF, 0);
+        for _ in 0..100 {
+            Ppu::update_state(&mut cpu);
+        }
+        assert!(!is_lcd_interrupt_requested(&cpu));
+
+        let mut cpu = create_cpu_with_stat_sources(4, 0);
+        cpu.memory_bus.write_byte(LYC, 5);
+        cpu.ppu.dots = 452;
+        Ppu::update_state(&mut cpu);
+        assert_ne!(cpu.memory_bus.read_byte(STAT) & 0b100, 0);
+        assert!(!is_lcd_interrupt_requested(&cpu), "the LYC source is disabled");
+    }
+
+    /// The PPU is boxed because a few copies of its screen buffers overflow the test thread stack.
+    #[cfg(feature = "save-states")]
+    fn create_ppu_with_screen_and_objects() -> Box<crate::gameboy_core::ppu::Ppu> {
+        use crate::gameboy_core::ppu::{Object, Ppu};
+
+        let mut ppu = Box::new(Ppu::new());
+        for (row_index, row) in ppu.screen.iter_mut().enumerate() {
+            for (column_index, pixel) in row.iter_mut().enumerate() {
+                *pixel = (row_index * GAME_SECTION_WIDTH + column_index) as u32;
+            }
+        }
+        ppu.bg_screen_buffer[143][159] = 0xFFFFFF;
+        ppu.dots = 204;
+        ppu.objects_to_be_rendered = vec![
+            Object::from_oam_entry([16, 8, 0x12, 0b1011_0000]),
+            Object::from_oam_entry([40, 30, 0x7F, 0b0100_0000]),
+        ];
+        ppu.need_to_render_line = true;
+        ppu.is_lcd_enabled = true;
+        ppu
+    }
+
+    /// Runs the test on a thread with an 8 MB stack. The derived deserializer keeps several copies of the screen
+    /// buffers on the stack in debug builds, which is more than the 2 MB given to test threads.
+    #[cfg(feature = "save-states")]
+    fn run_with_large_stack(test: impl FnOnce() + Send + 'static) {
+        std::thread::Builder::new()
+            .stack_size(8 * 1024 * 1024)
+            .spawn(test)
+            .unwrap()
+            .join()
+            .unwrap();
+    }
+
+    #[test]
+    #[cfg(feature = "save-states")]
+    fn test_ppu_serialization_round_trip() {
+        run_with_large_stack(|| {
+            let ppu = create_ppu_with_screen_and_objects();
+
+            let json = serde_json::to_string(&ppu).unwrap();
+            let deserialized: Box<crate::gameboy_core::ppu::Ppu> = serde_json::from_str(&json).unwrap();
+            assert!(deserialized == ppu);
+
+            let bytes = bincode::serialize(&ppu).unwrap();
+            let deserialized: Box<crate::gameboy_core::ppu::Ppu> = bincode::deserialize(&bytes).unwrap();
+            assert!(deserialized == ppu);
+        });
+    }
+
+    #[test]
+    #[cfg(feature = "save-states")]
+    fn test_ppu_binary_size_is_stable() {
+        let ppu = create_ppu_with_screen_and_objects();
+
+        // Each screen buffer: 8 bytes for the length + 160x144 pixels of 4 bytes
+        let screen_buffer_size = 8 + GAME_SECTION_WIDTH * GAME_SECTION_HEIGHT * 4;
+        // Each object: y, x, tile index (8 bytes), 3 flags and the palette variant (4 bytes)
+        let objects_size = 8 + 2 * (1 + 1 + 8 + 3 + 4);
+        // dots, need_to_render_line, is_lcd_enabled and window_internal_line
+        let other_fields_size = 2 + 1 + 1 + 1;
+
+        let bytes = bincode::serialize(&ppu).unwrap();
+        assert_eq!(bytes.len(), 2 * screen_buffer_size + objects_size + other_fields_size);
+    }
+
+    #[test]
+    #[cfg(feature = "save-states")]
+    fn test_ppu_deserialization_rejects_a_screen_of_the_wrong_size() {
+        run_with_large_stack(|| {
+            let ppu = create_ppu_with_screen_and_objects();
+            let mut json: serde_json::Value = serde_json::to_value(&ppu).unwrap();
+            json["screen"].as_array_mut().unwrap().pop();
+            let json = json.to_string();
+
+            assert!(serde_json::from_str::<Box<crate::gameboy_core::ppu::Ppu>>(&json).is_err());
+        });
+    }
+
+    /// Runs the PPU until the start of the H-Blank of the scanline LY. The mode is still H-Blank on the first dots
+    /// of a new line, so the dots are checked too.
+    fn run_ppu_until_hblank_of_line(cpu: &mut crate::gameboy_core::cpu::Cpu, ly: u8) {
+        while !(cpu.memory_bus.read_byte(LY) == ly && cpu.memory_bus.read_byte(STAT) & 0b11 == 0 && cpu.ppu.dots >= 252) {
+            cpu.update_ppu();
+        }
+    }
+
+    /// LCD, background and objects on, tile map at $9800 filled with tile 0, whose first row uses color 1 and the
+    /// others color 0. Tile 1 is filled with color 3.
+    fn create_cpu_with_striped_background() -> crate::gameboy_core::cpu::Cpu {
+        let mut cpu = crate::gameboy_core::cpu::Cpu::new();
+        cpu.memory_bus.set_lcdc_register(0x93);
+        cpu.memory_bus.write_byte(BGP, 0xE4);
+        cpu.memory_bus.write_byte(0xFF48, 0xE4);
+        cpu.memory_bus.write_byte(0x8000, 0xFF);
+        cpu.memory_bus.write_byte(0x8001, 0x00);
+        for address in 0x8010..0x8020 {
+            cpu.memory_bus.write_byte(address, 0xFF);
+        }
+        cpu
+    }
+
+    #[test]
+    fn test_scanline_is_rendered_when_hblank_starts() {
+        use crate::gameboy_core::constants::COLORS;
+        let mut cpu = create_cpu_with_striped_background();
+
+        // The line is not drawn during Pixel Transfer yet
+        while cpu.ppu.dots < 252 - 4 {
+            cpu.update_ppu();
+        }
+        assert_eq!(cpu.memory_bus.read_byte(STAT) & 0b11, 3);
+        assert!(cpu.ppu.screen[0].iter().all(|&pixel| pixel == 0));
+
+        run_ppu_until_hblank_of_line(&mut cpu, 0);
+        assert_eq!(cpu.ppu.dots, 252);
+        assert!(cpu.ppu.screen[0].iter().all(|&pixel| pixel == COLORS[1]));
+        assert!(cpu.ppu.screen[1].iter().all(|&pixel| pixel == 0), "The next line should not be drawn yet");
+
+        run_ppu_until_hblank_of_line(&mut cpu, 1);
+        assert!(cpu.ppu.screen[1].iter().all(|&pixel| pixel == COLORS[0]));
+    }
+
+    #[test]
+    fn test_scanline_uses_the_registers_at_the_end_of_its_pixel_transfer() {
+        use crate::gameboy_core::constants::COLORS;
+        let mut cpu = create_cpu_with_striped_background();
+
+        run_ppu_until_hblank_of_line(&mut cpu, 0);
+        // A palette change during the frame only affects the lines drawn afterwards
+        cpu.memory_bus.write_byte(BGP, 0xFF);
+        run_ppu_until_hblank_of_line(&mut cpu, 1);
+
+        assert!(cpu.ppu.screen[0].iter().all(|&pixel| pixel == COLORS[1]));
+        assert!(cpu.ppu.screen[1].iter().all(|&pixel| pixel == COLORS[3]));
+    }
+
+    /// LCD and background on with BGP = 0xE4. Tile n (0-3) is filled with the color n and the tile map at $9800 uses
+    /// the tile (row + column) % 4, the map at $9C00 only uses tile 3.
+    fn create_cpu_with_static_background() -> crate::gameboy_core::cpu::Cpu {
+        let mut cpu = crate::gameboy_core::cpu::Cpu::new();
+        cpu.memory_bus.set_lcdc_register(0x91);
+        cpu.memory_bus.write_byte(BGP, 0xE4);
+        for tile in 0..4u16 {
+            for row in 0..8 {
+                let address = 0x8000 + tile * 16 + row * 2;
+                cpu.memory_bus.write_byte(address, if tile & 0b01 != 0 { 0xFF } else { 0x00 });
+                cpu.memory_bus.write_byte(address + 1, if tile & 0b10 != 0 { 0xFF } else { 0x00 });
+            }
+        }
+        for index in 0..1024u16 {
+            cpu.memory_bus.write_byte(0x9800 + index, ((index / 32 + index % 32) % 4) as u8);
+            cpu.memory_bus.write_byte(0x9C00 + index, 3);
+        }
+        cpu
+    }
+
+    #[test]
+    fn test_frame_drawn_line_by_line_matches_the_whole_frame_for_a_static_background() {
+        use crate::gameboy_core::registers_contants::{SCX, SCY};
+        let mut cpu = create_cpu_with_static_background();
+        cpu.memory_bus.write_byte(SCX, 3);
+        cpu.memory_bus.write_byte(SCY, 250);
+        let lcdc = ppu_components::LcdcRegister::get_lcdc_register(&cpu.memory_bus);
+
+        while cpu.memory_bus.read_byte(LY) != 144 {
+            cpu.update_ppu();
+        }
+
+        let whole_frame = cpu.ppu.get_bg_screen_buffer_as_colors(&cpu.memory_bus, &lcdc);
+        assert!(cpu.ppu.screen == whole_frame, "each line drawn at its H-Blank should match the whole frame");
+    }
+
+    #[test]
+    fn test_lcdc_change_during_the_frame_only_affects_the_next_lines() {
+        use crate::gameboy_core::constants::COLORS;
+        let mut cpu = create_cpu_with_static_background();
+        for index in 0..1024u16 {
+            cpu.memory_bus.write_byte(0x9800 + index, 1);
+        }
+
+        run_ppu_until_hblank_of_line(&mut cpu, 71);
+        // The background now uses the tile map at $9C00
+        cpu.memory_bus.set_lcdc_register(0x99);
+        while cpu.memory_bus.read_byte(LY) != 144 {
+            cpu.update_ppu();
+        }
+
+        assert!(cpu.ppu.screen[..72].iter().all(|row| row.iter().all(|&pixel| pixel == COLORS[1])));
+        assert!(cpu.ppu.screen[72..].iter().all(|row| row.iter().all(|&pixel| pixel == COLORS[3])));
+    }
+
+    #[test]
+    fn test_objects_are_kept_when_the_frame_is_over() {
+        use crate::gameboy_core::{constants::{COLORS, OAM_START}, ppu::Ppu};
+        let mut cpu = create_cpu_with_striped_background();
+        // Object with tile 1 covering the lines 8-15 and the columns 0-7
+        for (offset, byte) in [24, 8, 1, 0].into_iter().enumerate() {
+            cpu.memory_bus.write_byte(OAM_START + offset as u16, byte);
+        }
+
+        run_ppu_until_hblank_of_line(&mut cpu, 8);
+        assert_eq!(cpu.ppu.screen[8][0], COLORS[3]);
+        assert_eq!(cpu.ppu.screen[8][8], COLORS[1]);
+
+        while cpu.memory_bus.read_byte(LY) != 144 {
+            Ppu::update_state(&mut cpu);
+        }
+        let screen = cpu.ppu.update_screen_buffer(&cpu.memory_bus);
+        assert_eq!(screen[15][7], COLORS[3]);
+        assert_eq!(screen[16][0], COLORS[1]);
+    }
+
+    /// Writes the object to OAM, Y and X are the OAM positions (screen position + 16 and + 8).
+    fn write_object_to_oam(cpu: &mut crate::gameboy_core::cpu::Cpu, oam_index: u16, y: u8, x: u8, tile_index: u8) {
+        use crate::gameboy_core::constants::OAM_START;
+        for (offset, byte) in [y, x, tile_index, 0].into_iter().enumerate() {
+            cpu.memory_bus.write_byte(OAM_START + oam_index * 4 + offset as u16, byte);
+        }
+    }
+
+    #[test]
+    fn test_scanline_rendered_during_an_oam_dma_reads_vram_and_oam() {
+        use crate::gameboy_core::{constants::COLORS, ppu::Ppu, registers_contants::DMA};
+        let mut cpu = create_cpu_with_striped_background();
+        // Tile 1 (color 3) at the screen X 16-23 of the line 0
+        write_object_to_oam(&mut cpu, 0, 16, 24, 1);
+
+        // The OAM DMA only blocks the CPU, the PPU keeps reading the tiles, the tile map and the objects
+        cpu.memory_bus.write_byte(DMA, 0xC0);
+        assert!(cpu.memory_bus.is_oam_dma_active());
+        let oam = Ppu::get_objects(&cpu.memory_bus);
+        cpu.ppu.objects_to_be_rendered = Ppu::objects_on_scanline(&oam, 0, 8).copied().collect();
+        cpu.ppu.step_scanline(&cpu.memory_bus, 0);
+
+        assert!(cpu.ppu.screen[0][..16].iter().all(|&pixel| pixel == COLORS[1]));
+        assert!(cpu.ppu.screen[0][16..24].iter().all(|&pixel| pixel == COLORS[3]));
+        assert!(cpu.ppu.screen[0][24..].iter().all(|&pixel| pixel == COLORS[1]));
+    }
+
+    #[test]
+    fn test_overlapping_objects_show_the_one_with_lower_x() {
+        use crate::gameboy_core::constants::COLORS;
+        let mut cpu = create_cpu_with_striped_background();
+        // Tile 2 filled with color 2
+        for address in (0x8020..0x8030).step_by(2) {
+            cpu.memory_bus.write_byte(address + 1, 0xFF);
+        }
+        // Tile 1 (color 3) at screen X 8-15 comes first in OAM, tile 2 (color 2) at screen X 4-11 has a lower X
+        write_object_to_oam(&mut cpu, 0, 24, 16, 1);
+        write_object_to_oam(&mut cpu, 1, 24, 12, 2);
+
+        run_ppu_until_hblank_of_line(&mut cpu, 9);
+
+        assert_eq!(cpu.ppu.screen[9][4], COLORS[2]);
+        assert_eq!(cpu.ppu.screen[9][8], COLORS[2], "the object with the lower X is on top where they overlap");
+        assert_eq!(cpu.ppu.screen[9][11], COLORS[2]);
+        assert_eq!(cpu.ppu.screen[9][12], COLORS[3]);
+        assert_eq!(cpu.ppu.screen[9][15], COLORS[3]);
+    }
+
+    #[test]
+    fn test_overlapping_objects_with_the_same_x_show_the_first_in_oam() {
+        use crate::gameboy_core::constants::COLORS;
+        let mut cpu = create_cpu_with_striped_background();
+        for address in (0x8020..0x8030).step_by(2) {
+            cpu.memory_bus.write_byte(address + 1, 0xFF);
+        }
+        write_object_to_oam(&mut cpu, 3, 24, 16, 2);
+        write_object_to_oam(&mut cpu, 7, 24, 16, 1);
+
+        run_ppu_until_hblank_of_line(&mut cpu, 9);
+
+        assert!(cpu.ppu.screen[9][8..16].iter().all(|&pixel| pixel == COLORS[2]));
+    }
+
+    #[test]
+    fn test_get_all_tiles_as_rgba_grid_with_2_columns() {
+        use crate::gameboy_core::{
+            constants::COLORS,
+            ppu::{Ppu, TILE_GRID_BORDER_COLOR},
+            ppu_components::{Tile, TilePixelValue},
+        };
+
+        // Tile 0 is empty, tile 1 is filled with color 3, tile 2 has color 1 on its first row and tile 3 color 2
+        // on its last column. The other 380 tiles are empty.
+        let mut tiles = [Tile::new(); 384];
+        tiles[1].pixels = [[TilePixelValue::Three; 8]; 8];
+        tiles[2].pixels[0] = [TilePixelValue::One; 8];
+        for row in tiles[3].pixels.iter_mut() {
+            row[7] = TilePixelValue::Two;
+        }
+
+        let grid = Ppu::get_all_tiles_as_rgba_grid(&tiles, &COLORS, 2);
+
+        // 2 tiles of 8 pixels and a border per row, 192 rows of tiles
+        let width = 17;
+        assert_eq!(grid.len(), width * (192 * 9 - 1));
+        let pixel = |row: usize, col: usize| grid[row * width + col];
+
+        assert_eq!(pixel(0, 0), COLORS[0]);
+        assert_eq!(pixel(7, 7), COLORS[0]);
+        // Vertical border between the columns, then tile 1
+        assert_eq!(pixel(0, 8), TILE_GRID_BORDER_COLOR);
+        assert_eq!(pixel(0, 9), COLORS[3]);
+        assert_eq!(pixel(7, 16), COLORS[3]);
+        // Horizontal border between the rows, then tile 2 and tile 3
+        assert_eq!(pixel(8, 0), TILE_GRID_BORDER_COLOR);
+        assert_eq!(pixel(8, 12), TILE_GRID_BORDER_COLOR);
+        assert_eq!(pixel(9, 0), COLORS[1]);
+        assert_eq!(pixel(10, 0), COLORS[0]);
+        assert_eq!(pixel(9, 15), COLORS[0]);
+        assert_eq!(pixel(9, 16), COLORS[2]);
+        assert_eq!(pixel(16, 16), COLORS[2]);
+        // Tile 383 is the last one, in the second column of the last row
+        assert_eq!(pixel(191 * 9 + 7, 16), COLORS[0]);
+    }
+
+    #[test]
+    fn test_get_all_tiles_as_rgba_grid_default_and_zero_columns() {
+        use crate::gameboy_core::{
+            constants::COLORS,
+            ppu::{DEFAULT_TILE_GRID_COLUMNS, Ppu, TILE_GRID_BORDER_COLOR},
+            ppu_components::Tile,
+        };
+        let tiles = [Tile::new(); 384];
+
+        // 16 columns and 24 rows of tiles
+        let grid = Ppu::get_all_tiles_as_rgba_grid(&tiles, &COLORS, DEFAULT_TILE_GRID_COLUMNS);
+        assert_eq!(grid.len(), (16 * 9 - 1) * (24 * 9 - 1));
+
+        // 0 columns is taken as a single column, so there are no vertical borders
+        let grid = Ppu::get_all_tiles_as_rgba_grid(&tiles, &COLORS, 0);
+        assert_eq!(grid.len(), 8 * (384 * 9 - 1));
+        assert_eq!(grid[8 * 8], TILE_GRID_BORDER_COLOR);
+        assert!(grid[..8 * 8].iter().all(|&pixel| pixel == COLORS[0]));
+    }
+
+    type BgBuffer = [[u8; BG_AND_WINDOW_MAP_SCREEN_SIZE]; BG_AND_WINDOW_MAP_SCREEN_SIZE];
+
+    /// Background buffer where every pixel holds the number of its row, or of its column when `by_column` is set.
+    fn create_numbered_bg_buffer(by_column: bool) -> BgBuffer {
+        let mut bg_buffer = [[0u8; BG_AND_WINDOW_MAP_SCREEN_SIZE]; BG_AND_WINDOW_MAP_SCREEN_SIZE];
+        for (row_index, row) in bg_buffer.iter_mut().enumerate() {
+            for (column_index, pixel) in row.iter_mut().enumerate() {
+                *pixel = if by_column { column_index } else { row_index } as u8;
+            }
+        }
+        bg_buffer
+    }
+
+    fn get_visible_bg_buffer_with_scroll(
+        bg_buffer: &BgBuffer,
+        scx: u8,
+        scy: u8,
+    ) -> [[u8; GAME_SECTION_WIDTH]; GAME_SECTION_HEIGHT] {
+        use crate::gameboy_core::{cpu_components::MemoryBus, ppu::Ppu};
+
+        let mut memory_bus = MemoryBus::new();
+        memory_bus.set_scx_register(scx);
+        memory_bus.set_scy_register(scy);
+        Ppu::new().get_visible_bg_buffer(bg_buffer, &memory_bus)
+    }
+
+    /// Checks every screen row shows the background row SCY + row, wrapped around the 256 pixels of the background.
+    fn assert_rows_are_scrolled_by(visible_bg_buffer: &[[u8; GAME_SECTION_WIDTH]; GAME_SECTION_HEIGHT], scy: u8) {
+        for (screen_row, row) in visible_bg_buffer.iter().enumerate() {
+            let expected = scy.wrapping_add(screen_row as u8);
+            assert!(row.iter().all(|&pixel| pixel == expected), "screen row {} with SCY={}", screen_row, scy);
+        }
+    }
+
+    /// Checks every screen column shows the background column SCX + column, wrapped around the 256 pixels.
+    fn assert_columns_are_scrolled_by(visible_bg_buffer: &[[u8; GAME_SECTION_WIDTH]; GAME_SECTION_HEIGHT], scx: u8) {
+        for row in visible_bg_buffer.iter() {
+            for (screen_col, &pixel) in row.iter().enumerate() {
+                assert_eq!(pixel, scx.wrapping_add(screen_col as u8), "screen column {} with SCX={}", screen_col, scx);
+            }
+        }
+    }
+
+    #[test]
+    fn test_visible_bg_buffer_without_wrapping() {
+        let rows_buffer = create_numbered_bg_buffer(false);
+        let columns_buffer = create_numbered_bg_buffer(true);
+
+        let visible_bg_buffer = get_visible_bg_buffer_with_scroll(&rows_buffer, 0, 0);
+        assert_rows_are_scrolled_by(&visible_bg_buffer, 0);
+        assert_eq!(visible_bg_buffer[143][0], 143, "the bottom of the screen is row 143 of the background");
+
+        let visible_bg_buffer = get_visible_bg_buffer_with_scroll(&columns_buffer, 0, 0);
+        assert_columns_are_scrolled_by(&visible_bg_buffer, 0);
+        assert_eq!(visible_bg_buffer[0][159], 159);
+    }
+
+    #[test]
+    fn test_visible_bg_buffer_wraps_vertically() {
+        let rows_buffer = create_numbered_bg_buffer(false);
+
+        // SCY=200: rows 200-255 are at the top of the screen, then the background starts again from row 0
+        let visible_bg_buffer = get_visible_bg_buffer_with_scroll(&rows_buffer, 0, 200);
+        assert_rows_are_scrolled_by(&visible_bg_buffer, 200);
+        assert_eq!(visible_bg_buffer[55][0], 255);
+        assert_eq!(visible_bg_buffer[56][0], 0);
+        assert_eq!(visible_bg_buffer[143][0], 87);
+
+        // SCY=248: the background wraps at pixel 8 of the screen
+        let visible_bg_buffer = get_visible_bg_buffer_with_scroll(&rows_buffer, 0, 248);
+        assert_rows_are_scrolled_by(&visible_bg_buffer, 248);
+        assert_eq!(visible_bg_buffer[7][0], 255);
+        assert_eq!(visible_bg_buffer[8][0], 0);
+    }
+
+    #[test]
+    fn test_visible_bg_buffer_wraps_horizontally() {
+        let columns_buffer = create_numbered_bg_buffer(true);
+
+        // SCX=200: the background wraps at pixel 56 of the screen
+        let visible_bg_buffer = get_visible_bg_buffer_with_scroll(&columns_buffer, 200, 0);
+        assert_columns_are_scrolled_by(&visible_bg_buffer, 200);
+        assert_eq!(visible_bg_buffer[0][55], 255);
+        assert_eq!(visible_bg_buffer[0][56], 0);
+        assert_eq!(visible_bg_buffer[0][159], 103);
+
+        // SCX=255: only the last column of the background is on the left of the wrap
+        let visible_bg_buffer = get_visible_bg_buffer_with_scroll(&columns_buffer, 255, 0);
+        assert_columns_are_scrolled_by(&visible_bg_buffer, 255);
+        assert_eq!(visible_bg_buffer[0][0], 255);
+        assert_eq!(visible_bg_buffer[0][1], 0);
+    }
+
+    #[test]
+    fn test_visible_bg_buffer_wraps_in_both_directions() {
+        let rows_buffer = create_numbered_bg_buffer(false);
+        let columns_buffer = create_numbered_bg_buffer(true);
+        assert_rows_are_scrolled_by(&get_visible_bg_buffer_with_scroll(&rows_buffer, 200, 248), 248);
+        assert_columns_are_scrolled_by(&get_visible_bg_buffer_with_scroll(&columns_buffer, 200, 248), 200);
+
+        // A single marked pixel at each corner of the background: with SCX=200 and SCY=248 the 4 corners meet
+        // at screen column 56 and row 8
+        let mut bg_buffer = [[0u8; BG_AND_WINDOW_MAP_SCREEN_SIZE]; BG_AND_WINDOW_MAP_SCREEN_SIZE];
+        bg_buffer[0][0] = 1;
+        bg_buffer[0][255] = 2;
+        bg_buffer[255][0] = 3;
+        bg_buffer[255][255] = 4;
+
+        let visible_bg_buffer = get_visible_bg_buffer_with_scroll(&bg_buffer, 200, 248);
+        assert_eq!(visible_bg_buffer[8][56], 1);
+        assert_eq!(visible_bg_buffer[8][55], 2);
+        assert_eq!(visible_bg_buffer[7][56], 3);
+        assert_eq!(visible_bg_buffer[7][55], 4);
+        let marked_pixels = visible_bg_buffer.iter().flatten().filter(|&&pixel| pixel != 0).count();
+        assert_eq!(marked_pixels, 4);
+    }
+
+    /// Background line used by the sprite tests: color indexes 0, 1, 2, 3 repeated, drawn with the 0xE4 palette.
+    fn create_background_line() -> ([u32; GAME_SECTION_WIDTH], [u8; GAME_SECTION_WIDTH]) {
+        use crate::gameboy_core::constants::COLORS;
+        let bg_color_indexes: [u8; GAME_SECTION_WIDTH] = std::array::from_fn(|col| (col % 4) as u8);
+        (bg_color_indexes.map(|index| COLORS[index as usize]), bg_color_indexes)
+    }
+
+    /// Objects on, OBP0 = 0xE4 and OBP1 = 0x1B (reversed colors). Tile 1 has the row 0 with the color indexes
+    /// 0, 1, 2, 3, 0, 1, 2, 3 and the other rows with color 3.
+    fn create_cpu_with_sprite_tile() -> crate::gameboy_core::cpu::Cpu {
+        use crate::gameboy_core::registers_contants::{OBP0, OBP1};
+        let mut cpu = crate::gameboy_core::cpu::Cpu::new();
+        cpu.memory_bus.set_lcdc_register(0x93);
+        cpu.memory_bus.write_byte(OBP0, 0xE4);
+        cpu.memory_bus.write_byte(OBP1, 0x1B);
+        cpu.memory_bus.write_byte(0x8010, 0b0101_0101);
+        cpu.memory_bus.write_byte(0x8011, 0b0011_0011);
+        for address in 0x8012..0x8020 {
+            cpu.memory_bus.write_byte(address, 0xFF);
+        }
+        cpu
+    }
+
+    /// Selects the objects of the scanline LY, as the PPU does at the start of the line, and draws them over the
+    /// background line.
+    fn render_sprites_over_background(cpu: &mut crate::gameboy_core::cpu::Cpu, ly: u8) -> [u32; GAME_SECTION_WIDTH] {
+        use crate::gameboy_core::ppu::Ppu;
+        let oam = Ppu::get_objects(&cpu.memory_bus);
+        let object_height = ppu_components::LcdcRegister::get_lcdc_register(&cpu.memory_bus).obj_size();
+        cpu.ppu.objects_to_be_rendered = Ppu::objects_on_scanline(&oam, ly, object_height).copied().collect();
+
+        let (mut line, bg_color_indexes) = create_background_line();
+        cpu.ppu.render_sprites_for_scanline(ly, &mut line, &bg_color_indexes, &cpu.memory_bus);
+        line
+    }
+
+    fn write_object_attributes(cpu: &mut crate::gameboy_core::cpu::Cpu, oam_index: u16, attributes: u8) {
+        use crate::gameboy_core::constants::OAM_START;
+        cpu.memory_bus.write_byte(OAM_START + oam_index * 4 + 3, attributes);
+    }
+
+    #[test]
+    fn test_sprite_color_0_is_transparent() {
+        use crate::gameboy_core::constants::COLORS;
+        let mut cpu = create_cpu_with_sprite_tile();
+        // Screen X 16-23 on the line 0
+        write_object_to_oam(&mut cpu, 0, 16, 24, 1);
+
+        let line = render_sprites_over_background(&mut cpu, 0);
+
+        let (background, _) = create_background_line();
+        assert_eq!(line[16], background[16], "color 0 shows the background");
+        assert_eq!(line[20], background[20]);
+        assert_eq!(line[17], COLORS[1]);
+        assert_eq!(line[18], COLORS[2]);
+        assert_eq!(line[19], COLORS[3]);
+        assert_eq!(line[..16], background[..16]);
+        assert_eq!(line[24..], background[24..]);
+    }
+
+    #[test]
+    fn test_sprite_uses_the_palette_selected_by_its_attributes() {
+        use crate::gameboy_core::constants::COLORS;
+        let mut cpu = create_cpu_with_sprite_tile();
+        write_object_to_oam(&mut cpu, 0, 16, 24, 1);
+        write_object_attributes(&mut cpu, 0, 0b0001_0000);
+
+        let line = render_sprites_over_background(&mut cpu, 0);
+
+        assert_eq!(line[16..20], [COLORS[0], COLORS[2], COLORS[1], COLORS[0]]);
+    }
+
+    #[test]
+    fn test_sprite_x_and_y_flips() {
+        use crate::gameboy_core::constants::COLORS;
+        let mut cpu = create_cpu_with_sprite_tile();
+        write_object_to_oam(&mut cpu, 0, 16, 24, 1);
+        write_object_attributes(&mut cpu, 0, 0b0010_0000);
+        let line = render_sprites_over_background(&mut cpu, 0);
+        assert_eq!(line[20..23], [COLORS[3], COLORS[2], COLORS[1]], "the row is mirrored");
+        assert_eq!(line[23], create_background_line().0[23]);
+
+        // With the Y flip the line 7 shows the row 0 of the tile, the line 0 shows the row 7
+        write_object_attributes(&mut cpu, 0, 0b0100_0000);
+        let line = render_sprites_over_background(&mut cpu, 7);
+        assert_eq!(line[16..20], [COLORS[0], COLORS[1], COLORS[2], COLORS[3]]);
+        let line = render_sprites_over_background(&mut cpu, 0);
+        assert!(line[16..24].iter().all(|&pixel| pixel == COLORS[3]));
+    }
+
+    #[test]
+    fn test_priority_sprite_is_only_drawn_over_background_color_0() {
+        use crate::gameboy_core::constants::COLORS;
+        let mut cpu = create_cpu_with_sprite_tile();
+        // Line 1 of the sprite is color 3
+        write_object_to_oam(&mut cpu, 0, 16, 24, 1);
+        write_object_attributes(&mut cpu, 0, 0b1000_0000);
+
+        let line = render_sprites_over_background(&mut cpu, 1);
+
+        let (background, _) = create_background_line();
+        assert_eq!(line[16], COLORS[3], "drawn over the background color 0");
+        assert_eq!(line[17..20], background[17..20], "hidden behind the background colors 1-3");
+        assert_eq!(line[20], COLORS[3]);
+    }
+
+    #[test]
+    fn test_priority_is_checked_on_the_color_index_not_the_palette_color() {
+        use crate::gameboy_core::{constants::COLORS, ppu::Ppu};
+        let mut cpu = create_cpu_with_sprite_tile();
+        write_object_to_oam(&mut cpu, 0, 16, 24, 1);
+        write_object_attributes(&mut cpu, 0, 0b1000_0000);
+        let oam = Ppu::get_objects(&cpu.memory_bus);
+        cpu.ppu.objects_to_be_rendered = Ppu::objects_on_scanline(&oam, 1, 8).copied().collect();
+
+        // The background color 0 is drawn dark gray by its palette, the sprite is still drawn over it
+        let mut line = [COLORS[2]; GAME_SECTION_WIDTH];
+        cpu.ppu.render_sprites_for_scanline(1, &mut line, &[0; GAME_SECTION_WIDTH], &cpu.memory_bus);
+        assert_eq!(line[16], COLORS[3]);
+
+        // The background color 1 is drawn white, the sprite stays behind it
+        let mut line = [COLORS[0]; GAME_SECTION_WIDTH];
+        cpu.ppu.render_sprites_for_scanline(1, &mut line, &[1; GAME_SECTION_WIDTH], &cpu.memory_bus);
+        assert_eq!(line[16], COLORS[0]);
+    }
+
+    #[test]
+    fn test_hidden_priority_sprite_still_covers_the_sprites_below_it() {
+        use crate::gameboy_core::constants::COLORS;
+        let mut cpu = create_cpu_with_sprite_tile();
+        // Tile 2 filled with color 1
+        for address in (0x8020..0x8030).step_by(2) {
+            cpu.memory_bus.write_byte(address, 0xFF);
+        }
+        // The object with the lower X has the highest priority but is behind the background
+        write_object_to_oam(&mut cpu, 0, 16, 24, 1);
+        write_object_attributes(&mut cpu, 0, 0b1000_0000);
+        write_object_to_oam(&mut cpu, 1, 16, 28, 2);
+
+        let line = render_sprites_over_background(&mut cpu, 1);
+
+        let (background, _) = create_background_line();
+        assert_eq!(line[21..24], background[21..24], "the object below doesn't show through");
+        assert_eq!(line[24], COLORS[1]);
+        assert_eq!(line[27], COLORS[1]);
+    }
+
+    #[test]
+    fn test_sprites_are_not_drawn_when_disabled_in_lcdc() {
+        let mut cpu = create_cpu_with_sprite_tile();
+        write_object_to_oam(&mut cpu, 0, 16, 24, 1);
+        cpu.memory_bus.set_lcdc_register(0x91);
+
+        let line = render_sprites_over_background(&mut cpu, 1);
+
+        assert_eq!(line, create_background_line().0);
+    }
+
+    #[test]
+    fn test_sprites_partially_outside_the_screen() {
+        use crate::gameboy_core::constants::COLORS;
+        let mut cpu = create_cpu_with_sprite_tile();
+        // Only the 4 rightmost columns on the left border, and only the 2 leftmost columns on the right border
+        write_object_to_oam(&mut cpu, 0, 16, 4, 1);
+        write_object_to_oam(&mut cpu, 1, 16, 166, 1);
+
+        let line = render_sprites_over_background(&mut cpu, 0);
+
+        assert_eq!(line[..4], [COLORS[0], COLORS[1], COLORS[2], COLORS[3]]);
+        assert_eq!(line[158..], [create_background_line().0[158], COLORS[1]]);
+    }
+
+    /// LCD, background and window on, the window uses the tile map at $9C00 filled with tile 1, whose first row
+    /// uses color 1 and the others color 2. The background map at $9800 uses tile 0 (color 0). BGP = 0xE4.
+    fn create_cpu_with_window(wx: u8, wy: u8) -> crate::gameboy_core::cpu::Cpu {
+        use crate::gameboy_core::registers_contants::{WX, WY};
+        let mut cpu = crate::gameboy_core::cpu::Cpu::new();
+        cpu.memory_bus.set_lcdc_register(0xF1);
+        cpu.memory_bus.write_byte(BGP, 0xE4);
+        cpu.memory_bus.write_byte(WX, wx);
+        cpu.memory_bus.write_byte(WY, wy);
+        cpu.memory_bus.write_byte(0x8010, 0xFF);
+        for address in (0x8012..0x8020).step_by(2) {
+            cpu.memory_bus.write_byte(address + 1, 0xFF);
+        }
+        for address in 0x9C00..0xA000 {
+            cpu.memory_bus.write_byte(address, 1);
+        }
+        cpu
+    }
+
+    #[test]
+    fn test_window_with_wx_7_covers_the_whole_line() {
+        use crate::gameboy_core::constants::COLORS;
+        let mut cpu = create_cpu_with_window(7, 0);
+
+        cpu.ppu.step_scanline(&cpu.memory_bus, 0);
+        cpu.ppu.step_scanline(&cpu.memory_bus, 1);
+
+        assert!(cpu.ppu.screen[0].iter().all(|&pixel| pixel == COLORS[1]));
+        assert!(cpu.ppu.screen[1].iter().all(|&pixel| pixel == COLORS[2]));
+        assert_eq!(cpu.ppu.window_internal_line, 2);
+    }
+
+    #[test]
+    fn test_window_with_wx_86_covers_the_right_half() {
+        use crate::gameboy_core::constants::COLORS;
+        let mut cpu = create_cpu_with_window(86, 0);
+
+        cpu.ppu.step_scanline(&cpu.memory_bus, 0);
+
+        assert!(cpu.ppu.screen[0][..79].iter().all(|&pixel| pixel == COLORS[0]), "the background is on the left");
+        assert!(cpu.ppu.screen[0][79..].iter().all(|&pixel| pixel == COLORS[1]));
+    }
+
+    #[test]
+    fn test_window_starts_at_wy() {
+        use crate::gameboy_core::constants::COLORS;
+        let mut cpu = create_cpu_with_window(7, 10);
+
+        for ly in 0..12 {
+            cpu.ppu.step_scanline(&cpu.memory_bus, ly);
+        }
+
+        assert!(cpu.ppu.screen[9].iter().all(|&pixel| pixel == COLORS[0]));
+        assert!(cpu.ppu.screen[10].iter().all(|&pixel| pixel == COLORS[1]), "the first row of the window is at WY");
+        assert!(cpu.ppu.screen[11].iter().all(|&pixel| pixel == COLORS[2]));
+        assert_eq!(cpu.ppu.window_internal_line, 2);
+    }
+
+    #[test]
+    fn test_window_line_counter_only_advances_when_the_window_is_visible() {
+        use crate::gameboy_core::constants::COLORS;
+        let mut cpu = create_cpu_with_window(7, 0);
+        cpu.ppu.step_scanline(&cpu.memory_bus, 0);
+
+        // Hidden on the lines 1-4, the window continues with its second row on the line 5
+        cpu.memory_bus.set_lcdc_register(0xD1);
+        for ly in 1..5 {
+            cpu.ppu.step_scanline(&cpu.memory_bus, ly);
+        }
+        assert_eq!(cpu.ppu.window_internal_line, 1);
+        cpu.memory_bus.set_lcdc_register(0xF1);
+        cpu.ppu.step_scanline(&cpu.memory_bus, 5);
+
+        assert!(cpu.ppu.screen[4].iter().all(|&pixel| pixel == COLORS[0]));
+        assert!(cpu.ppu.screen[5].iter().all(|&pixel| pixel == COLORS[2]));
+
+        // A new frame starts again from the first row
+        cpu.ppu.step_scanline(&cpu.memory_bus, 0);
+        assert!(cpu.ppu.screen[0].iter().all(|&pixel| pixel == COLORS[1]));
+    }
+
+    #[test]
+    fn test_window_is_hidden_with_the_background_or_past_the_right_border() {
+        use crate::gameboy_core::constants::COLORS;
+        let mut cpu = create_cpu_with_window(167, 0);
+        cpu.ppu.step_scanline(&cpu.memory_bus, 0);
+        assert!(cpu.ppu.screen[0].iter().all(|&pixel| pixel == COLORS[0]));
+
+        let mut cpu = create_cpu_with_window(7, 0);
+        cpu.memory_bus.set_lcdc_register(0xF0);
+        cpu.ppu.step_scanline(&cpu.memory_bus, 0);
+        assert!(cpu.ppu.screen[0].iter().all(|&pixel| pixel == 0xFFFFFF));
+        assert_eq!(cpu.ppu.window_internal_line, 0);
+    }
+
+    #[test]
+    fn test_priority_sprites_are_hidden_behind_the_window_colors_1_to_3() {
+        use crate::gameboy_core::{constants::COLORS, ppu::Ppu, registers_contants::OBP0};
+        let mut cpu = create_cpu_with_window(86, 0);
+        cpu.memory_bus.write_byte(OBP0, 0xE4);
+        // Tile 2 filled with color 3, object with the priority bit across the left border of the window
+        for address in 0x8020..0x8030 {
+            cpu.memory_bus.write_byte(address, 0xFF);
+        }
+        cpu.memory_bus.set_lcdc_register(0xF3);
+        write_object_to_oam(&mut cpu, 0, 16, 83, 2);
+        write_object_attributes(&mut cpu, 0, 0b1000_0000);
+        let oam = Ppu::get_objects(&cpu.memory_bus);
+        cpu.ppu.objects_to_be_rendered = Ppu::objects_on_scanline(&oam, 0, 8).copied().collect();
+
+        cpu.ppu.step_scanline(&cpu.memory_bus, 0);
+
+        assert_eq!(cpu.ppu.screen[0][75..79], [COLORS[3]; 4], "drawn over the background color 0");
+        assert_eq!(cpu.ppu.screen[0][79..83], [COLORS[1]; 4], "hidden behind the window color 1");
+    }
+
+    #[test]
+    fn test_bg_screen_buffer_as_colors_includes_the_window() {
+        use crate::gameboy_core::constants::COLORS;
+        let cpu = create_cpu_with_window(86, 100);
+        let lcdc = ppu_components::LcdcRegister::get_lcdc_register(&cpu.memory_bus);
+
+        let screen = cpu.ppu.get_bg_screen_buffer_as_colors(&cpu.memory_bus, &lcdc);
+
+        assert_eq!(screen[99][100], COLORS[0]);
+        assert_eq!(screen[100][78], COLORS[0]);
+        assert_eq!(screen[100][79], COLORS[1]);
+        assert_eq!(screen[101][159], COLORS[2]);
+    }
+
+    #[test]
+    fn test_background_and_window_colors_go_through_bgp() {
+        use crate::gameboy_core::constants::COLORS;
+        let mut cpu = create_cpu_with_window(86, 1);
+        cpu.memory_bus.write_byte(BGP, 0x1B);
+        let lcdc = ppu_components::LcdcRegister::get_lcdc_register(&cpu.memory_bus);
+
+        cpu.ppu.step_scanline(&cpu.memory_bus, 0);
+        cpu.ppu.step_scanline(&cpu.memory_bus, 1);
+        let screen = cpu.ppu.get_bg_screen_buffer_as_colors(&cpu.memory_bus, &lcdc);
+
+        assert_eq!(cpu.ppu.screen[0][0], COLORS[3], "background color 0 is the shade 3 with 0x1B");
+        assert_eq!(cpu.ppu.screen[1][79], COLORS[2], "window color 1 is the shade 2 with 0x1B");
+        assert_eq!(screen[0][0], COLORS[3]);
+        assert_eq!(screen[1][79], COLORS[2]);
+        assert_eq!(screen[2][79], COLORS[1]);
+    }
+
+    /// Color index of the pixel of tile 3 at the row and column, every pixel of a row or a column is different from
+    /// its mirrored one so both flips can be told apart.
+    fn get_asymmetric_tile_color_index(row: usize, col: usize) -> u8 {
+        ((row + col) % 4) as u8
+    }
+
+    /// Writes tile 3 with the colors of `get_asymmetric_tile_color_index`.
+    fn write_asymmetric_tile(cpu: &mut crate::gameboy_core::cpu::Cpu) {
+        for row in 0..8 {
+            let (mut low_byte, mut high_byte) = (0u8, 0u8);
+            for col in 0..8 {
+                let color_index = get_asymmetric_tile_color_index(row, col);
+                low_byte |= (color_index & 0x01) << (7 - col);
+                high_byte |= (color_index >> 1) << (7 - col);
+            }
+            cpu.memory_bus.write_byte(0x8030 + row as u16 * 2, low_byte);
+            cpu.memory_bus.write_byte(0x8031 + row as u16 * 2, high_byte);
+        }
+    }
+
+    #[test]
+    fn test_sprite_flips_and_priority_for_each_combination() {
+        use crate::gameboy_core::constants::COLORS;
+        let (background, bg_color_indexes) = create_background_line();
+
+        for x_flip in [false, true] {
+            for y_flip in [false, true] {
+                for priority in [false, true] {
+                    let mut cpu = create_cpu_with_sprite_tile();
+                    write_asymmetric_tile(&mut cpu);
+                    // Screen X 16-23 and lines 0-7
+                    write_object_to_oam(&mut cpu, 0, 16, 24, 3);
+                    let attributes = (priority as u8) << 7 | (y_flip as u8) << 6 | (x_flip as u8) << 5;
+                    write_object_attributes(&mut cpu, 0, attributes);
+
+                    for ly in 0..8 {
+                        let line = render_sprites_over_background(&mut cpu, ly as u8);
+
+                        for col in 0..8 {
+                            let screen_x = 16 + col;
+                            let tile_row = if y_flip { 7 - ly } else { ly };
+                            let tile_col = if x_flip { 7 - col } else { col };
+                            let color_index = get_asymmetric_tile_color_index(tile_row, tile_col);
+                            let is_hidden = color_index == 0 || (priority && bg_color_indexes[screen_x] != 0);
+                            // OBP0 = 0xE4 keeps the color indexes
+                            let expected = if is_hidden { background[screen_x] } else { COLORS[color_index as usize] };
+
+                            assert_eq!(
+                                line[screen_x], expected,
+                                "x_flip={} y_flip={} priority={} line {} column {}",
+                                x_flip, y_flip, priority, ly, col
+                            );
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    /// Tile 4 has its row 0 in color 3 and the others in color 1, tile 5 is filled with color 2. The object uses
+    /// tile 5 and covers the lines 0-7, or 0-15 in 8x16 mode.
+    fn create_cpu_with_8x16_sprite(lcdc: u8, attributes: u8) -> crate::gameboy_core::cpu::Cpu {
+        let mut cpu = create_cpu_with_sprite_tile();
+        cpu.memory_bus.set_lcdc_register(lcdc);
+        cpu.memory_bus.write_byte(0x8040, 0xFF);
+        cpu.memory_bus.write_byte(0x8041, 0xFF);
+        for address in (0x8042..0x8050).step_by(2) {
+            cpu.memory_bus.write_byte(address, 0xFF);
+        }
+        for address in (0x8050..0x8060).step_by(2) {
+            cpu.memory_bus.write_byte(address + 1, 0xFF);
+        }
+        write_object_to_oam(&mut cpu, 0, 16, 24, 5);
+        write_object_attributes(&mut cpu, 0, attributes);
+        cpu
+    }
+
+    #[test]
+    fn test_8x8_and_8x16_sprites_with_the_same_tile_index() {
+        use crate::gameboy_core::constants::COLORS;
+        let background = create_background_line().0;
+
+        let mut cpu = create_cpu_with_8x16_sprite(0x93, 0);
+        assert_eq!(render_sprites_over_background(&mut cpu, 0)[16], COLORS[2], "8x8 uses the tile as it is");
+        assert_eq!(render_sprites_over_background(&mut cpu, 7)[16], COLORS[2]);
+        assert_eq!(render_sprites_over_background(&mut cpu, 8)[16], background[16], "8x8 ends after 8 lines");
+
+        let mut cpu = create_cpu_with_8x16_sprite(0x97, 0);
+        assert_eq!(render_sprites_over_background(&mut cpu, 0)[16], COLORS[3], "the top half uses tile_index & 0xFE");
+        assert_eq!(render_sprites_over_background(&mut cpu, 7)[16], COLORS[1]);
+        assert_eq!(render_sprites_over_background(&mut cpu, 8)[16], COLORS[2], "the bottom half uses tile_index | 1");
+        assert_eq!(render_sprites_over_background(&mut cpu, 15)[16], COLORS[2]);
+        assert_eq!(render_sprites_over_background(&mut cpu, 16)[16], background[16]);
+    }
+
+    #[test]
+    fn test_8x16_sprite_y_flip_reverses_the_16_rows() {
+        use crate::gameboy_core::constants::COLORS;
+        let mut cpu = create_cpu_with_8x16_sprite(0x97, 0b0100_0000);
+
+        assert_eq!(render_sprites_over_background(&mut cpu, 0)[16], COLORS[2], "the bottom tile is drawn on top");
+        assert_eq!(render_sprites_over_background(&mut cpu, 7)[16], COLORS[2]);
+        assert_eq!(render_sprites_over_background(&mut cpu, 8)[16], COLORS[1]);
+        assert_eq!(render_sprites_over_background(&mut cpu, 15)[16], COLORS[3], "the row 0 is the last one");
+    }
+
+    #[test]
+    fn test_8x16_sprites_are_selected_on_16_lines() {
+        let mut cpu = create_cpu_with_8x16_sprite(0x97, 0);
+        cpu.memory_bus.write_byte(LY, 11);
+        cpu.memory_bus.write_byte(STAT, 0);
+
+        // The PPU selects the objects of the line when LY is incremented
+        while cpu.memory_bus.read_byte(LY) != 12 {
+            cpu.update_ppu();
+        }
+        assert_eq!(cpu.ppu.objects_to_be_rendered.len(), 1);
+
+        cpu.memory_bus.set_lcdc_register(0x93);
+        while cpu.memory_bus.read_byte(LY) != 13 {
+            cpu.update_ppu();
+        }
+        assert!(cpu.ppu.objects_to_be_rendered.is_empty());
+    }
 }
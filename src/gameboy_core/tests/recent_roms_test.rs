@@ -0,0 +1,75 @@
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    use crate::recent_roms::{MAX_RECENT_ROMS, RecentRoms};
+
+    fn create_recent_roms(names: &[&str]) -> RecentRoms {
+        let mut recent_roms = RecentRoms::new();
+        for name in names {
+            recent_roms.push(PathBuf::from(name));
+        }
+        recent_roms
+    }
+
+    #[test]
+    fn test_push_keeps_the_most_recent_rom_first() {
+        let recent_roms = create_recent_roms(&["tetris.gb", "zelda.gb", "mario.gb"]);
+
+        assert_eq!(
+            recent_roms.get_all(),
+            vec![PathBuf::from("mario.gb"), PathBuf::from("zelda.gb"), PathBuf::from("tetris.gb")]
+        );
+    }
+
+    #[test]
+    fn test_push_moves_an_already_opened_rom_to_the_top() {
+        let mut recent_roms = create_recent_roms(&["tetris.gb", "zelda.gb", "mario.gb"]);
+
+        recent_roms.push(PathBuf::from("tetris.gb"));
+
+        assert_eq!(
+            recent_roms.get_all(),
+            vec![PathBuf::from("tetris.gb"), PathBuf::from("mario.gb"), PathBuf::from("zelda.gb")]
+        );
+    }
+
+    #[test]
+    fn test_push_drops_the_oldest_rom_when_full() {
+        let mut recent_roms = RecentRoms::new();
+        for i in 0..MAX_RECENT_ROMS + 2 {
+            recent_roms.push(PathBuf::from(format!("rom{}.gb", i)));
+        }
+
+        let all = recent_roms.get_all();
+        assert_eq!(all.len(), MAX_RECENT_ROMS);
+        assert_eq!(all[0], PathBuf::from("rom11.gb"));
+        assert_eq!(all[MAX_RECENT_ROMS - 1], PathBuf::from("rom2.gb"));
+    }
+
+    #[test]
+    fn test_recent_roms_round_trip_through_json_file() {
+        let path = std::env::temp_dir()
+            .join(format!("gameboy_emulator_recent_roms_test_{}", std::process::id()))
+            .join("recent_roms.json");
+        let recent_roms = create_recent_roms(&["games/tetris.gb", "games/zelda.gb"]);
+
+        recent_roms.save_to_file(&path).unwrap();
+        let loaded = RecentRoms::load_from_file(&path).unwrap();
+        std::fs::remove_dir_all(path.parent().unwrap()).unwrap();
+
+        assert_eq!(loaded, recent_roms);
+    }
+
+    #[test]
+    fn test_parse_selection() {
+        let recent_roms = create_recent_roms(&["tetris.gb", "zelda.gb"]);
+
+        assert_eq!(recent_roms.parse_selection("1\n"), Some(PathBuf::from("zelda.gb")));
+        assert_eq!(recent_roms.parse_selection(" 2 "), Some(PathBuf::from("tetris.gb")));
+        assert_eq!(recent_roms.parse_selection("3"), None);
+        assert_eq!(recent_roms.parse_selection("0"), None);
+        assert_eq!(recent_roms.parse_selection(""), None);
+        assert_eq!(recent_roms.parse_selection("roms/mario.gb\n"), Some(PathBuf::from("roms/mario.gb")));
+    }
+}
@@ -0,0 +1,483 @@
+#[cfg(test)]
+mod tests {
+    use crate::gameboy_core::{
+        components::screen::{
+            OAM_VIEW_HEIGHT, OAM_VIEW_WIDTH, PALETTE_VIEW_HEIGHT, PALETTE_VIEW_WIDTH, Screen,
+            EmulationSpeed, GameImageRect, ScalingMode, TOTAL_WINDOW_HEIGHT, TOTAL_WINDOW_WIDTH, WINDOW_TITLE,
+            apply_crt_filter,
+        },
+        constants::{COLORS, GAME_SECTION_HEIGHT, GAME_SECTION_WIDTH, MAX_SCREEN_SCALE, MIN_SCREEN_SCALE},
+        cpu::Cpu,
+        emulator_error::EmulatorError,
+        ppu::Ppu,
+        registers_contants::{BGP, OBP0, OBP1},
+    };
+
+    /// Asserts that the whole scale x scale block starting at (row, col) has the expected color
+    fn assert_scaled_pixel(buffer: &[u32], scale: usize, row: usize, col: usize, expected: u32) {
+        let window_width = Screen::get_window_width(scale);
+
+        for scale_row in 0..scale {
+            for scale_col in 0..scale {
+                assert_eq!(
+                    buffer[(row + scale_row) * window_width + col + scale_col], expected,
+                    "Wrong color at buffer position ({}, {}) with scale {}", row + scale_row, col + scale_col, scale
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_window_dimensions_for_each_scale() {
+        // Tile data: 16 tiles of 8 pixels wide plus 15 margins of 2 pixels, 24 tiles tall plus 23 margins
+        let expected_dimensions = [(1, 338, 238), (2, 656, 476), (3, 974, 714), (4, 1292, 952)];
+
+        for (scale, width, height) in expected_dimensions {
+            assert_eq!(Screen::get_window_width(scale), width, "Wrong width with scale {}", scale);
+            assert_eq!(Screen::get_window_height(scale), height, "Wrong height with scale {}", scale);
+            assert_eq!(Screen::create_buffer(scale).len(), width * height);
+        }
+    }
+
+    #[test]
+    fn test_total_window_dimensions_are_the_default_scale_dimensions() {
+        assert_eq!((TOTAL_WINDOW_WIDTH, TOTAL_WINDOW_HEIGHT), (974, 714));
+    }
+
+    #[test]
+    fn test_new_screen_in_headless_environment() {
+        // minifb has no offscreen backend, without a display the window can't be opened and the error is returned
+        match Screen::new(WINDOW_TITLE.to_string()) {
+            Ok(screen) => {
+                assert_eq!(screen.buffer_width, TOTAL_WINDOW_WIDTH);
+                assert_eq!(screen.buffer_height, TOTAL_WINDOW_HEIGHT);
+                assert_eq!(screen.buffer.len(), TOTAL_WINDOW_WIDTH * TOTAL_WINDOW_HEIGHT);
+            }
+            Err(e) => assert!(matches!(e, EmulatorError::WindowError(_)), "Unexpected error: {}", e),
+        }
+    }
+
+    #[test]
+    fn test_tile_pixel_buffer_position() {
+        for scale in MIN_SCREEN_SCALE..=MAX_SCREEN_SCALE {
+            let tile_data_start_col = GAME_SECTION_WIDTH * scale + 20;
+
+            assert_eq!(Screen::get_tile_pixel_buffer_position(0, 0, 0, Screen::get_window_width(scale), scale), (0, tile_data_start_col));
+            // Second pixel of the first tile is one scaled pixel to the right
+            assert_eq!(Screen::get_tile_pixel_buffer_position(0, 0, 1, Screen::get_window_width(scale), scale), (0, tile_data_start_col + scale));
+            // Tile 1 starts after 8 pixels plus a 2 pixels margin
+            assert_eq!(Screen::get_tile_pixel_buffer_position(1, 0, 0, Screen::get_window_width(scale), scale), (0, tile_data_start_col + 10 * scale));
+            // Tile 17 is in the second row and second column of the grid, last pixel of that tile
+            assert_eq!(
+                Screen::get_tile_pixel_buffer_position(17, 7, 7, Screen::get_window_width(scale), scale),
+                (17 * scale, tile_data_start_col + 17 * scale)
+            );
+            // Last tile is at row 23, column 15, and its last pixel fits in the window
+            assert_eq!(
+                Screen::get_tile_pixel_buffer_position(383, 0, 0, Screen::get_window_width(scale), scale),
+                (23 * 10 * scale, tile_data_start_col + 15 * 10 * scale)
+            );
+            let (row, col) = Screen::get_tile_pixel_buffer_position(383, 7, 7, Screen::get_window_width(scale), scale);
+            assert_eq!(row + scale, Screen::get_window_height(scale));
+            assert_eq!(col + scale, Screen::get_window_width(scale));
+        }
+    }
+
+    #[test]
+    fn test_render_tiles_to_buffer_applies_bgp_palette() {
+        let mut cpu = Cpu::new();
+        // Tile 0, row 0: every pixel has color index 1 (low byte set, high byte clear)
+        cpu.memory_bus.write_byte(0x8000, 0xFF);
+        cpu.memory_bus.write_byte(0x8001, 0x00);
+        // Tile 17, row 7: left half color index 2 and right half color index 3
+        cpu.memory_bus.write_byte(0x8000 + 17 * 16 + 14, 0x0F);
+        cpu.memory_bus.write_byte(0x8000 + 17 * 16 + 15, 0xFF);
+
+        let tiles = cpu.ppu.get_tiles_data(&cpu.memory_bus);
+
+        for scale in MIN_SCREEN_SCALE..=MAX_SCREEN_SCALE {
+            let mut buffer = Screen::create_buffer(scale);
+
+            // Identity palette: 11 10 01 00
+            Screen::render_tiles_to_buffer(&mut buffer, Screen::get_window_width(scale), &tiles, 0xE4, scale);
+
+            let (row, col) = Screen::get_tile_pixel_buffer_position(0, 0, 0, Screen::get_window_width(scale), scale);
+            assert_scaled_pixel(&buffer, scale, row, col, COLORS[1]);
+            let (row, col) = Screen::get_tile_pixel_buffer_position(0, 1, 0, Screen::get_window_width(scale), scale);
+            assert_scaled_pixel(&buffer, scale, row, col, COLORS[0]);
+            let (row, col) = Screen::get_tile_pixel_buffer_position(17, 7, 0, Screen::get_window_width(scale), scale);
+            assert_scaled_pixel(&buffer, scale, row, col, COLORS[2]);
+            let (row, col) = Screen::get_tile_pixel_buffer_position(17, 7, 7, Screen::get_window_width(scale), scale);
+            assert_scaled_pixel(&buffer, scale, row, col, COLORS[3]);
+
+            // Inverted palette: 00 01 10 11
+            Screen::render_tiles_to_buffer(&mut buffer, Screen::get_window_width(scale), &tiles, 0x1B, scale);
+
+            let (row, col) = Screen::get_tile_pixel_buffer_position(0, 0, 0, Screen::get_window_width(scale), scale);
+            assert_scaled_pixel(&buffer, scale, row, col, COLORS[2]);
+            let (row, col) = Screen::get_tile_pixel_buffer_position(0, 1, 0, Screen::get_window_width(scale), scale);
+            assert_scaled_pixel(&buffer, scale, row, col, COLORS[3]);
+            let (row, col) = Screen::get_tile_pixel_buffer_position(17, 7, 7, Screen::get_window_width(scale), scale);
+            assert_scaled_pixel(&buffer, scale, row, col, COLORS[0]);
+        }
+    }
+
+    fn create_test_game_screen() -> [[u32; GAME_SECTION_WIDTH]; GAME_SECTION_HEIGHT] {
+        let mut game_screen = [[COLORS[0]; GAME_SECTION_WIDTH]; GAME_SECTION_HEIGHT];
+        game_screen[0][0] = COLORS[3];
+        game_screen[10][20] = COLORS[1];
+        game_screen[GAME_SECTION_HEIGHT - 1][GAME_SECTION_WIDTH - 1] = COLORS[2];
+        game_screen
+    }
+
+    #[test]
+    fn test_render_game_to_buffer_scales_each_pixel() {
+        let game_screen = create_test_game_screen();
+
+        for scale in MIN_SCREEN_SCALE..=MAX_SCREEN_SCALE {
+            let mut buffer = Screen::create_buffer(scale);
+            let window_width = Screen::get_window_width(scale);
+
+            // Game area of exactly the image size, so there is no letterboxing
+            Screen::render_game_to_buffer(
+                &mut buffer,
+                window_width,
+                GAME_SECTION_WIDTH * scale,
+                GAME_SECTION_HEIGHT * scale,
+                &game_screen,
+                ScalingMode::Integer(scale as u8),
+                0x123456,
+            );
+
+            assert_scaled_pixel(&buffer, scale, 0, 0, COLORS[3]);
+            assert_scaled_pixel(&buffer, scale, 0, scale, COLORS[0]);
+            assert_scaled_pixel(&buffer, scale, 10 * scale, 20 * scale, COLORS[1]);
+            assert_scaled_pixel(
+                &buffer,
+                scale,
+                (GAME_SECTION_HEIGHT - 1) * scale,
+                (GAME_SECTION_WIDTH - 1) * scale,
+                COLORS[2],
+            );
+            // The margin between the game and the tile data keeps the background color
+            assert_eq!(buffer[GAME_SECTION_WIDTH * scale], 0x000080);
+            assert_eq!(buffer[window_width * (GAME_SECTION_HEIGHT * scale - 1) + GAME_SECTION_WIDTH * scale], 0x000080);
+        }
+    }
+
+    #[test]
+    fn test_render_game_to_buffer_in_default_window_at_each_scale() {
+        let game_screen = create_test_game_screen();
+        let background_color = 0x000080;
+
+        for scale in MIN_SCREEN_SCALE..=MAX_SCREEN_SCALE {
+            let window_width = Screen::get_window_width(scale);
+            let window_height = Screen::get_window_height(scale);
+            let mut buffer = Screen::create_buffer(scale);
+
+            Screen::render_game_to_buffer(
+                &mut buffer,
+                window_width,
+                Screen::get_game_area_width(window_width, scale),
+                window_height,
+                &game_screen,
+                ScalingMode::Integer(scale as u8),
+                background_color,
+            );
+
+            // The window is taller than the game (238 vs 144 rows at 1x), so it is centered 47 rows down
+            let top = 47 * scale;
+            let game_pixel_index = |row: usize, col: usize| (top + row * scale) * window_width + col * scale;
+            assert_eq!(buffer[game_pixel_index(0, 0)], COLORS[3], "Wrong top-left pixel with scale {}", scale);
+            assert_eq!(buffer[game_pixel_index(10, 20)], COLORS[1], "Wrong pixel with scale {}", scale);
+            assert_eq!(buffer[game_pixel_index(10, 20) + scale - 1], COLORS[1]);
+            assert_eq!(buffer[game_pixel_index(143, 159) + (scale - 1) * window_width + scale - 1], COLORS[2]);
+            assert_eq!(buffer[0], background_color, "Rows above the game should have the background color");
+            assert_eq!(buffer[(top - 1) * window_width], background_color);
+            assert_eq!(buffer[(top + 144 * scale) * window_width], background_color, "Rows below the game too");
+        }
+    }
+
+    #[test]
+    fn test_draw_game_area_border() {
+        for scale in MIN_SCREEN_SCALE..=MAX_SCREEN_SCALE {
+            let (area_width, area_height) = (GAME_SECTION_WIDTH * scale + 10, GAME_SECTION_HEIGHT * scale + 20);
+            let buffer_width = area_width + 5;
+            let mut buffer = vec![0; buffer_width * area_height];
+            let rect = ScalingMode::Integer(scale as u8).get_game_image_rect(area_width, area_height);
+
+            Screen::draw_game_area_border(&mut buffer, buffer_width, &rect, area_width, area_height, 0xFF00FF);
+
+            // The image starts 5 pixels to the right and 10 pixels down
+            let (top, left) = (10, 5);
+            let (bottom, right) = (top + GAME_SECTION_HEIGHT * scale - 1, left + GAME_SECTION_WIDTH * scale - 1);
+            for (row, col) in [(top, left), (top, right), (bottom, left), (bottom, right), (top, left + 7), (bottom - 3, right)] {
+                assert_eq!(buffer[row * buffer_width + col], 0xFF00FF, "Missing border at ({}, {}) with scale {}", row, col, scale);
+            }
+            for (row, col) in [(top - 1, left), (top, left - 1), (top + 1, left + 1), (bottom + 1, right), (bottom, right + 1)] {
+                assert_eq!(buffer[row * buffer_width + col], 0, "Unexpected border at ({}, {}) with scale {}", row, col, scale);
+            }
+        }
+    }
+
+    #[test]
+    fn test_draw_game_area_border_with_empty_image() {
+        let mut buffer = vec![0; 100 * 100];
+        let empty_rects = [
+            GameImageRect { top: 0, left: 0, width: 0, height: 0 },
+            GameImageRect { top: 10, left: 10, width: 50, height: 0 },
+            GameImageRect { top: 10, left: 10, width: 0, height: 50 },
+        ];
+        for rect in &empty_rects {
+            Screen::draw_game_area_border(&mut buffer, 100, rect, 100, 100, 0xFF00FF);
+        }
+        // A window minimized to a zero size game area
+        let rect = ScalingMode::Fill.get_game_image_rect(0, 0);
+        Screen::draw_game_area_border(&mut buffer, 100, &rect, 0, 0, 0xFF00FF);
+
+        assert!(buffer.iter().all(|&pixel| pixel == 0), "No border should be drawn for an empty image");
+    }
+
+    #[test]
+    fn test_get_game_image_rect_for_each_scaling_mode() {
+        // Integer: fixed size centered in the area
+        assert_eq!(
+            ScalingMode::Integer(2).get_game_image_rect(400, 400),
+            GameImageRect { top: 56, left: 40, width: 320, height: 288 }
+        );
+        // Integer larger than the area is anchored at the top-left and clipped
+        assert_eq!(
+            ScalingMode::Integer(3).get_game_image_rect(320, 288),
+            GameImageRect { top: 0, left: 0, width: 480, height: 432 }
+        );
+        // Fit: largest integer multiplier, here limited by the height (500 / 144 = 3)
+        assert_eq!(
+            ScalingMode::Fit.get_game_image_rect(700, 500),
+            GameImageRect { top: 34, left: 110, width: 480, height: 432 }
+        );
+        // Fit never goes below 1x
+        assert_eq!(
+            ScalingMode::Fit.get_game_image_rect(100, 100),
+            GameImageRect { top: 0, left: 0, width: 160, height: 144 }
+        );
+        // Fill: limited by the height, bars on the left and right
+        assert_eq!(
+            ScalingMode::Fill.get_game_image_rect(700, 450),
+            GameImageRect { top: 0, left: 100, width: 500, height: 450 }
+        );
+        // Fill: limited by the width, bars on the top and bottom
+        assert_eq!(
+            ScalingMode::Fill.get_game_image_rect(400, 600),
+            GameImageRect { top: 120, left: 0, width: 400, height: 360 }
+        );
+    }
+
+    #[test]
+    fn test_render_game_to_buffer_centers_image_and_fills_letterbox() {
+        let game_screen = create_test_game_screen();
+        let letterbox_color = 0x123456;
+        let (buffer_width, area_width, area_height) = (500, 400, 360);
+        let mut buffer = vec![0; buffer_width * area_height];
+
+        // Fill: a 400x360 area holds a 400x360 image, 2.5x the Game Boy screen
+        Screen::render_game_to_buffer(
+            &mut buffer, buffer_width, area_width, area_height, &game_screen, ScalingMode::Fill, letterbox_color,
+        );
+        assert_eq!(buffer[0], COLORS[3]);
+        assert_eq!(buffer[2 * buffer_width + 2], COLORS[3]);
+        assert_eq!(buffer[3 * buffer_width + 3], COLORS[0]);
+        assert_eq!(buffer[(area_height - 1) * buffer_width + area_width - 1], COLORS[2]);
+        assert_eq!(buffer[area_width], 0, "Pixels outside the game area should not be touched");
+
+        // Fit: 2x image of 320x288 centered, with 40 pixels bars on the sides and 36 pixels bars above and below
+        Screen::render_game_to_buffer(
+            &mut buffer, buffer_width, area_width, area_height, &game_screen, ScalingMode::Fit, letterbox_color,
+        );
+        assert_eq!(buffer[0], letterbox_color);
+        assert_eq!(buffer[36 * buffer_width + 39], letterbox_color);
+        assert_eq!(buffer[35 * buffer_width + 40], letterbox_color);
+        assert_eq!(buffer[36 * buffer_width + 40], COLORS[3]);
+        assert_eq!(buffer[37 * buffer_width + 41], COLORS[3]);
+        assert_eq!(buffer[(36 + 20) * buffer_width + 40 + 40], COLORS[1]);
+        assert_eq!(buffer[(36 + 287) * buffer_width + 40 + 319], COLORS[2]);
+        assert_eq!(buffer[(36 + 288) * buffer_width + 40 + 319], letterbox_color);
+        assert_eq!(buffer[(area_height - 1) * buffer_width + area_width - 1], letterbox_color);
+    }
+
+    #[test]
+    fn test_format_oam_entry_decodes_attributes() {
+        let mut cpu = Cpu::new();
+        // Object 0: Y=16, X=8, tile 0x01, no flags (OBP0)
+        for (offset, value) in [0x10, 0x08, 0x01, 0x00].iter().enumerate() {
+            cpu.memory_bus.write_byte(0xFE00 + offset as u16, *value);
+        }
+        // Object 1: Y=0x50, X=0x2A, tile 0x7F, priority + Y flip + OBP1
+        for (offset, value) in [0x50, 0x2A, 0x7F, 0b1101_0000].iter().enumerate() {
+            cpu.memory_bus.write_byte(0xFE04 + offset as u16, *value);
+        }
+        // Object 2: X flip only
+        cpu.memory_bus.write_byte(0xFE0B, 0b0010_0000);
+
+        let objects = Ppu::get_objects(&cpu.memory_bus);
+
+        assert_eq!(
+            Screen::format_oam_entry(&objects[0]),
+            "Y: 16, X: 8, Tile: 0x01, Priority: 0, Y-Flip: 0, X-Flip: 0, Palette: OBP0"
+        );
+        assert_eq!(
+            Screen::format_oam_entry(&objects[1]),
+            "Y: 80, X: 42, Tile: 0x7F, Priority: 1, Y-Flip: 1, X-Flip: 0, Palette: OBP1"
+        );
+        assert_eq!(
+            Screen::format_oam_entry(&objects[2]),
+            "Y: 0, X: 0, Tile: 0x00, Priority: 0, Y-Flip: 0, X-Flip: 1, Palette: OBP0"
+        );
+    }
+
+    #[test]
+    fn test_render_oam_view_uses_object_palette() {
+        let mut cpu = Cpu::new();
+        // Tile 1, row 0: color index 1 on the leftmost pixel only
+        cpu.memory_bus.write_byte(0x8010, 0b1000_0000);
+        cpu.memory_bus.write_byte(OBP0, 0b0000_1100); // color 1 -> shade 3
+        cpu.memory_bus.write_byte(OBP1, 0b0000_0100); // color 1 -> shade 1
+        // Object 0 uses tile 1 with OBP0, object 9 (row 1, column 1) uses tile 1 with OBP1 and X flip
+        cpu.memory_bus.write_byte(0xFE02, 0x01);
+        cpu.memory_bus.write_byte(0xFE00 + 9 * 4 + 2, 0x01);
+        cpu.memory_bus.write_byte(0xFE00 + 9 * 4 + 3, 0b0011_0000);
+
+        let mut buffer = Vec::new();
+        Screen::render_oam_view(&cpu, &mut buffer);
+
+        assert_eq!(buffer.len(), OAM_VIEW_WIDTH * OAM_VIEW_HEIGHT);
+        // Objects are drawn with a 2 pixels padding inside their 32x36 cell
+        assert_eq!(buffer[2 * OAM_VIEW_WIDTH + 2], COLORS[3]);
+        assert_eq!(buffer[2 * OAM_VIEW_WIDTH + 3], 0x000080, "Color 0 is transparent");
+        let (cell_row, cell_col) = (36, 32);
+        assert_eq!(buffer[(cell_row + 2) * OAM_VIEW_WIDTH + cell_col + 2 + 7], COLORS[1], "X flip should move the pixel to the right");
+        assert_eq!(buffer[(cell_row + 2) * OAM_VIEW_WIDTH + cell_col + 2], 0x000080);
+    }
+
+    #[test]
+    fn test_get_palette_shades() {
+        assert_eq!(Screen::get_palette_shades(0xE4), [0, 1, 2, 3]);
+        assert_eq!(Screen::get_palette_shades(0x1B), [3, 2, 1, 0]);
+        assert_eq!(Screen::get_palette_shades(0xFC), [0, 3, 3, 3]);
+        assert_eq!(Screen::get_palette_shades(0x00), [0, 0, 0, 0]);
+    }
+
+    #[test]
+    fn test_render_palette_view_draws_swatches() {
+        let mut cpu = Cpu::new();
+        cpu.memory_bus.write_byte(BGP, 0xE4);
+        cpu.memory_bus.write_byte(OBP0, 0x1B);
+        cpu.memory_bus.write_byte(OBP1, 0xFF);
+
+        let mut buffer = Vec::new();
+        Screen::render_palette_view(&cpu.memory_bus, &mut buffer);
+
+        assert_eq!(buffer.len(), PALETTE_VIEW_WIDTH * PALETTE_VIEW_HEIGHT);
+        // Swatches start after the 24 pixels label column, each one in a 56 pixels wide column.
+        // Rows start at 4 pixels and are 28 pixels tall. Check the center pixel of each 16x16 swatch
+        let swatch_center = |palette_row: usize, color_index: usize| {
+            (4 + palette_row * 28 + 8) * PALETTE_VIEW_WIDTH + 24 + color_index * 56 + 8
+        };
+        for color_index in 0..4 {
+            assert_eq!(buffer[swatch_center(0, color_index)], COLORS[color_index], "Wrong BGP swatch {}", color_index);
+            assert_eq!(buffer[swatch_center(1, color_index)], COLORS[3 - color_index], "Wrong OBP0 swatch {}", color_index);
+            assert_eq!(buffer[swatch_center(2, color_index)], COLORS[3], "Wrong OBP1 swatch {}", color_index);
+        }
+    }
+
+    /// Asserts each RGB component of the pixel is within 1 of the expected value
+    fn assert_rgb_within_1_lsb(pixel: u32, expected: (u32, u32, u32)) {
+        let components = [(pixel >> 16) & 0xFF, (pixel >> 8) & 0xFF, pixel & 0xFF];
+        let expected_components = [expected.0, expected.1, expected.2];
+
+        for (component, expected_component) in components.iter().zip(expected_components) {
+            assert!(
+                component.abs_diff(expected_component) <= 1,
+                "Pixel {:#08X} is not within 1 LSB of {:?}", pixel, expected
+            );
+        }
+    }
+
+    #[test]
+    fn test_apply_crt_filter_darkens_odd_rows() {
+        let (width, height) = (3, 4);
+        let mut buffer = [0xFFFFFF, 0x808080, 0xC86432].repeat(height);
+
+        apply_crt_filter(&mut buffer, width, width, height, 0.5);
+
+        for row in 0..height {
+            let row_pixels = &buffer[row * width..(row + 1) * width];
+            if row % 2 == 0 {
+                assert_eq!(row_pixels, [0xFFFFFF, 0x808080, 0xC86432], "Even row {} should not change", row);
+            } else {
+                assert_rgb_within_1_lsb(row_pixels[0], (0x80, 0x80, 0x80));
+                assert_rgb_within_1_lsb(row_pixels[1], (0x40, 0x40, 0x40));
+                assert_rgb_within_1_lsb(row_pixels[2], (100, 50, 25));
+            }
+        }
+    }
+
+    #[test]
+    fn test_apply_crt_filter_leaves_the_columns_right_of_the_game_area_untouched() {
+        let (buffer_width, area_width, height) = (3, 2, 2);
+        let mut buffer = vec![0xC86432; buffer_width * height];
+
+        apply_crt_filter(&mut buffer, buffer_width, area_width, height, 0.5);
+
+        assert_eq!(buffer[..buffer_width], [0xC86432; 3], "The even row should not change");
+        assert_rgb_within_1_lsb(buffer[3], (100, 50, 25));
+        assert_rgb_within_1_lsb(buffer[4], (100, 50, 25));
+        assert_eq!(buffer[5], 0xC86432, "The tile data column should not change");
+    }
+
+    #[test]
+    fn test_apply_crt_filter_opacity_levels() {
+        let expected_by_opacity = [
+            (0.0, (200, 100, 50)),
+            (0.25, (150, 75, 38)),
+            (0.75, (50, 25, 13)),
+            (1.0, (0, 0, 0)),
+        ];
+
+        for (opacity, expected) in expected_by_opacity {
+            let mut buffer = vec![0xC86432; 2];
+            apply_crt_filter(&mut buffer, 1, 1, 2, opacity);
+
+            assert_eq!(buffer[0], 0xC86432);
+            assert_rgb_within_1_lsb(buffer[1], expected);
+        }
+    }
+
+    #[test]
+    fn test_frame_duration_for_each_speed() {
+        let speeds = [
+            (EmulationSpeed::Half, 33_485_412),
+            (EmulationSpeed::Normal, 16_742_706),
+            (EmulationSpeed::Double, 8_371_353),
+            (EmulationSpeed::Triple, 5_580_902),
+            (EmulationSpeed::Quadruple, 4_185_676),
+        ];
+
+        for (speed, expected_nanos) in speeds {
+            let frame_duration = speed.get_frame_duration().unwrap();
+            assert!(
+                frame_duration.as_nanos().abs_diff(expected_nanos) <= 1,
+                "Wrong frame duration {:?} for {:?}",
+                frame_duration,
+                speed
+            );
+        }
+        assert_eq!(EmulationSpeed::Uncapped.get_frame_duration(), None);
+    }
+
+    #[test]
+    fn test_title_with_speed() {
+        assert_eq!(Screen::get_title_with_speed("Gameboy Emulator", EmulationSpeed::Double), "Gameboy Emulator [2x]");
+        assert_eq!(Screen::get_title_with_speed("Gameboy Emulator", EmulationSpeed::Half), "Gameboy Emulator [0.5x]");
+        assert_eq!(Screen::get_title_with_speed("Gameboy Emulator", EmulationSpeed::Uncapped), "Gameboy Emulator [Uncapped]");
+    }
+}
@@ -0,0 +1,80 @@
+#[cfg(test)]
+mod tests {
+    use proptest::prelude::*;
+
+    use crate::gameboy_core::{cpu::Cpu, cpu_components::CpuRegisters};
+
+    /// PUSH and POP opcodes of BC, DE, HL and AF, in the order of bits 4-5 of the opcode.
+    const PUSH_OPCODES: [u8; 4] = [0xC5, 0xD5, 0xE5, 0xF5];
+    const POP_OPCODES: [u8; 4] = [0xC1, 0xD1, 0xE1, 0xF1];
+
+    /// Maximum number of values pushed in a sequence, the stack of the sequence must fit in WRAM.
+    const MAX_SEQUENCE_LENGTH: usize = 16;
+
+    fn get_register_pair(registers: &CpuRegisters, pair: usize) -> u16 {
+        match pair {
+            0 => registers.get_bc(),
+            1 => registers.get_de(),
+            2 => registers.get_hl(),
+            _ => registers.get_af(),
+        }
+    }
+
+    fn set_register_pair(registers: &mut CpuRegisters, pair: usize, value: u16) {
+        match pair {
+            0 => registers.set_bc(value),
+            1 => registers.set_de(value),
+            2 => registers.set_hl(value),
+            _ => registers.set_af(value),
+        }
+    }
+
+    /// Value the register pair holds after being set to `value`, the lower 4 bits of F are always 0.
+    fn stored_value(pair: usize, value: u16) -> u16 {
+        if pair == 3 { value & 0xFFF0 } else { value }
+    }
+
+    /// Initial SP with room in WRAM ($C000-$DFFF) for the whole sequence of pushes.
+    fn initial_sp() -> impl Strategy<Value = u16> {
+        (0xC000 + 2 * MAX_SEQUENCE_LENGTH as u16)..=0xDFFE
+    }
+
+    proptest! {
+        #[test]
+        fn test_push_then_pop_restores_value_and_sp(pair in 0usize..4, value: u16, sp in initial_sp()) {
+            let mut cpu = Cpu::new();
+            cpu.registers.sp = sp;
+            set_register_pair(&mut cpu.registers, pair, value);
+
+            cpu.execute(PUSH_OPCODES[pair]);
+            prop_assert_eq!(cpu.registers.sp, sp - 2);
+
+            set_register_pair(&mut cpu.registers, pair, !value);
+            cpu.execute(POP_OPCODES[pair]);
+
+            prop_assert_eq!(get_register_pair(&cpu.registers, pair), stored_value(pair, value));
+            prop_assert_eq!(cpu.registers.sp, sp);
+        }
+
+        #[test]
+        fn test_pushes_are_popped_in_lifo_order(
+            pushes in prop::collection::vec((0usize..4, any::<u16>()), 1..=MAX_SEQUENCE_LENGTH),
+            sp in initial_sp(),
+        ) {
+            let mut cpu = Cpu::new();
+            cpu.registers.sp = sp;
+
+            for &(pair, value) in &pushes {
+                set_register_pair(&mut cpu.registers, pair, value);
+                cpu.execute(PUSH_OPCODES[pair]);
+            }
+            prop_assert_eq!(cpu.registers.sp, sp - 2 * pushes.len() as u16);
+
+            for &(pair, value) in pushes.iter().rev() {
+                cpu.execute(POP_OPCODES[pair]);
+                prop_assert_eq!(get_register_pair(&cpu.registers, pair), stored_value(pair, value));
+            }
+            prop_assert_eq!(cpu.registers.sp, sp);
+        }
+    }
+}
@@ -0,0 +1,93 @@
+#[cfg(test)]
+mod tests {
+    use crate::gameboy_core::{
+        components::tile_map_viewer::{SelectedTileInfo, TileMapViewer},
+        constants::COLORS,
+        cpu::Cpu,
+        cpu_components::MemoryAccess,
+        registers_contants::BGP,
+    };
+
+    const WIDTH: usize = 256;
+
+    /// Creates a CPU where tile 1 is fully black (color index 3) and placed at row 1, column 1 of the tile map.
+    fn create_cpu_with_tile_map() -> Cpu {
+        let mut cpu = Cpu::new();
+        // LCD on, tile data at $8000, BG tile map at $9800, BG enabled
+        cpu.memory_bus.set_lcdc_register(0x91);
+        cpu.memory_bus.write_byte(BGP, 0xE4);
+        for offset in 0..16 {
+            cpu.memory_bus.write_byte(0x8010 + offset, 0xFF);
+        }
+        cpu.memory_bus.write_byte(0x9800 + 32 + 1, 0x01);
+        cpu
+    }
+
+    #[test]
+    fn test_render_tile_map_with_viewport_border() {
+        let mut cpu = create_cpu_with_tile_map();
+        cpu.memory_bus.set_scy_register(100);
+        cpu.memory_bus.set_scx_register(200);
+        let mut viewer = TileMapViewer::new();
+
+        viewer.render(&cpu);
+
+        assert_eq!(viewer.buffer[8 * WIDTH + 8], COLORS[3], "Tile 1 should be drawn at row 1, column 1");
+        assert_eq!(viewer.buffer[15 * WIDTH + 15], COLORS[3]);
+        assert_eq!(viewer.buffer[4 * WIDTH + 4], COLORS[0]);
+
+        // Viewport starts at (SCY, SCX) = (100, 200) and wraps horizontally to column (200 + 159) % 256 = 103
+        let viewport_color = 0xFF0000;
+        assert_eq!(viewer.buffer[100 * WIDTH + 200], viewport_color);
+        assert_eq!(viewer.buffer[100 * WIDTH + 50], viewport_color, "Top border should wrap around");
+        assert_eq!(viewer.buffer[243 * WIDTH + 210], viewport_color);
+        assert_eq!(viewer.buffer[150 * WIDTH + 103], viewport_color);
+        assert_ne!(viewer.buffer[150 * WIDTH + 150], viewport_color, "Inside of the viewport should not be overlaid");
+
+        // Selected tile (0, 0) is highlighted
+        assert_eq!(viewer.buffer[0], 0x0080FF);
+        assert_eq!(viewer.buffer[7 * WIDTH + 7], 0x0080FF);
+    }
+
+    #[test]
+    fn test_selected_tile_info() {
+        let mut cpu = create_cpu_with_tile_map();
+        cpu.memory_bus.write_byte(BGP, 0x1B);
+        let mut viewer = TileMapViewer::new();
+
+        viewer.move_selection(1, 1);
+
+        assert_eq!(
+            viewer.get_selected_tile_info(&cpu),
+            SelectedTileInfo {
+                row: 1,
+                col: 1,
+                tile_index: 1,
+                palette: [3, 2, 1, 0],
+            }
+        );
+    }
+
+    #[test]
+    fn test_move_selection_wraps_around() {
+        let mut viewer = TileMapViewer::new();
+
+        viewer.move_selection(-1, -1);
+        assert_eq!((viewer.selected_tile_row, viewer.selected_tile_col), (31, 31));
+
+        viewer.move_selection(1, 2);
+        assert_eq!((viewer.selected_tile_row, viewer.selected_tile_col), (0, 1));
+    }
+
+    #[test]
+    fn test_zoomed_tile_fills_the_view() {
+        let cpu = create_cpu_with_tile_map();
+        let mut viewer = TileMapViewer::new();
+        viewer.move_selection(1, 1);
+
+        viewer.toggle_zoom();
+        viewer.render(&cpu);
+
+        assert!(viewer.buffer.iter().all(|&color| color == COLORS[3]));
+    }
+}
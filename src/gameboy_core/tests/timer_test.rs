@@ -0,0 +1,115 @@
+#[cfg(test)]
+mod tests {
+    use crate::gameboy_core::{cpu::Cpu, registers_contants::{DIV, IF, TAC, TIMA, TMA}};
+
+    /// Runs the timers for the given number of T-cycles, 4 T-cycles (one machine cycle) per update.
+    fn run_timers(cpu: &mut Cpu, t_cycles: usize) {
+        for _ in 0..t_cycles / 4 {
+            cpu.update_timers();
+        }
+    }
+
+    /// Creates a CPU whose DIV was just reset by a write.
+    fn create_cpu_with_div_reset() -> Cpu {
+        let mut cpu = Cpu::new();
+        cpu.memory_bus.write_byte(DIV, 0x12);
+        cpu
+    }
+
+    #[test]
+    fn test_div_increments_every_256_t_cycles() {
+        let mut cpu = create_cpu_with_div_reset();
+
+        run_timers(&mut cpu, 252);
+        assert_eq!(cpu.memory_bus.read_byte(DIV), 0);
+        run_timers(&mut cpu, 4);
+        assert_eq!(cpu.memory_bus.read_byte(DIV), 1, "256 T-cycles");
+
+        run_timers(&mut cpu, 256);
+        assert_eq!(cpu.memory_bus.read_byte(DIV), 2, "512 T-cycles");
+    }
+
+    #[test]
+    fn test_writing_div_resets_it_and_restarts_the_256_cycles_window() {
+        let mut cpu = create_cpu_with_div_reset();
+        run_timers(&mut cpu, 200);
+
+        cpu.memory_bus.write_byte(DIV, 0xFF);
+        assert_eq!(cpu.memory_bus.read_byte(DIV), 0, "any value written resets DIV");
+
+        // Without the reset of the window DIV would be incremented 56 cycles after the write
+        run_timers(&mut cpu, 252);
+        assert_eq!(cpu.memory_bus.read_byte(DIV), 0);
+        run_timers(&mut cpu, 4);
+        assert_eq!(cpu.memory_bus.read_byte(DIV), 1);
+    }
+
+    #[test]
+    fn test_div_wraps_around() {
+        let mut cpu = Cpu::new();
+        cpu.memory_bus.set_div_register(0xFF);
+        assert_eq!(cpu.memory_bus.read_byte(DIV), 0xFF, "the timer sets DIV without resetting it");
+        run_timers(&mut cpu, 256);
+        assert_eq!(cpu.memory_bus.read_byte(DIV), 0x00);
+    }
+
+    /// Creates a CPU with the timer enabled at 262144 Hz (an increment every 16 T-cycles), TIMA = 0xFF and
+    /// TMA = 0x42, so TIMA overflows after 16 T-cycles.
+    fn create_cpu_before_tima_overflow() -> Cpu {
+        let mut cpu = Cpu::new();
+        cpu.memory_bus.write_byte(TAC, 0b101);
+        cpu.memory_bus.write_byte(TIMA, 0xFF);
+        cpu.memory_bus.write_byte(TMA, 0x42);
+        cpu.memory_bus.write_byte(IF, 0);
+        cpu
+    }
+
+    fn is_timer_interrupt_requested(cpu: &Cpu) -> bool {
+        cpu.memory_bus.read_byte(IF) & 0b100 != 0
+    }
+
+    #[test]
+    fn test_tima_reads_0x00_for_4_cycles_after_overflowing() {
+        let mut cpu = create_cpu_before_tima_overflow();
+
+        run_timers(&mut cpu, 16);
+        assert_eq!(cpu.memory_bus.read_byte(TIMA), 0x00);
+        assert!(cpu.timer.tima_overflow_pending);
+        assert!(!is_timer_interrupt_requested(&cpu), "the interrupt is requested after the delay");
+
+        run_timers(&mut cpu, 4);
+        assert_eq!(cpu.memory_bus.read_byte(TIMA), 0x42);
+        assert!(!cpu.timer.tima_overflow_pending);
+        assert!(is_timer_interrupt_requested(&cpu));
+    }
+
+    #[test]
+    fn test_tma_written_during_the_overflow_delay_is_loaded() {
+        let mut cpu = create_cpu_before_tima_overflow();
+        run_timers(&mut cpu, 16);
+
+        cpu.memory_bus.write_byte(TMA, 0x99);
+        run_timers(&mut cpu, 4);
+
+        assert_eq!(cpu.memory_bus.read_byte(TIMA), 0x99);
+    }
+
+    #[test]
+    fn test_tima_counts_from_tma_after_the_reload() {
+        let mut cpu = create_cpu_before_tima_overflow();
+        cpu.memory_bus.write_byte(TMA, 0xFE);
+        run_timers(&mut cpu, 20);
+        assert_eq!(cpu.memory_bus.read_byte(TIMA), 0xFE);
+
+        // The period goes on during the delay, the next increment is 16 T-cycles after the overflow
+        run_timers(&mut cpu, 12);
+        assert_eq!(cpu.memory_bus.read_byte(TIMA), 0xFF);
+
+        cpu.memory_bus.write_byte(IF, 0);
+        run_timers(&mut cpu, 16);
+        assert_eq!(cpu.memory_bus.read_byte(TIMA), 0x00, "overflows again");
+        run_timers(&mut cpu, 4);
+        assert_eq!(cpu.memory_bus.read_byte(TIMA), 0xFE);
+        assert!(is_timer_interrupt_requested(&cpu));
+    }
+}
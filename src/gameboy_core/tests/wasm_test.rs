@@ -0,0 +1,90 @@
+#[cfg(all(test, feature = "wasm"))]
+mod tests {
+    use crate::{
+        gameboy_core::{
+            constants::{GAME_SECTION_HEIGHT, GAME_SECTION_WIDTH, T_CYCLES_PER_FRAME},
+            cpu_utils,
+            joypad::JoypadButton,
+            registers_contants::IF,
+        },
+        wasm::{
+            create_emulator, destroy_emulator, get_framebuffer, get_framebuffer_width, press_button, release_button,
+            tick_frame,
+        },
+    };
+
+    #[test]
+    fn test_create_emulator_with_invalid_rom_returns_null() {
+        assert!(create_emulator(&[0x00; 16]).is_null());
+    }
+
+    #[test]
+    fn test_emulator_runs_frames_and_returns_the_framebuffer() {
+        let rom = cpu_utils::read_rom("files/roms/tests/nintendo_logo.gb").unwrap();
+        let emulator = create_emulator(&rom);
+        assert!(!emulator.is_null());
+
+        tick_frame(emulator);
+        tick_frame(emulator);
+
+        // SAFETY: the emulator was just created and is destroyed at the end of the test
+        assert!(unsafe { (*emulator).clock_cycles } >= 2 * T_CYCLES_PER_FRAME);
+        let framebuffer = get_framebuffer(emulator);
+        assert_eq!(framebuffer.len(), GAME_SECTION_WIDTH * GAME_SECTION_HEIGHT);
+        assert_eq!(get_framebuffer_width(), GAME_SECTION_WIDTH);
+
+        destroy_emulator(emulator);
+    }
+
+    #[test]
+    fn test_press_and_release_buttons() {
+        let rom = cpu_utils::read_rom("files/roms/tests/nintendo_logo.gb").unwrap();
+        let emulator = create_emulator(&rom);
+        // SAFETY: the emulator was just created and is destroyed at the end of the test
+        let is_pressed = |button| unsafe { (*emulator).memory_bus.joypad_state.is_pressed(button) };
+
+        press_button(emulator, 7);
+        press_button(emulator, 4);
+        // Values above 7 are not buttons and are ignored
+        press_button(emulator, 8);
+        assert!(is_pressed(JoypadButton::Start));
+        assert!(is_pressed(JoypadButton::A));
+        assert_eq!(unsafe { (*emulator).memory_bus.joypad_state.get_pressed_buttons() }, 0b1001_0000);
+
+        release_button(emulator, 7);
+        assert!(!is_pressed(JoypadButton::Start));
+        assert!(is_pressed(JoypadButton::A));
+
+        destroy_emulator(emulator);
+    }
+
+    #[test]
+    fn test_pressing_a_button_requests_the_joypad_interrupt() {
+        let rom = cpu_utils::read_rom("files/roms/tests/nintendo_logo.gb").unwrap();
+        let emulator = create_emulator(&rom);
+        // SAFETY: the emulator was just created and is destroyed at the end of the test
+        let cpu = unsafe { &mut *emulator };
+        cpu.memory_bus.write_byte(IF, 0x00);
+
+        press_button(emulator, 4);
+        assert_eq!(cpu.memory_bus.read_byte(IF) & 0b1_0000, 0b1_0000, "IF bit 4 is set when A is pressed");
+
+        // Only a button going from released to pressed requests the interrupt
+        cpu.memory_bus.write_byte(IF, 0x00);
+        press_button(emulator, 4);
+        release_button(emulator, 4);
+        assert_eq!(cpu.memory_bus.read_byte(IF) & 0b1_0000, 0);
+
+        destroy_emulator(emulator);
+    }
+
+    #[test]
+    fn test_null_emulator_is_ignored() {
+        let emulator = std::ptr::null_mut();
+
+        tick_frame(emulator);
+        press_button(emulator, 0);
+        destroy_emulator(emulator);
+        assert!(get_framebuffer(emulator).is_empty());
+    }
+}
@@ -1,13 +1,20 @@
-use crate::gameboy_core::{cpu::Cpu, interrupts::InterruptType};
+use crate::gameboy_core::{cpu::Cpu, cpu_components::MemoryAccess, interrupts::InterruptType};
 
+#[derive(Clone)]
 pub struct Timer {
     /// Number of cycles executed since last increment for the DIV register
     pub cycles_executed_div: u16,
     /// Number of cycles executed since last increment for the TIMA register
     pub cycles_executed_tima: u16,
-    pub tima_overflowed: bool,
+    /// True from the TIMA overflow until TMA is loaded into TIMA and the timer interrupt is requested
+    pub tima_overflow_pending: bool,
+    /// T-cycles left before the pending TIMA overflow is handled
+    pub tima_overflow_delay: u8,
 }
 
+/// T-cycles (one machine cycle) between the TIMA overflow and the TMA reload, TIMA reads 0x00 meanwhile.
+pub const TIMA_OVERFLOW_DELAY: u8 = 4;
+
 pub enum InterruptRequested {
     Yes,
     No,
@@ -18,13 +25,14 @@ impl Timer {
         Self {
             cycles_executed_div: 0,
             cycles_executed_tima: 0,
-            tima_overflowed: false,
+            tima_overflow_pending: false,
+            tima_overflow_delay: 0,
         }
     }
 
     /// Update the DIV and TIMA registers based on the number of cycles executed since the last instruction.
     /// Sets the IF register timer interrupt flag if TIMA overflows.
-    pub fn update(cpu: &mut Cpu) {
+    pub fn update<M: MemoryAccess>(cpu: &mut Cpu<M>) {
         let cycles_of_last_instruction: u8 = 4; 
         Self::update_div(cpu, cycles_of_last_instruction);
         Self::update_tima(cpu, cycles_of_last_instruction);
@@ -35,7 +43,12 @@ impl Timer {
     /// The math is 4,194,304 Hz / 16,384 Hz = 256 cycles.
     /// Update the DIV register based on the number of cycles executed since the last instruction.
     /// If total cycles exceed 256, increment DIV and reset the cycle counter.
-    fn update_div(cpu: &mut Cpu, cycles_of_last_instruction: u8) {
+    /// Writing DIV resets it to 0 and the count starts again, so the next increment is 256 cycles after the write.
+    fn update_div<M: MemoryAccess>(cpu: &mut Cpu<M>, cycles_of_last_instruction: u8) {
+        if cpu.memory_bus.take_div_reset() {
+            cpu.timer.cycles_executed_div = 0;
+        }
+
         let total_cycles = cpu.timer.cycles_executed_div + cycles_of_last_instruction as u16;
 
         if total_cycles >= 256 {
@@ -54,13 +67,18 @@ impl Timer {
     ///
     /// *When TIMA overflows, the value from TMA is copied, and the timer flag is set in IF, **but one M-cycle later (4 T-cycles).**
     /// This means that TIMA is equal to $00 for the M-cycle after it overflows.*
-    fn update_tima(cpu: &mut Cpu, cycles_of_last_instruction: u8) {
-        if cpu.timer.tima_overflowed {
-            let tma = cpu.memory_bus.get_tma_register();
-            cpu.memory_bus.set_tima_register(tma);
-            cpu.memory_bus.update_flag_in_if_register(InterruptType::Timer, true);
-            cpu.timer.tima_overflowed = false;
-            cpu.timer.cycles_executed_tima += (cycles_of_last_instruction - 4) as u16; // Account for the 4 T-cycles delay
+    /// TMA is read when the delay is over, so a value written to TMA during the delay is the one loaded.
+    fn update_tima<M: MemoryAccess>(cpu: &mut Cpu<M>, cycles_of_last_instruction: u8) {
+        if cpu.timer.tima_overflow_pending {
+            cpu.timer.tima_overflow_delay = cpu.timer.tima_overflow_delay.saturating_sub(cycles_of_last_instruction);
+            if cpu.timer.tima_overflow_delay == 0 {
+                let tma = cpu.memory_bus.get_tma_register();
+                cpu.memory_bus.set_tima_register(tma);
+                cpu.memory_bus.update_flag_in_if_register(InterruptType::Timer, true);
+                cpu.timer.tima_overflow_pending = false;
+            }
+            // The delay is shorter than the shortest period, so the cycles only count for the next increment
+            cpu.timer.cycles_executed_tima += cycles_of_last_instruction as u16;
             return;
         }
 
@@ -84,7 +102,8 @@ impl Timer {
 
             if tima_overflowed {
                 tima = 0;
-                cpu.timer.tima_overflowed = true;
+                cpu.timer.tima_overflow_pending = true;
+                cpu.timer.tima_overflow_delay = TIMA_OVERFLOW_DELAY;
             } else {
                 tima = increment_result;
             }
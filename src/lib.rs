@@ -0,0 +1,20 @@
+//! Game Boy emulator library. `gameboy_core` holds the emulated hardware (CPU, memory bus, PPU, timer) and the
+//! minifb based components used to display it, the other modules hold the settings of the desktop frontend.
+//! With the `wasm` feature, `wasm` exposes the emulator core to JavaScript.
+//!
+//! Without the default `std` feature the library is `no_std`: it only holds the emulator core, which needs `alloc`
+//! for the ROM and the cartridge RAM, and leaves out the file system, the windows and the desktop settings.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+extern crate alloc;
+
+#[cfg(all(feature = "std", not(target_arch = "wasm32")))]
+pub mod args;
+#[cfg(all(feature = "std", not(target_arch = "wasm32")))]
+pub mod config;
+pub mod gameboy_core;
+#[cfg(all(feature = "std", not(target_arch = "wasm32")))]
+pub mod recent_roms;
+#[cfg(feature = "wasm")]
+pub mod wasm;
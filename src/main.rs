@@ -1,27 +1,88 @@
-use crate::gameboy_core::{
-    components::screen::{Screen, TOTAL_WINDOW_HEIGHT, TOTAL_WINDOW_WIDTH},
-    constants::{COLORS, GAME_SECTION_HEIGHT, GAME_SECTION_WIDTH, SCREEN_SCALE, TILE_SIZE},
+use gameboy_emulator_rust::{
+    args::{self, Args},
+    config::Config,
+    recent_roms::{self, RecentRoms},
+    gameboy_core::{
+        self,
+        components::{
+            debug_overlay::{self, DebugOverlay, FrameStats},
+            debug_window::DebugWindow,
+            keyboard::{self, InputMapping},
+            palette::BUILTIN_PALETTES,
+            screen::{Screen, WINDOW_TITLE},
+        },
+        emulator_error::EmulatorError,
+        constants::{
+            COLORS, GAME_SECTION_HEIGHT, GAME_SECTION_WIDTH, MAX_SCREEN_SCALE, MIN_SCREEN_SCALE, SCREEN_SCALE,
+        },
+    },
+};
+use gameboy_emulator_rust::gameboy_core::{
     cpu_utils,
     ppu_components::{Tile, TilePixelValue},
+    registers_contants::LY,
 };
-use minifb::{Key, Window};
-
-pub mod gameboy_core;
+#[cfg(feature = "gamepad")]
+use gameboy_emulator_rust::gameboy_core::components::gamepad::{GamepadInput, GamepadMapping};
+use minifb::{Key, KeyRepeat, Window};
+use std::path::Path;
+use std::time::Instant;
 
 fn main() {
-    // let rom_file = "games/Super Mario Land.gb";
-    let rom_file = "games/Tetris.gb";
-    let rom_binary = cpu_utils::read_rom(format!("files/roms/{}", rom_file).as_str()).unwrap();
+    // e.g. cargo run -- "files/roms/games/Tetris.gb" --scale 2
+    let args = args::parse_args();
+    let mut config = Config::load().unwrap_or_else(|e| {
+        eprintln!("{}. Using the default settings", e);
+        Config::default()
+    });
+    // Command line arguments override the config file only for this run, they are not saved
+    let mut settings = config.clone();
+    settings.merge_args(&args);
+
+    let mut recent_roms = RecentRoms::load().unwrap_or_else(|e| {
+        eprintln!("{}", e);
+        RecentRoms::new()
+    });
+    let Some(rom) = args.rom.clone().or_else(|| recent_roms::pick_rom(&recent_roms)) else {
+        eprintln!("No ROM selected");
+        std::process::exit(1);
+    };
+
+    if let Err(e) = run(&rom, &args, &settings, &mut config) {
+        eprintln!("Could not run {}: {}", rom.display(), e);
+        std::process::exit(1);
+    }
+
+    recent_roms.push(rom);
+    if let Some(path) = RecentRoms::get_default_path()
+        && let Err(e) = recent_roms.save_to_file(&path)
+    {
+        eprintln!("{}", e);
+    }
+
+    if let Some(path) = Config::get_default_path()
+        && let Err(e) = config.save_to_file(&path)
+    {
+        eprintln!("{}", e);
+    }
+}
 
-    let debug_mode = false;
-    let mut cpu = gameboy_core::cpu::Cpu::start(rom_binary, debug_mode);
+/// Loads the ROM and runs it until the window is closed, keeping the selected palette in the config.
+fn run(rom: &Path, args: &Args, settings: &Config, config: &mut Config) -> Result<(), EmulatorError> {
+    let mut cpu = gameboy_core::cpu::Cpu::new();
+    cpu.load_rom_from_file(rom)?;
+    cpu.is_debug_mode = args.debug;
 
     // clear previous logs
-    cpu_utils::clear_logs().unwrap();
-    cpu_utils::clear_dr_gameboy_log().unwrap();
+    if args.debug {
+        cpu_utils::clear_logs()?;
+        cpu_utils::clear_dr_gameboy_log()?;
+    }
 
     // Run the event loop
-    run_gameboy(&mut cpu);
+    config.palette_index = run_gameboy(&mut cpu, settings)?;
+    cpu.save_game()?;
+    Ok(())
 }
 
 /// Creates a tile with a colored square: outer border, middle frame, and inner square
@@ -55,7 +116,7 @@ fn create_tile_of_colored_square() -> Tile {
 }
 
 /// Renders a tile to the buffer starting at row and col (0-indexed)
-fn render_tile_to_buffer(tile: &Tile, buffer: &mut [u32], start_row: usize, start_col: usize) {
+fn render_tile_to_buffer(tile: &Tile, buffer: &mut [u32], start_row: usize, start_col: usize, scale: usize) {
     for row in 0..8 {
         for col in 0..8 {
             let pixel_value = tile.pixels[row][col];
@@ -64,12 +125,12 @@ fn render_tile_to_buffer(tile: &Tile, buffer: &mut [u32], start_row: usize, star
             let screen_row = start_row + row;
             let screen_col = start_col + col;
 
-            // Write to buffer (each pixel is scaled by SCREEN_SCALE)
-            for scale_row in 0..SCREEN_SCALE {
-                for scale_col in 0..SCREEN_SCALE {
-                    let buffer_row = screen_row * SCREEN_SCALE + scale_row;
-                    let buffer_col = screen_col * SCREEN_SCALE + scale_col;
-                    let buffer_idx = buffer_row * (GAME_SECTION_WIDTH * SCREEN_SCALE) + buffer_col;
+            // Write to buffer (each pixel is scaled by scale)
+            for scale_row in 0..scale {
+                for scale_col in 0..scale {
+                    let buffer_row = screen_row * scale + scale_row;
+                    let buffer_col = screen_col * scale + scale_col;
+                    let buffer_idx = buffer_row * (GAME_SECTION_WIDTH * scale) + buffer_col;
 
                     if buffer_idx < buffer.len() {
                         buffer[buffer_idx] = color;
@@ -80,27 +141,92 @@ fn render_tile_to_buffer(tile: &Tile, buffer: &mut [u32], start_row: usize, star
     }
 }
 
-fn run_gameboy(cpu: &mut gameboy_core::cpu::Cpu) {
-    let mut screen = Screen::new("Gameboy Emulator".to_string()).unwrap_or_else(|e| {
-        panic!("{}", e);
-    });
+/// Runs the emulator until the window is closed, returns the index of the palette selected at that moment.
+/// Fails if a window can't be created, e.g. when there is no display.
+fn run_gameboy(cpu: &mut gameboy_core::cpu::Cpu, settings: &Config) -> Result<usize, EmulatorError> {
+    let scale = (settings.scale as usize).clamp(MIN_SCREEN_SCALE, MAX_SCREEN_SCALE);
+    let mut screen = Screen::with_scale(WINDOW_TITLE.to_string(), scale)?;
+    screen.set_palette(BUILTIN_PALETTES[settings.palette_index % BUILTIN_PALETTES.len()]);
+    screen.crt_filter.scanline_opacity = settings.scanline_opacity;
+
+    // In debug mode the background tile map is shown in a second window
+    let mut debug_window = if cpu.is_debug_mode {
+        Some(DebugWindow::new()?)
+    } else {
+        None
+    };
 
     cpu.set_debug_mode(false);
 
+    let input_mapping = InputMapping::from_bindings(&settings.key_bindings);
+
+    // Controllers are only read when the emulator is built with the gamepad feature
+    #[cfg(feature = "gamepad")]
+    let mut gamepad_input = GamepadInput::new(GamepadMapping::from_bindings(&settings.gamepad_bindings));
+
+    let mut frame_stats = FrameStats::new();
+    let mut last_frame_start = Instant::now();
+
     while screen.window.is_open() && !screen.window.is_key_down(Key::Escape) {
-        // FIX: Run for a COMPLETE FRAME (70,224 T-cycles)
-        // Each cpu.tick() executes ONE instruction, which calls increment_4_cycles_update_timers_and_ppu()
-        // multiple times based on instruction timing. We need to track actual T-cycles, not instruction count.
-        // 456 T-cycles per scanline × 154 total lines = 70,224 T-cycles per frame
-        let target_cycles = cpu.clock_cycles + 70224;
-        while cpu.clock_cycles < target_cycles {
-            cpu.tick();
+        let frame_start = Instant::now();
+        frame_stats.push_frame_duration(frame_start - last_frame_start);
+        last_frame_start = frame_start;
+
+        keyboard::poll_input(&screen.window, &mut cpu.memory_bus, &input_mapping);
+        #[cfg(feature = "gamepad")]
+        if let Some(gamepad_input) = gamepad_input.as_mut() {
+            gamepad_input.poll(&mut cpu.memory_bus);
+        }
+
+        // F5 performs a hard reset (power cycle), F6 a soft reset which keeps the RAM contents
+        if screen.window.is_key_pressed(Key::F5, KeyRepeat::No) {
+            cpu.reset();
+        } else if screen.window.is_key_pressed(Key::F6, KeyRepeat::No) {
+            cpu.soft_reset();
+        }
+
+        // Ctrl+M dumps the zero page to stdout
+        if screen.is_ctrl_down() && screen.window.is_key_pressed(Key::M, KeyRepeat::No) {
+            print!("{}", cpu_utils::format_hex_dump(&cpu.get_memory_dump(0x0000, 0x100), 0x0000));
         }
 
+        // Ctrl + number keys 1-4 change the scale of the window, Ctrl + 5 and 6 fit/fill the game to the window
+        screen.handle_scale_keys()?;
+        screen.handle_window_resize();
+        // Number keys change the speed: 0 = 0.5x, 1-4 = 1x-4x, 5 = uncapped
+        screen.handle_speed_keys();
+        // C toggles the CRT scanline filter
+        screen.handle_crt_filter_key();
+        // P cycles through the builtin color palettes
+        screen.handle_palette_key();
+        // B shows a debug border around the game image
+        screen.handle_game_area_border_key();
+        // F1 shows the FPS, frame time, CPU cycles and LY on top of the game
+        screen.handle_debug_overlay_key();
+
+        let cycles_before_frame = cpu.clock_cycles;
+        cpu.run_frame();
+
         screen.render_tile_data_to_screen_buffer(cpu);
         screen.render_game_to_screen_buffer(cpu);
+        if screen.show_debug_overlay {
+            let overlay = DebugOverlay {
+                fps: frame_stats.get_fps(),
+                frame_time_ms: frame_start.elapsed().as_secs_f32() * 1000.0,
+                cpu_cycles_per_frame: cpu.clock_cycles - cycles_before_frame,
+                current_ly: cpu.memory_bus.read_byte(LY),
+            };
+            debug_overlay::render_debug_overlay(&overlay, &mut screen.buffer, screen.buffer_width);
+        }
         screen.update_window_with_buffer();
+        screen.wait_for_next_frame();
+
+        if let Some(debug_window) = debug_window.as_mut().filter(|window| window.is_open()) {
+            debug_window.update(cpu);
+        }
     }
+
+    Ok(screen.palette_index)
 }
 
 /// Runs the CPU with a minifb window. Press SPACE to execute a CPU tick.
@@ -123,7 +249,7 @@ fn run_cpu_with_keyboard(
         // Check if R is pressed to call render screen once per key press.
         let r_down = window.is_key_down(Key::R);
         if r_down && !r_pressed {
-            cpu.get_screen_buffer();
+            cpu.update_screen();
         }
         r_pressed = r_down;
 
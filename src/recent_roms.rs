@@ -0,0 +1,116 @@
+use std::{
+    collections::VecDeque,
+    fs,
+    path::{Path, PathBuf},
+};
+
+use serde::{Deserialize, Serialize};
+
+use crate::config::{Config, ConfigError};
+
+/// Number of ROMs kept in the list, the oldest one is dropped when a new ROM is opened.
+pub const MAX_RECENT_ROMS: usize = 10;
+const RECENT_ROMS_FILE_NAME: &str = "recent_roms.json";
+
+/// ROMs opened recently, from the most recent to the oldest. It is kept as JSON next to the config file.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct RecentRoms {
+    paths: VecDeque<PathBuf>,
+}
+
+impl RecentRoms {
+    pub fn new() -> Self {
+        Self { paths: VecDeque::new() }
+    }
+
+    /// Moves the ROM to the top of the list. It is added when it isn't in the list yet.
+    pub fn push(&mut self, path: PathBuf) {
+        self.paths.retain(|recent_path| *recent_path != path);
+        self.paths.push_front(path);
+        self.paths.truncate(MAX_RECENT_ROMS);
+    }
+
+    /// Returns the ROMs from the most recent to the oldest.
+    pub fn get_all(&self) -> Vec<PathBuf> {
+        self.paths.iter().cloned().collect()
+    }
+
+    pub fn load_from_file(path: &Path) -> Result<RecentRoms, ConfigError> {
+        let content = fs::read_to_string(path)?;
+        Ok(serde_json::from_str(&content)?)
+    }
+
+    /// Saves the list as JSON, creating the parent folders if needed.
+    pub fn save_to_file(&self, path: &Path) -> Result<(), ConfigError> {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(path, serde_json::to_string_pretty(self)?)?;
+        Ok(())
+    }
+
+    /// Loads the list from the default path, it is empty when no ROM was opened yet.
+    pub fn load() -> Result<RecentRoms, ConfigError> {
+        match Self::get_default_path() {
+            Some(path) if path.exists() => Self::load_from_file(&path),
+            _ => Ok(RecentRoms::new()),
+        }
+    }
+
+    /// Path of the recent ROMs file, in the same folder as the config file.
+    pub fn get_default_path() -> Option<PathBuf> {
+        Some(Config::get_config_dir()?.join(RECENT_ROMS_FILE_NAME))
+    }
+
+    /// Parses the answer to the stdin picker: the number of a recent ROM (starting at 1) or the path of a ROM.
+    /// Returns None for an empty answer or a number out of the list.
+    pub fn parse_selection(&self, input: &str) -> Option<PathBuf> {
+        let input = input.trim();
+        if input.is_empty() {
+            return None;
+        }
+
+        match input.parse::<usize>() {
+            Ok(number) => number.checked_sub(1).and_then(|index| self.paths.get(index)).cloned(),
+            Err(_) => Some(PathBuf::from(input)),
+        }
+    }
+}
+
+/// Asks the user for the ROM to run when none was passed as argument. With the file-picker feature the native
+/// file dialog is shown, otherwise the recent ROMs are listed on stdout and the selection is read from stdin.
+pub fn pick_rom(recent_roms: &RecentRoms) -> Option<PathBuf> {
+    #[cfg(feature = "file-picker")]
+    {
+        let mut dialog = rfd::FileDialog::new().add_filter("Game Boy ROM", &["gb", "gbc"]);
+        if let Some(folder) = recent_roms.paths.front().and_then(|path| path.parent()) {
+            dialog = dialog.set_directory(folder);
+        }
+        dialog.pick_file()
+    }
+
+    #[cfg(not(feature = "file-picker"))]
+    {
+        pick_rom_from_stdin(recent_roms)
+    }
+}
+
+#[cfg(not(feature = "file-picker"))]
+fn pick_rom_from_stdin(recent_roms: &RecentRoms) -> Option<PathBuf> {
+    use std::io::{self, BufRead, Write};
+
+    if recent_roms.paths.is_empty() {
+        println!("No ROM was opened yet. Enter the path of the ROM to run:");
+    } else {
+        println!("Recent ROMs:");
+        for (index, path) in recent_roms.paths.iter().enumerate() {
+            println!("  {}. {}", index + 1, path.display());
+        }
+        println!("Enter the number of a recent ROM or the path of the ROM to run:");
+    }
+    io::stdout().flush().ok()?;
+
+    let mut input = String::new();
+    io::stdin().lock().read_line(&mut input).ok()?;
+    recent_roms.parse_selection(&input)
+}
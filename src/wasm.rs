@@ -0,0 +1,71 @@
+//! JavaScript API of the emulator for the WebAssembly build, enabled with the `wasm` feature:
+//! `cargo rustc --lib --crate-type cdylib --target wasm32-unknown-unknown --features wasm`
+//!
+//! The emulator is handed to JavaScript as a pointer created by `create_emulator`, which has to be passed to the
+//! other functions and released with `destroy_emulator`.
+
+use wasm_bindgen::prelude::wasm_bindgen;
+
+use crate::gameboy_core::{constants::GAME_SECTION_WIDTH, cpu::Cpu, joypad::JoypadButton};
+
+/// Loads the ROM and returns the emulator ready to run it, or a null pointer when the ROM can't be loaded.
+#[wasm_bindgen]
+pub fn create_emulator(rom_bytes: &[u8]) -> *mut Cpu {
+    match Cpu::start(rom_bytes.to_vec(), false) {
+        Ok(cpu) => Box::into_raw(Box::new(cpu)),
+        Err(_) => std::ptr::null_mut(),
+    }
+}
+
+/// Releases an emulator created by `create_emulator`, the pointer must not be used afterwards.
+/// It can't be marked unsafe since it is called from JavaScript.
+#[wasm_bindgen]
+#[allow(clippy::not_unsafe_ptr_arg_deref)]
+pub fn destroy_emulator(cpu: *mut Cpu) {
+    if !cpu.is_null() {
+        // SAFETY: non-null pointers passed by JavaScript were created by Box::into_raw in create_emulator
+        drop(unsafe { Box::from_raw(cpu) });
+    }
+}
+
+/// Runs the emulator until the end of the current frame.
+#[wasm_bindgen]
+pub fn tick_frame(cpu: *mut Cpu) {
+    with_cpu(cpu, |cpu| cpu.run_frame());
+}
+
+/// Returns the 160x144 pixels of the screen row by row, each one as a 0xRRGGBB color.
+#[wasm_bindgen]
+pub fn get_framebuffer(cpu: *mut Cpu) -> Vec<u32> {
+    with_cpu(cpu, |cpu| cpu.ppu.screen.as_flattened().to_vec()).unwrap_or_default()
+}
+
+/// Presses a button, given by its value: 0 = Right, 1 = Left, 2 = Up, 3 = Down, 4 = A, 5 = B, 6 = Select, 7 = Start.
+#[wasm_bindgen]
+pub fn press_button(cpu: *mut Cpu, button: u8) {
+    set_button(cpu, button, true);
+}
+
+/// Releases a button, given by the same value as in `press_button`.
+#[wasm_bindgen]
+pub fn release_button(cpu: *mut Cpu, button: u8) {
+    set_button(cpu, button, false);
+}
+
+/// Width in pixels of the framebuffer returned by `get_framebuffer`.
+#[wasm_bindgen]
+pub fn get_framebuffer_width() -> usize {
+    GAME_SECTION_WIDTH
+}
+
+fn set_button(cpu: *mut Cpu, button: u8, pressed: bool) {
+    if let Some(button) = JoypadButton::from_value(button) {
+        with_cpu(cpu, |cpu| cpu.memory_bus.set_joypad_button(button, pressed));
+    }
+}
+
+/// Runs the function with the emulator behind the pointer, nothing is done for null pointers.
+fn with_cpu<T>(cpu: *mut Cpu, function: impl FnOnce(&mut Cpu) -> T) -> Option<T> {
+    // SAFETY: non-null pointers passed by JavaScript were created by create_emulator and not destroyed yet
+    unsafe { cpu.as_mut() }.map(function)
+}